@@ -5,11 +5,12 @@ use clap::builder::Styles;
 #[cfg(not(feature = "no_colors"))]
 use clap::builder::styling::AnsiColor;
 use czkawka_core::CZKAWKA_VERSION;
-use czkawka_core::common::model::{CheckingMethod, HashType};
-use czkawka_core::common::tool_data::DeleteMethod;
-use czkawka_core::tools::broken_files::CheckedTypes;
+use czkawka_core::common::metadata_filter::parse_date;
+use czkawka_core::common::model::{CheckingMethod, HashType, ToolType};
+use czkawka_core::common::tool_data::{DeleteMethod, KeeperRules, KeptFile};
+use czkawka_core::tools::broken_files::{CheckedTypes, Severity};
 use czkawka_core::tools::same_music::MusicSimilarity;
-use czkawka_core::tools::similar_images::SimilarityPreset;
+use czkawka_core::tools::similar_images::{HashCombineMode, ImagesGroupingMode, SimilarityPreset};
 use czkawka_core::tools::similar_videos::{ALLOWED_SKIP_FORWARD_AMOUNT, ALLOWED_VID_HASH_DURATION, DEFAULT_SKIP_FORWARD_AMOUNT, crop_detect_from_str_opt};
 use image_hasher::{FilterType, HashAlg};
 use vid_dup_finder_lib::Cropdetect;
@@ -96,6 +97,24 @@ pub enum Commands {
         after_help = "EXAMPLE:\n    czkawka ext -d /home/czokolada/ -f results.txt"
     )]
     BadExtensions(BadExtensionsArgs),
+    #[clap(
+        name = "ignore-group",
+        about = "Manages groups of files marked as not duplicates",
+        after_help = "EXAMPLE:\n    czkawka ignore-group -t image /home/rafal/a.jpg /home/rafal/b.jpg\n    czkawka ignore-group -l"
+    )]
+    IgnoreGroup(IgnoreGroupArgs),
+    #[clap(
+        name = "undo",
+        about = "Reverts deleting, moving, renaming or linking files",
+        after_help = "EXAMPLE:\n    czkawka undo\n    czkawka undo -l\n    czkawka undo -o 1718000000000"
+    )]
+    Undo(UndoArgs),
+    #[clap(
+        name = "quarantine-purge",
+        about = "Permanently removes files kept in quarantine for too long",
+        after_help = "EXAMPLE:\n    czkawka quarantine-purge -q /home/rafal/quarantine -a 30"
+    )]
+    QuarantinePurge(QuarantinePurgeArgs),
 }
 
 #[derive(Debug, clap::Args)]
@@ -152,8 +171,8 @@ pub struct DuplicatesArgs {
         long,
         default_value = "HASH",
         value_parser = parse_checking_method_duplicate,
-        help = "Search method (NAME, SIZE, HASH)",
-        long_help = "Methods to search files.\nNAME - Fast but but rarely usable,\nSIZE - Fast but not accurate, checking by the file's size,\nHASH - The slowest method, checking by the hash of the entire file"
+        help = "Search method (NAME, SIZE, HASH, SAMPLED_HASH)",
+        long_help = "Methods to search files.\nNAME - Fast but but rarely usable,\nSIZE - Fast but not accurate, checking by the file's size,\nHASH - The slowest method, checking by the hash of the entire file,\nSAMPLED_HASH - Fast, checking by the hash of beginning, middle and end of file, so files may still differ - found files are deleted or linked only with --verify-content"
     )]
     pub search_method: CheckingMethod,
     #[clap(flatten)]
@@ -166,6 +185,26 @@ pub struct DuplicatesArgs {
         help = "Hash type (BLAKE3, CRC32, XXH3)"
     )]
     pub hash_type: HashType,
+    #[clap(
+        long,
+        help = "Verify content of files byte by byte",
+        long_help = "After hashing, compares content of files in each group byte by byte, so groups created by hash collisions are split. Recommended with fast hashes like CRC32 or XXH3, before deleting or hardlinking files"
+    )]
+    pub verify_content: bool,
+    #[clap(
+        long,
+        help = "Find whole duplicated directories",
+        long_help = "Works only with HASH and SAMPLED_HASH search methods. Directories in which all files have duplicates with same relative paths in other directory are shown as one result, also when they are contained in bigger directory. All files inside directory are counted, also ones skipped by size or other filters. Delete methods other than linking remove duplicated files from such directories and then directories, which are left empty"
+    )]
+    pub find_duplicate_directories: bool,
+    #[clap(
+        long,
+        value_name = "PERCENT",
+        value_parser = parse_minimal_directories_similarity,
+        help = "Find pairs of directories with at least this percent of same files",
+        long_help = "Works only with HASH and SAMPLED_HASH search methods. Files are same, when they have same content and relative path inside directory. Similarity is counted as number of shared files divided by number of all files in both directories(Jaccard index), e.g. 90 means that 90% of files are shared. All files inside directories are counted, also ones skipped by size or other filters. Files present in more than 50 directories are not used to find pairs"
+    )]
+    pub minimal_directories_similarity: Option<f64>,
     #[clap(flatten)]
     pub case_sensitive_name_comparison: CaseSensitiveNameComparison,
     #[clap(flatten)]
@@ -275,6 +314,41 @@ pub struct SimilarImagesArgs {
         help = "Hash size (allowed: 8, 16, 32, 64)"
     )]
     pub hash_size: u8,
+    #[clap(
+        long,
+        value_parser = parse_additional_hash_algorithm,
+        help = "Additional hash algorithm with optional weight e.g. DoubleGradient:2, can be used multiple times",
+        long_help = "Additional hash algorithm with optional weight(default 1) e.g. DoubleGradient:2, can be used multiple times.\nImages found as similar with main hash algorithm, are also compared with additional hashes, which reduces number of false positives."
+    )]
+    pub additional_hash_alg: Vec<(HashAlg, f32)>,
+    #[clap(
+        long,
+        default_value = "ALL",
+        value_parser = parse_hash_combine_mode,
+        help = "How additional hashes are combined (allowed: ALL, WEIGHTED)",
+        long_help = "How distances of main and additional hashes are combined.\nALL - distance of every hash must be below similarity threshold,\nWEIGHTED - weighted average of distances must be below similarity threshold, main hash have weight 1"
+    )]
+    pub hash_combine_mode: HashCombineMode,
+    #[clap(
+        long,
+        help = "Find also rotated and flipped copies of images",
+        long_help = "Hashes also rotated and flipped variants of images, so copies rotated by 90, 180, 270 degrees or mirrored are found. Makes hashing several times slower."
+    )]
+    pub match_rotations: bool,
+    #[clap(
+        long,
+        help = "Find also images which are cropped from other images",
+        long_help = "Hashes also parts of images, to find images which are crops of other images(at least half of width and height). Results are shown as parent/child pairs with part of parent area, visible in child image."
+    )]
+    pub find_cropped: bool,
+    #[clap(
+        long,
+        default_value = "FIRST_HIT",
+        value_parser = parse_images_grouping_mode,
+        help = "How similar images are grouped (allowed: FIRST_HIT, CONNECTED, CLIQUES)",
+        long_help = "How similar images are grouped.\nFIRST_HIT - each image is added to group of first similar image found,\nCONNECTED - images connected by chain of similar images are in same group,\nCLIQUES - every pair of images in group is similar\nCONNECTED and CLIQUES give results which not depends on order of checking images"
+    )]
+    pub grouping_mode: ImagesGroupingMode,
 }
 
 #[derive(Debug, clap::Args)]
@@ -361,6 +435,18 @@ fn parse_maximum_difference(src: &str) -> Result<f64, String> {
         Err(e) => Err(e.to_string()),
     }
 }
+fn parse_minimal_directories_similarity(src: &str) -> Result<f64, String> {
+    match src.parse::<f64>() {
+        Ok(similarity) => {
+            if !(0.0..=100.0).contains(&similarity) {
+                Err("Minimal directories similarity must be in range 0-100".to_string())
+            } else {
+                Ok(similarity)
+            }
+        }
+        Err(e) => Err(e.to_string()),
+    }
+}
 fn parse_minimum_segment_duration(src: &str) -> Result<f32, String> {
     match src.parse::<f32>() {
         Ok(minimum_segment_duration) => {
@@ -395,10 +481,36 @@ pub struct BrokenFilesArgs {
         long,
         default_value = "PDF",
         value_parser = parse_broken_files,
-        help = "Checking file types (PDF, AUDIO, IMAGE, ARCHIVE)",
-        long_help = "Methods to search files - default PDF.\nPDF - finds broken PDF files,\nAUDIO - finds broken audio files,\nIMAGE - finds broken image files,\nARCHIVE - finds broken archive files"
+        help = "Checking file types (PDF, AUDIO, IMAGE, ARCHIVE, VIDEO, TAR, GZIP, XZ, ZSTD, BZIP2, 7Z, DOCUMENT, FONT, SQLITE, JSON, XML, YAML)",
+        long_help = "Methods to search files - default PDF.\nPDF - finds broken PDF files,\nAUDIO - finds broken audio files,\nIMAGE - finds broken image files,\nARCHIVE - finds broken zip archive files,\nVIDEO - finds truncated video files and files with broken index,\nTAR - finds tar archives with invalid header checksums,\nGZIP, XZ, ZSTD, BZIP2 - finds compressed files with invalid checksums, tar archives inside them are also checked,\n7Z - finds broken 7z archive files,\nDOCUMENT - finds broken zip based documents(DOCX, XLSX, PPTX, ODT, ODS, ODP, EPUB, APK) and documents without mandatory entries,\nFONT - finds broken TTF and OTF fonts, fonts with invalid checksums are reported as warnings,\nSQLITE - finds SQLite databases with broken structure,\nJSON, XML, YAML - finds files which cannot be parsed"
     )]
     pub checked_types: Vec<CheckedTypes>,
+    #[clap(
+        long,
+        help = "Decode first frames of videos with ffmpeg",
+        long_help = "Besides checking structure of video containers, decodes first frames of every video with ffmpeg, which finds also broken video streams. Requires ffmpeg to be installed and is much slower"
+    )]
+    pub decode_video_frames: bool,
+    #[clap(
+        long,
+        help = "Verify content of zip archives and documents",
+        long_help = "Besides opening zip archives and zip based documents, reads every entry inside them and verifies its CRC. Finds archives with corrupted content, but is much slower"
+    )]
+    pub verify_zip_content: bool,
+    #[clap(
+        long,
+        help = "Check structure of images strictly",
+        long_help = "Besides decoding images, checks end of image markers, PNG chunk CRCs and looks for uniform grey or black region at the bottom of images, which is typical for partially downloaded files. Such images are reported as warnings"
+    )]
+    pub strict_image_check: bool,
+    #[clap(
+        long,
+        default_value = "WARNING",
+        value_parser = parse_severity,
+        help = "Minimal severity of reported files (WARNING, UNREADABLE)",
+        long_help = "Minimal severity of reported files - default WARNING.\nWARNING - reports also files, which can be opened, but are probably damaged e.g. partially downloaded images,\nUNREADABLE - reports only files, which cannot be opened"
+    )]
+    pub min_severity: Severity,
 }
 
 #[derive(Debug, clap::Args)]
@@ -477,6 +589,58 @@ pub struct BadExtensionsArgs {
     pub common_cli_items: CommonCliItems,
 }
 
+#[derive(Debug, clap::Args)]
+pub struct IgnoreGroupArgs {
+    #[clap(
+        short,
+        long,
+        value_parser = parse_ignored_group_tool_type,
+        required_unless_present_any = ["list", "remove", "clear"],
+        help = "Tool of group (DUP, IMAGE, VIDEO, MUSIC)",
+        long_help = "Tool for which group is ignored - DUP, IMAGE, VIDEO or MUSIC. When used with --remove or --clear, only groups of this tool are removed"
+    )]
+    pub tool_type: Option<ToolType>,
+    #[clap(
+        help = "Files which are not duplicates of each other",
+        long_help = "List of files which will not be shown again as group of duplicates or similar files, relative paths and symlinks are resolved before saving"
+    )]
+    pub paths: Vec<PathBuf>,
+    #[clap(short, long, help = "Prints all ignored groups")]
+    pub list: bool,
+    #[clap(short, long, conflicts_with = "paths", help = "Removes all ignored groups containing this file")]
+    pub remove: Option<PathBuf>,
+    #[clap(short, long, conflicts_with_all = ["paths", "remove"], help = "Removes all ignored groups")]
+    pub clear: bool,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct UndoArgs {
+    #[clap(short, long, help = "Prints operations from journal, which were not reverted yet")]
+    pub list: bool,
+    #[clap(
+        short,
+        long,
+        conflicts_with = "list",
+        help = "Id of operation to revert",
+        long_help = "Id of operation to revert, shown by --list. By default last operation which can be reverted is used"
+    )]
+    pub operation: Option<u64>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct QuarantinePurgeArgs {
+    #[clap(short, long, help = "Quarantine folder")]
+    pub quarantine_folder: PathBuf,
+    #[clap(
+        short,
+        long,
+        default_value = "30",
+        help = "Maximal age of quarantined files in days",
+        long_help = "Files moved to quarantine more than given number of days ago are permanently removed, 0 removes all files"
+    )]
+    pub max_age_days: u64,
+}
+
 #[derive(Debug, clap::Args)]
 pub struct CommonCliItems {
     #[clap(short = 'T', long, default_value = "0", help = "Limits thread number, 0(default) will use all available threads")]
@@ -484,7 +648,7 @@ pub struct CommonCliItems {
     #[clap(
         short,
         long,
-        required = true,
+        required_unless_present = "load_results",
         help = "Directorie(s) to search",
         long_help = "List of directorie(s) which will be searched(absolute path) - this directories are not set as reference folders"
     )]
@@ -500,9 +664,15 @@ pub struct CommonCliItems {
         short = 'E',
         long,
         help = "Excluded item(s)",
-        long_help = "List of excluded item(s) which contains * wildcard(may be slow, so use -e where possible)"
+        long_help = "List of excluded item(s), globs with *, **, ? and [] wildcards(use [?] and [[] to match these characters literally) or regular expressions prefixed with \"regex:\", matched against full path(may be slow, so use -e where possible)"
     )]
     pub excluded_items: Vec<String>,
+    #[clap(
+        long,
+        help = "Included item(s)",
+        long_help = "List of included item(s), when set only files which full path matches at least one glob or regular expression prefixed with \"regex:\" are checked"
+    )]
+    pub included_items: Vec<String>,
     #[clap(
         short = 'x',
         long,
@@ -518,8 +688,23 @@ pub struct CommonCliItems {
     pub json_compact_file_to_save: JsonCompactFileToSave,
     #[clap(flatten)]
     pub json_pretty_file_to_save: JsonPrettyFileToSave,
+    #[clap(flatten)]
+    pub results_file: ResultsFile,
     #[clap(short = 'R', long, help = "Prevents from recursive check of folders")]
     pub not_recursive: bool,
+    #[clap(
+        long,
+        value_name = "levels",
+        help = "Maximal depth of checked subfolders",
+        long_help = "Limits how many levels of subfolders are checked below included directories, 0 checks only files placed directly inside them. Without this option all subfolders are checked"
+    )]
+    pub max_depth: Option<usize>,
+    #[clap(
+        long,
+        help = "Follow symlinked folders",
+        long_help = "Enters folders pointed by symbolic links like normal folders. Every folder is checked only once, even if it can be reached by multiple paths, so symlink loops are safe. Not used when searching for empty folders, because folder containing symlink is not empty"
+    )]
+    pub follow_symlinks: bool,
     #[cfg(target_family = "unix")]
     #[clap(short = 'X', long, help = "Exclude files on other filesystems")]
     pub exclude_other_filesystems: bool,
@@ -529,6 +714,53 @@ pub struct CommonCliItems {
     pub ignore_error_code_on_found: bool,
     #[clap(short = 'H', long, help = "Disable cache")]
     pub disable_cache: bool,
+    #[clap(
+        long,
+        help = "Skip reading directories not changed since previous scan",
+        long_help = "Saves modification date and content of every scanned directory and reuses it in next scan, if directory was not modified since then. Speeds up rescans of big, rarely changed trees, but files modified in place are not detected until their directory changes"
+    )]
+    pub use_directory_snapshot: bool,
+    #[clap(
+        long,
+        help = "Honour .gitignore and .czkawkaignore files",
+        long_help = "Skips files and folders matched by .gitignore and .czkawkaignore files found inside scanned folders. Patterns use gitignore syntax, so negation, anchored and directory only patterns are supported. Rules from .czkawkaignore are more important than from .gitignore in the same folder"
+    )]
+    pub use_ignore_files: bool,
+    #[clap(
+        long,
+        help = "Move deleted files to quarantine folder",
+        long_help = "Files and folders removed by delete method are moved to this folder instead of being permanently deleted. Original folder structure is recreated inside it, so files can be restored with undo command or purged later with quarantine-purge command. Quarantine folder is never scanned"
+    )]
+    pub quarantine_folder: Option<PathBuf>,
+    #[clap(
+        long,
+        value_name = "date",
+        value_parser = parse_date,
+        help = "Skips files modified before this date",
+        long_help = "Skips files modified before this date, accepts YYYY-MM-DD(UTC) or unix timestamp in seconds. Bare year(e.g. 2024) is rejected, use 2024-01-01 instead"
+    )]
+    pub min_modified_date: Option<u64>,
+    #[clap(
+        long,
+        value_name = "date",
+        value_parser = parse_date,
+        help = "Skips files modified after this date",
+        long_help = "Skips files modified after this date, accepts YYYY-MM-DD(UTC) or unix timestamp in seconds. Bare year(e.g. 2024) is rejected, use 2024-01-01 instead"
+    )]
+    pub max_modified_date: Option<u64>,
+    #[clap(
+        long,
+        value_name = "days",
+        help = "Checks only files not accessed for given number of days",
+        long_help = "Checks only files not accessed for given number of days, files on filesystems mounted with noatime may have outdated access time"
+    )]
+    pub not_accessed_for_days: Option<u64>,
+    #[cfg(target_family = "unix")]
+    #[clap(long, value_name = "uid", help = "Checks only files owned by user(s) with given id")]
+    pub owner_uid: Vec<u32>,
+    #[cfg(target_family = "unix")]
+    #[clap(long, value_name = "gid", help = "Checks only files owned by group(s) with given id")]
+    pub owner_gid: Vec<u32>,
 }
 
 #[derive(Debug, clap::Args)]
@@ -546,10 +778,35 @@ pub struct DMethod {
         long,
         default_value = "NONE",
         value_parser = parse_delete_method,
-        help = "Delete method (AEN, AEO, ON, OO, AEB, AES, OE, OS, HARD)",
-        long_help = "Methods to delete the files.\nAEN - All files except the newest,\nAEO - All files except the oldest,\nON - Only 1 file, the newest,\nOO - Only 1 file, the oldest\nAEB - All files except the biggest,\nAES - All files except the smallest,\nOB - Only 1 file, the biggest,\nOS - Only 1 file, the smallest\nHARD - create hard link\nNONE - not delete files"
+        help = "Delete method (AEN, AEO, ON, OO, AEB, AES, OE, OS, HARD, REFLINK, SN, SO, SB, SS)",
+        long_help = "Methods to delete the files.\nAEN - All files except the newest,\nAEO - All files except the oldest,\nON - Only 1 file, the newest,\nOO - Only 1 file, the oldest\nAEB - All files except the biggest,\nAES - All files except the smallest,\nOB - Only 1 file, the biggest,\nOS - Only 1 file, the smallest\nHARD - create hard link\nREFLINK - share data of files with copy-on-write, files stay independent(only Btrfs, XFS and similar filesystems on Linux)\nSN - replace all files except the newest with symlinks to it,\nSO - replace all files except the oldest with symlinks to it,\nSB - replace all files except the biggest with symlinks to it,\nSS - replace all files except the smallest with symlinks to it\nNONE - not delete files"
     )]
     pub delete_method: DeleteMethod,
+    #[clap(long, help = "Symlinks created by SN, SO, SB and SS delete methods use paths relative to the symlink location")]
+    pub relative_symlinks: bool,
+    #[clap(
+        long,
+        value_name = "file-name",
+        help = "File with rules choosing kept file in group",
+        long_help = "File with rules choosing kept file in group, used by delete methods which keep one file(AEN, AEO, AEB, AES, HARD, REFLINK, SN, SO, SB, SS).\nRules are checked in order, one per line, criterion of delete method is used as last tie-breaker:\nprefer_path <folder> - prefer files inside folder,\nprefer_shortest_path,\nprefer_longest_path,\navoid_name <text> - prefer files which name not contains text, quotes allow to use spaces e.g. avoid_name \" (1)\",\nprefer_exif - prefer files with EXIF data,\nnever_delete_read_only - never delete read-only files or files on read-only mounts,\nprefer_newest,\nprefer_oldest,\nprefer_biggest,\nprefer_smallest"
+    )]
+    pub keeper_rules: Option<PathBuf>,
+}
+
+impl DMethod {
+    pub fn get_delete_method(&self) -> DeleteMethod {
+        match self.delete_method {
+            DeleteMethod::Symlink { keep, .. } => DeleteMethod::Symlink {
+                keep,
+                relative: self.relative_symlinks,
+            },
+            delete_method => delete_method,
+        }
+    }
+
+    pub fn get_keeper_rules(&self) -> Result<KeeperRules, String> {
+        self.keeper_rules.as_deref().map_or_else(|| Ok(KeeperRules::default()), KeeperRules::load_from_file)
+    }
 }
 
 #[derive(Debug, clap::Args)]
@@ -558,6 +815,24 @@ pub struct FileToSave {
     pub file_to_save: Option<PathBuf>,
 }
 
+#[derive(Debug, clap::Args)]
+pub struct ResultsFile {
+    #[clap(
+        long,
+        value_name = "results-file-name",
+        help = "Saves the results into the results file, which can be loaded later",
+        long_help = "Saves the results into the versioned results file, which can be loaded later with --load-results to e.g. delete files without scanning again"
+    )]
+    pub save_results: Option<PathBuf>,
+    #[clap(
+        long,
+        value_name = "results-file-name",
+        help = "Loads the results from the results file instead of scanning",
+        long_help = "Loads the results from the file created with --save-results instead of scanning directories. Files, whose size or modification date changed since saving, are skipped and will not be deleted"
+    )]
+    pub load_results: Option<PathBuf>,
+}
+
 #[derive(Debug, clap::Args)]
 pub struct ReferenceDirectories {
     #[clap(
@@ -614,6 +889,22 @@ impl FileToSave {
         None
     }
 }
+impl ResultsFile {
+    pub(crate) fn save_file_name(&self) -> Option<&str> {
+        if let Some(file_name) = &self.save_results {
+            return file_name.to_str();
+        }
+
+        None
+    }
+    pub(crate) fn load_file_name(&self) -> Option<&str> {
+        if let Some(file_name) = &self.load_results {
+            return file_name.to_str();
+        }
+
+        None
+    }
+}
 impl JsonCompactFileToSave {
     pub(crate) fn file_name(&self) -> Option<&str> {
         if let Some(file_name) = &self.compact_file_to_save {
@@ -693,7 +984,8 @@ fn parse_checking_method_duplicate(src: &str) -> Result<CheckingMethod, &'static
         "size" => Ok(CheckingMethod::Size),
         "size_name" => Ok(CheckingMethod::SizeName),
         "hash" => Ok(CheckingMethod::Hash),
-        _ => Err("Couldn't parse the search method (allowed: NAME, SIZE, HASH)"),
+        "sampled_hash" => Ok(CheckingMethod::SampledHash),
+        _ => Err("Couldn't parse the search method (allowed: NAME, SIZE, HASH, SAMPLED_HASH)"),
     }
 }
 
@@ -703,7 +995,38 @@ fn parse_broken_files(src: &str) -> Result<CheckedTypes, &'static str> {
         "audio" => Ok(CheckedTypes::AUDIO),
         "image" => Ok(CheckedTypes::IMAGE),
         "archive" => Ok(CheckedTypes::ARCHIVE),
-        _ => Err("Couldn't parse the broken files type (allowed: PDF, AUDIO, IMAGE, ARCHIVE)"),
+        "video" => Ok(CheckedTypes::VIDEO),
+        "tar" => Ok(CheckedTypes::TAR),
+        "gzip" => Ok(CheckedTypes::GZIP),
+        "xz" => Ok(CheckedTypes::XZ),
+        "zstd" => Ok(CheckedTypes::ZSTD),
+        "bzip2" => Ok(CheckedTypes::BZIP2),
+        "7z" => Ok(CheckedTypes::SEVEN_ZIP),
+        "document" => Ok(CheckedTypes::DOCUMENT),
+        "font" => Ok(CheckedTypes::FONT),
+        "sqlite" => Ok(CheckedTypes::SQLITE),
+        "json" => Ok(CheckedTypes::JSON),
+        "xml" => Ok(CheckedTypes::XML),
+        "yaml" => Ok(CheckedTypes::YAML),
+        _ => Err("Couldn't parse the broken files type (allowed: PDF, AUDIO, IMAGE, ARCHIVE, VIDEO, TAR, GZIP, XZ, ZSTD, BZIP2, 7Z, DOCUMENT, FONT, SQLITE, JSON, XML, YAML)"),
+    }
+}
+
+fn parse_severity(src: &str) -> Result<Severity, &'static str> {
+    match src.to_ascii_lowercase().as_str() {
+        "warning" => Ok(Severity::Warning),
+        "unreadable" => Ok(Severity::Unreadable),
+        _ => Err("Couldn't parse the severity (allowed: WARNING, UNREADABLE)"),
+    }
+}
+
+fn parse_ignored_group_tool_type(src: &str) -> Result<ToolType, &'static str> {
+    match src.to_ascii_lowercase().as_str() {
+        "dup" => Ok(ToolType::Duplicate),
+        "image" => Ok(ToolType::SimilarImages),
+        "video" => Ok(ToolType::SimilarVideos),
+        "music" => Ok(ToolType::SameMusic),
+        _ => Err("Couldn't parse the tool type (allowed: DUP, IMAGE, VIDEO, MUSIC)"),
     }
 }
 
@@ -721,13 +1044,30 @@ fn parse_delete_method(src: &str) -> Result<DeleteMethod, &'static str> {
         "aen" => Ok(DeleteMethod::AllExceptNewest),
         "aeo" => Ok(DeleteMethod::AllExceptOldest),
         "hard" => Ok(DeleteMethod::HardLink),
+        "reflink" => Ok(DeleteMethod::Reflink),
         "on" => Ok(DeleteMethod::OneNewest),
         "oo" => Ok(DeleteMethod::OneOldest),
         "aeb" => Ok(DeleteMethod::AllExceptBiggest),
         "aes" => Ok(DeleteMethod::AllExceptSmallest),
         "ob" => Ok(DeleteMethod::OneBiggest),
         "os" => Ok(DeleteMethod::OneSmallest),
-        _ => Err("Couldn't parse the delete method (allowed: AEN, AEO, ON, OO, HARD, AEB, AES, OB, OS)"),
+        "sn" => Ok(DeleteMethod::Symlink {
+            keep: KeptFile::Newest,
+            relative: false,
+        }),
+        "so" => Ok(DeleteMethod::Symlink {
+            keep: KeptFile::Oldest,
+            relative: false,
+        }),
+        "sb" => Ok(DeleteMethod::Symlink {
+            keep: KeptFile::Biggest,
+            relative: false,
+        }),
+        "ss" => Ok(DeleteMethod::Symlink {
+            keep: KeptFile::Smallest,
+            relative: false,
+        }),
+        _ => Err("Couldn't parse the delete method (allowed: AEN, AEO, ON, OO, HARD, REFLINK, AEB, AES, OB, OS, SN, SO, SB, SS)"),
     }
 }
 
@@ -789,6 +1129,32 @@ fn parse_similar_hash_algorithm(src: &str) -> Result<HashAlg, String> {
     Ok(algorithm)
 }
 
+fn parse_additional_hash_algorithm(src: &str) -> Result<(HashAlg, f32), String> {
+    let (algorithm, weight) = src.split_once(':').unwrap_or((src, "1"));
+    let weight = weight.parse::<f32>().map_err(|e| format!("Couldn't parse the hash algorithm weight \"{weight}\": {e}"))?;
+    if !weight.is_finite() || weight < 0.0 {
+        return Err(format!("Hash algorithm weight must be non negative number, got \"{weight}\""));
+    }
+    Ok((parse_similar_hash_algorithm(algorithm)?, weight))
+}
+
+fn parse_images_grouping_mode(src: &str) -> Result<ImagesGroupingMode, &'static str> {
+    match src.to_ascii_lowercase().replace('_', "").as_str() {
+        "firsthit" => Ok(ImagesGroupingMode::FirstHit),
+        "connected" => Ok(ImagesGroupingMode::ConnectedComponents),
+        "cliques" => Ok(ImagesGroupingMode::StrictCliques),
+        _ => Err("Couldn't parse the grouping mode (allowed: FIRST_HIT, CONNECTED, CLIQUES)"),
+    }
+}
+
+fn parse_hash_combine_mode(src: &str) -> Result<HashCombineMode, &'static str> {
+    match src.to_ascii_lowercase().as_str() {
+        "all" => Ok(HashCombineMode::AllUnderThreshold),
+        "weighted" => Ok(HashCombineMode::WeightedSum),
+        _ => Err("Couldn't parse the hash combine mode (allowed: ALL, WEIGHTED)"),
+    }
+}
+
 fn parse_image_hash_size(src: &str) -> Result<u8, String> {
     let hash_size = match src.to_lowercase().as_str() {
         "8" => 8,
@@ -1,3 +1,4 @@
+use std::fmt::Write;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
@@ -8,11 +9,15 @@ use commands::Commands;
 use crossbeam_channel::{Receiver, Sender, unbounded};
 use czkawka_core::common::config_cache_path::{print_infos_and_warnings, set_config_cache_path};
 use czkawka_core::common::consts::DEFAULT_THREAD_SIZE;
+use czkawka_core::common::deletion_journal::DeletionJournal;
+use czkawka_core::common::ignored_groups::IgnoredGroups;
 use czkawka_core::common::logger::{filtering_messages, print_version_mode, setup_logger};
+use czkawka_core::common::model::WorkContinueStatus;
 use czkawka_core::common::progress_data::ProgressData;
+use czkawka_core::common::quarantine::purge_quarantine;
 use czkawka_core::common::set_number_of_threads;
-use czkawka_core::common::tool_data::{CommonData, DeleteMethod};
-use czkawka_core::common::traits::{AllTraits, PrintResults, Search};
+use czkawka_core::common::tool_data::{CommonData, DeleteMethod, KeeperRules};
+use czkawka_core::common::traits::{AllTraits, PersistentResults, PrintResults};
 use czkawka_core::tools::bad_extensions::{BadExtensions, BadExtensionsParameters};
 use czkawka_core::tools::big_file::{BigFile, BigFileParameters, SearchMode};
 use czkawka_core::tools::broken_files::{BrokenFiles, BrokenFilesParameters, CheckedTypes};
@@ -28,8 +33,8 @@ use czkawka_core::tools::temporary::Temporary;
 use log::{debug, error, info};
 
 use crate::commands::{
-    Args, BadExtensionsArgs, BiggestFilesArgs, BrokenFilesArgs, CommonCliItems, DuplicatesArgs, EmptyFilesArgs, EmptyFoldersArgs, InvalidSymlinksArgs, SameMusicArgs,
-    SimilarImagesArgs, SimilarVideosArgs, TemporaryArgs,
+    Args, BadExtensionsArgs, BiggestFilesArgs, BrokenFilesArgs, CommonCliItems, DMethod, DuplicatesArgs, EmptyFilesArgs, EmptyFoldersArgs, IgnoreGroupArgs, InvalidSymlinksArgs,
+    QuarantinePurgeArgs, SameMusicArgs, SimilarImagesArgs, SimilarVideosArgs, TemporaryArgs, UndoArgs,
 };
 use crate::progress::connect_progress;
 
@@ -77,6 +82,9 @@ fn main() {
             Commands::BrokenFiles(broken_files_args) => broken_files(broken_files_args, &stop_flag, &progress_sender),
             Commands::SimilarVideos(similar_videos_args) => similar_videos(similar_videos_args, &stop_flag, &progress_sender),
             Commands::BadExtensions(bad_extensions_args) => bad_extensions(bad_extensions_args, &stop_flag, &progress_sender),
+            Commands::IgnoreGroup(ignore_group_args) => ignore_group(ignore_group_args),
+            Commands::Undo(undo_args) => undo(&undo_args),
+            Commands::QuarantinePurge(quarantine_purge_args) => quarantine_purge(&quarantine_purge_args),
         })
         .expect("Failed to spawn calculation thread");
 
@@ -115,6 +123,9 @@ fn duplicates(duplicates: DuplicatesArgs, stop_flag: &Arc<AtomicBool>, progress_
         search_method,
         delete_method,
         hash_type,
+        verify_content,
+        find_duplicate_directories,
+        minimal_directories_similarity,
         allow_hard_links,
         dry_run,
         case_sensitive_name_comparison,
@@ -130,16 +141,20 @@ fn duplicates(duplicates: DuplicatesArgs, stop_flag: &Arc<AtomicBool>, progress_
         minimal_cached_file_size,
         minimal_prehash_cache_file_size,
         case_sensitive_name_comparison.case_sensitive_name_comparison,
+        verify_content,
+        find_duplicate_directories,
+        minimal_directories_similarity.map(|similarity| similarity / 100.0),
     );
     let mut tool = DuplicateFinder::new(params);
 
     set_common_settings(&mut tool, &common_cli_items, Some(reference_directories.reference_directories.as_ref()));
     tool.set_minimal_file_size(minimal_file_size);
     tool.set_maximal_file_size(maximal_file_size);
-    tool.set_delete_method(delete_method.delete_method);
+    tool.set_delete_method(delete_method.get_delete_method());
+    set_keeper_rules(&mut tool, &delete_method);
     tool.set_dry_run(dry_run.dry_run);
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_load_results(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
@@ -154,7 +169,7 @@ fn empty_folders(empty_folders: EmptyFoldersArgs, stop_flag: &Arc<AtomicBool>, p
         tool.set_delete_method(DeleteMethod::Delete);
     }
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_load_results(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
@@ -176,7 +191,7 @@ fn biggest_files(biggest_files: BiggestFilesArgs, stop_flag: &Arc<AtomicBool>, p
         tool.set_delete_method(DeleteMethod::Delete);
     }
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_load_results(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
@@ -191,7 +206,7 @@ fn empty_files(empty_files: EmptyFilesArgs, stop_flag: &Arc<AtomicBool>, progres
         tool.set_delete_method(DeleteMethod::Delete);
     }
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_load_results(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
@@ -206,7 +221,7 @@ fn temporary(temporary: TemporaryArgs, stop_flag: &Arc<AtomicBool>, progress_sen
         tool.set_delete_method(DeleteMethod::Delete);
     }
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_load_results(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
@@ -225,6 +240,11 @@ fn similar_images(similar_images: SimilarImagesArgs, stop_flag: &Arc<AtomicBool>
         dry_run,
         allow_hard_links,
         ignore_same_size,
+        additional_hash_alg,
+        hash_combine_mode,
+        match_rotations,
+        find_cropped,
+        grouping_mode,
     } = similar_images;
 
     let similarity = return_similarity_from_similarity_preset(&similarity_preset, hash_size);
@@ -235,16 +255,22 @@ fn similar_images(similar_images: SimilarImagesArgs, stop_flag: &Arc<AtomicBool>
         image_filter,
         ignore_same_size.ignore_same_size,
         !allow_hard_links.allow_hard_links,
+        additional_hash_alg,
+        hash_combine_mode,
+        match_rotations,
+        find_cropped,
+        grouping_mode,
     );
     let mut tool = SimilarImages::new(params);
 
     set_common_settings(&mut tool, &common_cli_items, Some(reference_directories.reference_directories.as_ref()));
     tool.set_minimal_file_size(minimal_file_size);
     tool.set_maximal_file_size(maximal_file_size);
-    tool.set_delete_method(delete_method.delete_method);
+    tool.set_delete_method(delete_method.get_delete_method());
+    set_keeper_rules(&mut tool, &delete_method);
     tool.set_dry_run(dry_run.dry_run);
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_load_results(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
@@ -278,10 +304,11 @@ fn same_music(same_music: SameMusicArgs, stop_flag: &Arc<AtomicBool>, progress_s
     set_common_settings(&mut tool, &common_cli_items, Some(reference_directories.reference_directories.as_ref()));
     tool.set_minimal_file_size(minimal_file_size);
     tool.set_maximal_file_size(maximal_file_size);
-    tool.set_delete_method(delete_method.delete_method);
+    tool.set_delete_method(delete_method.get_delete_method());
+    set_keeper_rules(&mut tool, &delete_method);
     tool.set_dry_run(dry_run.dry_run);
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_load_results(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
@@ -296,7 +323,7 @@ fn invalid_symlinks(invalid_symlinks: InvalidSymlinksArgs, stop_flag: &Arc<Atomi
         tool.set_delete_method(DeleteMethod::Delete);
     }
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_load_results(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
@@ -306,13 +333,17 @@ fn broken_files(broken_files: BrokenFilesArgs, stop_flag: &Arc<AtomicBool>, prog
         common_cli_items,
         delete_files,
         checked_types,
+        decode_video_frames,
+        verify_zip_content,
+        strict_image_check,
+        min_severity,
     } = broken_files;
 
     let mut checked_type = CheckedTypes::NONE;
     for check_type in checked_types {
         checked_type |= check_type;
     }
-    let params = BrokenFilesParameters::new(checked_type);
+    let params = BrokenFilesParameters::new(checked_type, decode_video_frames, verify_zip_content, strict_image_check, min_severity);
     let mut tool = BrokenFiles::new(params);
 
     set_common_settings(&mut tool, &common_cli_items, None);
@@ -320,7 +351,7 @@ fn broken_files(broken_files: BrokenFilesArgs, stop_flag: &Arc<AtomicBool>, prog
         tool.set_delete_method(DeleteMethod::Delete);
     }
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_load_results(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
@@ -354,10 +385,11 @@ fn similar_videos(similar_videos: SimilarVideosArgs, stop_flag: &Arc<AtomicBool>
     set_common_settings(&mut tool, &common_cli_items, Some(reference_directories.reference_directories.as_ref()));
     tool.set_minimal_file_size(minimal_file_size);
     tool.set_maximal_file_size(maximal_file_size);
-    tool.set_delete_method(delete_method.delete_method);
+    tool.set_delete_method(delete_method.get_delete_method());
+    set_keeper_rules(&mut tool, &delete_method);
     tool.set_dry_run(dry_run.dry_run);
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_load_results(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
@@ -370,12 +402,133 @@ fn bad_extensions(bad_extensions: BadExtensionsArgs, stop_flag: &Arc<AtomicBool>
 
     set_common_settings(&mut tool, &common_cli_items, None);
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_load_results(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
 
-fn save_and_write_results_to_writer<T: CommonData + PrintResults>(component: &T, common_cli_items: &CommonCliItems) -> CliOutput {
+fn ignore_group(ignore_group: IgnoreGroupArgs) -> CliOutput {
+    let IgnoreGroupArgs {
+        tool_type,
+        paths,
+        list,
+        remove,
+        clear,
+    } = ignore_group;
+
+    let mut warnings = Vec::new();
+    let mut ignored_groups = IgnoredGroups::load(&mut warnings);
+    let mut output = String::new();
+    for warning in warnings {
+        writeln!(output, "{warning}").expect("Writing to string cannot fail");
+    }
+
+    let modified = if clear {
+        ignored_groups.clear(tool_type);
+        writeln!(output, "Cleared ignored groups").expect("Writing to string cannot fail");
+        true
+    } else if let Some(remove) = remove {
+        let removed = ignored_groups.remove_groups_with_path(tool_type, &remove);
+        writeln!(output, "Removed {removed} ignored groups containing \"{}\"", remove.to_string_lossy()).expect("Writing to string cannot fail");
+        removed > 0
+    } else if let Some(tool_type) = tool_type {
+        // Scanned files are reported with absolute paths, so relative paths or paths with symlinks would never match them
+        let mut canonicalized_paths = Vec::new();
+        for path in paths {
+            match path.canonicalize() {
+                Ok(canonicalized_path) => canonicalized_paths.push(canonicalized_path),
+                Err(e) => writeln!(output, "Cannot canonicalize path \"{}\", reason {e}", path.to_string_lossy()).expect("Writing to string cannot fail"),
+            }
+        }
+        let added = ignored_groups.add_group(tool_type, canonicalized_paths);
+        if added {
+            writeln!(output, "Added new ignored group").expect("Writing to string cannot fail");
+        } else {
+            writeln!(output, "Group was not added - at least 2 files are needed and group cannot be already ignored").expect("Writing to string cannot fail");
+        }
+        added
+    } else {
+        false
+    };
+
+    if modified && let Err(e) = ignored_groups.save() {
+        error!("{e}");
+    }
+
+    if list {
+        for group in ignored_groups.get_groups() {
+            writeln!(output, "{:?} - {} files", group.tool_type, group.paths.len()).expect("Writing to string cannot fail");
+            for path in &group.paths {
+                writeln!(output, "    \"{}\"", path.to_string_lossy()).expect("Writing to string cannot fail");
+            }
+        }
+    }
+
+    CliOutput {
+        found_any_files: false,
+        ignored_error_code_on_found: false,
+        output: output.trim_end().to_string(),
+    }
+}
+
+fn undo(undo: &UndoArgs) -> CliOutput {
+    let &UndoArgs { list, operation } = undo;
+
+    let output = if list {
+        let mut warnings = Vec::new();
+        let entries = DeletionJournal::load_active_entries(&mut warnings);
+        let mut output = String::new();
+        for warning in warnings {
+            writeln!(output, "{warning}").expect("Writing to string cannot fail");
+        }
+        let mut last_operation_id = None;
+        for entry in entries {
+            if last_operation_id != Some(entry.operation_id) {
+                writeln!(output, "Operation {}:", entry.operation_id).expect("Writing to string cannot fail");
+                last_operation_id = Some(entry.operation_id);
+            }
+            writeln!(output, "    \"{}\" - {:?}", entry.path.to_string_lossy(), entry.action).expect("Writing to string cannot fail");
+        }
+        output
+    } else {
+        DeletionJournal::undo_operation(operation).create_messages_text()
+    };
+
+    CliOutput {
+        found_any_files: false,
+        ignored_error_code_on_found: false,
+        output: output.trim_end().to_string(),
+    }
+}
+
+fn quarantine_purge(quarantine_purge: &QuarantinePurgeArgs) -> CliOutput {
+    let QuarantinePurgeArgs { quarantine_folder, max_age_days } = quarantine_purge;
+
+    CliOutput {
+        found_any_files: false,
+        ignored_error_code_on_found: false,
+        output: purge_quarantine(quarantine_folder, *max_age_days).create_messages_text().trim_end().to_string(),
+    }
+}
+
+// When results file is loaded, only delete action is executed on entries that not changed since saving
+fn search_or_load_results<T: AllTraits>(component: &mut T, common_cli_items: &CommonCliItems, stop_flag: &Arc<AtomicBool>, progress_sender: &Sender<ProgressData>) {
+    let Some(file_name) = common_cli_items.results_file.load_file_name() else {
+        component.search(stop_flag, Some(progress_sender));
+        return;
+    };
+
+    if let Err(e) = component.load_results_from_results_file(file_name) {
+        error!("Failed to load results from file {e}");
+        component.get_text_messages_mut().errors.push(e);
+        return;
+    }
+    if component.delete_files(stop_flag, Some(progress_sender)) == WorkContinueStatus::Stop {
+        component.set_stopped_search(true);
+    }
+}
+
+fn save_and_write_results_to_writer<T: CommonData + PrintResults + PersistentResults>(component: &T, common_cli_items: &CommonCliItems) -> CliOutput {
     if let Some(file_name) = common_cli_items.file_to_save.file_name()
         && let Err(e) = component.print_results_to_file(file_name)
     {
@@ -391,6 +544,11 @@ fn save_and_write_results_to_writer<T: CommonData + PrintResults>(component: &T,
     {
         error!("Failed to save pretty json results to file {e}");
     }
+    if let Some(file_name) = common_cli_items.results_file.save_file_name()
+        && let Err(e) = component.save_results_to_results_file(file_name)
+    {
+        error!("Failed to save results to results file {e}");
+    }
 
     let mut buf_writer = std::io::BufWriter::new(Vec::new());
     if !common_cli_items.do_not_print.do_not_print_results {
@@ -420,24 +578,66 @@ fn save_and_write_results_to_writer<T: CommonData + PrintResults>(component: &T,
     cli_output
 }
 
+// Invalid rules could change which files are kept, so nothing is deleted in such case
+fn set_keeper_rules<T: CommonData>(component: &mut T, delete_method: &DMethod) {
+    match delete_method
+        .get_keeper_rules()
+        .and_then(|keeper_rules| check_keeper_rules_method(keeper_rules, delete_method.get_delete_method()))
+    {
+        Ok(keeper_rules) => component.set_keeper_rules(keeper_rules),
+        Err(e) => {
+            error!("{e}");
+            component.get_text_messages_mut().errors.push(e);
+            component.set_delete_method(DeleteMethod::None);
+        }
+    }
+}
+
+// Delete and One* methods do not keep one file in group, so rules would be silently ignored
+fn check_keeper_rules_method(keeper_rules: KeeperRules, delete_method: DeleteMethod) -> Result<KeeperRules, String> {
+    if !keeper_rules.is_empty() && delete_method != DeleteMethod::None && !delete_method.supports_keeper_rules() {
+        return Err(format!(
+            "Keeper rules cannot be used with delete method {delete_method:?}, only methods which keep one file in group are supported(AEN, AEO, AEB, AES, HARD, REFLINK, SN, SO, SB, SS)"
+        ));
+    }
+    Ok(keeper_rules)
+}
+
 fn set_common_settings<T>(component: &mut T, common_cli_items: &CommonCliItems, reference_directories: Option<&Vec<PathBuf>>)
 where
     T: AllTraits,
 {
     set_number_of_threads(common_cli_items.thread_number);
 
-    let mut included_directories = common_cli_items.directories.clone();
-    if let Some(reference_directories) = reference_directories {
-        included_directories.extend_from_slice(reference_directories);
-        component.set_reference_directory(reference_directories.clone());
-    }
+    // Directories are not scanned when results are loaded from file, so they may be empty
+    if common_cli_items.results_file.load_file_name().is_none() {
+        let mut included_directories = common_cli_items.directories.clone();
+        if let Some(reference_directories) = reference_directories {
+            included_directories.extend_from_slice(reference_directories);
+            component.set_reference_directory(reference_directories.clone());
+        }
 
-    component.set_included_directory(included_directories);
+        component.set_included_directory(included_directories);
+    }
     component.set_excluded_directory(common_cli_items.excluded_directories.clone());
     component.set_excluded_items(common_cli_items.excluded_items.clone());
+    component.set_included_items(common_cli_items.included_items.clone());
     component.set_recursive_search(!common_cli_items.not_recursive);
+    component.set_max_depth(common_cli_items.max_depth);
+    component.set_follow_symlinks(common_cli_items.follow_symlinks);
     #[cfg(target_family = "unix")]
     component.set_exclude_other_filesystems(common_cli_items.exclude_other_filesystems);
     component.set_allowed_extensions(common_cli_items.allowed_extensions.clone().join(","));
     component.set_use_cache(!common_cli_items.disable_cache);
+    component.set_use_directory_snapshot(common_cli_items.use_directory_snapshot);
+    component.set_use_ignore_files(common_cli_items.use_ignore_files);
+    component.set_quarantine_folder(common_cli_items.quarantine_folder.clone());
+    component.set_minimal_modified_date(common_cli_items.min_modified_date);
+    component.set_maximal_modified_date(common_cli_items.max_modified_date);
+    component.set_not_accessed_for_days(common_cli_items.not_accessed_for_days);
+    #[cfg(target_family = "unix")]
+    {
+        component.set_owner_uids(common_cli_items.owner_uid.clone());
+        component.set_owner_gids(common_cli_items.owner_gid.clone());
+    }
 }
@@ -62,6 +62,8 @@ pub(crate) fn get_progress_message(progress_data: &ProgressData) -> String {
         CurrentStage::SameMusicComparingFingerprints => "Comparing fingerprints",
         CurrentStage::DuplicatePreHashing => "Calculating prehashes",
         CurrentStage::DuplicateFullHashing => "Calculating hashes",
+        CurrentStage::DuplicateSampledHashing => "Calculating sampled hashes",
+        CurrentStage::DuplicateComparingContent => "Comparing content of files",
         CurrentStage::SimilarImagesCalculatingHashes => "Calculating image hashes",
         CurrentStage::SimilarImagesComparingHashes => "Comparing image hashes",
         CurrentStage::SimilarVideosCalculatingHashes => "Reading similar values",
@@ -6,6 +6,7 @@ core_similarity_medium = Medium
 core_similarity_small = Small
 core_similarity_very_small = Very Small
 core_similarity_minimal = Minimal
+core_similarity_indirect = Indirect
 
 core_cannot_open_dir = Cannot open dir {$dir}, reason {$reason}
 core_cannot_read_entry_dir = Cannot read entry in dir {$dir}, reason {$reason}
@@ -14,6 +15,8 @@ core_file_modified_before_epoch = File {$name} seems to be modified before Unix
 core_folder_modified_before_epoch = Folder {$name} seems to be modified before Unix Epoch
 core_file_no_modification_date = Unable to get modification date from file {$name}, reason {$reason}
 core_folder_no_modification_date = Unable to get modification date from folder {$name}, reason {$reason}
+core_cannot_read_ignore_file = Cannot read ignore file {$name}, reason {$reason}
+core_invalid_ignore_pattern = Invalid pattern "{$pattern}" in ignore file {$name}, reason {$reason}
 
 core_missing_no_chosen_included_directory = At least one directory must be provided
 core_directory_must_exists = Directories:  Provided folder path must exist, ignoring { $path }
@@ -19,10 +19,11 @@ use crate::helpers::messages::Messages;
 
 pub(crate) const CACHE_VERSION: &str = "100";
 pub(crate) const CACHE_DUPLICATE_VERSION: &str = "100";
-pub(crate) const CACHE_IMAGE_VERSION: &str = "100";
+pub(crate) const CACHE_IMAGE_VERSION: &str = "103";
+pub(crate) const CACHE_BROKEN_FILES_VERSION: &str = "102";
 pub(crate) const CACHE_VIDEO_VERSION: &str = "100";
 
-const MEMORY_LIMIT: u64 = 8 * 1024 * 1024 * 1024;
+pub(crate) const MEMORY_LIMIT: u64 = 8 * 1024 * 1024 * 1024;
 
 fn get_cache_size(file_name: &Path) -> String {
     fs::metadata(file_name).map_or_else(|_| "<unknown size>".to_string(), |metadata| format_size(metadata.len(), BINARY))
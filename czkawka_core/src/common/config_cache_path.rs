@@ -18,6 +18,11 @@ pub fn get_config_cache_path() -> Option<ConfigCachePath> {
     CONFIG_CACHE_PATH.get().expect("Cannot fail if set_config_cache_path was called before").clone()
 }
 
+// Core may be used as library without setting config and cache folders, e.g. in tests
+pub fn is_config_cache_path_set() -> bool {
+    CONFIG_CACHE_PATH.get().is_some()
+}
+
 fn resolve_folder(env_var: &str, default_folder: Option<PathBuf>, name: &'static str, warnings: &mut Vec<String>) -> Option<PathBuf> {
     let default_folder_str = default_folder.as_ref().map_or("<not available>".to_string(), |t| t.to_string_lossy().to_string());
 
@@ -25,10 +25,26 @@ pub const IMAGE_RS_BROKEN_FILES_EXTENSIONS: &[&str] = &[
 ];
 pub const HEIC_EXTENSIONS: &[&str] = &["heif", "heifs", "heic", "heics", "avci", "avcs"];
 pub const ZIP_FILES_EXTENSIONS: &[&str] = &["zip", "jar"];
+pub const TAR_FILES_EXTENSIONS: &[&str] = &["tar"];
+pub const GZIP_FILES_EXTENSIONS: &[&str] = &["gz", "tgz"];
+pub const XZ_FILES_EXTENSIONS: &[&str] = &["xz", "txz"];
+pub const ZSTD_FILES_EXTENSIONS: &[&str] = &["zst", "tzst"];
+pub const BZIP2_FILES_EXTENSIONS: &[&str] = &["bz2", "tbz", "tbz2"];
+pub const SEVEN_ZIP_FILES_EXTENSIONS: &[&str] = &["7z"];
+pub const ZIP_DOCUMENT_FILES_EXTENSIONS: &[&str] = &["docx", "xlsx", "pptx", "odt", "ods", "odp", "epub", "apk"];
+pub const FONT_FILES_EXTENSIONS: &[&str] = &["ttf", "otf"];
+pub const SQLITE_FILES_EXTENSIONS: &[&str] = &["sqlite", "sqlite3", "db3", "s3db"];
+pub const JSON_FILES_EXTENSIONS: &[&str] = &["json", "geojson"];
+pub const XML_FILES_EXTENSIONS: &[&str] = &["xml", "xsd", "xsl", "xslt", "svg", "gpx", "kml"];
+pub const YAML_FILES_EXTENSIONS: &[&str] = &["yaml", "yml"];
 pub const PDF_FILES_EXTENSIONS: &[&str] = &["pdf"];
 pub const AUDIO_FILES_EXTENSIONS: &[&str] = &[
     "mp3", "flac", "wav", "ogg", "m4a", "aac", "aiff", "pcm", "aif", "aiff", "aifc", "m3a", "mp2", "mp4a", "mp2a", "mpga", "wave", "weba", "wma", "oga",
 ];
+// Without extensions used also by audio files
+pub const VIDEO_BROKEN_FILES_EXTENSIONS: &[&str] = &[
+    "mp4", "m4v", "m4p", "mov", "qt", "3gp", "mkv", "webm", "avi", "flv", "wmv", "mpg", "mpeg", "mpv", "mts", "m2ts",
+];
 pub const VIDEO_FILES_EXTENSIONS: &[&str] = &[
     "mp4", "mpv", "flv", "mp4a", "webm", "mpg", "mp2", "mpeg", "m4p", "m4v", "avi", "wmv", "qt", "mov", "swf", "mkv", "mts", "m2ts",
 ];
@@ -0,0 +1,567 @@
+use std::collections::HashSet;
+use std::fs::{self, File, FileTimes, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use fun_time::fun_time;
+use log::{debug, error};
+use serde::{Deserialize, Serialize};
+
+use crate::common::config_cache_path::{get_config_cache_path, is_config_cache_path_set};
+use crate::common::quarantine::{copy_and_remove, remove_restored_item_from_manifest};
+use crate::helpers::messages::Messages;
+
+pub const DELETION_JOURNAL_FILE: &str = "deletion_journal.jsonl";
+// When journal file is bigger, reverted entries and the oldest ones above limit are removed from it
+const MAX_JOURNAL_FILE_SIZE: u64 = 10 * 1024 * 1024;
+const MAX_JOURNAL_ENTRIES: usize = 20_000;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JournalAction {
+    // Permanently removed, cannot be restored
+    Delete,
+    MoveToTrash,
+    Move { destination: PathBuf },
+    Rename { destination: PathBuf },
+    // Moved into quarantine folder instead of removing
+    Quarantine { destination: PathBuf },
+    Hardlink { original: PathBuf },
+    Symlink { original: PathBuf },
+    // Entry with same operation id and path was already reverted
+    Undone,
+}
+
+impl JournalAction {
+    // Only files which still exist after action can be restored, so only for them hash is needed to check if they were modified
+    fn is_restorable_file(&self) -> bool {
+        matches!(
+            self,
+            Self::Move { .. } | Self::Rename { .. } | Self::Quarantine { .. } | Self::Hardlink { .. } | Self::Symlink { .. }
+        )
+    }
+}
+
+/// Single destructive action saved in journal, file is only appended, so old entries are never modified
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub operation_id: u64,
+    pub timestamp: u64,
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified_date: u64,
+    pub hash: Option<String>,
+    pub action: JournalAction,
+}
+
+/// Collects entries of single operation (e.g. one deletion started by user) and appends them to journal saved in cache folder
+#[derive(Debug)]
+pub struct DeletionJournal {
+    operation_id: u64,
+    // Without configured cache folder, journal is not saved, so entries are not collected
+    enabled: bool,
+    entries: Mutex<Vec<JournalEntry>>,
+}
+
+impl Default for DeletionJournal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeletionJournal {
+    pub fn new() -> Self {
+        Self {
+            operation_id: get_current_time().as_millis() as u64,
+            enabled: is_config_cache_path_set(),
+            entries: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn get_operation_id(&self) -> u64 {
+        self.operation_id
+    }
+
+    fn add_entry(&self, path: &Path, size: u64, modified_date: u64, hash: Option<String>, action: JournalAction) {
+        let entry = JournalEntry {
+            operation_id: self.operation_id,
+            timestamp: get_current_time().as_secs(),
+            path: path.to_path_buf(),
+            size,
+            modified_date,
+            hash,
+            action,
+        };
+        self.entries.lock().expect("Journal lock poisoned").push(entry);
+    }
+
+    // Runs action and adds entry to journal only if it succeeded - size, modification date and hash are read before running it
+    // Hash is needed to check later if restored file was not modified, so it is calculated only for actions which can be reverted
+    // File which cannot be read is still processed, but its entry has no hash
+    pub fn record(&self, path: &Path, action: JournalAction, action_fn: impl FnOnce() -> Result<(), String>) -> Result<(), String> {
+        if !self.enabled {
+            return action_fn();
+        }
+        let metadata = fs::symlink_metadata(path).map_err(|e| format!("Cannot read metadata of \"{}\", reason {e}", path.to_string_lossy()))?;
+        let modified_date = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |duration| duration.as_secs());
+        // Folders have no content, so only files are hashed
+        let hash = if metadata.is_file() && action.is_restorable_file() {
+            calculate_journal_hash(path).map_err(|e| debug!("{e}")).ok()
+        } else {
+            None
+        };
+
+        action_fn()?;
+        self.add_entry(path, metadata.len(), modified_date, hash, action);
+        Ok(())
+    }
+
+    #[fun_time(message = "save_deletion_journal", level = "debug")]
+    pub fn save(&self) -> Result<(), String> {
+        let entries = std::mem::take(&mut *self.entries.lock().expect("Journal lock poisoned"));
+        append_entries(&entries)
+    }
+
+    #[fun_time(message = "load_deletion_journal", level = "debug")]
+    pub fn load_entries(warnings: &mut Vec<String>) -> Vec<JournalEntry> {
+        let Some(journal_file) = get_journal_file() else {
+            return Vec::new();
+        };
+        read_entries(&journal_file, warnings)
+    }
+
+    // Returns entries which were not reverted yet
+    pub fn load_active_entries(warnings: &mut Vec<String>) -> Vec<JournalEntry> {
+        remove_reverted_entries(Self::load_entries(warnings))
+    }
+
+    // Reverts all still active entries of operation, when no operation is given, the last one which can be reverted is used
+    #[fun_time(message = "undo_operation", level = "debug")]
+    pub fn undo_operation(operation_id: Option<u64>) -> Messages {
+        let mut messages = Messages::new();
+        let entries = Self::load_active_entries(&mut messages.warnings);
+
+        let Some(operation_id) = operation_id.or_else(|| entries.iter().rev().find(|entry| entry.action != JournalAction::Delete).map(|entry| entry.operation_id)) else {
+            messages.messages.push("There is nothing to undo".to_string());
+            return messages;
+        };
+        let entries_to_undo: Vec<_> = entries.into_iter().filter(|entry| entry.operation_id == operation_id).collect();
+        if entries_to_undo.is_empty() {
+            messages.errors.push(format!("Operation {operation_id} not found or was already reverted"));
+            return messages;
+        }
+
+        let mut undone_entries = Vec::new();
+        // Reverting in reverse order, to e.g. restore file moved twice in same operation
+        for entry in entries_to_undo.iter().rev() {
+            match undo_entry(entry) {
+                Ok(()) => undone_entries.push(JournalEntry {
+                    timestamp: get_current_time().as_secs(),
+                    action: JournalAction::Undone,
+                    ..entry.clone()
+                }),
+                Err(e) => messages.errors.push(e),
+            }
+        }
+        messages
+            .messages
+            .push(format!("Restored {}/{} entries from operation {operation_id}", undone_entries.len(), entries_to_undo.len()));
+
+        if let Err(e) = append_entries(&undone_entries) {
+            messages.errors.push(e);
+        }
+        messages
+    }
+}
+
+pub fn undo_entry(entry: &JournalEntry) -> Result<(), String> {
+    let path = &entry.path;
+    match &entry.action {
+        JournalAction::Delete => Err(format!("File \"{}\" was permanently deleted and cannot be restored", path.to_string_lossy())),
+        JournalAction::MoveToTrash => restore_from_trash(path),
+        JournalAction::Move { destination } | JournalAction::Rename { destination } | JournalAction::Quarantine { destination } => {
+            if path.exists() {
+                return Err(format!("Cannot restore \"{}\", because file with this name already exists", path.to_string_lossy()));
+            }
+            check_if_destination_is_unchanged(entry, destination)?;
+            if let Some(parent) = path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            let move_result = match fs::rename(destination, path) {
+                // Rename fails when file was moved to different partition
+                Err(e) if e.kind() == ErrorKind::CrossesDevices && !destination.is_dir() => copy_and_remove(destination, path),
+                result => result,
+            };
+            move_result.map_err(|e| format!("Failed to move \"{}\" back to \"{}\", reason {e}", destination.to_string_lossy(), path.to_string_lossy()))?;
+
+            if matches!(entry.action, JournalAction::Quarantine { .. })
+                && let Err(e) = remove_restored_item_from_manifest(destination)
+            {
+                error!("{e}");
+            }
+            Ok(())
+        }
+        // File content still exists in original file, so link is replaced by its independent copy
+        JournalAction::Hardlink { original } | JournalAction::Symlink { original } => replace_link_with_copy(path, entry.modified_date).map_err(|e| {
+            format!(
+                "Failed to replace link \"{}\" to \"{}\" with copy, reason {e}",
+                path.to_string_lossy(),
+                original.to_string_lossy()
+            )
+        }),
+        JournalAction::Undone => Ok(()),
+    }
+}
+
+// File moved by user or changed after operation, must not be overwritten by restored one
+fn check_if_destination_is_unchanged(entry: &JournalEntry, destination: &Path) -> Result<(), String> {
+    let metadata = fs::symlink_metadata(destination).map_err(|e| format!("Cannot read metadata of \"{}\", reason {e}", destination.to_string_lossy()))?;
+    if !metadata.is_file() {
+        return Ok(());
+    }
+    let is_changed = metadata.len() != entry.size
+        || match &entry.hash {
+            Some(hash) => calculate_journal_hash(destination)? != *hash,
+            None => false,
+        };
+    if is_changed {
+        return Err(format!(
+            "Cannot restore \"{}\", because \"{}\" was modified after operation",
+            entry.path.to_string_lossy(),
+            destination.to_string_lossy()
+        ));
+    }
+    Ok(())
+}
+
+pub fn calculate_journal_hash(path: &Path) -> Result<String, String> {
+    let file_handler = File::open(path).map_err(|e| format!("Cannot open \"{}\" to calculate hash, reason {e}", path.to_string_lossy()))?;
+    let mut hasher = blake3::Hasher::new();
+    hasher
+        .update_reader(file_handler)
+        .map_err(|e| format!("Cannot read \"{}\" to calculate hash, reason {e}", path.to_string_lossy()))?;
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+fn replace_link_with_copy(path: &Path, modified_date: u64) -> std::io::Result<()> {
+    let temp = path.with_file_name(format!("{}.czkawka_tmp", rand::random::<u128>()));
+    // Copy follows symlinks, so in both cases content and permissions of original file are copied
+    fs::copy(path, &temp)?;
+    if let Err(e) = set_modified_date_keeping_permissions(&temp, modified_date).and_then(|()| fs::rename(&temp, path)) {
+        let _ = fs::remove_file(&temp);
+        return Err(e);
+    }
+    Ok(())
+}
+
+// Read-only file cannot be opened for writing, so permissions are changed only for time of setting modification date
+fn set_modified_date_keeping_permissions(path: &Path, modified_date: u64) -> std::io::Result<()> {
+    let permissions = fs::metadata(path)?.permissions();
+    if permissions.readonly() {
+        let mut writable_permissions = permissions.clone();
+        #[expect(clippy::permissions_set_readonly_false)] // Permissions are restored just after setting modification date
+        writable_permissions.set_readonly(false);
+        fs::set_permissions(path, writable_permissions)?;
+    }
+    let set_times = OpenOptions::new()
+        .write(true)
+        .open(path)
+        .and_then(|file| file.set_times(FileTimes::new().set_modified(UNIX_EPOCH + Duration::from_secs(modified_date))));
+    let set_permissions = fs::set_permissions(path, permissions);
+    set_times.and(set_permissions)
+}
+
+#[cfg(any(
+    target_os = "windows",
+    all(target_family = "unix", not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+))]
+fn restore_from_trash(path: &Path) -> Result<(), String> {
+    if path.exists() {
+        return Err(format!("Cannot restore \"{}\", because file with this name already exists", path.to_string_lossy()));
+    }
+    let items = trash::os_limited::list().map_err(|e| format!("Cannot read trash content, reason {e}"))?;
+    // The same path could be trashed multiple times, so the most recently deleted item is restored
+    let Some(item) = items.into_iter().filter(|item| item.original_path() == path).max_by_key(|item| item.time_deleted) else {
+        return Err(format!("Cannot find \"{}\" in trash", path.to_string_lossy()));
+    };
+    trash::os_limited::restore_all([item]).map_err(|e| format!("Cannot restore \"{}\" from trash, reason {e}", path.to_string_lossy()))
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    all(target_family = "unix", not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+)))]
+fn restore_from_trash(path: &Path) -> Result<(), String> {
+    Err(format!("Restoring \"{}\" from trash is not supported on this platform", path.to_string_lossy()))
+}
+
+fn read_entries(journal_file: &Path, warnings: &mut Vec<String>) -> Vec<JournalEntry> {
+    let Ok(file_handler) = File::open(journal_file) else {
+        debug!("Deletion journal {journal_file:?} not exists");
+        return Vec::new();
+    };
+
+    let mut entries = Vec::new();
+    for (idx, line) in BufReader::new(file_handler).lines().enumerate() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                warnings.push(format!("Failed to read deletion journal {journal_file:?}, reason {e}"));
+                break;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        // Single broken line e.g. written when app crashed, should not make whole journal unusable
+        match serde_json::from_str(&line) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => warnings.push(format!("Failed to parse line {} of deletion journal {journal_file:?}, reason {e}", idx + 1)),
+        }
+    }
+    entries
+}
+
+fn remove_reverted_entries(entries: Vec<JournalEntry>) -> Vec<JournalEntry> {
+    let undone: HashSet<(u64, PathBuf)> = entries
+        .iter()
+        .filter(|entry| entry.action == JournalAction::Undone)
+        .map(|entry| (entry.operation_id, entry.path.clone()))
+        .collect();
+    entries
+        .into_iter()
+        .filter(|entry| entry.action != JournalAction::Undone && !undone.contains(&(entry.operation_id, entry.path.clone())))
+        .collect()
+}
+
+fn get_journal_file() -> Option<PathBuf> {
+    if !is_config_cache_path_set() {
+        return None;
+    }
+    Some(get_config_cache_path()?.cache_folder.join(DELETION_JOURNAL_FILE))
+}
+
+fn get_current_time() -> Duration {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+fn append_entries(entries: &[JournalEntry]) -> Result<(), String> {
+    // Without configured cache folder, journal is not used at all
+    if entries.is_empty() || !is_config_cache_path_set() {
+        return Ok(());
+    }
+    let Some(journal_file) = get_journal_file() else {
+        return Err("Cannot find cache folder to save deletion journal".to_string());
+    };
+
+    let file_handler = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(&journal_file)
+        .map_err(|e| format!("Cannot open deletion journal {journal_file:?}, reason {e}"))?;
+    let mut writer = BufWriter::new(file_handler);
+    for entry in entries {
+        let line = serde_json::to_string(entry).map_err(|e| format!("Cannot serialize deletion journal entry, reason {e}"))?;
+        writeln!(writer, "{line}").map_err(|e| format!("Cannot write to deletion journal {journal_file:?}, reason {e}"))?;
+    }
+    writer.flush().map_err(|e| format!("Cannot write to deletion journal {journal_file:?}, reason {e}"))?;
+    drop(writer);
+    debug!("Saved {} entries to deletion journal {journal_file:?}", entries.len());
+
+    if fs::metadata(&journal_file).is_ok_and(|metadata| metadata.len() > MAX_JOURNAL_FILE_SIZE) {
+        compact_journal(&journal_file, MAX_JOURNAL_ENTRIES)?;
+    }
+    Ok(())
+}
+
+#[fun_time(message = "compact_deletion_journal", level = "debug")]
+fn compact_journal(journal_file: &Path, max_entries: usize) -> Result<(), String> {
+    let mut warnings = Vec::new();
+    let mut entries = remove_reverted_entries(read_entries(journal_file, &mut warnings));
+    if entries.len() > max_entries {
+        entries.drain(..entries.len() - max_entries);
+    }
+
+    // Journal is replaced at once, so it is never left partially written
+    let temp_file = journal_file.with_extension("jsonl.tmp");
+    let write_result = File::create(&temp_file).map_err(|e| e.to_string()).and_then(|file_handler| {
+        let mut writer = BufWriter::new(file_handler);
+        for entry in &entries {
+            let line = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+            writeln!(writer, "{line}").map_err(|e| e.to_string())?;
+        }
+        writer.flush().map_err(|e| e.to_string())
+    });
+    if let Err(e) = write_result.and_then(|()| fs::rename(&temp_file, journal_file).map_err(|e| e.to_string())) {
+        let _ = fs::remove_file(&temp_file);
+        return Err(format!("Cannot compact deletion journal {journal_file:?}, reason {e}"));
+    }
+    debug!("Compacted deletion journal {journal_file:?} to {} entries", entries.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn entry(path: &Path, action: JournalAction) -> JournalEntry {
+        JournalEntry {
+            operation_id: 1,
+            timestamp: 0,
+            path: path.to_path_buf(),
+            size: 0,
+            modified_date: 1_000_000,
+            hash: None,
+            action,
+        }
+    }
+
+    // Entry of file moved to destination, with size and hash of its current content
+    fn moved_entry(path: &Path, destination: &Path) -> JournalEntry {
+        JournalEntry {
+            size: fs::metadata(destination).unwrap().len(),
+            hash: Some(calculate_journal_hash(destination).unwrap()),
+            ..entry(
+                path,
+                JournalAction::Move {
+                    destination: destination.to_path_buf(),
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn test_undo_move_and_hardlink() {
+        let temp_dir = tempdir().unwrap();
+        let original = temp_dir.path().join("original.txt");
+        let moved = temp_dir.path().join("moved.txt");
+        let linked = temp_dir.path().join("linked.txt");
+        fs::write(&original, "content").unwrap();
+        fs::write(&moved, "moved").unwrap();
+        fs::hard_link(&original, &linked).unwrap();
+
+        let restored = temp_dir.path().join("restored.txt");
+        undo_entry(&moved_entry(&restored, &moved)).unwrap();
+        assert!(!moved.exists());
+        assert_eq!(fs::read_to_string(&restored).unwrap(), "moved");
+        // Restoring over existing file is not allowed
+        assert!(undo_entry(&entry(&restored, JournalAction::Rename { destination: original.clone() })).is_err());
+
+        // Link to read-only file is replaced with read-only copy
+        let mut permissions = fs::metadata(&original).unwrap().permissions();
+        permissions.set_readonly(true);
+        fs::set_permissions(&original, permissions).unwrap();
+        undo_entry(&entry(&linked, JournalAction::Hardlink { original: original.clone() })).unwrap();
+        assert!(fs::metadata(&linked).unwrap().permissions().readonly());
+        let mut permissions = fs::metadata(&original).unwrap().permissions();
+        #[expect(clippy::permissions_set_readonly_false)]
+        permissions.set_readonly(false);
+        fs::set_permissions(&original, permissions).unwrap();
+        fs::write(&original, "changed").unwrap();
+        assert_eq!(fs::read_to_string(&linked).unwrap(), "content");
+        let modified = fs::metadata(&linked).unwrap().modified().unwrap();
+        assert_eq!(modified, UNIX_EPOCH + Duration::from_secs(1_000_000));
+
+        assert!(undo_entry(&entry(&original, JournalAction::Delete)).is_err());
+    }
+
+    #[test]
+    fn test_undo_does_not_restore_modified_file() {
+        let temp_dir = tempdir().unwrap();
+        let moved = temp_dir.path().join("moved.txt");
+        let restored = temp_dir.path().join("restored.txt");
+        fs::write(&moved, "moved").unwrap();
+        let journal_entry = moved_entry(&restored, &moved);
+
+        // Same size, different content
+        fs::write(&moved, "other").unwrap();
+        assert!(undo_entry(&journal_entry).is_err());
+        fs::write(&moved, "bigger content").unwrap();
+        assert!(undo_entry(&journal_entry).is_err());
+        assert!(!restored.exists());
+
+        fs::write(&moved, "moved").unwrap();
+        undo_entry(&journal_entry).unwrap();
+        assert_eq!(fs::read_to_string(&restored).unwrap(), "moved");
+    }
+
+    #[test]
+    fn test_record_hashes_only_restorable_files() {
+        let temp_dir = tempdir().unwrap();
+        let file = temp_dir.path().join("file.txt");
+        let moved = temp_dir.path().join("moved.txt");
+        fs::write(&file, "content").unwrap();
+
+        let journal = DeletionJournal {
+            enabled: true,
+            ..DeletionJournal::new()
+        };
+        let action = JournalAction::Move { destination: moved.clone() };
+        journal.record(&file, action, || fs::rename(&file, &moved).map_err(|e| e.to_string())).unwrap();
+        journal
+            .record(&moved, JournalAction::Delete, || fs::remove_file(&moved).map_err(|e| e.to_string()))
+            .unwrap();
+        // Action is not run and not recorded, when file not exists
+        assert!(journal.record(&file, JournalAction::Delete, || Ok(())).is_err());
+
+        let entries = journal.entries.lock().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].size, 7);
+        assert_eq!(entries[0].hash.as_deref(), Some(blake3::hash(b"content").to_hex().as_str()));
+        // Permanently deleted file cannot be restored, so it is not hashed
+        assert_eq!(entries[1].size, 7);
+        assert_eq!(entries[1].hash, None);
+    }
+
+    #[test]
+    fn test_record_is_skipped_when_journal_is_disabled() {
+        let temp_dir = tempdir().unwrap();
+        let file = temp_dir.path().join("file.txt");
+
+        let journal = DeletionJournal {
+            enabled: false,
+            ..DeletionJournal::new()
+        };
+        // Action is run even without reading file, e.g. when it cannot be read
+        let mut action_run = false;
+        journal
+            .record(&file, JournalAction::Rename { destination: file.clone() }, || {
+                action_run = true;
+                Ok(())
+            })
+            .unwrap();
+        assert!(action_run);
+        assert!(journal.entries.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_compact_journal() {
+        let temp_dir = tempdir().unwrap();
+        let journal_file = temp_dir.path().join(DELETION_JOURNAL_FILE);
+        let mut entries = (0..5)
+            .map(|idx| JournalEntry {
+                operation_id: idx,
+                ..entry(Path::new("/file"), JournalAction::MoveToTrash)
+            })
+            .collect::<Vec<_>>();
+        entries.push(JournalEntry {
+            operation_id: 4,
+            ..entry(Path::new("/file"), JournalAction::Undone)
+        });
+        let content = entries.iter().map(|entry| serde_json::to_string(entry).unwrap() + "\n").collect::<String>();
+        fs::write(&journal_file, content).unwrap();
+
+        compact_journal(&journal_file, 3).unwrap();
+        let mut warnings = Vec::new();
+        let entries = read_entries(&journal_file, &mut warnings);
+        assert!(warnings.is_empty());
+        assert_eq!(entries.iter().map(|entry| entry.operation_id).collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+}
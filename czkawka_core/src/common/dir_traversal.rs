@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
 use std::fs::{DirEntry, FileType, Metadata};
 #[cfg(target_family = "unix")]
@@ -14,8 +14,11 @@ use log::debug;
 use rayon::prelude::*;
 
 use crate::common::directories::Directories;
+use crate::common::directory_snapshot::{DirectorySnapshot, DirectorySnapshots, load_directory_snapshots, save_directory_snapshots};
 use crate::common::extensions::Extensions;
-use crate::common::items::ExcludedItems;
+use crate::common::ignore_files::IgnoreStack;
+use crate::common::items::{ExcludedItems, IncludedItems};
+use crate::common::metadata_filter::MetadataFilter;
 use crate::common::model::{CheckingMethod, FileEntry, ToolType};
 use crate::common::progress_data::{CurrentStage, ProgressData};
 use crate::common::progress_stop_handler::{check_if_stop_received, prepare_thread_handler_common};
@@ -43,13 +46,21 @@ pub struct DirTraversalBuilder<'b, F> {
     progress_sender: Option<&'b Sender<ProgressData>>,
     minimal_file_size: Option<u64>,
     maximal_file_size: Option<u64>,
+    metadata_filter: MetadataFilter,
     checking_method: CheckingMethod,
+    has_optional_stage: bool,
     collect: Collect,
     recursive_search: bool,
     directories: Option<Directories>,
     excluded_items: Option<ExcludedItems>,
+    included_items: IncludedItems,
     extensions: Option<Extensions>,
     tool_type: ToolType,
+    use_directory_snapshot: bool,
+    use_ignore_files: bool,
+    max_depth: Option<usize>,
+    follow_symlinks: bool,
+    delete_outdated_cache: bool,
 }
 
 pub struct DirTraversal<'b, F> {
@@ -60,12 +71,20 @@ pub struct DirTraversal<'b, F> {
     recursive_search: bool,
     directories: Directories,
     excluded_items: ExcludedItems,
+    included_items: IncludedItems,
     extensions: Extensions,
     minimal_file_size: u64,
     maximal_file_size: u64,
+    metadata_filter: MetadataFilter,
     checking_method: CheckingMethod,
+    has_optional_stage: bool,
     tool_type: ToolType,
     collect: Collect,
+    use_directory_snapshot: bool,
+    use_ignore_files: bool,
+    max_depth: Option<usize>,
+    follow_symlinks: bool,
+    delete_outdated_cache: bool,
 }
 
 impl Default for DirTraversalBuilder<'_, ()> {
@@ -82,14 +101,22 @@ impl DirTraversalBuilder<'_, ()> {
             stop_flag: None,
             progress_sender: None,
             checking_method: CheckingMethod::None,
+            has_optional_stage: false,
             minimal_file_size: None,
             maximal_file_size: None,
+            metadata_filter: MetadataFilter::default(),
             collect: Collect::Files,
             recursive_search: false,
             directories: None,
             extensions: None,
             excluded_items: None,
+            included_items: IncludedItems::default(),
             tool_type: ToolType::None,
+            use_directory_snapshot: false,
+            use_ignore_files: false,
+            max_depth: None,
+            follow_symlinks: false,
+            delete_outdated_cache: false,
         }
     }
 }
@@ -99,11 +126,18 @@ impl<'b, F> DirTraversalBuilder<'b, F> {
         self.root_dirs = common_tool_data.directories.included_directories.clone();
         self.extensions = Some(common_tool_data.extensions.clone());
         self.excluded_items = Some(common_tool_data.excluded_items.clone());
+        self.included_items = common_tool_data.included_items.clone();
         self.recursive_search = common_tool_data.recursive_search;
         self.minimal_file_size = Some(common_tool_data.minimal_file_size);
         self.maximal_file_size = Some(common_tool_data.maximal_file_size);
+        self.metadata_filter = common_tool_data.metadata_filter.clone();
         self.tool_type = common_tool_data.tool_type;
         self.directories = Some(common_tool_data.directories.clone());
+        self.use_directory_snapshot = common_tool_data.use_directory_snapshot;
+        self.use_ignore_files = common_tool_data.use_ignore_files;
+        self.max_depth = common_tool_data.max_depth;
+        self.follow_symlinks = common_tool_data.follow_symlinks;
+        self.delete_outdated_cache = common_tool_data.delete_outdated_cache;
         self
     }
 
@@ -122,6 +156,11 @@ impl<'b, F> DirTraversalBuilder<'b, F> {
         self
     }
 
+    pub(crate) fn has_optional_stage(mut self, has_optional_stage: bool) -> Self {
+        self.has_optional_stage = has_optional_stage;
+        self
+    }
+
     pub(crate) fn minimal_file_size(mut self, minimal_file_size: u64) -> Self {
         self.minimal_file_size = Some(minimal_file_size);
         self
@@ -149,12 +188,20 @@ impl<'b, F> DirTraversalBuilder<'b, F> {
             directories: self.directories,
             extensions: self.extensions,
             excluded_items: self.excluded_items,
+            included_items: self.included_items,
             recursive_search: self.recursive_search,
             maximal_file_size: self.maximal_file_size,
             minimal_file_size: self.minimal_file_size,
+            metadata_filter: self.metadata_filter,
             collect: self.collect,
             checking_method: self.checking_method,
+            has_optional_stage: self.has_optional_stage,
             tool_type: self.tool_type,
+            use_directory_snapshot: self.use_directory_snapshot,
+            use_ignore_files: self.use_ignore_files,
+            max_depth: self.max_depth,
+            follow_symlinks: self.follow_symlinks,
+            delete_outdated_cache: self.delete_outdated_cache,
         }
     }
 
@@ -165,18 +212,33 @@ impl<'b, F> DirTraversalBuilder<'b, F> {
             stop_flag: self.stop_flag.expect("Stop flag must be always initialized"),
             progress_sender: self.progress_sender,
             checking_method: self.checking_method,
+            has_optional_stage: self.has_optional_stage,
             minimal_file_size: self.minimal_file_size.unwrap_or(0),
             maximal_file_size: self.maximal_file_size.unwrap_or(u64::MAX),
+            metadata_filter: self.metadata_filter,
             collect: self.collect,
             directories: self.directories.expect("could not build"),
             excluded_items: self.excluded_items.expect("could not build"),
+            included_items: self.included_items,
             extensions: self.extensions.unwrap_or_default(),
             recursive_search: self.recursive_search,
             tool_type: self.tool_type,
+            use_directory_snapshot: self.use_directory_snapshot,
+            use_ignore_files: self.use_ignore_files,
+            max_depth: self.max_depth,
+            follow_symlinks: self.follow_symlinks,
+            delete_outdated_cache: self.delete_outdated_cache,
         }
     }
 }
 
+// Folder waiting to be read, together with state inherited from its parent
+struct FolderToCheck {
+    path: PathBuf,
+    depth: usize,
+    ignore_stack: IgnoreStack,
+}
+
 pub enum DirTraversalResult<T: Ord + PartialOrd> {
     SuccessFiles {
         warnings: Vec<String>,
@@ -204,43 +266,135 @@ where
 {
     #[fun_time(message = "run(collecting files/dirs)", level = "debug")]
     pub(crate) fn run(self) -> DirTraversalResult<T> {
+        // Snapshot contains only files and real folders, so it cannot be used to find or follow symlinks or to filter by access time and owner
+        if !self.use_directory_snapshot || self.collect != Collect::Files || self.follow_symlinks || self.metadata_filter.needs_full_metadata() {
+            return self.run_with_snapshots(None).0;
+        }
+
+        let mut snapshot_warnings = vec![];
+        let snapshots = load_directory_snapshots(&mut snapshot_warnings);
+        let delete_outdated_cache = self.delete_outdated_cache;
+
+        let (mut result, snapshots) = self.run_with_snapshots(Some(snapshots));
+        if let DirTraversalResult::SuccessFiles { warnings, .. } = &mut result {
+            if let Some((snapshots, changed)) = snapshots {
+                save_directory_snapshots(snapshots, changed, delete_outdated_cache, &mut snapshot_warnings);
+            }
+            warnings.extend(snapshot_warnings);
+        }
+        result
+    }
+
+    // Directories with unchanged modification date and inode are not read again, instead data from snapshot is used
+    // Returned snapshots are marked as changed, when at least one directory was read again
+    fn run_with_snapshots(self, mut snapshots: Option<DirectorySnapshots>) -> (DirTraversalResult<T>, Option<(DirectorySnapshots, bool)>) {
         assert_ne!(self.tool_type, ToolType::None, "Tool type cannot be None");
 
         let mut all_warnings = vec![];
         let mut grouped_file_entries: BTreeMap<T, Vec<FileEntry>> = BTreeMap::new();
+        let mut snapshots_changed = false;
 
         // Add root folders for finding
-        let mut folders_to_check: Vec<PathBuf> = self.root_dirs.clone();
+        let mut folders_to_check: Vec<FolderToCheck> = self
+            .root_dirs
+            .iter()
+            .map(|dir| FolderToCheck {
+                path: dir.clone(),
+                depth: 0,
+                ignore_stack: IgnoreStack::default(),
+            })
+            .collect();
+
+        // Symlinked folders may point to its parents, so every visited folder is remembered to avoid infinite loops
+        let mut visited_folders = HashSet::new();
+        if self.follow_symlinks {
+            visited_folders.extend(folders_to_check.iter().filter_map(|folder| get_folder_id(&folder.path)));
+        }
 
-        let progress_handler = prepare_thread_handler_common(self.progress_sender, CurrentStage::CollectingFiles, 0, (self.tool_type, self.checking_method), 0);
+        let progress_handler = prepare_thread_handler_common(
+            self.progress_sender,
+            CurrentStage::CollectingFiles,
+            0,
+            (self.tool_type, self.checking_method, self.has_optional_stage),
+            0,
+        );
 
         let DirTraversal {
             collect,
             directories,
             excluded_items,
+            included_items,
             extensions,
             recursive_search,
             minimal_file_size,
             maximal_file_size,
+            metadata_filter,
+            use_ignore_files,
+            max_depth,
+            follow_symlinks,
             stop_flag,
             ..
         } = self;
         while !folders_to_check.is_empty() {
             if check_if_stop_received(&stop_flag) {
                 progress_handler.join_thread();
-                return DirTraversalResult::Stopped;
+                return (DirTraversalResult::Stopped, None);
             }
 
             let segments: Vec<_> = folders_to_check
                 .into_par_iter()
                 .with_max_len(2) // Avoiding checking too many folders in batch
-                .map(|current_folder| {
+                .map(|folder_to_check| {
+                    let FolderToCheck {
+                        path: current_folder,
+                        depth,
+                        ignore_stack: parent_ignore_stack,
+                    } = folder_to_check;
                     let mut dir_result = vec![];
                     let mut warnings = vec![];
                     let mut fe_result = vec![];
+                    let ignore_stack = if use_ignore_files {
+                        parent_ignore_stack.with_folder(&current_folder, &mut warnings)
+                    } else {
+                        parent_ignore_stack
+                    };
+                    let check_subfolders = recursive_search && max_depth.is_none_or(|max_depth| depth < max_depth);
+                    let mut new_snapshot = if let Some(snapshots) = &snapshots {
+                        let metadata = fs::metadata(&current_folder);
+                        if let Ok(metadata) = &metadata
+                            && let Some(snapshot) = snapshots.get(&current_folder)
+                            && snapshot.is_up_to_date(metadata)
+                        {
+                            for dir_path in &snapshot.directories {
+                                process_dir_in_file_symlink_mode(check_subfolders, dir_path.clone(), &directories, &mut dir_result, &mut warnings, &excluded_items);
+                            }
+                            for fe in &snapshot.files {
+                                process_file_entry_from_snapshot(
+                                    fe,
+                                    &mut warnings,
+                                    &mut fe_result,
+                                    &extensions,
+                                    &directories,
+                                    &excluded_items,
+                                    &included_items,
+                                    minimal_file_size,
+                                    maximal_file_size,
+                                    &metadata_filter,
+                                );
+                            }
+                            if !snapshot.files.is_empty() {
+                                progress_handler.increase_items(snapshot.files.len());
+                            }
+                            remove_ignored_entries(&ignore_stack, &mut dir_result, &mut fe_result);
+                            return Some((into_folders_to_check(dir_result, depth, &ignore_stack), warnings, fe_result, None));
+                        }
+                        metadata.ok().and_then(|metadata| DirectorySnapshot::new(&metadata))
+                    } else {
+                        None
+                    };
 
                     let Some(read_dir) = common_read_dir(&current_folder, &mut warnings) else {
-                        return Some((dir_result, warnings, fe_result));
+                        return Some((vec![], warnings, fe_result, None));
                     };
 
                     let mut counter = 0;
@@ -257,7 +411,32 @@ where
 
                         match (entry_type(file_type), collect) {
                             (EntryType::Dir, Collect::Files | Collect::InvalidSymlinks) => {
-                                process_dir_in_file_symlink_mode(recursive_search, entry_data, &directories, &mut dir_result, &mut warnings, &excluded_items);
+                                if let Some(snapshot) = &mut new_snapshot {
+                                    snapshot.directories.push(entry_data.path());
+                                }
+                                process_dir_in_file_symlink_mode(check_subfolders, entry_data.path(), &directories, &mut dir_result, &mut warnings, &excluded_items);
+                            }
+                            (EntryType::File, Collect::Files) if new_snapshot.is_some() => {
+                                counter += 1;
+                                // All files must be saved in snapshot, even if they are not used now
+                                let Some(fe) = get_file_entry(entry_data, &mut warnings) else {
+                                    continue;
+                                };
+                                process_file_entry_from_snapshot(
+                                    &fe,
+                                    &mut warnings,
+                                    &mut fe_result,
+                                    &extensions,
+                                    &directories,
+                                    &excluded_items,
+                                    &included_items,
+                                    minimal_file_size,
+                                    maximal_file_size,
+                                    &metadata_filter,
+                                );
+                                if let Some(snapshot) = &mut new_snapshot {
+                                    snapshot.files.push(fe);
+                                }
                             }
                             (EntryType::File, Collect::Files) => {
                                 counter += 1;
@@ -268,8 +447,10 @@ where
                                     &extensions,
                                     &directories,
                                     &excluded_items,
+                                    &included_items,
                                     minimal_file_size,
                                     maximal_file_size,
+                                    &metadata_filter,
                                 );
                             }
                             (EntryType::File, Collect::InvalidSymlinks) => {
@@ -277,7 +458,13 @@ where
                             }
                             (EntryType::Symlink, Collect::InvalidSymlinks) => {
                                 counter += 1;
-                                process_symlink_in_symlink_mode(entry_data, &mut warnings, &mut fe_result, &extensions, &directories, &excluded_items);
+                                process_symlink_in_symlink_mode(entry_data, &mut warnings, &mut fe_result, &extensions, &directories, &excluded_items, &included_items);
+                            }
+                            (EntryType::Symlink, Collect::Files) if follow_symlinks => {
+                                // Only symlinks pointing to folders are followed
+                                if fs::metadata(entry_data.path()).is_ok_and(|metadata| metadata.is_dir()) {
+                                    process_dir_in_file_symlink_mode(check_subfolders, entry_data.path(), &directories, &mut dir_result, &mut warnings, &excluded_items);
+                                }
                             }
                             (EntryType::Symlink, Collect::Files) | (EntryType::Other, _) => {
                                 // nothing to do
@@ -288,17 +475,35 @@ where
                         // Increase counter in batch, because usually it may be slow to add multiple times atomic value
                         progress_handler.increase_items(counter);
                     }
-                    Some((dir_result, warnings, fe_result))
+
+                    // Folder which was not fully read, must be checked again in next scan
+                    let new_snapshot = if warnings.is_empty() {
+                        new_snapshot.map(|snapshot| (current_folder, snapshot))
+                    } else {
+                        None
+                    };
+                    remove_ignored_entries(&ignore_stack, &mut dir_result, &mut fe_result);
+                    Some((into_folders_to_check(dir_result, depth, &ignore_stack), warnings, fe_result, new_snapshot))
                 })
                 .while_some()
                 .collect();
 
-            let required_size = segments.iter().map(|(segment, _, _)| segment.len()).sum::<usize>();
+            let required_size = segments.iter().map(|(segment, _, _, _)| segment.len()).sum::<usize>();
             folders_to_check = Vec::with_capacity(required_size);
 
             // Process collected data
-            for (segment, warnings, mut fe_result) in segments {
-                folders_to_check.extend(segment);
+            for (segment, warnings, mut fe_result, new_snapshot) in segments {
+                if let Some(snapshots) = &mut snapshots
+                    && let Some((folder, snapshot)) = new_snapshot
+                {
+                    snapshots.insert(folder, snapshot);
+                    snapshots_changed = true;
+                }
+                if follow_symlinks {
+                    folders_to_check.extend(segment.into_iter().filter(|folder| get_folder_id(&folder.path).is_none_or(|id| visited_folders.insert(id))));
+                } else {
+                    folders_to_check.extend(segment);
+                }
                 all_warnings.extend(warnings);
                 fe_result.sort_by_cached_key(|fe| fe.path.to_string_lossy().to_string());
                 for fe in fe_result {
@@ -313,14 +518,48 @@ where
         debug!("Collected {} files", grouped_file_entries.values().map(Vec::len).sum::<usize>());
 
         match collect {
-            Collect::Files | Collect::InvalidSymlinks => DirTraversalResult::SuccessFiles {
-                grouped_file_entries,
-                warnings: all_warnings,
-            },
+            Collect::Files | Collect::InvalidSymlinks => (
+                DirTraversalResult::SuccessFiles {
+                    grouped_file_entries,
+                    warnings: all_warnings,
+                },
+                snapshots.map(|snapshots| (snapshots, snapshots_changed)),
+            ),
         }
     }
 }
 
+fn into_folders_to_check(dir_result: Vec<PathBuf>, parent_depth: usize, ignore_stack: &IgnoreStack) -> Vec<FolderToCheck> {
+    dir_result
+        .into_iter()
+        .map(|path| FolderToCheck {
+            path,
+            depth: parent_depth + 1,
+            ignore_stack: ignore_stack.clone(),
+        })
+        .collect()
+}
+
+// Identifies folder independently of path used to reach it
+#[cfg(target_family = "unix")]
+pub(crate) fn get_folder_id(path: &Path) -> Option<(u64, u64)> {
+    fs::metadata(path).ok().map(|metadata| (metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(target_family = "unix"))]
+pub(crate) fn get_folder_id(path: &Path) -> Option<PathBuf> {
+    dunce::canonicalize(path).ok()
+}
+
+// Snapshots always contain all entries, so ignore files are applied after reading folder
+fn remove_ignored_entries(ignore_stack: &IgnoreStack, dir_result: &mut Vec<PathBuf>, fe_result: &mut Vec<FileEntry>) {
+    if ignore_stack.is_empty() {
+        return;
+    }
+    dir_result.retain(|dir_path| !ignore_stack.is_ignored(dir_path, true));
+    fe_result.retain(|fe| !ignore_stack.is_ignored(&fe.path, false));
+}
+
 fn process_file_in_file_mode(
     entry_data: &DirEntry,
     warnings: &mut Vec<String>,
@@ -328,15 +567,17 @@ fn process_file_in_file_mode(
     extensions: &Extensions,
     directories: &Directories,
     excluded_items: &ExcludedItems,
+    included_items: &IncludedItems,
     minimal_file_size: u64,
     maximal_file_size: u64,
+    metadata_filter: &MetadataFilter,
 ) {
     if !extensions.check_if_entry_have_valid_extension(entry_data) {
         return;
     }
 
     let current_file_name = entry_data.path();
-    if excluded_items.is_excluded(&current_file_name) {
+    if excluded_items.is_excluded(&current_file_name) || !included_items.is_included(&current_file_name) {
         return;
     }
 
@@ -357,10 +598,15 @@ fn process_file_in_file_mode(
     };
 
     if (minimal_file_size..=maximal_file_size).contains(&metadata.len()) {
+        let modified_date = get_modified_time(&metadata, warnings, &current_file_name, false);
+        if !metadata_filter.is_allowed(&metadata, modified_date) {
+            return;
+        }
+
         // Creating new file entry
         let fe: FileEntry = FileEntry {
             size: metadata.len(),
-            modified_date: get_modified_time(&metadata, warnings, &current_file_name, false),
+            modified_date,
             path: current_file_name,
         };
 
@@ -368,9 +614,60 @@ fn process_file_in_file_mode(
     }
 }
 
+fn get_file_entry(entry_data: &DirEntry, warnings: &mut Vec<String>) -> Option<FileEntry> {
+    let current_file_name = entry_data.path();
+    let metadata = common_get_metadata_dir(entry_data, warnings, &current_file_name)?;
+    Some(FileEntry {
+        size: metadata.len(),
+        modified_date: get_modified_time(&metadata, warnings, &current_file_name, false),
+        path: current_file_name,
+    })
+}
+
+// Same checks as in process_file_in_file_mode, but metadata are already known
+fn process_file_entry_from_snapshot(
+    fe: &FileEntry,
+    warnings: &mut Vec<String>,
+    fe_result: &mut Vec<FileEntry>,
+    extensions: &Extensions,
+    directories: &Directories,
+    excluded_items: &ExcludedItems,
+    included_items: &IncludedItems,
+    minimal_file_size: u64,
+    maximal_file_size: u64,
+    metadata_filter: &MetadataFilter,
+) {
+    let Some(file_name) = fe.path.file_name() else {
+        return;
+    };
+    if !extensions.check_if_file_name_have_valid_extension(file_name) {
+        return;
+    }
+
+    if excluded_items.is_excluded(&fe.path) || !included_items.is_included(&fe.path) {
+        return;
+    }
+
+    #[cfg(target_family = "unix")]
+    if directories.exclude_other_filesystems() {
+        match directories.is_on_other_filesystems(&fe.path) {
+            Ok(true) => return,
+            Err(e) => warnings.push(e),
+            _ => (),
+        }
+    }
+
+    #[cfg(windows)]
+    let _ = (directories, warnings); // Silence unused variable warning on Windows
+
+    if (minimal_file_size..=maximal_file_size).contains(&fe.size) && metadata_filter.is_modified_date_allowed(fe.modified_date) {
+        fe_result.push(fe.clone());
+    }
+}
+
 fn process_dir_in_file_symlink_mode(
     recursive_search: bool,
-    entry_data: &DirEntry,
+    dir_path: PathBuf,
     directories: &Directories,
     dir_result: &mut Vec<PathBuf>,
     warnings: &mut Vec<String>,
@@ -380,7 +677,6 @@ fn process_dir_in_file_symlink_mode(
         return;
     }
 
-    let dir_path = entry_data.path();
     if directories.is_excluded(&dir_path) {
         return;
     }
@@ -411,13 +707,14 @@ fn process_symlink_in_symlink_mode(
     extensions: &Extensions,
     directories: &Directories,
     excluded_items: &ExcludedItems,
+    included_items: &IncludedItems,
 ) {
     if !extensions.check_if_entry_have_valid_extension(entry_data) {
         return;
     }
 
     let current_file_name = entry_data.path();
-    if excluded_items.is_excluded(&current_file_name) {
+    if excluded_items.is_excluded(&current_file_name) || !included_items.is_included(&current_file_name) {
         return;
     }
 
@@ -536,6 +833,7 @@ pub(crate) fn take_1_per_inode((k, mut v): (Option<u64>, Vec<FileEntry>)) -> Vec
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::fs::File;
     use std::io::prelude::*;
     use std::time::{Duration, SystemTime};
@@ -630,6 +928,156 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_traversal_with_directory_snapshot() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        let (src, hard, other) = create_files(&dir)?;
+
+        let mut common_data = CommonToolData::new(ToolType::SimilarImages);
+        common_data.directories.set_included_directory([dir.path().to_owned()].to_vec());
+        common_data.set_minimal_file_size(0);
+        common_data.set_use_directory_snapshot(true);
+
+        let run = |snapshots: DirectorySnapshots| {
+            let (DirTraversalResult::SuccessFiles { grouped_file_entries, .. }, Some((snapshots, changed))) = DirTraversalBuilder::new()
+                .group_by(|_fe| ())
+                .stop_flag(&Arc::default())
+                .common_data(&common_data)
+                .build()
+                .run_with_snapshots(Some(snapshots))
+            else {
+                panic!("Expect SuccessFiles.");
+            };
+            let files: IndexSet<_> = grouped_file_entries.into_values().flatten().collect();
+            (files, snapshots, changed)
+        };
+
+        let (files, snapshots, changed) = run(BTreeMap::new());
+        assert!(changed);
+        assert_eq!(
+            IndexSet::from([src.clone(), hard.clone(), other.clone()]),
+            files.iter().map(|fe| fe.path.clone()).collect::<IndexSet<_>>()
+        );
+        assert_eq!(snapshots[dir.path()].files.len(), 3);
+
+        // Directory is not changed, so files are taken from snapshot and nothing needs to be saved
+        let mut snapshot = snapshots[dir.path()].clone();
+        snapshot.files.retain(|fe| fe.path != other);
+        let (files, _, changed) = run(BTreeMap::from([(dir.path().to_owned(), snapshot.clone())]));
+        assert!(!changed);
+        assert_eq!(IndexSet::from([src.clone(), hard.clone()]), files.into_iter().map(|fe| fe.path).collect::<IndexSet<_>>());
+
+        // Directory created again in the same place has different inode
+        snapshot.inode = snapshot.inode.wrapping_add(1);
+        let (files, _, changed) = run(BTreeMap::from([(dir.path().to_owned(), snapshot)]));
+        assert!(changed);
+        assert_eq!(
+            IndexSet::from([src.clone(), hard.clone(), other.clone()]),
+            files.into_iter().map(|fe| fe.path).collect::<IndexSet<_>>()
+        );
+
+        // Outdated snapshot must be replaced by real content of directory
+        let mut snapshot = snapshots[dir.path()].clone();
+        snapshot.modified = SystemTime::UNIX_EPOCH;
+        let (files, snapshots, _) = run(BTreeMap::from([(dir.path().to_owned(), snapshot)]));
+        assert_eq!(IndexSet::from([src.clone(), hard, other]), files.into_iter().map(|fe| fe.path).collect::<IndexSet<_>>());
+        assert_eq!(snapshots[dir.path()].modified, fs::metadata(dir.path())?.modified()?);
+
+        // Files are not checked one by one, so file edited in place (without changing modification date of directory) is reported with its old size
+        let old_size = snapshots[dir.path()].files.iter().find(|fe| fe.path == src).expect("File must be in snapshot").size;
+        fs::write(&src, b"Different content of file")?;
+        let (files, _, changed) = run(snapshots);
+        assert!(!changed);
+        let edited = files.into_iter().find(|fe| fe.path == src).expect("Edited file must be found");
+        assert_eq!(edited.size, old_size);
+        Ok(())
+    }
+
+    #[test]
+    fn test_traversal_with_ignore_files() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        let (src, hard, _other) = create_files(&dir)?;
+        fs::write(dir.path().join(".czkawkaignore"), "c\nbuild/\n.*ignore\n")?;
+        fs::create_dir_all(dir.path().join("build"))?;
+        fs::write(dir.path().join("build").join("d"), CONTENT)?;
+        fs::create_dir_all(dir.path().join("sub"))?;
+        fs::write(dir.path().join("sub").join("c"), CONTENT)?;
+        fs::write(dir.path().join("sub").join(".gitignore"), "!c\n")?;
+
+        let mut common_data = CommonToolData::new(ToolType::SimilarImages);
+        common_data.directories.set_included_directory([dir.path().to_owned()].to_vec());
+        common_data.set_minimal_file_size(0);
+        common_data.set_recursive_search(true);
+        common_data.set_use_ignore_files(true);
+
+        let DirTraversalResult::SuccessFiles { grouped_file_entries, .. } = DirTraversalBuilder::new()
+            .group_by(|_fe| ())
+            .stop_flag(&Arc::default())
+            .common_data(&common_data)
+            .build()
+            .run()
+        else {
+            panic!("Expect SuccessFiles.");
+        };
+        let actual: IndexSet<_> = grouped_file_entries.into_values().flatten().map(|fe| fe.path).collect();
+        // File `c` is ignored only in root folder, because nested ignore file negates this rule
+        assert_eq!(IndexSet::from([src, hard, dir.path().join("sub").join("c")]), actual);
+        Ok(())
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn test_traversal_max_depth_and_follow_symlinks() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        let outside = tempfile::Builder::new().tempdir()?;
+        let (src, hard, other) = create_files(&dir)?;
+        fs::create_dir_all(dir.path().join("sub").join("deep"))?;
+        fs::write(dir.path().join("sub").join("d"), CONTENT)?;
+        fs::write(dir.path().join("sub").join("deep").join("e"), CONTENT)?;
+        fs::write(outside.path().join("f"), CONTENT)?;
+        std::os::unix::fs::symlink(outside.path(), dir.path().join("linked"))?;
+        // Loop pointing to root folder must not be followed again
+        std::os::unix::fs::symlink(dir.path(), dir.path().join("sub").join("loop"))?;
+
+        let collect = |common_data: &CommonToolData| {
+            let DirTraversalResult::SuccessFiles { grouped_file_entries, .. } = DirTraversalBuilder::new()
+                .group_by(|_fe| ())
+                .stop_flag(&Arc::default())
+                .common_data(common_data)
+                .build()
+                .run()
+            else {
+                panic!("Expect SuccessFiles.");
+            };
+            grouped_file_entries.into_values().flatten().map(|fe| fe.path).collect::<IndexSet<_>>()
+        };
+
+        let mut common_data = CommonToolData::new(ToolType::SimilarImages);
+        common_data.directories.set_included_directory([dir.path().to_owned()].to_vec());
+        common_data.set_minimal_file_size(0);
+        common_data.set_recursive_search(true);
+        common_data.set_max_depth(Some(1));
+        assert_eq!(
+            IndexSet::from([src.clone(), hard.clone(), other.clone(), dir.path().join("sub").join("d")]),
+            collect(&common_data)
+        );
+
+        common_data.set_max_depth(None);
+        common_data.set_follow_symlinks(true);
+        assert_eq!(
+            IndexSet::from([
+                src,
+                hard,
+                other,
+                dir.path().join("sub").join("d"),
+                dir.path().join("sub").join("deep").join("e"),
+                dir.path().join("linked").join("f"),
+            ]),
+            collect(&common_data)
+        );
+        Ok(())
+    }
+
     #[cfg(target_family = "unix")]
     #[test]
     fn test_traversal_group_by_inode() -> io::Result<()> {
@@ -100,6 +100,15 @@ impl Directories {
         messages
     }
 
+    // Used for folders created by app, which should never be scanned, so no warnings are reported for them
+    pub(crate) fn add_excluded_directory(&mut self, excluded_directory: &Path) {
+        if let (Some(dir), _) = Self::canonicalize_and_clear_path(excluded_directory, true)
+            && !self.excluded_directories.contains(&dir)
+        {
+            self.excluded_directories.push(dir);
+        }
+    }
+
     fn canonicalize_and_clear_path(directory: &Path, is_excluded: bool) -> (Option<PathBuf>, Messages) {
         let mut messages = Messages::new();
         let mut directory = directory.to_path_buf();
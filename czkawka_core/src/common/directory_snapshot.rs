@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+use std::fs::Metadata;
+use std::io::{BufReader, BufWriter};
+#[cfg(target_family = "unix")]
+use std::os::unix::fs::MetadataExt;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use bincode::Options;
+use fun_time::fun_time;
+use log::debug;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::common::cache::{CACHE_VERSION, MEMORY_LIMIT};
+use crate::common::config_cache_path::open_cache_folder;
+use crate::common::model::FileEntry;
+
+/// State of single directory from previous scan.
+/// Contains all files and subdirectories, without applying any filters, so it can be used by every tool
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct DirectorySnapshot {
+    pub(crate) modified: SystemTime,
+    pub(crate) inode: u64,
+    pub(crate) files: Vec<FileEntry>,
+    pub(crate) directories: Vec<PathBuf>,
+}
+
+impl DirectorySnapshot {
+    pub(crate) fn new(metadata: &Metadata) -> Option<Self> {
+        Some(Self {
+            modified: metadata.modified().ok()?,
+            inode: get_inode(metadata),
+            files: vec![],
+            directories: vec![],
+        })
+    }
+
+    // Only metadata of directory itself is compared, so files edited in place (which doesn't change modification date of directory)
+    // are not detected - checking every file would be as slow as reading directory again
+    pub(crate) fn is_up_to_date(&self, metadata: &Metadata) -> bool {
+        metadata.modified().is_ok_and(|modified| modified == self.modified) && get_inode(metadata) == self.inode
+    }
+}
+
+// Directory removed and created again in the same place may have the same modification date, but different inode
+#[cfg(target_family = "unix")]
+fn get_inode(metadata: &Metadata) -> u64 {
+    metadata.ino()
+}
+
+#[cfg(not(target_family = "unix"))]
+fn get_inode(_metadata: &Metadata) -> u64 {
+    0
+}
+
+pub(crate) type DirectorySnapshots = BTreeMap<PathBuf, DirectorySnapshot>;
+
+pub(crate) fn get_directory_snapshot_file() -> String {
+    format!("cache_directory_snapshot_{CACHE_VERSION}.bin")
+}
+
+#[fun_time(message = "load_directory_snapshots", level = "debug")]
+pub(crate) fn load_directory_snapshots(warnings: &mut Vec<String>) -> DirectorySnapshots {
+    let cache_file_name = get_directory_snapshot_file();
+    let Some(((Some(file_handler), cache_file), _)) = open_cache_folder(&cache_file_name, false, false, warnings) else {
+        debug!("Failed to load directory snapshot from file {cache_file_name} because not exists");
+        return Default::default();
+    };
+
+    let reader = BufReader::new(file_handler);
+    let options = bincode::DefaultOptions::new().with_limit(MEMORY_LIMIT);
+    match options.deserialize_from::<_, DirectorySnapshots>(reader) {
+        Ok(snapshots) => {
+            debug!("Loaded {} directory snapshots from file {cache_file:?}", snapshots.len());
+            snapshots
+        }
+        Err(e) => {
+            warnings.push(format!("Failed to load data from directory snapshot file {cache_file:?}, reason {e}"));
+            Default::default()
+        }
+    }
+}
+
+#[fun_time(message = "save_directory_snapshots", level = "debug")]
+pub(crate) fn save_directory_snapshots(mut snapshots: DirectorySnapshots, mut changed: bool, delete_outdated_cache: bool, warnings: &mut Vec<String>) {
+    if delete_outdated_cache {
+        let outdated = snapshots.par_iter().filter(|(path, _)| !path.is_dir()).map(|(path, _)| path.clone()).collect::<Vec<_>>();
+        changed |= !outdated.is_empty();
+        for path in outdated {
+            snapshots.remove(&path);
+        }
+    }
+    // Writing the same data again would only slow down scans of unchanged trees
+    if !changed {
+        debug!("Directory snapshots are unchanged, skipping saving");
+        return;
+    }
+
+    let cache_file_name = get_directory_snapshot_file();
+    let Some(((Some(file_handler), cache_file), _)) = open_cache_folder(&cache_file_name, true, false, warnings) else {
+        debug!("Failed to save directory snapshot to file {cache_file_name}");
+        return;
+    };
+
+    let writer = BufWriter::new(file_handler);
+    let options = bincode::DefaultOptions::new().with_limit(MEMORY_LIMIT);
+    if let Err(e) = options.serialize_into(writer, &snapshots) {
+        warnings.push(format!("Cannot write data to directory snapshot file {cache_file:?}, reason {e}"));
+        return;
+    }
+    debug!("Saved {} directory snapshots to file {cache_file:?}", snapshots.len());
+}
@@ -1,3 +1,4 @@
+use std::ffi::OsStr;
 use std::fs::DirEntry;
 
 use indexmap::IndexSet;
@@ -68,14 +69,21 @@ impl Extensions {
         messages
     }
 
-    #[expect(clippy::string_slice)] // Valid, because we address go to dot, which is known ascii character
     pub(crate) fn check_if_entry_have_valid_extension(&self, entry_data: &DirEntry) -> bool {
         if self.allowed_extensions_hashset.is_empty() && self.excluded_extensions_hashset.is_empty() {
             return true;
         }
 
         // Using entry_data.path().extension() is a lot of slower, even 5 times
-        let file_name = entry_data.file_name();
+        self.check_if_file_name_have_valid_extension(&entry_data.file_name())
+    }
+
+    #[expect(clippy::string_slice)] // Valid, because we address go to dot, which is known ascii character
+    pub(crate) fn check_if_file_name_have_valid_extension(&self, file_name: &OsStr) -> bool {
+        if self.allowed_extensions_hashset.is_empty() && self.excluded_extensions_hashset.is_empty() {
+            return true;
+        }
+
         let Some(file_name_str) = file_name.to_str() else { return false };
         let Some(extension_idx) = file_name_str.rfind('.') else { return false };
         let extension = &file_name_str[extension_idx + 1..];
@@ -0,0 +1,196 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use glob::{MatchOptions, Pattern};
+
+use crate::flc;
+
+// `.czkawkaignore` is checked after `.gitignore`, so its rules take precedence in the same folder
+pub const IGNORE_FILE_NAMES: &[&str] = &[".gitignore", ".czkawkaignore"];
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+#[derive(Debug)]
+struct IgnoreRule {
+    pattern: Pattern,
+    negated: bool,
+    directory_only: bool,
+    // Patterns without slash are matched against file name at any level, other against path relative to folder with ignore file
+    anchored: bool,
+}
+
+#[derive(Debug)]
+struct IgnoreFile {
+    folder: PathBuf,
+    rules: Vec<IgnoreRule>,
+}
+
+/// Rules from ignore files found in currently checked folder and all its parents, which were visited during scan.
+/// Cloning is cheap, because files are shared between folders
+#[derive(Debug, Clone, Default)]
+pub(crate) struct IgnoreStack {
+    files: Vec<Arc<IgnoreFile>>,
+}
+
+impl IgnoreStack {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    // Returns new stack with rules from ignore files placed directly inside folder
+    pub(crate) fn with_folder(&self, folder: &Path, warnings: &mut Vec<String>) -> Self {
+        let mut stack = self.clone();
+        for ignore_file_name in IGNORE_FILE_NAMES {
+            let ignore_file_path = folder.join(ignore_file_name);
+            let content = match fs::read_to_string(&ignore_file_path) {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => {
+                    warnings.push(flc!(
+                        "core_cannot_read_ignore_file",
+                        name = ignore_file_path.to_string_lossy().to_string(),
+                        reason = e.to_string()
+                    ));
+                    continue;
+                }
+            };
+            let rules = parse_ignore_file(&content, &ignore_file_path, warnings);
+            if !rules.is_empty() {
+                stack.files.push(Arc::new(IgnoreFile {
+                    folder: folder.to_path_buf(),
+                    rules,
+                }));
+            }
+        }
+        stack
+    }
+
+    // Like in git, last matching rule wins and rules from deeper folders are more important
+    pub(crate) fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        for ignore_file in self.files.iter().rev() {
+            let Ok(relative_path) = path.strip_prefix(&ignore_file.folder) else {
+                continue;
+            };
+            let relative_path = relative_path.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+            let file_name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+
+            for rule in ignore_file.rules.iter().rev() {
+                if rule.directory_only && !is_dir {
+                    continue;
+                }
+                let matched = if rule.anchored {
+                    rule.pattern.matches_with(&relative_path, MATCH_OPTIONS)
+                } else {
+                    rule.pattern.matches_with(&file_name, MATCH_OPTIONS)
+                };
+                if matched {
+                    return !rule.negated;
+                }
+            }
+        }
+        false
+    }
+}
+
+fn parse_ignore_file(content: &str, ignore_file_path: &Path, warnings: &mut Vec<String>) -> Vec<IgnoreRule> {
+    let mut rules = Vec::new();
+    for line in content.lines() {
+        let mut line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let negated = line.starts_with('!');
+        if negated || line.starts_with("\\!") || line.starts_with("\\#") {
+            line = line.get(1..).unwrap_or_default();
+        }
+
+        let directory_only = line.ends_with('/');
+        let line = line.trim_end_matches('/');
+        let anchored = line.contains('/');
+        let line = line.strip_prefix('/').unwrap_or(line);
+        if line.is_empty() {
+            continue;
+        }
+
+        match Pattern::new(line) {
+            Ok(pattern) => rules.push(IgnoreRule {
+                pattern,
+                negated,
+                directory_only,
+                anchored,
+            }),
+            Err(e) => warnings.push(flc!(
+                "core_invalid_ignore_pattern",
+                pattern = line.to_string(),
+                name = ignore_file_path.to_string_lossy().to_string(),
+                reason = e.to_string()
+            )),
+        }
+    }
+    rules
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stack_from(folder: &Path, content: &str) -> IgnoreStack {
+        let mut warnings = vec![];
+        let rules = parse_ignore_file(content, &folder.join(".gitignore"), &mut warnings);
+        assert!(warnings.is_empty());
+        IgnoreStack {
+            files: vec![Arc::new(IgnoreFile {
+                folder: folder.to_path_buf(),
+                rules,
+            })],
+        }
+    }
+
+    #[test]
+    fn test_ignore_rules() {
+        let root = Path::new("/project");
+        let stack = stack_from(root, "# comment\n*.log\n!keep.log\n/build\ncache/\ndocs/**/*.tmp\n");
+
+        assert!(stack.is_ignored(&root.join("a.log"), false));
+        assert!(stack.is_ignored(&root.join("src/deep/b.log"), false));
+        assert!(!stack.is_ignored(&root.join("src/keep.log"), false));
+
+        // Anchored pattern matches only relative to folder with ignore file
+        assert!(stack.is_ignored(&root.join("build"), true));
+        assert!(!stack.is_ignored(&root.join("src/build"), true));
+
+        // Directory only pattern
+        assert!(stack.is_ignored(&root.join("src/cache"), true));
+        assert!(!stack.is_ignored(&root.join("src/cache"), false));
+
+        assert!(stack.is_ignored(&root.join("docs/a/b/c.tmp"), false));
+        assert!(stack.is_ignored(&root.join("docs/c.tmp"), false));
+        assert!(!stack.is_ignored(&root.join("src/c.tmp"), false));
+    }
+
+    #[test]
+    fn test_deeper_ignore_file_wins() {
+        let root = Path::new("/project");
+        let mut stack = stack_from(root, "*.txt\n");
+        let nested = stack_from(&root.join("sub"), "!important.txt\n");
+        stack.files.extend(nested.files);
+
+        assert!(stack.is_ignored(&root.join("sub/other.txt"), false));
+        assert!(!stack.is_ignored(&root.join("sub/important.txt"), false));
+        assert!(stack.is_ignored(&root.join("important.txt"), false));
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let mut warnings = vec![];
+        let rules = parse_ignore_file("[a\nvalid\n", Path::new("/project/.czkawkaignore"), &mut warnings);
+        assert_eq!(rules.len(), 1);
+        assert_eq!(warnings.len(), 1);
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::{BTreeSet, HashMap};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use fun_time::fun_time;
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::common::config_cache_path::{is_config_cache_path_set, open_cache_folder};
+use crate::common::model::ToolType;
+use crate::common::traits::ResultEntry;
+
+pub const IGNORED_GROUPS_FILE: &str = "ignored_groups.json";
+
+/// Group of files, which user marked as not being duplicates or similar to each other
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IgnoredGroup {
+    pub tool_type: ToolType,
+    pub paths: BTreeSet<PathBuf>,
+}
+
+/// Groups dismissed by user, saved in cache folder, so they are not shown again in next scans.
+/// Dismissed pairs of files are never put into same group, so new file similar to dismissed ones is shown only with files not dismissed with it
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct IgnoredGroups {
+    groups: Vec<IgnoredGroup>,
+}
+
+impl IgnoredGroups {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    #[fun_time(message = "load_ignored_groups", level = "debug")]
+    pub fn load(warnings: &mut Vec<String>) -> Self {
+        if !is_config_cache_path_set() {
+            return Self::new();
+        }
+        let Some(((Some(file_handler), file_path), _)) = open_cache_folder(IGNORED_GROUPS_FILE, false, false, warnings) else {
+            debug!("Ignored groups file {IGNORED_GROUPS_FILE} not exists");
+            return Self::new();
+        };
+
+        match serde_json::from_reader::<_, Self>(BufReader::new(file_handler)) {
+            Ok(ignored_groups) => {
+                debug!("Loaded {} ignored groups from file {file_path:?}", ignored_groups.groups.len());
+                ignored_groups
+            }
+            Err(e) => {
+                warnings.push(format!("Failed to load ignored groups from file {file_path:?}, reason {e}"));
+                Self::new()
+            }
+        }
+    }
+
+    #[fun_time(message = "save_ignored_groups", level = "debug")]
+    pub fn save(&self) -> Result<(), String> {
+        let mut warnings = Vec::new();
+        let Some(((Some(file_handler), file_path), _)) = open_cache_folder(IGNORED_GROUPS_FILE, true, false, &mut warnings) else {
+            return Err(warnings.pop().unwrap_or_else(|| "Cannot find cache folder to save ignored groups".to_string()));
+        };
+
+        serde_json::to_writer_pretty(BufWriter::new(file_handler), self).map_err(|e| format!("Cannot save ignored groups to file {file_path:?}, reason {e}"))?;
+        debug!("Saved {} ignored groups to file {file_path:?}", self.groups.len());
+        Ok(())
+    }
+
+    pub fn get_groups(&self) -> &[IgnoredGroup] {
+        &self.groups
+    }
+
+    // Returns false, when group is too small or was already ignored
+    pub fn add_group(&mut self, tool_type: ToolType, paths: impl IntoIterator<Item = PathBuf>) -> bool {
+        let paths: BTreeSet<PathBuf> = paths.into_iter().collect();
+        if paths.len() < 2 || self.groups.iter().any(|group| group.tool_type == tool_type && paths.is_subset(&group.paths)) {
+            return false;
+        }
+        // Smaller groups are covered by new group, so they are not needed anymore
+        self.groups.retain(|group| group.tool_type != tool_type || !group.paths.is_subset(&paths));
+        self.groups.push(IgnoredGroup { tool_type, paths });
+        true
+    }
+
+    // Removes all groups containing given path, returns number of removed groups
+    pub fn remove_groups_with_path(&mut self, tool_type: Option<ToolType>, path: &Path) -> usize {
+        let before = self.groups.len();
+        self.groups
+            .retain(|group| !(tool_type.is_none_or(|tool_type| group.tool_type == tool_type) && group.paths.contains(path)));
+        before - self.groups.len()
+    }
+
+    pub fn clear(&mut self, tool_type: Option<ToolType>) {
+        self.groups.retain(|group| tool_type.is_some_and(|tool_type| group.tool_type != tool_type));
+    }
+
+    pub(crate) fn get_filter(&self, tool_type: ToolType) -> IgnoredGroupsFilter<'_> {
+        let mut groups_with_path: HashMap<&Path, Vec<usize>> = Default::default();
+        for (idx, group) in self.groups.iter().enumerate().filter(|(_idx, group)| group.tool_type == tool_type) {
+            for path in &group.paths {
+                groups_with_path.entry(path).or_default().push(idx);
+            }
+        }
+        IgnoredGroupsFilter { groups_with_path }
+    }
+}
+
+pub(crate) struct IgnoredGroupsFilter<'a> {
+    // Path -> indexes of ignored groups containing it
+    groups_with_path: HashMap<&'a Path, Vec<usize>>,
+}
+
+impl IgnoredGroupsFilter<'_> {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.groups_with_path.is_empty()
+    }
+
+    pub(crate) fn is_pair_ignored(&self, first: &Path, second: &Path) -> bool {
+        let (Some(first_groups), Some(second_groups)) = (self.groups_with_path.get(first), self.groups_with_path.get(second)) else {
+            return false;
+        };
+        first_groups.iter().any(|idx| second_groups.contains(idx))
+    }
+
+    pub(crate) fn is_group_ignored<T: ResultEntry>(&self, group: &[T]) -> bool {
+        if self.is_empty() || group.len() < 2 {
+            return false;
+        }
+        group
+            .iter()
+            .enumerate()
+            .all(|(idx, first)| group.iter().skip(idx + 1).all(|second| self.is_pair_ignored(first.get_path(), second.get_path())))
+    }
+
+    // Used when building groups, to not connect items, when every pair of files between them was dismissed
+    pub(crate) fn are_all_pairs_ignored<T: ResultEntry>(&self, first: &[T], second: &[T]) -> bool {
+        if self.is_empty() || first.is_empty() || second.is_empty() {
+            return false;
+        }
+        first.iter().all(|f| second.iter().all(|s| self.is_pair_ignored(f.get_path(), s.get_path())))
+    }
+
+    // Splits group into smaller ones, so no group contains dismissed pair of files
+    // Files are checked in order, so first file of group stays first in it
+    pub(crate) fn split_group<T: ResultEntry>(&self, group: Vec<T>) -> Vec<Vec<T>> {
+        if self.is_empty() {
+            return if group.len() > 1 { vec![group] } else { Vec::new() };
+        }
+        let mut new_groups = Vec::new();
+        let mut remaining = group;
+        while remaining.len() > 1 {
+            let mut current_group: Vec<T> = Vec::new();
+            let mut rest = Vec::new();
+            for entry in remaining {
+                if current_group.iter().any(|e| self.is_pair_ignored(e.get_path(), entry.get_path())) {
+                    rest.push(entry);
+                } else {
+                    current_group.push(entry);
+                }
+            }
+            if current_group.len() > 1 {
+                new_groups.push(current_group);
+            }
+            remaining = rest;
+        }
+        new_groups
+    }
+
+    pub(crate) fn filter_groups<T: ResultEntry>(&self, groups: Vec<Vec<T>>) -> Vec<Vec<T>> {
+        if self.is_empty() {
+            return groups;
+        }
+        groups.into_iter().flat_map(|group| self.split_group(group)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::model::FileEntry;
+
+    fn entries(paths: &[&str]) -> Vec<FileEntry> {
+        paths
+            .iter()
+            .map(|path| FileEntry {
+                path: PathBuf::from(path),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ignored_groups_filter() {
+        let mut ignored_groups = IgnoredGroups::new();
+        assert!(ignored_groups.add_group(ToolType::SimilarImages, ["/a", "/b"].map(PathBuf::from)));
+        assert!(ignored_groups.add_group(ToolType::SimilarImages, ["/c", "/d", "/e"].map(PathBuf::from)));
+        assert!(ignored_groups.add_group(ToolType::SimilarImages, ["/a", "/c"].map(PathBuf::from)));
+        assert!(ignored_groups.add_group(ToolType::SameMusic, ["/x", "/y"].map(PathBuf::from)));
+        // Already covered by other group
+        assert!(!ignored_groups.add_group(ToolType::SimilarImages, ["/d", "/c"].map(PathBuf::from)));
+        assert!(!ignored_groups.add_group(ToolType::SimilarImages, ["/d"].map(PathBuf::from)));
+
+        let filter = ignored_groups.get_filter(ToolType::SimilarImages);
+        assert!(filter.is_group_ignored(&entries(&["/a", "/b"])));
+        assert!(filter.is_group_ignored(&entries(&["/e", "/c", "/d"])));
+        // Pair b-c was never dismissed
+        assert!(!filter.is_group_ignored(&entries(&["/a", "/b", "/c"])));
+        assert!(!filter.is_group_ignored(&entries(&["/c", "/d", "/f"])));
+        assert!(!filter.is_group_ignored(&entries(&["/x", "/y"])));
+
+        let groups = filter.filter_groups(vec![entries(&["/a", "/b"]), entries(&["/a", "/f"])]);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0][1].path, PathBuf::from("/f"));
+
+        // Dismissed pairs are split into separate groups, only groups with at least 2 files are kept
+        let groups = filter.filter_groups(vec![entries(&["/a", "/b", "/f", "/c", "/g"])]);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].iter().map(|e| e.path.clone()).collect::<Vec<_>>(), ["/a", "/f", "/g"].map(PathBuf::from));
+        assert_eq!(groups[1].iter().map(|e| e.path.clone()).collect::<Vec<_>>(), ["/b", "/c"].map(PathBuf::from));
+        let groups = filter.filter_groups(vec![entries(&["/c", "/d", "/e", "/f"])]);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].iter().map(|e| e.path.clone()).collect::<Vec<_>>(), ["/c", "/f"].map(PathBuf::from));
+
+        assert!(filter.are_all_pairs_ignored(&entries(&["/c", "/d"]), &entries(&["/e"])));
+        assert!(!filter.are_all_pairs_ignored(&entries(&["/c", "/f"]), &entries(&["/e"])));
+
+        // Bigger group replaces smaller ones
+        assert!(ignored_groups.add_group(ToolType::SimilarImages, ["/a", "/b", "/c"].map(PathBuf::from)));
+        assert_eq!(ignored_groups.get_groups().len(), 3);
+        assert_eq!(ignored_groups.remove_groups_with_path(None, Path::new("/c")), 2);
+        ignored_groups.clear(Some(ToolType::SimilarImages));
+        assert_eq!(ignored_groups.get_groups().len(), 1);
+        ignored_groups.clear(None);
+        assert!(ignored_groups.get_groups().is_empty());
+    }
+}
@@ -197,6 +197,10 @@ pub enum ExifOrientation {
     Rotate270CW,
 }
 
+pub(crate) fn has_exif_data(path: &Path) -> bool {
+    panic::catch_unwind(|| MediaSource::file_path(path).is_ok_and(|ms| ms.has_exif())).unwrap_or(false)
+}
+
 pub(crate) fn get_rotation_from_exif(path: &str) -> Result<Option<ExifOrientation>, nom_exif::Error> {
     let res = panic::catch_unwind(|| {
         let mut parser = MediaParser::new();
@@ -1,8 +1,10 @@
 use std::path::Path;
 
+use glob::{MatchOptions, Pattern};
+use regex::Regex;
+
 #[cfg(not(target_family = "unix"))]
 use crate::common::normalize_windows_path;
-use crate::common::regex_check;
 use crate::helpers::messages::Messages;
 
 #[cfg(target_family = "unix")]
@@ -15,17 +17,47 @@ pub const DEFAULT_EXCLUDED_ITEMS: &str = "*/.git/*,*/node_modules/*,*/lost+found
 #[cfg(not(target_family = "unix"))]
 pub const DEFAULT_EXCLUDED_ITEMS: &str = "*\\.git\\*,*\\node_modules\\*,*\\lost+found\\*,*:\\windows\\*,*:\\$RECYCLE.BIN\\*,*:\\$SysReset\\*,*:\\System Volume Information\\*,*:\\OneDriveTemp\\*,*:\\hiberfil.sys,*:\\pagefile.sys,*:\\swapfile.sys";
 
+// Patterns with this prefix are treated as regular expressions, all other as globs
+pub const REGEX_PREFIX: &str = "regex:";
+
+// `*` may match also path separator, so old `*/.git/*` like patterns still work
+const GLOB_MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: false,
+    require_literal_leading_dot: false,
+};
+
 #[derive(Debug, Clone, Default)]
 pub struct ExcludedItems {
     expressions: Vec<String>,
     connected_expressions: Vec<SingleExcludedItem>,
 }
 
+/// When not empty, only files which full path matches at least one of items are checked
 #[derive(Debug, Clone, Default)]
+pub struct IncludedItems {
+    connected_expressions: Vec<SingleExcludedItem>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ItemMatcher {
+    Glob(Pattern),
+    Regex(Regex),
+}
+
+#[derive(Debug, Clone)]
 pub struct SingleExcludedItem {
     pub expression: String,
-    pub expression_splits: Vec<String>,
-    pub unique_extensions_splits: Vec<String>,
+    pub matcher: ItemMatcher,
+}
+
+impl SingleExcludedItem {
+    pub fn matches(&self, path: &str) -> bool {
+        match &self.matcher {
+            ItemMatcher::Glob(pattern) => pattern.matches_with(path, GLOB_MATCH_OPTIONS),
+            ItemMatcher::Regex(regex) => regex.is_match(path),
+        }
+    }
 }
 
 impl ExcludedItems {
@@ -40,46 +72,12 @@ impl ExcludedItems {
     }
 
     pub(crate) fn set_excluded_items(&mut self, excluded_items: Vec<String>) -> Messages {
-        let mut warnings: Vec<String> = Vec::new();
-        if excluded_items.is_empty() {
-            return Messages::new();
-        }
-
-        let expressions: Vec<String> = excluded_items;
-        let mut checked_expressions: Vec<String> = Vec::new();
-
-        for expression in expressions {
-            let expression: String = expression.trim().to_string();
-
-            if expression.is_empty() {
-                continue;
-            }
-
-            #[cfg(target_family = "windows")]
-            let expression = expression.replace("/", "\\");
-
-            if expression == "DEFAULT" {
-                checked_expressions.push(DEFAULT_EXCLUDED_ITEMS.to_string());
-                continue;
-            }
-            if !expression.contains('*') {
-                warnings.push("Excluded Items Warning: Wildcard * is required in expression, ignoring ".to_string() + expression.as_str());
-                continue;
-            }
-
-            checked_expressions.push(expression);
-        }
-
-        for checked_expression in &checked_expressions {
-            let item = new_excluded_item(checked_expression);
+        let (connected_expressions, warnings) = parse_items(excluded_items, "Excluded Items Warning");
+        for item in connected_expressions {
             self.expressions.push(item.expression.clone());
             self.connected_expressions.push(item);
         }
-        Messages {
-            messages: vec![],
-            warnings,
-            errors: vec![],
-        }
+        Messages::new_from_warnings(warnings)
     }
 
     pub(crate) fn get_excluded_items(&self) -> &Vec<String> {
@@ -89,30 +87,90 @@ impl ExcludedItems {
         if self.connected_expressions.is_empty() {
             return false;
         }
-        #[cfg(target_family = "windows")]
-        let path = normalize_windows_path(path);
+        matches_any(&self.connected_expressions, path)
+    }
+}
+
+impl IncludedItems {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub(crate) fn set_included_items(&mut self, included_items: Vec<String>) -> Messages {
+        let (connected_expressions, warnings) = parse_items(included_items, "Included Items Warning");
+        self.connected_expressions.extend(connected_expressions);
+        Messages::new_from_warnings(warnings)
+    }
+
+    pub(crate) fn is_included(&self, path: &Path) -> bool {
+        if self.connected_expressions.is_empty() {
+            return true;
+        }
+        matches_any(&self.connected_expressions, path)
+    }
+}
 
-        let path_str = path.to_string_lossy();
+fn matches_any(items: &[SingleExcludedItem], path: &Path) -> bool {
+    #[cfg(target_family = "windows")]
+    let path = normalize_windows_path(path);
 
-        for expression in &self.connected_expressions {
-            if regex_check(expression, &path_str) {
-                return true;
+    let path_str = path.to_string_lossy();
+    items.iter().any(|item| item.matches(&path_str))
+}
+
+fn parse_items(items: Vec<String>, warning_prefix: &str) -> (Vec<SingleExcludedItem>, Vec<String>) {
+    let mut warnings: Vec<String> = Vec::new();
+    let mut checked_items = Vec::new();
+
+    for expression in items {
+        let expression: String = expression.trim().to_string();
+
+        if expression.is_empty() {
+            continue;
+        }
+
+        #[cfg(target_family = "windows")]
+        let expression = if expression.starts_with(REGEX_PREFIX) {
+            expression
+        } else {
+            expression.replace("/", "\\")
+        };
+
+        if expression == "DEFAULT" {
+            for default_expression in DEFAULT_EXCLUDED_ITEMS.split(',') {
+                match try_new_excluded_item(default_expression) {
+                    Ok(item) => checked_items.push(item),
+                    Err(e) => warnings.push(format!("{warning_prefix}: {e}")),
+                }
             }
+            continue;
+        }
+
+        match try_new_excluded_item(&expression) {
+            Ok(item) => checked_items.push(item),
+            Err(e) => warnings.push(format!("{warning_prefix}: {e}")),
         }
-        false
     }
+    (checked_items, warnings)
 }
 
+// Invalid patterns are matched literally, use `try_new_excluded_item` to get information about errors
 pub fn new_excluded_item(expression: &str) -> SingleExcludedItem {
+    try_new_excluded_item(expression).unwrap_or_else(|_| {
+        let expression = expression.trim().to_string();
+        SingleExcludedItem {
+            matcher: ItemMatcher::Glob(Pattern::new(&Pattern::escape(&expression)).expect("Escaped pattern is always valid")),
+            expression,
+        }
+    })
+}
+
+pub fn try_new_excluded_item(expression: &str) -> Result<SingleExcludedItem, String> {
     let expression = expression.trim().to_string();
-    let expression_splits: Vec<String> = expression.split('*').filter_map(|e| if e.is_empty() { None } else { Some(e.to_string()) }).collect();
-    let mut unique_extensions_splits = expression_splits.clone();
-    unique_extensions_splits.sort();
-    unique_extensions_splits.dedup();
-    unique_extensions_splits.sort_by_key(|b| std::cmp::Reverse(b.len()));
-    SingleExcludedItem {
-        expression,
-        expression_splits,
-        unique_extensions_splits,
-    }
+    let matcher = if let Some(regex) = expression.strip_prefix(REGEX_PREFIX) {
+        ItemMatcher::Regex(Regex::new(regex).map_err(|e| format!("Invalid regex \"{regex}\", ignoring - {e}"))?)
+    } else {
+        ItemMatcher::Glob(Pattern::new(&expression).map_err(|e| format!("Invalid glob pattern \"{expression}\", ignoring - {e}"))?)
+    };
+    Ok(SingleExcludedItem { expression, matcher })
 }
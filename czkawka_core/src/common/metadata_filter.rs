@@ -0,0 +1,178 @@
+use std::fs::Metadata;
+#[cfg(target_family = "unix")]
+use std::os::unix::fs::MetadataExt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECONDS_IN_DAY: u64 = 24 * 60 * 60;
+
+/// Filters files by modification date, last access and owner.
+/// Owner filters are only supported on unix, on other systems they are ignored
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MetadataFilter {
+    minimal_modified_date: Option<u64>,
+    maximal_modified_date: Option<u64>,
+    // Files accessed after this date are skipped, computed from number of days when set
+    maximal_accessed_date: Option<u64>,
+    owner_uids: Vec<u32>,
+    owner_gids: Vec<u32>,
+}
+
+impl MetadataFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_minimal_modified_date(&mut self, minimal_modified_date: Option<u64>) {
+        self.minimal_modified_date = minimal_modified_date;
+    }
+    pub fn set_maximal_modified_date(&mut self, maximal_modified_date: Option<u64>) {
+        self.maximal_modified_date = maximal_modified_date;
+    }
+    pub fn set_not_accessed_for_days(&mut self, not_accessed_for_days: Option<u64>) {
+        let current_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        self.maximal_accessed_date = not_accessed_for_days.map(|days| current_time.saturating_sub(days.saturating_mul(SECONDS_IN_DAY)));
+    }
+    pub fn set_owner_uids(&mut self, owner_uids: Vec<u32>) {
+        self.owner_uids = owner_uids;
+    }
+    pub fn set_owner_gids(&mut self, owner_gids: Vec<u32>) {
+        self.owner_gids = owner_gids;
+    }
+
+    // Directory snapshots contain only size and modification date, so they cannot be used with other filters
+    pub(crate) fn needs_full_metadata(&self) -> bool {
+        self.maximal_accessed_date.is_some() || !self.owner_uids.is_empty() || !self.owner_gids.is_empty()
+    }
+
+    pub(crate) fn is_modified_date_allowed(&self, modified_date: u64) -> bool {
+        self.minimal_modified_date.is_none_or(|minimal| modified_date >= minimal) && self.maximal_modified_date.is_none_or(|maximal| modified_date <= maximal)
+    }
+
+    pub(crate) fn is_allowed(&self, metadata: &Metadata, modified_date: u64) -> bool {
+        if !self.is_modified_date_allowed(modified_date) {
+            return false;
+        }
+        if let Some(maximal_accessed_date) = self.maximal_accessed_date {
+            // Without access time it is not possible to tell, that file is unused
+            let Some(accessed) = metadata.accessed().ok().and_then(|accessed| accessed.duration_since(UNIX_EPOCH).ok()) else {
+                return false;
+            };
+            if accessed.as_secs() > maximal_accessed_date {
+                return false;
+            }
+        }
+        self.is_owner_allowed(metadata)
+    }
+
+    #[cfg(target_family = "unix")]
+    fn is_owner_allowed(&self, metadata: &Metadata) -> bool {
+        (self.owner_uids.is_empty() || self.owner_uids.contains(&metadata.uid())) && (self.owner_gids.is_empty() || self.owner_gids.contains(&metadata.gid()))
+    }
+
+    #[cfg(not(target_family = "unix"))]
+    fn is_owner_allowed(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+}
+
+const MAXIMAL_YEAR: u64 = 9999;
+
+// Accepts date in YYYY-MM-DD format (midnight UTC) or unix timestamp in seconds
+// Bare year (e.g. 2024) is rejected, because it would be silently used as timestamp from the first hour of 1970
+pub fn parse_date(text: &str) -> Result<u64, String> {
+    let text = text.trim();
+    if let Ok(timestamp) = text.parse::<u64>() {
+        if text.len() <= 4 {
+            return Err(format!("Invalid date \"{text}\", year must be given as YYYY-MM-DD, e.g. {text}-01-01"));
+        }
+        return Ok(timestamp);
+    }
+
+    let invalid_date = || format!("Invalid date \"{text}\", expected YYYY-MM-DD (years 1970-{MAXIMAL_YEAR}) or unix timestamp");
+    let mut parts = text.splitn(3, '-').map(str::parse::<u64>);
+    let (Some(Ok(year)), Some(Ok(month)), Some(Ok(day))) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(invalid_date());
+    };
+    if !(1970..=MAXIMAL_YEAR).contains(&year) || !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) {
+        return Err(invalid_date());
+    }
+
+    days_from_civil(year, month, day).and_then(|days| days.checked_mul(SECONDS_IN_DAY)).ok_or_else(invalid_date)
+}
+
+// Days from civil algorithm, counted from 1970-01-01, returns None on overflow
+fn days_from_civil(year: u64, month: u64, day: u64) -> Option<u64> {
+    let (year, month) = if month <= 2 { (year.checked_sub(1)?, month + 9) } else { (year, month - 3) };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = ((153 * month + 2) / 5).checked_add(day)? - 1;
+    let day_of_era = (year_of_era * 365 + year_of_era / 4 - year_of_era / 100).checked_add(day_of_year)?;
+    era.checked_mul(146_097)?.checked_add(day_of_era)?.checked_sub(719_468)
+}
+
+const fn days_in_month(year: u64, month: u64) -> u64 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Parses comma separated list of user or group ids, empty entries are skipped
+pub fn parse_ids(text: &str) -> Result<Vec<u32>, String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| id.parse::<u32>().map_err(|_| format!("Invalid user or group id \"{id}\"")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(parse_date("1970-01-01"), Ok(0));
+        assert_eq!(parse_date("2000-03-01"), Ok(951_868_800));
+        assert_eq!(parse_date("2024-02-29"), Ok(1_709_164_800));
+        assert_eq!(parse_date(" 1709164800 "), Ok(1_709_164_800));
+        parse_date("2024-13-01").unwrap_err();
+        parse_date("2024-02-30").unwrap_err();
+        parse_date("2023-02-29").unwrap_err();
+        parse_date("2024-04-31").unwrap_err();
+        parse_date("1900-02-29").unwrap_err();
+        assert_eq!(parse_date("2000-02-29"), Ok(951_782_400));
+        parse_date("yesterday").unwrap_err();
+        parse_date("2024").unwrap_err();
+        parse_date("10000-01-01").unwrap_err();
+        parse_date("18446744073709551615-01-01").unwrap_err();
+        assert_eq!(parse_date("9999-12-31"), Ok(253_402_214_400));
+    }
+
+    #[test]
+    fn test_parse_ids() {
+        assert_eq!(parse_ids("1000, 1001,,0,"), Ok(vec![1000, 1001, 0]));
+        assert_eq!(parse_ids(" "), Ok(vec![]));
+        parse_ids("1000,abc").unwrap_err();
+        parse_ids("-1").unwrap_err();
+    }
+
+    #[test]
+    fn test_modified_date_filter() {
+        let mut filter = MetadataFilter::new();
+        assert!(filter.is_modified_date_allowed(0));
+        assert!(!filter.needs_full_metadata());
+
+        filter.set_minimal_modified_date(Some(100));
+        filter.set_maximal_modified_date(Some(200));
+        assert!(!filter.is_modified_date_allowed(99));
+        assert!(filter.is_modified_date_allowed(100));
+        assert!(filter.is_modified_date_allowed(200));
+        assert!(!filter.is_modified_date_allowed(201));
+
+        filter.set_not_accessed_for_days(Some(30));
+        assert!(filter.needs_full_metadata());
+    }
+}
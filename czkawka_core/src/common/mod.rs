@@ -2,15 +2,22 @@ pub mod basic_gui_cli;
 pub mod cache;
 pub mod config_cache_path;
 pub mod consts;
+pub mod deletion_journal;
 pub mod dir_traversal;
 pub mod directories;
+pub mod directory_snapshot;
 pub mod extensions;
+pub mod ignore_files;
+pub mod ignored_groups;
 pub mod image;
 pub mod items;
 pub mod logger;
+pub mod metadata_filter;
 pub mod model;
 pub mod progress_data;
 pub mod progress_stop_handler;
+pub mod quarantine;
+pub mod results_file;
 pub mod tool_data;
 pub mod traits;
 
@@ -183,49 +190,9 @@ pub(crate) fn create_crash_message(library_name: &str, file_path: &str, home_lib
     )
 }
 
-#[expect(clippy::string_slice)]
-#[expect(clippy::indexing_slicing)]
+// Supports globs(`*`, `**`, `?`, `[a-z]`) and regular expressions prefixed with `regex:`
 pub fn regex_check(expression_item: &SingleExcludedItem, directory_name: &str) -> bool {
-    if expression_item.expression_splits.is_empty() {
-        return true;
-    }
-
-    // Early checking if directory contains all parts needed by expression
-    for split in &expression_item.unique_extensions_splits {
-        if !directory_name.contains(split) {
-            return false;
-        }
-    }
-
-    // `git*` shouldn't be true for `/gitsfafasfs`
-    if !expression_item.expression.starts_with('*')
-        && directory_name
-            .find(&expression_item.expression_splits[0])
-            .expect("Cannot fail, because split must exists in directory_name")
-            > 0
-    {
-        return false;
-    }
-    // `*home` shouldn't be true for `/homeowner`
-    if !expression_item.expression.ends_with('*')
-        && !directory_name.ends_with(expression_item.expression_splits.last().expect("Cannot fail, because at least one item is available"))
-    {
-        return false;
-    }
-
-    // At the end we check if parts between * are correctly positioned
-    let mut last_split_point = directory_name.find(&expression_item.expression_splits[0]).expect("Cannot fail, because is checked earlier");
-    let mut current_index: usize = 0;
-    let mut found_index: usize;
-    for spl in &expression_item.expression_splits[1..] {
-        found_index = match directory_name[current_index..].find(spl) {
-            Some(t) => t,
-            None => return false,
-        };
-        current_index = last_split_point + spl.len();
-        last_split_point = found_index + current_index;
-    }
-    true
+    expression_item.matches(directory_name)
 }
 
 #[expect(clippy::string_slice)] // Is in char boundary
@@ -323,11 +290,121 @@ pub fn make_file_symlink<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> io::
     }
 }
 
+// Layout of structs used by FIDEDUPERANGE ioctl, they are not available in libc crate
+#[cfg(target_os = "linux")]
+#[repr(C)]
+struct FileDedupeRangeInfo {
+    dest_fd: i64,
+    dest_offset: u64,
+    bytes_deduped: u64,
+    status: i32,
+    reserved: u32,
+}
+
+// Kernel struct contains flexible array of infos, but only one destination is used here
+#[cfg(target_os = "linux")]
+#[repr(C)]
+struct FileDedupeRange {
+    src_offset: u64,
+    src_length: u64,
+    dest_count: u16,
+    reserved1: u16,
+    reserved2: u32,
+    info: FileDedupeRangeInfo,
+}
+
+// _IOWR(0x94, 54, struct file_dedupe_range)
+#[cfg(target_os = "linux")]
+const FIDEDUPERANGE: libc::Ioctl = 0xC018_9436_u32 as libc::Ioctl;
+#[cfg(target_os = "linux")]
+const FILE_DEDUPE_RANGE_SAME: i32 = 0;
+#[cfg(target_os = "linux")]
+const FILE_DEDUPE_RANGE_DIFFERS: i32 = 1;
+
+// Function to share data of src with dst(copy-on-write), supported on e.g. Btrfs and XFS
+// Unlike hardlinks, files stay independent, so modifying one of them, not changes the other
+// Kernel compares content of files before sharing it, so files which differ are never modified
+// Dst keeps its own permissions, owner and modification date
+#[cfg(target_os = "linux")]
+pub fn make_reflink<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> io::Result<()> {
+    use std::fs::{File, FileTimes, OpenOptions};
+    use std::os::fd::AsRawFd;
+
+    let src_file = File::open(src)?;
+    // Without root privileges, destination must be opened for writing
+    let dst_file = OpenOptions::new().write(true).open(dst)?;
+    let dst_metadata = dst_file.metadata()?;
+    let size = src_file.metadata()?.len();
+    if size != dst_metadata.len() {
+        return Err(Error::other(format!("Files have different sizes ({size} and {} bytes)", dst_metadata.len())));
+    }
+
+    let mut offset = 0;
+    // Filesystems may limit amount of data deduplicated at once, so this needs to be done in a loop
+    while offset < size {
+        let mut range = FileDedupeRange {
+            src_offset: offset,
+            src_length: size - offset,
+            dest_count: 1,
+            reserved1: 0,
+            reserved2: 0,
+            info: FileDedupeRangeInfo {
+                dest_fd: i64::from(dst_file.as_raw_fd()),
+                dest_offset: offset,
+                bytes_deduped: 0,
+                status: 0,
+                reserved: 0,
+            },
+        };
+        // SAFETY: Both file descriptors are valid until end of function and range has layout expected by kernel
+        let result = unsafe { libc::ioctl(src_file.as_raw_fd(), FIDEDUPERANGE, &raw mut range) };
+        if result < 0 {
+            return Err(reflink_error(Error::last_os_error()));
+        }
+        match range.info.status {
+            FILE_DEDUPE_RANGE_SAME => {}
+            FILE_DEDUPE_RANGE_DIFFERS => return Err(Error::other("Content of files is not identical")),
+            status => return Err(reflink_error(Error::from_raw_os_error(-status))),
+        }
+        if range.info.bytes_deduped == 0 {
+            return Err(Error::other("Filesystem did not share any data"));
+        }
+        offset += range.info.bytes_deduped;
+    }
+
+    // Some filesystems update modification date of deduplicated file
+    dst_file.set_times(FileTimes::new().set_accessed(dst_metadata.accessed()?).set_modified(dst_metadata.modified()?))?;
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn reflink_error(e: Error) -> Error {
+    match e.raw_os_error() {
+        Some(libc::EOPNOTSUPP | libc::ENOTTY | libc::EINVAL) => Error::new(io::ErrorKind::Unsupported, format!("Filesystem does not support reflinks ({e})")),
+        Some(libc::EXDEV) => Error::new(io::ErrorKind::Unsupported, format!("Files are on different filesystems ({e})")),
+        _ => e,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn make_reflink<P: AsRef<Path>, Q: AsRef<Path>>(_src: P, _dst: Q) -> io::Result<()> {
+    Err(Error::new(io::ErrorKind::Unsupported, "Reflinks are supported only on Linux"))
+}
+
 #[cfg(not(any(target_family = "unix", target_family = "windows")))]
 pub fn make_file_symlink<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> io::Result<()> {
     Err(Error::new(io::ErrorKind::Other, "Soft links are not supported on this platform"))
 }
 
+// Returns path which symlink placed at `dst` should point to, to reach `original`
+pub fn get_symlink_target(original: &Path, dst: &Path, relative: bool) -> Result<PathBuf, String> {
+    if !relative {
+        return Ok(original.to_path_buf());
+    }
+    let dst_dir = dst.parent().ok_or_else(|| format!("Cannot get parent folder of \"{}\"", dst.to_string_lossy()))?;
+    pathdiff::diff_paths(original, dst_dir).ok_or_else(|| format!("Cannot create relative path from \"{}\" to \"{}\"", dst_dir.to_string_lossy(), original.to_string_lossy()))
+}
+
 #[cfg(test)]
 mod test {
     use std::fs::{File, Metadata, read_dir};
@@ -339,8 +416,8 @@ mod test {
 
     use tempfile::tempdir;
 
-    use crate::common::items::new_excluded_item;
-    use crate::common::{make_file_symlink, make_hard_link, normalize_windows_path, regex_check, remove_folder_if_contains_only_empty_folders};
+    use crate::common::items::{ExcludedItems, new_excluded_item, try_new_excluded_item};
+    use crate::common::{get_symlink_target, make_file_symlink, make_hard_link, make_reflink, normalize_windows_path, regex_check, remove_folder_if_contains_only_empty_folders};
 
     #[cfg(target_family = "unix")]
     fn assert_inode(before: &Metadata, after: &Metadata) {
@@ -417,6 +494,20 @@ mod test {
     }
 
     #[cfg(any(target_family = "unix", target_family = "windows"))]
+    #[test]
+    fn test_get_symlink_target() {
+        let original = Path::new("/home/user/photos/a.jpg");
+        assert_eq!(
+            get_symlink_target(original, Path::new("/home/user/backup/b.jpg"), false),
+            Ok(PathBuf::from("/home/user/photos/a.jpg"))
+        );
+        assert_eq!(
+            get_symlink_target(original, Path::new("/home/user/backup/b.jpg"), true),
+            Ok(PathBuf::from("../photos/a.jpg"))
+        );
+        assert_eq!(get_symlink_target(original, Path::new("/home/user/photos/c.jpg"), true), Ok(PathBuf::from("a.jpg")));
+    }
+
     #[test]
     fn test_make_file_symlink_fails() -> io::Result<()> {
         let dir = tempfile::Builder::new().tempdir()?;
@@ -441,6 +532,34 @@ mod test {
         Ok(())
     }
 
+    // Reflinks are available only on some filesystems, so on others only error path is checked
+    #[test]
+    fn test_make_reflink() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        let (src, same, different, shorter) = (dir.path().join("a"), dir.path().join("b"), dir.path().join("c"), dir.path().join("d"));
+        fs::write(&src, vec![1u8; 100_000])?;
+        fs::write(&same, vec![1u8; 100_000])?;
+        fs::write(&different, vec![2u8; 100_000])?;
+        fs::write(&shorter, vec![1u8; 10])?;
+        let metadata = fs::metadata(&same)?;
+
+        match make_reflink(&src, &same) {
+            Ok(()) => {
+                assert_inode(&metadata, &fs::metadata(&same)?);
+                assert_eq!(metadata.modified()?, fs::metadata(&same)?.modified()?);
+            }
+            Err(e) => assert_eq!(e.kind(), io::ErrorKind::Unsupported),
+        }
+        assert_eq!(fs::read(&same)?, vec![1u8; 100_000]);
+
+        assert!(make_reflink(&src, &different).is_err());
+        assert_eq!(fs::read(&different)?, vec![2u8; 100_000]);
+
+        assert!(make_reflink(&src, &shorter).is_err());
+        assert!(make_reflink(dir.path().join("not_existing"), &same).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_remove_folder_if_contains_only_empty_folders() {
         let dir = tempdir().expect("Cannot create temporary directory");
@@ -497,6 +616,27 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_glob_and_regex_items() {
+        assert!(regex_check(&new_excluded_item("/home/**/*.jp?g"), "/home/rafal/photos/a.jpeg"));
+        assert!(regex_check(&new_excluded_item("/home/rafal/[abc].txt"), "/home/rafal/b.txt"));
+        assert!(!regex_check(&new_excluded_item("/home/rafal/[abc].txt"), "/home/rafal/d.txt"));
+        assert!(regex_check(&new_excluded_item("/home/rafal/file?.txt"), "/home/rafal/file1.txt"));
+        assert!(!regex_check(&new_excluded_item("/home/rafal/file?.txt"), "/home/rafal/file10.txt"));
+        assert!(regex_check(&new_excluded_item(r"regex:.*/IMG_\d{4}\.jpg$"), "/home/rafal/IMG_1234.jpg"));
+        assert!(!regex_check(&new_excluded_item(r"regex:.*/IMG_\d{4}\.jpg$"), "/home/rafal/IMG_12345.jpg"));
+
+        try_new_excluded_item("/home/[abc").unwrap_err();
+        try_new_excluded_item("regex:(unclosed").unwrap_err();
+        // Invalid pattern passed to infallible function is matched literally
+        assert!(regex_check(&new_excluded_item("/home/[abc"), "/home/[abc"));
+
+        let mut excluded_items = ExcludedItems::new();
+        let messages = excluded_items.set_excluded_items(vec!["/home/[abc".to_string(), "*/.git/*".to_string()]);
+        assert_eq!(messages.warnings.len(), 1);
+        assert!(excluded_items.is_excluded(Path::new("/home/rafal/.git/config")));
+    }
+
     #[test]
     fn test_windows_path() {
         assert_eq!(PathBuf::from("C:\\path.txt"), normalize_windows_path("c:/PATH.tXt"));
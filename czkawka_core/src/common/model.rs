@@ -6,7 +6,7 @@ use xxhash_rust::xxh3::Xxh3;
 use crate::common::traits::ResultEntry;
 use crate::tools::duplicate::MyHasher;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Deserialize, Serialize)]
 pub enum ToolType {
     Duplicate,
     EmptyFolders,
@@ -31,6 +31,8 @@ pub enum CheckingMethod {
     SizeName,
     Size,
     Hash,
+    // Only beginning, middle and end of files are hashed
+    SampledHash,
     AudioTags,
     AudioContent,
 }
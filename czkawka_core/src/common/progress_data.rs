@@ -104,6 +104,8 @@ pub enum CurrentStage {
     DuplicateScanningSize,
     DuplicatePreHashing,
     DuplicateFullHashing,
+    DuplicateSampledHashing,
+    DuplicateComparingContent,
 
     SameMusicCacheSavingTags,
     SameMusicCacheLoadingTags,
@@ -122,7 +124,7 @@ pub enum CurrentStage {
 }
 
 impl ProgressData {
-    pub(crate) fn validate(&self) {
+    pub(crate) fn validate(&self, has_optional_stage: bool) {
         assert!(
             self.current_stage_idx <= self.max_stage_idx,
             "Current stage index: {}, max stage index: {}, stage {:?}",
@@ -132,7 +134,7 @@ impl ProgressData {
         );
         assert_eq!(
             self.max_stage_idx,
-            self.tool_type.get_max_stage(self.checking_method),
+            self.tool_type.get_max_stage(self.checking_method, has_optional_stage),
             "Max stage index: {}, tool type: {:?}, checking method: {:?}",
             self.max_stage_idx,
             self.tool_type,
@@ -158,7 +160,7 @@ impl ProgressData {
 
         let tool_type_checking_method: Option<ToolType> = match self.checking_method {
             CheckingMethod::AudioTags | CheckingMethod::AudioContent => Some(ToolType::SameMusic),
-            CheckingMethod::Name | CheckingMethod::SizeName | CheckingMethod::Size | CheckingMethod::Hash => Some(ToolType::Duplicate),
+            CheckingMethod::Name | CheckingMethod::SizeName | CheckingMethod::Size | CheckingMethod::Hash | CheckingMethod::SampledHash => Some(ToolType::Duplicate),
             CheckingMethod::None => None,
         };
         if let Some(tool_type) = tool_type_checking_method {
@@ -173,7 +175,9 @@ impl ProgressData {
             | CurrentStage::DuplicateScanningSizeName
             | CurrentStage::DuplicateScanningSize
             | CurrentStage::DuplicatePreHashing
-            | CurrentStage::DuplicateFullHashing => Some(ToolType::Duplicate),
+            | CurrentStage::DuplicateFullHashing
+            | CurrentStage::DuplicateSampledHashing
+            | CurrentStage::DuplicateComparingContent => Some(ToolType::Duplicate),
             CurrentStage::SameMusicCacheLoadingTags
             | CurrentStage::SameMusicCacheSavingTags
             | CurrentStage::SameMusicCacheLoadingFingerprints
@@ -194,9 +198,10 @@ impl ProgressData {
 }
 
 impl ToolType {
-    pub(crate) fn get_max_stage(self, checking_method: CheckingMethod) -> u8 {
+    // Optional stage is counted only when it is enabled, e.g. verifying content of duplicates
+    pub(crate) fn get_max_stage(self, checking_method: CheckingMethod, has_optional_stage: bool) -> u8 {
         match self {
-            Self::Duplicate => 6,
+            Self::Duplicate => 6 + u8::from(has_optional_stage),
             Self::EmptyFolders | Self::EmptyFiles | Self::InvalidSymlinks | Self::BigFile | Self::TemporaryFiles => 0,
             Self::BrokenFiles | Self::BadExtensions | Self::SimilarVideos => 1,
             Self::SimilarImages => 2,
@@ -230,7 +235,9 @@ impl CurrentStage {
             Self::DuplicatePreHashCacheSaving => 3,
             Self::DuplicateCacheLoading => 4,
             Self::DuplicateFullHashing => 5,
+            Self::DuplicateSampledHashing => 5,
             Self::DuplicateCacheSaving => 6,
+            Self::DuplicateComparingContent => 7,
             Self::SimilarImagesCalculatingHashes => 1,
             Self::SimilarImagesComparingHashes => 2,
             Self::SimilarVideosCalculatingHashes => 1,
@@ -63,10 +63,10 @@ pub(crate) fn prepare_thread_handler_common(
     progress_sender: Option<&Sender<ProgressData>>,
     sstage: CurrentStage,
     max_items: usize,
-    test_type: (ToolType, CheckingMethod),
+    test_type: (ToolType, CheckingMethod, bool),
     max_size: u64,
 ) -> ProgressThreadHandler {
-    let (tool_type, checking_method) = test_type;
+    let (tool_type, checking_method, has_optional_stage) = test_type;
     assert_ne!(tool_type, ToolType::None, "Cannot send progress data for ToolType::None");
     let progress_status = ProgressStatus::new();
     let progress_thread_running = Arc::new(AtomicBool::new(true));
@@ -84,7 +84,7 @@ pub(crate) fn prepare_thread_handler_common(
                         sstage,
                         checking_method,
                         current_stage_idx: sstage.get_current_stage(),
-                        max_stage_idx: tool_type.get_max_stage(checking_method),
+                        max_stage_idx: tool_type.get_max_stage(checking_method, has_optional_stage),
                         entries_checked: progress_status.items_counter.load(atomic::Ordering::Relaxed),
                         entries_to_check: max_items,
                         bytes_checked: progress_status.size_counter.load(atomic::Ordering::Relaxed),
@@ -92,7 +92,7 @@ pub(crate) fn prepare_thread_handler_common(
                         tool_type,
                     };
 
-                    progress_data.validate();
+                    progress_data.validate(has_optional_stage);
 
                     progress_sender.send(progress_data).expect("Cannot send progress data");
                     time_since_last_send = Instant::now();
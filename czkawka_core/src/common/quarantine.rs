@@ -0,0 +1,347 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, ErrorKind, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use fun_time::fun_time;
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::common::check_if_folder_contains_only_empty_folders;
+use crate::helpers::messages::Messages;
+
+pub const QUARANTINE_MANIFEST_FILE: &str = "czkawka_quarantine_manifest.jsonl";
+// Locked while manifest is modified, so entries appended by one app are not lost when other app rewrites manifest
+const QUARANTINE_MANIFEST_LOCK_FILE: &str = "czkawka_quarantine_manifest.lock";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuarantineEntry {
+    pub original_path: PathBuf,
+    pub quarantined_path: PathBuf,
+    pub size: u64,
+    pub modified_date: u64,
+    pub quarantined_at: u64,
+}
+
+/// Moves items into quarantine folder instead of removing them.
+/// Every operation uses its own subfolder, in which original absolute paths are recreated, so moved items never collide
+#[derive(Debug)]
+pub struct Quarantine {
+    folder: PathBuf,
+    operation_folder: PathBuf,
+    quarantined_at: u64,
+    entries: Mutex<Vec<QuarantineEntry>>,
+}
+
+impl Quarantine {
+    pub fn new(folder: impl AsRef<Path>) -> Self {
+        let folder = folder.as_ref().to_path_buf();
+        let current_time = get_current_time();
+        Self {
+            operation_folder: folder.join(current_time.as_millis().to_string()),
+            folder,
+            quarantined_at: current_time.as_secs(),
+            entries: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn get_destination(&self, path: &Path) -> PathBuf {
+        let mut destination = self.operation_folder.clone();
+        for component in path.components() {
+            match component {
+                // Windows drive letter or network share becomes normal folder
+                Component::Prefix(prefix) => destination.push(prefix.as_os_str().to_string_lossy().replace(['\\', '/'], "_").replace([':', '?'], "")),
+                Component::Normal(name) => destination.push(name),
+                Component::RootDir | Component::CurDir | Component::ParentDir => {}
+            }
+        }
+        destination
+    }
+
+    // Moves file or folder into quarantine, folders are moved only when they contain nothing except empty folders
+    pub fn move_item(&self, path: &Path, only_empty_folder: bool) -> Result<PathBuf, String> {
+        let metadata = fs::symlink_metadata(path).map_err(|e| format!("Cannot read metadata of \"{}\", reason {e}", path.to_string_lossy()))?;
+        if only_empty_folder {
+            check_if_folder_contains_only_empty_folders(path)?;
+        }
+
+        let destination = self.get_destination(path);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Cannot create quarantine folder \"{}\", reason {e}", parent.to_string_lossy()))?;
+        }
+
+        if let Err(e) = fs::rename(path, &destination) {
+            if e.kind() != ErrorKind::CrossesDevices {
+                return Err(format!(
+                    "Cannot move \"{}\" to quarantine \"{}\", reason {e}",
+                    path.to_string_lossy(),
+                    destination.to_string_lossy()
+                ));
+            }
+            // Quarantine may be on different partition, so file needs to be copied, folders are not supported in such case
+            if metadata.is_dir() {
+                return Err(format!(
+                    "Cannot move folder \"{}\" to quarantine \"{}\", it needs to be on the same partition",
+                    path.to_string_lossy(),
+                    self.folder.to_string_lossy()
+                ));
+            }
+            copy_and_remove(path, &destination)
+                .map_err(|e| format!("Cannot move \"{}\" to quarantine \"{}\", reason {e}", path.to_string_lossy(), destination.to_string_lossy()))?;
+        }
+
+        let modified_date = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |duration| duration.as_secs());
+        self.entries.lock().expect("Quarantine lock poisoned").push(QuarantineEntry {
+            original_path: path.to_path_buf(),
+            quarantined_path: destination.clone(),
+            size: metadata.len(),
+            modified_date,
+            quarantined_at: self.quarantined_at,
+        });
+        Ok(destination)
+    }
+
+    // Appends entries of items moved in this operation to manifest
+    #[fun_time(message = "save_quarantine_manifest", level = "debug")]
+    pub fn save_manifest(&self) -> Result<(), String> {
+        let entries = std::mem::take(&mut *self.entries.lock().expect("Quarantine lock poisoned"));
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let _lock = lock_manifest(&self.folder)?;
+        let manifest_file = self.folder.join(QUARANTINE_MANIFEST_FILE);
+        let file_handler = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&manifest_file)
+            .map_err(|e| format!("Cannot open quarantine manifest {manifest_file:?}, reason {e}"))?;
+        write_entries(file_handler, &entries).map_err(|e| format!("Cannot write to quarantine manifest {manifest_file:?}, reason {e}"))?;
+        debug!("Saved {} entries to quarantine manifest {manifest_file:?}", entries.len());
+        Ok(())
+    }
+}
+
+#[fun_time(message = "load_quarantine_manifest", level = "debug")]
+pub fn load_quarantine_manifest(folder: &Path, warnings: &mut Vec<String>) -> Vec<QuarantineEntry> {
+    let manifest_file = folder.join(QUARANTINE_MANIFEST_FILE);
+    let Ok(file_handler) = File::open(&manifest_file) else {
+        debug!("Quarantine manifest {manifest_file:?} not exists");
+        return Vec::new();
+    };
+
+    let mut entries = Vec::new();
+    for (idx, line) in BufReader::new(file_handler).lines().enumerate() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                warnings.push(format!("Failed to read quarantine manifest {manifest_file:?}, reason {e}"));
+                break;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(&line) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => warnings.push(format!("Failed to parse line {} of quarantine manifest {manifest_file:?}, reason {e}", idx + 1)),
+        }
+    }
+    entries
+}
+
+// Permanently removes items quarantined more than given number of days ago
+#[fun_time(message = "purge_quarantine", level = "debug")]
+pub fn purge_quarantine(folder: &Path, max_age_days: u64) -> Messages {
+    let mut messages = Messages::new();
+    let _lock = match lock_manifest(folder) {
+        Ok(lock) => lock,
+        Err(e) => {
+            messages.errors.push(e);
+            return messages;
+        }
+    };
+    let entries = load_quarantine_manifest(folder, &mut messages.warnings);
+    let cutoff = get_current_time().as_secs().saturating_sub(max_age_days * 24 * 60 * 60);
+
+    let (expired, mut remaining): (Vec<_>, Vec<_>) = entries.into_iter().partition(|entry| entry.quarantined_at <= cutoff);
+    let mut purged = 0;
+    for entry in expired {
+        let path = &entry.quarantined_path;
+        let result = match fs::symlink_metadata(path) {
+            Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+            Ok(_) => fs::remove_file(path),
+            // Item was already restored or removed by user
+            Err(_) => Ok(()),
+        };
+        match result {
+            Ok(()) => {
+                purged += 1;
+                remove_empty_parents(path, folder);
+            }
+            Err(e) => {
+                messages.errors.push(format!("Failed to remove \"{}\" from quarantine, reason {e}", path.to_string_lossy()));
+                remaining.push(entry);
+            }
+        }
+    }
+    messages.messages.push(format!("Removed {purged} items from quarantine, {} items left", remaining.len()));
+
+    if purged > 0
+        && let Err(e) = rewrite_manifest(folder, &remaining)
+    {
+        messages.errors.push(e);
+    }
+    messages
+}
+
+// Removes entry of item restored from quarantine, quarantine folder is found from path of item, because it is not saved in journal
+pub fn remove_restored_item_from_manifest(quarantined_path: &Path) -> Result<(), String> {
+    let Some(folder) = quarantined_path.ancestors().skip(1).find(|folder| folder.join(QUARANTINE_MANIFEST_FILE).is_file()) else {
+        return Ok(());
+    };
+    let _lock = lock_manifest(folder)?;
+    let mut warnings = Vec::new();
+    let mut entries = load_quarantine_manifest(folder, &mut warnings);
+    let entries_number = entries.len();
+    entries.retain(|entry| entry.quarantined_path != quarantined_path);
+    remove_empty_parents(quarantined_path, folder);
+    if entries.len() == entries_number {
+        return Ok(());
+    }
+    rewrite_manifest(folder, &entries)
+}
+
+// Lock is released, when returned file is dropped
+fn lock_manifest(folder: &Path) -> Result<File, String> {
+    let lock_file = folder.join(QUARANTINE_MANIFEST_LOCK_FILE);
+    fs::create_dir_all(folder).map_err(|e| format!("Cannot create quarantine folder \"{}\", reason {e}", folder.to_string_lossy()))?;
+    let file_handler = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&lock_file)
+        .map_err(|e| format!("Cannot open quarantine manifest lock {lock_file:?}, reason {e}"))?;
+    file_handler.lock().map_err(|e| format!("Cannot lock quarantine manifest {lock_file:?}, reason {e}"))?;
+    Ok(file_handler)
+}
+
+// Copy keeps only permissions, so modification date is copied separately, to be the same after restoring file
+pub(crate) fn copy_and_remove(source: &Path, destination: &Path) -> std::io::Result<()> {
+    fs::copy(source, destination)?;
+    if let Ok(modified) = fs::metadata(source).and_then(|metadata| metadata.modified()) {
+        let _ = File::options().write(true).open(destination).and_then(|file| file.set_modified(modified));
+    }
+    fs::remove_file(source)
+}
+
+// Folders recreated inside quarantine are not needed, when last item inside them was removed
+fn remove_empty_parents(path: &Path, quarantine_folder: &Path) {
+    for parent in path.ancestors().skip(1) {
+        if parent == quarantine_folder || !parent.starts_with(quarantine_folder) || fs::remove_dir(parent).is_err() {
+            break;
+        }
+    }
+}
+
+fn rewrite_manifest(folder: &Path, entries: &[QuarantineEntry]) -> Result<(), String> {
+    let manifest_file = folder.join(QUARANTINE_MANIFEST_FILE);
+    let temp_file = folder.join(format!("{QUARANTINE_MANIFEST_FILE}.czkawka_tmp"));
+    File::create(&temp_file)
+        .and_then(|file_handler| write_entries(file_handler, entries))
+        .and_then(|()| fs::rename(&temp_file, &manifest_file))
+        .map_err(|e| format!("Cannot save quarantine manifest {manifest_file:?}, reason {e}"))
+}
+
+fn write_entries(file_handler: File, entries: &[QuarantineEntry]) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(file_handler);
+    for entry in entries {
+        serde_json::to_writer(&mut writer, entry)?;
+        writeln!(writer)?;
+    }
+    writer.flush()
+}
+
+fn get_current_time() -> Duration {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::common::deletion_journal::{DeletionJournal, JournalAction, JournalEntry, calculate_journal_hash, undo_entry};
+
+    #[test]
+    fn test_quarantine_and_purge() {
+        let temp_dir = tempdir().unwrap();
+        let quarantine_folder = temp_dir.path().join("quarantine");
+        let file = temp_dir.path().join("data").join("file.txt");
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, "content").unwrap();
+
+        let quarantine = Quarantine::new(&quarantine_folder);
+        let destination = quarantine.move_item(&file, false).unwrap();
+        assert!(!file.exists());
+        assert!(destination.starts_with(&quarantine_folder));
+        assert!(destination.ends_with(file.strip_prefix("/").unwrap_or(&file)));
+        assert_eq!(fs::read_to_string(&destination).unwrap(), "content");
+        quarantine.save_manifest().unwrap();
+
+        let entries = load_quarantine_manifest(&quarantine_folder, &mut Vec::new());
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].original_path, file);
+
+        // Item is too new to be purged
+        purge_quarantine(&quarantine_folder, 1);
+        assert!(destination.exists());
+
+        let messages = purge_quarantine(&quarantine_folder, 0);
+        assert!(messages.errors.is_empty());
+        assert!(!destination.exists());
+        assert!(load_quarantine_manifest(&quarantine_folder, &mut Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn test_undo_removes_entry_from_manifest() {
+        let temp_dir = tempdir().unwrap();
+        let quarantine_folder = temp_dir.path().join("quarantine");
+        let files = ["first.txt", "second.txt"].map(|name| temp_dir.path().join(name));
+        for file in &files {
+            fs::write(file, "content").unwrap();
+        }
+
+        let quarantine = Quarantine::new(&quarantine_folder);
+        let journal = DeletionJournal::new();
+        for file in &files {
+            let action = JournalAction::Quarantine {
+                destination: quarantine.get_destination(file),
+            };
+            journal.record(file, action, || quarantine.move_item(file, false).map(|_| ())).unwrap();
+        }
+        quarantine.save_manifest().unwrap();
+
+        let first_entry = JournalEntry {
+            operation_id: journal.get_operation_id(),
+            timestamp: 0,
+            path: files[0].clone(),
+            size: 7,
+            modified_date: 0,
+            hash: Some(calculate_journal_hash(&quarantine.get_destination(&files[0])).unwrap()),
+            action: JournalAction::Quarantine {
+                destination: quarantine.get_destination(&files[0]),
+            },
+        };
+        undo_entry(&first_entry).unwrap();
+        assert_eq!(fs::read_to_string(&files[0]).unwrap(), "content");
+
+        let entries = load_quarantine_manifest(&quarantine_folder, &mut Vec::new());
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].original_path, files[1]);
+    }
+}
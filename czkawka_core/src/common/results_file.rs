@@ -0,0 +1,301 @@
+use std::fs;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+
+use fun_time::fun_time;
+use log::debug;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::common::dir_traversal::get_modified_time;
+use crate::common::model::{CheckingMethod, ToolType};
+use crate::common::traits::ResultEntry;
+
+/// Version of results file format, must be increased when layout of saved results of any tool changes
+pub const RESULTS_FILE_VERSION: u32 = 2;
+
+#[derive(Serialize, Deserialize)]
+struct ResultsFile<T> {
+    version: u32,
+    tool_type: ToolType,
+    checking_method: CheckingMethod,
+    use_reference_folders: bool,
+    results: T,
+}
+
+// Used only to provide better error message, when full file cannot be parsed
+#[derive(Deserialize)]
+struct ResultsFileHeader {
+    version: u32,
+    tool_type: ToolType,
+    checking_method: CheckingMethod,
+}
+
+/// Results of tools which group similar files, only one of fields is filled, depending on usage of reference folders
+#[derive(Default, Serialize, Deserialize)]
+pub struct GroupedResults<T> {
+    pub groups: Vec<Vec<T>>,
+    pub referenced_groups: Vec<(T, Vec<T>)>,
+}
+
+#[derive(Debug)]
+pub(crate) struct LoadedResults<T> {
+    pub(crate) use_reference_folders: bool,
+    pub(crate) results: T,
+}
+
+#[fun_time(message = "save_results_file", level = "debug")]
+pub(crate) fn save_results_file<T: Serialize>(
+    file_name: &str,
+    tool_type: ToolType,
+    checking_method: CheckingMethod,
+    use_reference_folders: bool,
+    results: &T,
+) -> std::io::Result<()> {
+    let results_file = ResultsFile {
+        version: RESULTS_FILE_VERSION,
+        tool_type,
+        checking_method,
+        use_reference_folders,
+        results,
+    };
+
+    let file_handler = File::create(file_name)?;
+    let mut writer = BufWriter::new(file_handler);
+    serde_json::to_writer(&mut writer, &results_file)?;
+    writer.flush()?;
+    debug!("Saved results of {tool_type:?} to file \"{file_name}\"");
+    Ok(())
+}
+
+#[fun_time(message = "load_results_file", level = "debug")]
+pub(crate) fn load_results_file<T>(file_name: &str, tool_type: ToolType, checking_method: CheckingMethod) -> Result<LoadedResults<T>, String>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    let open_file = || {
+        File::open(file_name)
+            .map(BufReader::new)
+            .map_err(|e| format!("Cannot open results file \"{file_name}\", reason {e}"))
+    };
+
+    let results_file: ResultsFile<T> = match serde_json::from_reader(open_file()?) {
+        Ok(t) => t,
+        Err(e) => {
+            // Header is checked only here, to not parse possibly huge file twice in normal situation
+            if let Ok(header) = serde_json::from_reader::<_, ResultsFileHeader>(open_file()?) {
+                check_results_file_header(file_name, header.version, header.tool_type, header.checking_method, tool_type, checking_method)?;
+            }
+            return Err(format!("Cannot load results file \"{file_name}\", reason {e}"));
+        }
+    };
+    check_results_file_header(
+        file_name,
+        results_file.version,
+        results_file.tool_type,
+        results_file.checking_method,
+        tool_type,
+        checking_method,
+    )?;
+
+    Ok(LoadedResults {
+        use_reference_folders: results_file.use_reference_folders,
+        results: results_file.results,
+    })
+}
+
+fn check_results_file_header(
+    file_name: &str,
+    version: u32,
+    file_tool_type: ToolType,
+    file_checking_method: CheckingMethod,
+    tool_type: ToolType,
+    checking_method: CheckingMethod,
+) -> Result<(), String> {
+    if version != RESULTS_FILE_VERSION {
+        return Err(format!(
+            "Results file \"{file_name}\" was saved in format version {version}, but only version {RESULTS_FILE_VERSION} is supported"
+        ));
+    }
+    if file_tool_type != tool_type {
+        return Err(format!("Results file \"{file_name}\" contains results of {file_tool_type:?} tool, not {tool_type:?}"));
+    }
+    if file_checking_method != checking_method {
+        return Err(format!(
+            "Results file \"{file_name}\" was created with {file_checking_method:?} checking method, but {checking_method:?} is used now"
+        ));
+    }
+    Ok(())
+}
+
+/// Checks if file still has same size and modification date as when it was found
+pub(crate) fn check_if_entry_is_unchanged<T: ResultEntry>(entry: &T) -> Result<(), String> {
+    let path = entry.get_path();
+    let metadata = fs::symlink_metadata(path).map_err(|e| format!("Cannot read metadata of \"{}\", reason {e}", path.to_string_lossy()))?;
+
+    let is_folder = metadata.is_dir();
+    if !is_folder && metadata.len() != entry.get_size() {
+        return Err(format!(
+            "Size of \"{}\" changed from {} to {} bytes since results were saved",
+            path.to_string_lossy(),
+            entry.get_size(),
+            metadata.len()
+        ));
+    }
+
+    let mut warnings = Vec::new();
+    let modified_date = get_modified_time(&metadata, &mut warnings, path, is_folder);
+    if modified_date != entry.get_modified_date() {
+        return Err(format!(
+            "Modification date of \"{}\" changed from {} to {modified_date} since results were saved",
+            path.to_string_lossy(),
+            entry.get_modified_date()
+        ));
+    }
+
+    Ok(())
+}
+
+fn split_unchanged_entries<T: ResultEntry>(entries: Vec<T>) -> (Vec<T>, Vec<String>) {
+    let mut unchanged = Vec::with_capacity(entries.len());
+    let mut errors = Vec::new();
+    for entry in entries {
+        match check_if_entry_is_unchanged(&entry) {
+            Ok(()) => unchanged.push(entry),
+            Err(e) => errors.push(e),
+        }
+    }
+    (unchanged, errors)
+}
+
+/// Removes entries that changed on disk since results were saved, reason of removal is added to warnings
+pub(crate) fn retain_unchanged_entries<T: ResultEntry + Send>(entries: Vec<T>, warnings: &mut Vec<String>) -> Vec<T> {
+    let checked = entries.into_par_iter().map(|entry| check_if_entry_is_unchanged(&entry).map(|()| entry)).collect::<Vec<_>>();
+
+    let mut unchanged = Vec::with_capacity(checked.len());
+    for result in checked {
+        match result {
+            Ok(entry) => unchanged.push(entry),
+            Err(e) => warnings.push(e),
+        }
+    }
+    unchanged
+}
+
+/// Same as `retain_unchanged_entries`, but also removes groups which no longer contain at least 2 elements
+pub(crate) fn retain_unchanged_groups<T: ResultEntry + Send>(groups: Vec<Vec<T>>, warnings: &mut Vec<String>) -> Vec<Vec<T>> {
+    let checked = groups.into_par_iter().map(split_unchanged_entries).collect::<Vec<_>>();
+
+    let mut unchanged_groups = Vec::with_capacity(checked.len());
+    for (group, errors) in checked {
+        warnings.extend(errors);
+        if group.len() > 1 {
+            unchanged_groups.push(group);
+        }
+    }
+    unchanged_groups
+}
+
+/// Group is removed when its reference file changed or when no other file in it is unchanged
+pub(crate) fn retain_unchanged_referenced_groups<T: ResultEntry + Send>(groups: Vec<(T, Vec<T>)>, warnings: &mut Vec<String>) -> Vec<(T, Vec<T>)> {
+    let checked = groups
+        .into_par_iter()
+        .map(|(reference, group)| {
+            if let Err(e) = check_if_entry_is_unchanged(&reference) {
+                let mut errors = vec![e];
+                errors.extend(group.iter().filter_map(|entry| check_if_entry_is_unchanged(entry).err()));
+                return (None, errors);
+            }
+            let (group, errors) = split_unchanged_entries(group);
+            (Some((reference, group)), errors)
+        })
+        .collect::<Vec<_>>();
+
+    let mut unchanged_groups = Vec::with_capacity(checked.len());
+    for (group, errors) in checked {
+        warnings.extend(errors);
+        if let Some((reference, group)) = group
+            && !group.is_empty()
+        {
+            unchanged_groups.push((reference, group));
+        }
+    }
+    unchanged_groups
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::Write;
+
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::common::model::FileEntry;
+
+    fn create_entry(path: &std::path::Path, content: &[u8]) -> FileEntry {
+        let mut file = File::create(path).expect("Cannot create file");
+        file.write_all(content).expect("Cannot write to file");
+        let metadata = fs::metadata(path).expect("Cannot read metadata");
+        FileEntry {
+            path: path.to_path_buf(),
+            size: metadata.len(),
+            modified_date: get_modified_time(&metadata, &mut Vec::new(), path, false),
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_results_file() {
+        let dir = tempdir().expect("Cannot create temp dir");
+        let results_path = dir.path().join("results.json");
+        let results_file = results_path.to_string_lossy().to_string();
+        let entry = create_entry(&dir.path().join("a.txt"), b"abc");
+
+        save_results_file(&results_file, ToolType::Duplicate, CheckingMethod::Hash, true, &vec![vec![entry.clone()]]).expect("Cannot save results");
+
+        let loaded: LoadedResults<Vec<Vec<FileEntry>>> = load_results_file(&results_file, ToolType::Duplicate, CheckingMethod::Hash).expect("Cannot load results");
+        assert!(loaded.use_reference_folders);
+        assert_eq!(loaded.results, vec![vec![entry]]);
+
+        let err = load_results_file::<Vec<Vec<FileEntry>>>(&results_file, ToolType::SimilarImages, CheckingMethod::None).expect_err("Tool type should not match");
+        assert!(err.contains("Duplicate"));
+        let err = load_results_file::<Vec<Vec<FileEntry>>>(&results_file, ToolType::Duplicate, CheckingMethod::Size).expect_err("Checking method should not match");
+        assert!(err.contains("Size"));
+        let err = load_results_file::<Vec<FileEntry>>(&results_file, ToolType::EmptyFiles, CheckingMethod::None).expect_err("Content should not match");
+        assert!(err.contains("EmptyFiles"));
+    }
+
+    #[test]
+    fn test_retain_unchanged_entries() {
+        let dir = tempdir().expect("Cannot create temp dir");
+        let unchanged = create_entry(&dir.path().join("a.txt"), b"abc");
+        let mut resized = create_entry(&dir.path().join("b.txt"), b"abc");
+        resized.size += 1;
+        let mut modified = create_entry(&dir.path().join("c.txt"), b"abc");
+        modified.modified_date += 1;
+        let removed = create_entry(&dir.path().join("d.txt"), b"abc");
+        fs::remove_file(&removed.path).expect("Cannot remove file");
+
+        let mut warnings = Vec::new();
+        let retained = retain_unchanged_entries(vec![unchanged.clone(), resized.clone(), modified.clone(), removed.clone()], &mut warnings);
+        assert_eq!(retained, vec![unchanged.clone()]);
+        assert_eq!(warnings.len(), 3);
+
+        let mut warnings = Vec::new();
+        let groups = retain_unchanged_groups(vec![vec![unchanged.clone(), resized.clone()], vec![unchanged.clone(), unchanged.clone()]], &mut warnings);
+        assert_eq!(groups, vec![vec![unchanged.clone(), unchanged.clone()]]);
+        assert_eq!(warnings.len(), 1);
+
+        let mut warnings = Vec::new();
+        let groups = retain_unchanged_referenced_groups(
+            vec![
+                (modified, vec![unchanged.clone()]),
+                (unchanged.clone(), vec![removed]),
+                (unchanged.clone(), vec![unchanged.clone(), resized]),
+            ],
+            &mut warnings,
+        );
+        assert_eq!(groups, vec![(unchanged.clone(), vec![unchanged])]);
+        assert_eq!(warnings.len(), 3);
+    }
+}
@@ -1,5 +1,5 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::Duration;
@@ -9,13 +9,18 @@ use humansize::{BINARY, format_size};
 use log::info;
 use rayon::prelude::*;
 
+use crate::common::deletion_journal::{DeletionJournal, JournalAction};
 use crate::common::directories::Directories;
 use crate::common::extensions::Extensions;
-use crate::common::items::ExcludedItems;
+use crate::common::ignored_groups::{IgnoredGroups, IgnoredGroupsFilter};
+use crate::common::image::has_exif_data;
+use crate::common::items::{ExcludedItems, IncludedItems};
+use crate::common::metadata_filter::MetadataFilter;
 use crate::common::model::{CheckingMethod, ToolType, WorkContinueStatus};
 use crate::common::progress_data::{CurrentStage, ProgressData};
-use crate::common::remove_folder_if_contains_only_empty_folders;
+use crate::common::quarantine::Quarantine;
 use crate::common::traits::ResultEntry;
+use crate::common::{get_symlink_target, make_file_symlink, make_reflink, remove_folder_if_contains_only_empty_folders};
 use crate::helpers::delayed_sender::DelayedSender;
 use crate::helpers::messages::Messages;
 
@@ -26,16 +31,25 @@ pub struct CommonToolData {
     pub(crate) directories: Directories,
     pub(crate) extensions: Extensions,
     pub(crate) excluded_items: ExcludedItems,
+    pub(crate) included_items: IncludedItems,
     pub(crate) recursive_search: bool,
     pub(crate) delete_method: DeleteMethod,
     pub(crate) maximal_file_size: u64,
     pub(crate) minimal_file_size: u64,
+    pub(crate) metadata_filter: MetadataFilter,
     pub(crate) stopped_search: bool,
     pub(crate) use_cache: bool,
     pub(crate) delete_outdated_cache: bool,
     pub(crate) save_also_as_json: bool,
     pub(crate) use_reference_folders: bool,
     pub(crate) dry_run: bool,
+    pub(crate) use_directory_snapshot: bool,
+    pub(crate) use_ignore_files: bool,
+    pub(crate) max_depth: Option<usize>,
+    pub(crate) follow_symlinks: bool,
+    pub(crate) ignored_groups: IgnoredGroups,
+    pub(crate) quarantine_folder: Option<PathBuf>,
+    pub(crate) keeper_rules: KeeperRules,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -59,20 +73,39 @@ pub enum DeleteItemType<T: ResultEntry + Sized + Send + Sync> {
     DeletingFiles(Vec<T>),
     DeletingFolders(Vec<T>),
     HardlinkingFiles(Vec<(T, Vec<T>)>),
+    ReflinkingFiles(Vec<(T, Vec<T>)>),
+    // Bool is set, when symlinks should use relative paths
+    SymlinkingFiles(Vec<(T, Vec<T>)>, bool),
 }
 
 impl<T: ResultEntry + Sized + Send + Sync> DeleteItemType<T> {
     fn calculate_size_to_delete(&self) -> u64 {
         match &self {
             Self::DeletingFiles(items) | Self::DeletingFolders(items) => items.iter().map(|item| item.get_size()).sum(),
-            Self::HardlinkingFiles(items) => items.iter().map(|(item, _)| item.get_size()).sum(),
+            Self::HardlinkingFiles(items) | Self::ReflinkingFiles(items) | Self::SymlinkingFiles(items, _) => items.iter().map(|(item, _)| item.get_size()).sum(),
+        }
+    }
+
+    // Reflinked files stay independent, so there is nothing to revert later
+    fn get_journal_action(&self, item: &T, original: Option<&T>, quarantine: Option<&Quarantine>) -> Option<JournalAction> {
+        let original = || original.map(|e| e.get_path().to_path_buf()).unwrap_or_default();
+        match &self {
+            Self::DeletingFiles(_) | Self::DeletingFolders(_) => Some(match quarantine {
+                Some(quarantine) => JournalAction::Quarantine {
+                    destination: quarantine.get_destination(item.get_path()),
+                },
+                None => JournalAction::Delete,
+            }),
+            Self::HardlinkingFiles(_) => Some(JournalAction::Hardlink { original: original() }),
+            Self::SymlinkingFiles(..) => Some(JournalAction::Symlink { original: original() }),
+            Self::ReflinkingFiles(_) => None,
         }
     }
 
     fn calculate_entries_to_delete(&self) -> usize {
         match &self {
             Self::DeletingFiles(items) | Self::DeletingFolders(items) => items.len(),
-            Self::HardlinkingFiles(items) => items.iter().map(|(_original, files)| files.len()).sum(),
+            Self::HardlinkingFiles(items) | Self::ReflinkingFiles(items) | Self::SymlinkingFiles(items, _) => items.iter().map(|(_original, files)| files.len()).sum(),
         }
     }
 }
@@ -87,13 +120,245 @@ pub enum DeleteMethod {
     OneOldest,
     OneNewest,
     HardLink,
+    Reflink, // Share data of files with copy-on-write, supported only on some filesystems
     AllExceptBiggest,
     AllExceptSmallest,
     OneBiggest,
     OneSmallest,
+    // Other files in group are replaced by symlinks to the kept one
+    Symlink {
+        keep: KeptFile,
+        relative: bool,
+    },
+}
+
+#[derive(Eq, PartialEq, Clone, Debug, Copy, Default)]
+pub enum KeptFile {
+    #[default]
+    Newest,
+    Oldest,
+    Biggest,
+    Smallest,
+}
+
+impl DeleteMethod {
+    // Whether entries in group are compared by size instead of modification date
+    pub(crate) fn sorts_by_size(self) -> bool {
+        matches!(
+            self,
+            Self::AllExceptBiggest
+                | Self::AllExceptSmallest
+                | Self::OneBiggest
+                | Self::OneSmallest
+                | Self::Symlink {
+                    keep: KeptFile::Biggest | KeptFile::Smallest,
+                    ..
+                }
+        )
+    }
+
+    pub fn supports_keeper_rules(self) -> bool {
+        self.get_keeper_tie_breaker().is_some()
+    }
+
+    // Rule matching file kept by this method, used as last tie-breaker of keeper rules
+    // Methods which do not keep exactly one file in group, cannot be used with keeper rules
+    pub(crate) fn get_keeper_tie_breaker(self) -> Option<KeeperRule> {
+        match self {
+            Self::AllExceptNewest | Self::Symlink { keep: KeptFile::Newest, .. } => Some(KeeperRule::PreferNewest),
+            Self::AllExceptOldest | Self::HardLink | Self::Reflink | Self::Symlink { keep: KeptFile::Oldest, .. } => Some(KeeperRule::PreferOldest),
+            Self::AllExceptBiggest | Self::Symlink { keep: KeptFile::Biggest, .. } => Some(KeeperRule::PreferBiggest),
+            Self::AllExceptSmallest | Self::Symlink { keep: KeptFile::Smallest, .. } => Some(KeeperRule::PreferSmallest),
+            Self::None | Self::Delete | Self::OneOldest | Self::OneNewest | Self::OneBiggest | Self::OneSmallest => None,
+        }
+    }
+}
+
+/// Declarative rule used to choose file kept in group.
+/// Rules are checked in order, next rule is used only when all previous rules consider files equal
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KeeperRule {
+    // Prefer files inside given folder
+    PreferPath(PathBuf),
+    PreferShortestPath,
+    PreferLongestPath,
+    // Prefer files which name does not contain given text e.g. " (1)"
+    AvoidName(String),
+    PreferExif,
+    // Read-only files and files on read-only mounts are never deleted or replaced by links
+    NeverDeleteReadOnly,
+    PreferNewest,
+    PreferOldest,
+    PreferBiggest,
+    PreferSmallest,
+}
+
+impl KeeperRule {
+    // Lower value means more preferred file
+    fn get_key<T: ResultEntry>(&self, entry: &T) -> u64 {
+        let path = entry.get_path();
+        match self {
+            Self::PreferPath(folder) => u64::from(!path.starts_with(folder)),
+            Self::PreferShortestPath => path.as_os_str().len() as u64,
+            Self::PreferLongestPath => u64::MAX - path.as_os_str().len() as u64,
+            Self::AvoidName(text) => u64::from(path.file_name().is_some_and(|name| name.to_string_lossy().contains(text.as_str()))),
+            Self::PreferExif => u64::from(!has_exif_data(path)),
+            Self::NeverDeleteReadOnly => u64::from(!is_read_only(path)),
+            Self::PreferNewest => u64::MAX - entry.get_modified_date(),
+            Self::PreferOldest => entry.get_modified_date(),
+            Self::PreferBiggest => u64::MAX - entry.get_size(),
+            Self::PreferSmallest => entry.get_size(),
+        }
+    }
+
+    fn parse(line: &str) -> Result<Self, String> {
+        let (name, argument) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        // Quotes allow to use text starting or ending with spaces
+        let argument = argument.trim();
+        let argument = argument.strip_prefix('"').and_then(|e| e.strip_suffix('"')).unwrap_or(argument);
+        let rule = match name {
+            "prefer_path" => Self::PreferPath(PathBuf::from(argument)),
+            "prefer_shortest_path" => Self::PreferShortestPath,
+            "prefer_longest_path" => Self::PreferLongestPath,
+            "avoid_name" => Self::AvoidName(argument.to_string()),
+            "prefer_exif" => Self::PreferExif,
+            "never_delete_read_only" => Self::NeverDeleteReadOnly,
+            "prefer_newest" => Self::PreferNewest,
+            "prefer_oldest" => Self::PreferOldest,
+            "prefer_biggest" => Self::PreferBiggest,
+            "prefer_smallest" => Self::PreferSmallest,
+            _ => return Err(format!("Unknown keeper rule \"{name}\"")),
+        };
+        if matches!(rule, Self::PreferPath(_) | Self::AvoidName(_)) && argument.is_empty() {
+            return Err(format!("Keeper rule \"{name}\" requires argument"));
+        }
+        Ok(rule)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeeperRules {
+    rules: Vec<KeeperRule>,
+}
+
+impl KeeperRules {
+    pub fn new(rules: Vec<KeeperRule>) -> Self {
+        Self { rules }
+    }
+
+    // One rule per line or separated by ";", lines starting with "#" are comments, e.g.
+    // prefer_path /home/user/archive
+    // avoid_name " (1)"
+    // prefer_shortest_path
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let rules = split_keeper_rules(content)?
+            .iter()
+            .map(|rule| rule.trim())
+            .filter(|rule| !rule.is_empty())
+            .map(KeeperRule::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn load_from_file(file_name: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(file_name).map_err(|e| format!("Cannot read keeper rules file \"{}\", reason {e}", file_name.to_string_lossy()))?;
+        Self::parse(&content)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn get_rules(&self) -> &[KeeperRule] {
+        &self.rules
+    }
+
+    pub(crate) fn with_tie_breaker(&self, rule: KeeperRule) -> Self {
+        let mut rules = self.rules.clone();
+        rules.push(rule);
+        Self { rules }
+    }
+
+    // Index of entry, which should be kept, when rules consider files equal, path decides
+    pub fn select_keeper<T: ResultEntry>(&self, group: &[T]) -> usize {
+        group
+            .iter()
+            .enumerate()
+            .min_by_key(|(_idx, entry)| (self.rules.iter().map(|rule| rule.get_key(*entry)).collect::<Vec<_>>(), entry.get_path()))
+            .map_or(0, |(idx, _entry)| idx)
+    }
+
+    pub fn is_protected<T: ResultEntry>(&self, entry: &T) -> bool {
+        self.rules.contains(&KeeperRule::NeverDeleteReadOnly) && is_read_only(entry.get_path())
+    }
+
+    // Splits group into kept entry and entries which may be deleted or replaced by links
+    pub fn split_group<T: ResultEntry>(&self, mut group: Vec<T>) -> (T, Vec<T>) {
+        let keeper = group.swap_remove(self.select_keeper(&group));
+        group.retain(|entry| !self.is_protected(entry));
+        (keeper, group)
+    }
+}
+
+// Separators and "#" inside quotes are part of rule argument
+fn split_keeper_rules(content: &str) -> Result<Vec<String>, String> {
+    let mut rules = Vec::new();
+    for line in content.lines() {
+        if line.trim_start().starts_with('#') {
+            continue;
+        }
+        let mut current_rule = String::new();
+        let mut in_quotes = false;
+        for character in line.chars() {
+            match character {
+                '"' => {
+                    in_quotes = !in_quotes;
+                    current_rule.push(character);
+                }
+                ';' if !in_quotes => rules.push(std::mem::take(&mut current_rule)),
+                _ => current_rule.push(character),
+            }
+        }
+        if in_quotes {
+            return Err(format!("Missing closing quote in keeper rule \"{}\"", line.trim()));
+        }
+        rules.push(current_rule);
+    }
+    Ok(rules)
+}
+
+fn is_read_only(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|metadata| metadata.permissions().readonly()) || is_on_read_only_mount(path)
+}
+
+#[cfg(target_os = "linux")]
+fn is_on_read_only_mount(path: &Path) -> bool {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(c_path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    let mut stat = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: Path is valid null terminated string and stat points to memory big enough for statvfs struct
+    if unsafe { libc::statvfs(c_path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+        return false;
+    }
+    // SAFETY: statvfs returned success, so struct is initialized
+    let stat = unsafe { stat.assume_init() };
+    stat.f_flag & libc::ST_RDONLY != 0
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_on_read_only_mount(_path: &Path) -> bool {
+    false
 }
 
 impl CommonToolData {
+    pub(crate) fn get_ignored_groups_filter(&self) -> IgnoredGroupsFilter<'_> {
+        self.ignored_groups.get_filter(self.tool_type)
+    }
+
     pub fn new(tool_type: ToolType) -> Self {
         Self {
             tool_type,
@@ -101,20 +366,52 @@ impl CommonToolData {
             directories: Directories::new(),
             extensions: Extensions::new(),
             excluded_items: ExcludedItems::new(),
+            included_items: IncludedItems::new(),
             recursive_search: true,
             delete_method: DeleteMethod::None,
             maximal_file_size: u64::MAX,
             minimal_file_size: 8192,
+            metadata_filter: MetadataFilter::new(),
             stopped_search: false,
             use_cache: true,
             delete_outdated_cache: true,
             save_also_as_json: false,
             use_reference_folders: false,
             dry_run: false,
+            use_directory_snapshot: false,
+            use_ignore_files: false,
+            max_depth: None,
+            follow_symlinks: false,
+            ignored_groups: IgnoredGroups::new(),
+            quarantine_folder: None,
+            keeper_rules: KeeperRules::default(),
         }
     }
 }
 
+// Selects entries, which should be removed from group with given delete method
+#[expect(clippy::indexing_slicing)] // Safe, because groups always have at least 1 element
+pub(crate) fn get_entries_to_delete<T: ResultEntry + Clone>(delete_method: DeleteMethod, mut sorted_group: Vec<T>) -> Vec<T> {
+    sorted_group.sort_unstable_by_key(if delete_method.sorts_by_size() {
+        ResultEntry::get_size
+    } else {
+        ResultEntry::get_modified_date
+    });
+    // TODO - probably a little too much cloning, so later could be this optimized
+    let len = sorted_group.len();
+    match delete_method {
+        DeleteMethod::Delete => &sorted_group,
+        DeleteMethod::AllExceptNewest | DeleteMethod::AllExceptBiggest => &sorted_group[..(len - 1)],
+        DeleteMethod::AllExceptOldest | DeleteMethod::AllExceptSmallest => &sorted_group[1..],
+        DeleteMethod::OneOldest | DeleteMethod::OneSmallest => &sorted_group[..1],
+        DeleteMethod::OneNewest | DeleteMethod::OneBiggest => &sorted_group[(len - 1)..],
+        DeleteMethod::HardLink | DeleteMethod::Reflink | DeleteMethod::Symlink { .. } | DeleteMethod::None => {
+            unreachable!("HardLink, Reflink, Symlink and None should be handled before")
+        }
+    }
+    .to_vec()
+}
+
 pub trait CommonData {
     type Info;
     type Parameters;
@@ -127,8 +424,12 @@ pub trait CommonData {
     fn get_check_method(&self) -> CheckingMethod {
         CheckingMethod::None
     }
-    fn get_test_type(&self) -> (ToolType, CheckingMethod) {
-        (self.get_cd().tool_type, self.get_check_method())
+    // Whether optional stage of tool is enabled, so progress reports one more stage
+    fn has_optional_stage(&self) -> bool {
+        false
+    }
+    fn get_test_type(&self) -> (ToolType, CheckingMethod, bool) {
+        (self.get_cd().tool_type, self.get_check_method(), self.has_optional_stage())
     }
     fn found_any_broken_files(&self) -> bool;
 
@@ -143,6 +444,22 @@ pub trait CommonData {
         self.get_cd().dry_run
     }
 
+    // When set, deleted items are moved into this folder instead of being permanently removed
+    fn set_quarantine_folder(&mut self, quarantine_folder: Option<PathBuf>) {
+        self.get_cd_mut().quarantine_folder = quarantine_folder;
+    }
+    fn get_quarantine_folder(&self) -> Option<&PathBuf> {
+        self.get_cd().quarantine_folder.as_ref()
+    }
+
+    // Rules choosing kept file in group, used instead of delete method criterion, which becomes last tie-breaker
+    fn set_keeper_rules(&mut self, keeper_rules: KeeperRules) {
+        self.get_cd_mut().keeper_rules = keeper_rules;
+    }
+    fn get_keeper_rules(&self) -> &KeeperRules {
+        &self.get_cd().keeper_rules
+    }
+
     fn set_use_cache(&mut self, use_cache: bool) {
         self.get_cd_mut().use_cache = use_cache;
     }
@@ -157,6 +474,39 @@ pub trait CommonData {
         self.get_cd().delete_outdated_cache
     }
 
+    // Directories not changed since previous scan are not read again, but files inside them may still be modified
+    fn set_use_directory_snapshot(&mut self, use_directory_snapshot: bool) {
+        self.get_cd_mut().use_directory_snapshot = use_directory_snapshot;
+    }
+    fn get_use_directory_snapshot(&self) -> bool {
+        self.get_cd().use_directory_snapshot
+    }
+
+    // Honours .gitignore and .czkawkaignore files found inside scanned folders
+    fn set_use_ignore_files(&mut self, use_ignore_files: bool) {
+        self.get_cd_mut().use_ignore_files = use_ignore_files;
+    }
+    fn get_use_ignore_files(&self) -> bool {
+        self.get_cd().use_ignore_files
+    }
+
+    // Limits how many levels of subfolders are checked, 0 checks only files directly inside included directories
+    fn set_max_depth(&mut self, max_depth: Option<usize>) {
+        self.get_cd_mut().max_depth = max_depth;
+    }
+    fn get_max_depth(&self) -> Option<usize> {
+        self.get_cd().max_depth
+    }
+
+    // Symlinked folders are entered like normal ones, each folder is checked only once even if it is reachable by multiple paths
+    // Not used by empty folders finder, where folder containing symlink is never empty
+    fn set_follow_symlinks(&mut self, follow_symlinks: bool) {
+        self.get_cd_mut().follow_symlinks = follow_symlinks;
+    }
+    fn get_follow_symlinks(&self) -> bool {
+        self.get_cd().follow_symlinks
+    }
+
     fn get_stopped_search(&self) -> bool {
         self.get_cd().stopped_search
     }
@@ -184,6 +534,26 @@ pub trait CommonData {
         self.get_cd().minimal_file_size
     }
 
+    // Dates are unix timestamps in seconds
+    fn set_minimal_modified_date(&mut self, minimal_modified_date: Option<u64>) {
+        self.get_cd_mut().metadata_filter.set_minimal_modified_date(minimal_modified_date);
+    }
+    fn set_maximal_modified_date(&mut self, maximal_modified_date: Option<u64>) {
+        self.get_cd_mut().metadata_filter.set_maximal_modified_date(maximal_modified_date);
+    }
+    fn set_not_accessed_for_days(&mut self, not_accessed_for_days: Option<u64>) {
+        self.get_cd_mut().metadata_filter.set_not_accessed_for_days(not_accessed_for_days);
+    }
+    fn set_owner_uids(&mut self, owner_uids: Vec<u32>) {
+        self.get_cd_mut().metadata_filter.set_owner_uids(owner_uids);
+    }
+    fn set_owner_gids(&mut self, owner_gids: Vec<u32>) {
+        self.get_cd_mut().metadata_filter.set_owner_gids(owner_gids);
+    }
+    fn get_metadata_filter(&self) -> &MetadataFilter {
+        &self.get_cd().metadata_filter
+    }
+
     fn set_reference_directory(&mut self, reference_directory: Vec<PathBuf>) {
         let messages = self.get_cd_mut().directories.set_reference_directory(&reference_directory);
         self.get_cd_mut().text_messages.extend_with_another_messages(messages);
@@ -253,12 +623,30 @@ pub trait CommonData {
         let messages = self.get_cd_mut().excluded_items.set_excluded_items(excluded_items);
         self.get_cd_mut().text_messages.extend_with_another_messages(messages);
     }
+    // Globs or regexes(prefixed with `regex:`) matched against full path, when set only matching files are checked
+    fn set_included_items(&mut self, included_items: Vec<String>) {
+        let messages = self.get_cd_mut().included_items.set_included_items(included_items);
+        self.get_cd_mut().text_messages.extend_with_another_messages(messages);
+    }
 
     fn prepare_items(&mut self) {
         let recursive_search = self.get_cd().recursive_search;
+        // Files moved to quarantine are deleted ones, so they must not be found again as e.g. duplicates
+        if let Some(quarantine_folder) = self.get_cd().quarantine_folder.clone() {
+            self.get_cd_mut().directories.add_excluded_directory(&quarantine_folder);
+        }
         // Optimizes directories and removes recursive calls
         let messages = self.get_cd_mut().directories.optimize_directories(recursive_search);
         self.get_cd_mut().text_messages.extend_with_another_messages(messages);
+
+        // Only tools which group files, can hide groups dismissed by user
+        if matches!(
+            self.get_cd().tool_type,
+            ToolType::Duplicate | ToolType::SameMusic | ToolType::SimilarImages | ToolType::SimilarVideos
+        ) {
+            let cd = self.get_cd_mut();
+            cd.ignored_groups = IgnoredGroups::load(&mut cd.text_messages.warnings);
+        }
     }
 
     fn delete_simple_elements_and_add_to_messages<T: ResultEntry + Sized + Send + Sync>(
@@ -277,7 +665,6 @@ pub trait CommonData {
         }
     }
 
-    #[expect(clippy::indexing_slicing)] // Safe, because input is always checked to have at least 1 element
     fn delete_advanced_elements_and_add_to_messages<T: ResultEntry + Sized + Send + Sync + Clone>(
         &mut self,
         stop_flag: &Arc<AtomicBool>,
@@ -285,16 +672,32 @@ pub trait CommonData {
         files_to_process: Vec<Vec<T>>,
     ) -> WorkContinueStatus {
         let delete_method = self.get_cd().delete_method;
-        let sorting_by_size = matches!(
-            delete_method,
-            DeleteMethod::AllExceptBiggest | DeleteMethod::AllExceptSmallest | DeleteMethod::OneBiggest | DeleteMethod::OneSmallest
-        );
+        let sorting_by_size = delete_method.sorts_by_size();
         let sort_items = |mut input: Vec<T>| -> Vec<T> {
             input.sort_unstable_by_key(if sorting_by_size { ResultEntry::get_size } else { ResultEntry::get_modified_date });
             input
         };
 
-        let delete_results = if delete_method == DeleteMethod::HardLink {
+        if !self.get_cd().keeper_rules.is_empty() && !delete_method.supports_keeper_rules() {
+            self.get_cd_mut().text_messages.warnings.push(format!(
+                "Keeper rules are ignored, because delete method {delete_method:?} does not keep one file in group - use AEN, AEO, AEB, AES, HARD, REFLINK or symlink methods with them"
+            ));
+        }
+
+        let keeper_rules = &self.get_cd().keeper_rules;
+        let delete_results = if let Some(tie_breaker) = delete_method.get_keeper_tie_breaker().filter(|_| !keeper_rules.is_empty()) {
+            let keeper_rules = keeper_rules.with_tie_breaker(tie_breaker);
+            let res = files_to_process.into_iter().map(|values| keeper_rules.split_group(values)).collect::<Vec<_>>();
+            match delete_method {
+                DeleteMethod::HardLink => self.delete_elements(stop_flag, progress_sender, DeleteItemType::HardlinkingFiles(res)),
+                DeleteMethod::Reflink => self.delete_elements(stop_flag, progress_sender, DeleteItemType::ReflinkingFiles(res)),
+                DeleteMethod::Symlink { relative, .. } => self.delete_elements(stop_flag, progress_sender, DeleteItemType::SymlinkingFiles(res, relative)),
+                _ => {
+                    let res = res.into_iter().flat_map(|(_kept, files)| files).collect::<Vec<_>>();
+                    self.delete_elements(stop_flag, progress_sender, DeleteItemType::DeletingFiles(res))
+                }
+            }
+        } else if matches!(delete_method, DeleteMethod::HardLink | DeleteMethod::Reflink) {
             let res = files_to_process
                 .into_iter()
                 .map(|values| {
@@ -303,25 +706,29 @@ pub trait CommonData {
                     (original, all_values)
                 })
                 .collect::<Vec<_>>();
-            self.delete_elements(stop_flag, progress_sender, DeleteItemType::HardlinkingFiles(res))
-        } else {
+            if delete_method == DeleteMethod::HardLink {
+                self.delete_elements(stop_flag, progress_sender, DeleteItemType::HardlinkingFiles(res))
+            } else {
+                self.delete_elements(stop_flag, progress_sender, DeleteItemType::ReflinkingFiles(res))
+            }
+        } else if let DeleteMethod::Symlink { keep, relative } = delete_method {
             let res = files_to_process
                 .into_iter()
-                .flat_map(|values| {
-                    // TODO - probably a little too much cloning, so later could be this optimized
-                    let len = values.len();
-                    let all_values = sort_items(values);
-                    match delete_method {
-                        DeleteMethod::Delete => &all_values,
-                        DeleteMethod::AllExceptNewest | DeleteMethod::AllExceptBiggest => &all_values[..(len - 1)],
-                        DeleteMethod::AllExceptOldest | DeleteMethod::AllExceptSmallest => &all_values[1..],
-                        DeleteMethod::OneOldest | DeleteMethod::OneSmallest => &all_values[..1],
-                        DeleteMethod::OneNewest | DeleteMethod::OneBiggest => &all_values[(len - 1)..],
-                        DeleteMethod::HardLink | DeleteMethod::None => unreachable!("HardLink and None should be handled before"),
-                    }
-                    .to_vec()
+                .map(|values| {
+                    let mut all_values = sort_items(values);
+                    let original = match keep {
+                        KeptFile::Oldest | KeptFile::Smallest => all_values.remove(0),
+                        KeptFile::Newest | KeptFile::Biggest => all_values.remove(all_values.len() - 1),
+                    };
+                    (original, all_values)
                 })
                 .collect::<Vec<_>>();
+            self.delete_elements(stop_flag, progress_sender, DeleteItemType::SymlinkingFiles(res, relative))
+        } else {
+            let res = files_to_process
+                .into_iter()
+                .flat_map(|values| get_entries_to_delete(delete_method, values))
+                .collect::<Vec<_>>();
             self.delete_elements(stop_flag, progress_sender, DeleteItemType::DeletingFiles(res))
         };
 
@@ -340,11 +747,14 @@ pub trait CommonData {
         delete_item_type: DeleteItemType<T>,
     ) -> DeleteResult {
         let dry_run = self.get_cd().dry_run;
+        let quarantine = self.get_cd().quarantine_folder.as_ref().map(Quarantine::new);
         let mut progress = ProgressData::get_empty_state(CurrentStage::DeletingFiles);
         progress.bytes_to_check = delete_item_type.calculate_size_to_delete();
         progress.entries_to_check = delete_item_type.calculate_entries_to_delete();
 
         let is_hardlinking = matches!(delete_item_type, DeleteItemType::HardlinkingFiles(_));
+        let is_reflinking = matches!(delete_item_type, DeleteItemType::ReflinkingFiles(_));
+        let is_symlinking = matches!(delete_item_type, DeleteItemType::SymlinkingFiles(..));
 
         let msg_common = format!(
             "{} items, total size: {} bytes, dry_run: {dry_run}",
@@ -353,6 +763,10 @@ pub trait CommonData {
         );
         if is_hardlinking {
             info!("Hardlinking {msg_common}");
+        } else if is_reflinking {
+            info!("Reflinking {msg_common}");
+        } else if is_symlinking {
+            info!("Symlinking {msg_common}");
         } else {
             info!("Deleting {msg_common}");
         }
@@ -362,6 +776,9 @@ pub trait CommonData {
         let bytes_processed = Arc::new(std::sync::atomic::AtomicU64::new(0));
         let files_processed = Arc::new(std::sync::atomic::AtomicUsize::new(0));
 
+        // Entries are added to journal only for successful actions, with hash of file read before changing it
+        let journal = DeletionJournal::new();
+
         let res = match delete_item_type {
             DeleteItemType::DeletingFiles(ref items) | DeleteItemType::DeletingFolders(ref items) => items
                 .into_par_iter()
@@ -379,24 +796,32 @@ pub trait CommonData {
                     }
 
                     if dry_run {
-                        return Some(vec![(e, None)]);
+                        return Some(vec![(e, None, None)]);
                     }
 
-                    let delete_res = if matches!(delete_item_type, DeleteItemType::DeletingFiles(_)) {
-                        fs::remove_file(e.get_path()).map_err(|err| format!("Failed to delete \"{}\": {err}", e.get_path().to_string_lossy()))
-                    } else {
-                        remove_folder_if_contains_only_empty_folders(e.get_path(), false) // TODO remove to trash should be an option
+                    let delete_fn = || match (&quarantine, &delete_item_type) {
+                        (Some(quarantine), _) => quarantine
+                            .move_item(e.get_path(), matches!(delete_item_type, DeleteItemType::DeletingFolders(_)))
+                            .map(|_| ()),
+                        (None, DeleteItemType::DeletingFiles(_)) => {
+                            fs::remove_file(e.get_path()).map_err(|err| format!("Failed to delete \"{}\": {err}", e.get_path().to_string_lossy()))
+                        }
+                        _ => remove_folder_if_contains_only_empty_folders(e.get_path(), false), // TODO remove to trash should be an option
+                    };
+                    let delete_res = match delete_item_type.get_journal_action(e, None, quarantine.as_ref()) {
+                        Some(action) => journal.record(e.get_path(), action, delete_fn),
+                        None => delete_fn(),
                     };
 
                     match delete_res {
-                        Ok(()) => Some(vec![(e, None)]),
-                        Err(err) => Some(vec![(e, Some(err))]),
+                        Ok(()) => Some(vec![(e, None, None)]),
+                        Err(err) => Some(vec![(e, None, Some(err))]),
                     }
                 })
                 .while_some()
                 .flatten()
                 .collect::<Vec<_>>(),
-            DeleteItemType::HardlinkingFiles(ref items) => items
+            DeleteItemType::HardlinkingFiles(ref items) | DeleteItemType::ReflinkingFiles(ref items) | DeleteItemType::SymlinkingFiles(ref items, _) => items
                 .into_par_iter()
                 .map(|(original, files)| {
                     if stop_flag.load(std::sync::atomic::Ordering::Relaxed) {
@@ -412,21 +837,34 @@ pub trait CommonData {
                     }
 
                     if dry_run {
-                        return Some(files.iter().map(|e| (e, None)).collect::<Vec<_>>());
+                        return Some(files.iter().map(|e| (e, Some(original), None)).collect::<Vec<_>>());
                     }
 
                     let res = files
                         .iter()
                         .map(|file| {
-                            let err = match fs::hard_link(original.get_path(), file.get_path()) {
-                                Ok(()) => None,
-                                Err(err) => Some(format!(
-                                    "Failed to hardlink \"{}\" to \"{}\": {err}",
-                                    original.get_path().to_string_lossy(),
-                                    file.get_path().to_string_lossy()
-                                )),
+                            let link_fn = || {
+                                let (result, operation) = if is_reflinking {
+                                    (make_reflink(original.get_path(), file.get_path()), "reflink")
+                                } else if let DeleteItemType::SymlinkingFiles(_, relative) = delete_item_type {
+                                    let target = get_symlink_target(original.get_path(), file.get_path(), relative)?;
+                                    (make_file_symlink(target, file.get_path()), "symlink")
+                                } else {
+                                    (fs::hard_link(original.get_path(), file.get_path()), "hardlink")
+                                };
+                                result.map_err(|err| {
+                                    format!(
+                                        "Failed to {operation} \"{}\" to \"{}\": {err}",
+                                        original.get_path().to_string_lossy(),
+                                        file.get_path().to_string_lossy()
+                                    )
+                                })
                             };
-                            (file, err)
+                            let result = match delete_item_type.get_journal_action(file, Some(original), None) {
+                                Some(action) => journal.record(file.get_path(), action, link_fn),
+                                None => link_fn(),
+                            };
+                            (file, Some(original), result.err())
                         })
                         .collect::<Vec<_>>();
 
@@ -439,18 +877,29 @@ pub trait CommonData {
 
         let mut delete_result = DeleteResult::default();
 
-        for (file_entry, delete_err) in res {
+        for (file_entry, original, delete_err) in res {
             if let Some(err) = delete_err {
                 delete_result.errors.push(err);
                 delete_result.failed_to_delete_files += 1;
             } else {
                 if dry_run {
+                    let original_path = original.map(|e| e.get_path().to_string_lossy().to_string()).unwrap_or_default();
                     if is_hardlinking {
-                        delete_result.infos.push(format!(
-                            "Would hardlink: \"{}\" to \"{}\"",
-                            file_entry.get_path().to_string_lossy(),
-                            file_entry.get_path().to_string_lossy()
-                        ));
+                        delete_result
+                            .infos
+                            .push(format!("Would hardlink: \"{}\" to \"{original_path}\"", file_entry.get_path().to_string_lossy()));
+                    } else if is_reflinking {
+                        delete_result
+                            .infos
+                            .push(format!("Would reflink: \"{}\" to \"{original_path}\"", file_entry.get_path().to_string_lossy()));
+                    } else if is_symlinking {
+                        delete_result
+                            .infos
+                            .push(format!("Would replace \"{}\" with symlink to \"{original_path}\"", file_entry.get_path().to_string_lossy()));
+                    } else if quarantine.is_some() {
+                        delete_result
+                            .infos
+                            .push(format!("Would move to quarantine: \"{}\"", file_entry.get_path().to_string_lossy()));
                     } else {
                         delete_result.infos.push(format!("Would delete: \"{}\"", file_entry.get_path().to_string_lossy()));
                     }
@@ -460,6 +909,15 @@ pub trait CommonData {
             }
         }
 
+        if let Err(e) = journal.save() {
+            delete_result.errors.push(e);
+        }
+        if let Some(quarantine) = &quarantine
+            && let Err(e) = quarantine.save_manifest()
+        {
+            delete_result.errors.push(e);
+        }
+
         if !dry_run {
             info!(
                 "{} items deleted, {} bytes gained, {} failed to delete",
@@ -479,9 +937,11 @@ pub trait CommonData {
         println!("Directories: {:?}", self.get_cd().directories);
         println!("Extensions: {:?}", self.get_cd().extensions);
         println!("Excluded items: {:?}", self.get_cd().excluded_items);
+        println!("Included items: {:?}", self.get_cd().included_items);
         println!("Recursive search: {}", self.get_cd().recursive_search);
         println!("Maximal file size: {}", self.get_cd().maximal_file_size);
         println!("Minimal file size: {}", self.get_cd().minimal_file_size);
+        println!("Metadata filter: {:?}", self.get_cd().metadata_filter);
         println!("Stopped search: {}", self.get_cd().stopped_search);
         println!("Use cache: {}", self.get_cd().use_cache);
         println!("Delete outdated cache: {}", self.get_cd().delete_outdated_cache);
@@ -489,6 +949,12 @@ pub trait CommonData {
         println!("Delete method: {:?}", self.get_cd().delete_method);
         println!("Use reference folders: {}", self.get_cd().use_reference_folders);
         println!("Dry run: {}", self.get_cd().dry_run);
+        println!("Quarantine folder: {:?}", self.get_cd().quarantine_folder);
+        println!("Keeper rules: {:?}", self.get_cd().keeper_rules);
+        println!("Use directory snapshot: {}", self.get_cd().use_directory_snapshot);
+        println!("Use ignore files: {}", self.get_cd().use_ignore_files);
+        println!("Max depth: {:?}", self.get_cd().max_depth);
+        println!("Follow symlinks: {}", self.get_cd().follow_symlinks);
 
         println!("---------------DEBUG PRINT MESSAGES---------------");
         println!("Errors size - {}", self.get_cd().text_messages.errors.len());
@@ -496,3 +962,62 @@ pub trait CommonData {
         println!("Messages size - {}", self.get_cd().text_messages.messages.len());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::common::model::FileEntry;
+
+    fn entry(path: &str, size: u64, modified_date: u64) -> FileEntry {
+        FileEntry {
+            path: PathBuf::from(path),
+            size,
+            modified_date,
+        }
+    }
+
+    #[test]
+    fn test_parse_keeper_rules() {
+        let rules = KeeperRules::parse("# comment\nprefer_path /archive\navoid_name \" (1)\"; prefer_shortest_path\n\n").unwrap();
+        assert_eq!(
+            rules.get_rules(),
+            &[
+                KeeperRule::PreferPath(PathBuf::from("/archive")),
+                KeeperRule::AvoidName(" (1)".to_string()),
+                KeeperRule::PreferShortestPath
+            ]
+        );
+        KeeperRules::parse("prefer_unknown").unwrap_err();
+        KeeperRules::parse("prefer_path").unwrap_err();
+
+        // Separator inside quotes is part of argument
+        let rules = KeeperRules::parse("avoid_name \"a;b\";prefer_path \"/my folder; 2\"\n  # comment; prefer_exif").unwrap();
+        assert_eq!(
+            rules.get_rules(),
+            &[KeeperRule::AvoidName("a;b".to_string()), KeeperRule::PreferPath(PathBuf::from("/my folder; 2"))]
+        );
+        KeeperRules::parse("avoid_name \"a;b").unwrap_err();
+    }
+
+    #[test]
+    fn test_keeper_rules_order_and_tie_breaker() {
+        let group = vec![
+            entry("/home/user/photo (1).jpg", 10, 3),
+            entry("/archive/a/photo.jpg", 10, 2),
+            entry("/archive/photo.jpg", 10, 1),
+            entry("/archive/photo (1).jpg", 10, 4),
+        ];
+
+        let rules = KeeperRules::parse("prefer_path /archive\navoid_name \" (1)\"").unwrap();
+        // Both files under /archive without " (1)" are equal, so path decides
+        assert_eq!(rules.select_keeper(&group), 1);
+        assert_eq!(rules.with_tie_breaker(KeeperRule::PreferOldest).select_keeper(&group), 2);
+
+        let rules = KeeperRules::parse("avoid_name \" (1)\"; prefer_newest").unwrap();
+        let (kept, to_delete) = rules.split_group(group);
+        assert_eq!(kept.path, PathBuf::from("/archive/a/photo.jpg"));
+        assert_eq!(to_delete.len(), 3);
+    }
+}
@@ -6,10 +6,11 @@ use std::sync::atomic::AtomicBool;
 
 use crossbeam_channel::Sender;
 use fun_time::fun_time;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::common::model::WorkContinueStatus;
 use crate::common::progress_data::ProgressData;
+use crate::common::results_file::{load_results_file, save_results_file};
 use crate::common::tool_data::CommonData;
 
 pub trait DebugPrint {
@@ -89,6 +90,32 @@ pub trait DeletingItems {
     fn delete_files(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus;
 }
 
+// Results saved this way, can be later loaded and used e.g. to delete files without scanning again
+pub trait PersistentResults: CommonData {
+    type Results: Serialize + for<'a> Deserialize<'a>;
+
+    fn get_results_to_persist(&self) -> Self::Results;
+    // Replaces current results - every entry which changed on disk since saving must be removed here
+    fn set_persisted_results(&mut self, results: Self::Results);
+
+    fn save_results_to_results_file(&self, file_name: &str) -> std::io::Result<()> {
+        save_results_file(
+            file_name,
+            self.get_tool_type(),
+            self.get_check_method(),
+            self.get_use_reference_folders(),
+            &self.get_results_to_persist(),
+        )
+    }
+
+    fn load_results_from_results_file(&mut self, file_name: &str) -> Result<(), String> {
+        let loaded = load_results_file(file_name, self.get_tool_type(), self.get_check_method())?;
+        self.set_use_reference_folders(loaded.use_reference_folders);
+        self.set_persisted_results(loaded.results);
+        Ok(())
+    }
+}
+
 pub trait ResultEntry {
     fn get_path(&self) -> &Path;
     fn get_modified_date(&self) -> u64;
@@ -99,4 +126,4 @@ pub trait Search {
     fn search(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>);
 }
 
-pub trait AllTraits: DebugPrint + PrintResults + DeletingItems + CommonData + Search {}
+pub trait AllTraits: DebugPrint + PrintResults + DeletingItems + CommonData + PersistentResults + Search {}
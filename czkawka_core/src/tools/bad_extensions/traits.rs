@@ -7,9 +7,10 @@ use fun_time::fun_time;
 
 use crate::common::model::WorkContinueStatus;
 use crate::common::progress_data::ProgressData;
+use crate::common::results_file::retain_unchanged_entries;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteItemType, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
-use crate::tools::bad_extensions::{BadExtensions, BadExtensionsParameters, Info};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PersistentResults, PrintResults, Search};
+use crate::tools::bad_extensions::{BadExtensions, BadExtensionsParameters, BadFileEntry, Info};
 
 impl AllTraits for BadExtensions {}
 
@@ -94,3 +95,16 @@ impl CommonData for BadExtensions {
         self.get_information().number_of_files_with_bad_extension > 0
     }
 }
+
+impl PersistentResults for BadExtensions {
+    type Results = Vec<BadFileEntry>;
+
+    fn get_results_to_persist(&self) -> Self::Results {
+        self.bad_extensions_files.clone()
+    }
+
+    fn set_persisted_results(&mut self, results: Self::Results) {
+        self.bad_extensions_files = retain_unchanged_entries(results, &mut self.common_data.text_messages.warnings);
+        self.information.number_of_files_with_bad_extension = self.bad_extensions_files.len();
+    }
+}
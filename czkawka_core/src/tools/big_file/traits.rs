@@ -6,10 +6,11 @@ use crossbeam_channel::Sender;
 use fun_time::fun_time;
 use humansize::{BINARY, format_size};
 
-use crate::common::model::WorkContinueStatus;
+use crate::common::model::{FileEntry, WorkContinueStatus};
 use crate::common::progress_data::ProgressData;
+use crate::common::results_file::retain_unchanged_entries;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteItemType, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PersistentResults, PrintResults, Search};
 use crate::tools::big_file::{BigFile, BigFileParameters, Info, SearchMode};
 
 impl AllTraits for BigFile {}
@@ -113,3 +114,16 @@ impl CommonData for BigFile {
         self.information.number_of_real_files > 0
     }
 }
+
+impl PersistentResults for BigFile {
+    type Results = Vec<FileEntry>;
+
+    fn get_results_to_persist(&self) -> Self::Results {
+        self.big_files.clone()
+    }
+
+    fn set_persisted_results(&mut self, results: Self::Results) {
+        self.big_files = retain_unchanged_entries(results, &mut self.common_data.text_messages.warnings);
+        self.information.number_of_real_files = self.big_files.len();
+    }
+}
@@ -0,0 +1,318 @@
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use bzip2::read::MultiBzDecoder;
+use flate2::read::MultiGzDecoder;
+use lzma_rust2::XzReader;
+use ruzstd::decoding::errors::{FrameDecoderError, ReadFrameHeaderError};
+use ruzstd::decoding::{BlockDecodingStrategy, FrameDecoder};
+use sevenz_rust2::{ArchiveReader, Password};
+
+use crate::common::progress_stop_handler::check_if_stop_received;
+use crate::tools::broken_files::TypeOfFile;
+
+// Short extensions of compressed files, which always contain tar archive inside
+const COMPRESSED_TAR_EXTENSIONS: &[&str] = &["tgz", "txz", "tzst", "tbz", "tbz2"];
+
+// Whole archive is always read, because checksums of compressed streams are stored at their end
+pub(crate) fn check_archive<R: Read + Seek>(reader: R, file_name: &str, type_of_file: TypeOfFile, stop_flag: &Arc<AtomicBool>) -> Result<(), String> {
+    let contains_tar = contains_tar_archive(file_name);
+    let reader = StopFlagReader { inner: reader, stop_flag };
+    match type_of_file {
+        TypeOfFile::ArchiveTar => check_tar(BufReader::new(reader)),
+        TypeOfFile::ArchiveGzip => check_decompressed_stream(MultiGzDecoder::new(reader), contains_tar),
+        TypeOfFile::ArchiveXz => check_decompressed_stream(XzReader::new(BufReader::new(reader), true), contains_tar),
+        TypeOfFile::ArchiveZstd => check_decompressed_stream(ZstdReader::new(BufReader::new(reader)), contains_tar),
+        TypeOfFile::ArchiveBzip2 => check_decompressed_stream(MultiBzDecoder::new(reader), contains_tar),
+        TypeOfFile::Archive7z => check_7z(reader),
+        _ => Ok(()),
+    }
+}
+
+fn contains_tar_archive(file_name: &str) -> bool {
+    let file_name = file_name.to_ascii_lowercase();
+    let Some((stem, extension)) = file_name.rsplit_once('.') else {
+        return false;
+    };
+    stem.ends_with(".tar") || COMPRESSED_TAR_EXTENSIONS.contains(&extension)
+}
+
+fn check_decompressed_stream<R: Read>(mut reader: R, contains_tar: bool) -> Result<(), String> {
+    if contains_tar {
+        check_tar(&mut reader)?;
+    }
+    // Tar archive may be followed by padding, which still needs to be decompressed to verify checksum
+    io::copy(&mut reader, &mut io::sink()).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn check_tar<R: Read>(reader: R) -> Result<(), String> {
+    let mut tracked_reader = EofTrackingReader {
+        inner: reader,
+        reached_eof: false,
+    };
+    for entry in tar::Archive::new(&mut tracked_reader).entries().map_err(|e| e.to_string())? {
+        let mut entry = entry.map_err(|e| e.to_string())?;
+        let expected_size = entry.size();
+        let read_size = io::copy(&mut entry, &mut io::sink()).map_err(|e| e.to_string())?;
+        if read_size != expected_size {
+            let entry_path = entry.path().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+            return Err(format!("Tar entry \"{entry_path}\" is truncated - expected {expected_size} bytes, found {read_size}"));
+        }
+    }
+
+    // Tar reader treats end of data as end of archive, so missing terminating zero block is the only sign of truncation at entry boundary
+    if tracked_reader.reached_eof {
+        return Err("Tar archive is missing end of archive marker, file is probably truncated".to_string());
+    }
+    Ok(())
+}
+
+fn check_7z<R: Read + Seek>(reader: R) -> Result<(), String> {
+    let result = ArchiveReader::new(reader, Password::empty()).and_then(|mut archive| {
+        archive.for_each_entries(|_entry, entry_reader| {
+            io::copy(entry_reader, &mut io::sink())?;
+            Ok(true)
+        })
+    });
+    match result {
+        // Encrypted archives and not supported compression methods cannot be verified
+        Ok(())
+        | Err(
+            sevenz_rust2::Error::PasswordRequired
+            | sevenz_rust2::Error::MaybeBadPassword(_)
+            | sevenz_rust2::Error::UnsupportedCompressionMethod(_)
+            | sevenz_rust2::Error::ExternalUnsupported
+            | sevenz_rust2::Error::MaxMemLimited { .. },
+        ) => Ok(()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+// Decompressing big archive may take a long time, so reading is interrupted as soon as search is stopped
+struct StopFlagReader<'a, R> {
+    inner: R,
+    stop_flag: &'a Arc<AtomicBool>,
+}
+
+impl<R: Read> Read for StopFlagReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if check_if_stop_received(self.stop_flag) {
+            return Err(io::Error::other("Checking archive was stopped"));
+        }
+        self.inner.read(buf)
+    }
+}
+
+impl<R: Seek> Seek for StopFlagReader<'_, R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+struct EofTrackingReader<R> {
+    inner: R,
+    reached_eof: bool,
+}
+
+impl<R: Read> Read for EofTrackingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read_bytes = self.inner.read(buf)?;
+        if read_bytes == 0 && !buf.is_empty() {
+            self.reached_eof = true;
+        }
+        Ok(read_bytes)
+    }
+}
+
+// StreamingDecoder from ruzstd decodes only single frame and does not verify its checksum
+struct ZstdReader<R> {
+    source: R,
+    decoder: FrameDecoder,
+    in_frame: bool,
+}
+
+impl<R: BufRead> ZstdReader<R> {
+    fn new(source: R) -> Self {
+        Self {
+            source,
+            decoder: FrameDecoder::new(),
+            in_frame: false,
+        }
+    }
+}
+
+impl<R: BufRead> Read for ZstdReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if !self.in_frame {
+                if self.source.fill_buf()?.is_empty() {
+                    return Ok(0);
+                }
+                match self.decoder.reset(&mut self.source) {
+                    Ok(()) => self.in_frame = true,
+                    Err(FrameDecoderError::ReadFrameHeaderError(ReadFrameHeaderError::SkipFrame { length, .. })) => {
+                        let skipped = io::copy(&mut (&mut self.source).take(u64::from(length)), &mut io::sink())?;
+                        if skipped != u64::from(length) {
+                            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Skippable zstd frame is truncated"));
+                        }
+                    }
+                    Err(e) => return Err(io::Error::other(e)),
+                }
+                continue;
+            }
+
+            if self.decoder.can_collect() > 0 {
+                return self.decoder.read(buf);
+            }
+
+            if self.decoder.is_finished() {
+                if let Some(checksum) = self.decoder.get_checksum_from_data()
+                    && self.decoder.get_calculated_checksum() != Some(checksum)
+                {
+                    return Err(io::Error::other("Zstd frame checksum mismatch"));
+                }
+                self.in_frame = false;
+                continue;
+            }
+
+            self.decoder
+                .decode_blocks(&mut self.source, BlockDecodingStrategy::UptoBlocks(1))
+                .map_err(io::Error::other)?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use bzip2::write::BzEncoder;
+    use flate2::write::GzEncoder;
+    use lzma_rust2::{XzOptions, XzWriter};
+    use ruzstd::encoding::{CompressionLevel, compress_to_vec};
+    use sevenz_rust2::{ArchiveEntry, ArchiveWriter};
+
+    use super::*;
+
+    fn create_tar() -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, size) in [("first.txt", 1500), ("second.bin", 70_000)] {
+            let data = (0..size).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_cksum();
+            builder.append_data(&mut header, name, data.as_slice()).expect("Failed to append data");
+        }
+        builder.into_inner().expect("Failed to finish tar")
+    }
+
+    fn check(data: &[u8], file_name: &str, type_of_file: TypeOfFile) -> Result<(), String> {
+        check_archive(Cursor::new(data), file_name, type_of_file, &Arc::default())
+    }
+
+    fn truncated(data: &[u8], len: usize) -> &[u8] {
+        data.get(..len).expect("Truncating outside of data")
+    }
+
+    #[test]
+    fn test_contains_tar_archive() {
+        assert!(contains_tar_archive("backup.tar.zst"));
+        assert!(contains_tar_archive("BACKUP.TAR.GZ"));
+        assert!(contains_tar_archive("backup.tgz"));
+        assert!(!contains_tar_archive("file.txt.gz"));
+        assert!(!contains_tar_archive("tar.xz"));
+        assert!(!contains_tar_archive("xz"));
+    }
+
+    #[test]
+    fn test_tar() {
+        let tar = create_tar();
+        check(&tar, "a.tar", TypeOfFile::ArchiveTar).unwrap();
+
+        // Truncated inside entry data
+        assert!(check(truncated(&tar, 2000), "a.tar", TypeOfFile::ArchiveTar).is_err());
+        // Truncated exactly at header boundary of second entry
+        assert!(check(truncated(&tar, 512 + 1536), "a.tar", TypeOfFile::ArchiveTar).is_err());
+        // Truncated inside header
+        assert!(check(truncated(&tar, 512 + 1536 + 100), "a.tar", TypeOfFile::ArchiveTar).is_err());
+        assert!(check(&[], "a.tar", TypeOfFile::ArchiveTar).is_err());
+
+        let mut corrupted = tar;
+        if let Some(byte) = corrupted.get_mut(10) {
+            *byte ^= 0xFF;
+        }
+        assert!(check(&corrupted, "a.tar", TypeOfFile::ArchiveTar).is_err());
+    }
+
+    #[test]
+    fn test_compressed_tar() {
+        let tar = create_tar();
+
+        let mut gz = GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        gz.write_all(&tar).expect("Failed to compress");
+        let gz = gz.finish().expect("Failed to compress");
+
+        let mut bz = BzEncoder::new(Vec::new(), bzip2::Compression::fast());
+        bz.write_all(&tar).expect("Failed to compress");
+        let bz = bz.finish().expect("Failed to compress");
+
+        let mut xz = XzWriter::new(Vec::new(), XzOptions::with_preset(1)).expect("Failed to create xz writer");
+        xz.write_all(&tar).expect("Failed to compress");
+        let xz = xz.finish().expect("Failed to compress");
+
+        let zst = compress_to_vec(tar.as_slice(), CompressionLevel::Fastest);
+
+        for (data, file_name, type_of_file) in [
+            (gz, "a.tar.gz", TypeOfFile::ArchiveGzip),
+            (bz, "a.tar.bz2", TypeOfFile::ArchiveBzip2),
+            (xz, "a.txz", TypeOfFile::ArchiveXz),
+            (zst, "a.tar.zst", TypeOfFile::ArchiveZstd),
+        ] {
+            check(&data, file_name, type_of_file).unwrap_or_else(|e| panic!("{file_name} - {e}"));
+            check_archive(Cursor::new(&data), file_name, type_of_file, &Arc::new(AtomicBool::new(true))).unwrap_err();
+            assert!(check(truncated(&data, data.len() - 1), file_name, type_of_file).is_err(), "{file_name}");
+            assert!(check(truncated(&data, data.len() / 2), file_name, type_of_file).is_err(), "{file_name}");
+
+            // Checksums are stored at the end of stream
+            let mut corrupted = data.clone();
+            let idx = corrupted.len() - 2;
+            if let Some(byte) = corrupted.get_mut(idx) {
+                *byte ^= 0xFF;
+            }
+            assert!(check(&corrupted, file_name, type_of_file).is_err(), "{file_name}");
+        }
+    }
+
+    #[test]
+    fn test_zstd_multiple_frames() {
+        let mut data = compress_to_vec(&b"first frame"[..], CompressionLevel::Fastest);
+        // Skippable frame
+        data.extend_from_slice(&0x184D_2A50_u32.to_le_bytes());
+        data.extend_from_slice(&3_u32.to_le_bytes());
+        data.extend_from_slice(&[1, 2, 3]);
+        data.extend(compress_to_vec(&b"second frame"[..], CompressionLevel::Fastest));
+
+        let mut decompressed = String::new();
+        ZstdReader::new(data.as_slice()).read_to_string(&mut decompressed).expect("Failed to decompress");
+        assert_eq!(decompressed, "first framesecond frame");
+
+        check(&data, "a.zst", TypeOfFile::ArchiveZstd).unwrap();
+        assert!(check(truncated(&data, data.len() - 3), "a.zst", TypeOfFile::ArchiveZstd).is_err());
+    }
+
+    #[test]
+    fn test_7z() {
+        let mut writer = ArchiveWriter::new(Cursor::new(Vec::new())).expect("Failed to create 7z writer");
+        let content = (0..100_000).map(|i| (i % 13) as u8).collect::<Vec<_>>();
+        writer
+            .push_archive_entry(ArchiveEntry::new_file("file.bin"), Some(content.as_slice()))
+            .expect("Failed to add 7z entry");
+        let data = writer.finish().expect("Failed to finish 7z").into_inner();
+
+        check(&data, "a.7z", TypeOfFile::Archive7z).unwrap();
+        assert!(check(truncated(&data, data.len() / 2), "a.7z", TypeOfFile::Archive7z).is_err());
+        assert!(check(b"Not a 7z archive", "a.7z", TypeOfFile::Archive7z).is_err());
+    }
+}
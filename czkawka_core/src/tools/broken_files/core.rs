@@ -7,13 +7,17 @@ use std::{mem, panic};
 
 use crossbeam_channel::Sender;
 use fun_time::fun_time;
-use indexmap::IndexSet;
+use indexmap::IndexMap;
 use log::{debug, error};
 use lopdf::Document;
 use rayon::prelude::*;
 
-use crate::common::cache::{CACHE_VERSION, extract_loaded_cache, load_cache_from_file_generalized_by_path, save_cache_to_file_generalized};
-use crate::common::consts::{AUDIO_FILES_EXTENSIONS, IMAGE_RS_BROKEN_FILES_EXTENSIONS, PDF_FILES_EXTENSIONS, ZIP_FILES_EXTENSIONS};
+use crate::common::cache::{CACHE_BROKEN_FILES_VERSION, load_cache_from_file_generalized_by_path, save_cache_to_file_generalized};
+use crate::common::consts::{
+    AUDIO_FILES_EXTENSIONS, BZIP2_FILES_EXTENSIONS, FONT_FILES_EXTENSIONS, GZIP_FILES_EXTENSIONS, IMAGE_RS_BROKEN_FILES_EXTENSIONS, JSON_FILES_EXTENSIONS, PDF_FILES_EXTENSIONS,
+    SEVEN_ZIP_FILES_EXTENSIONS, SQLITE_FILES_EXTENSIONS, TAR_FILES_EXTENSIONS, VIDEO_BROKEN_FILES_EXTENSIONS, XML_FILES_EXTENSIONS, XZ_FILES_EXTENSIONS, YAML_FILES_EXTENSIONS,
+    ZIP_DOCUMENT_FILES_EXTENSIONS, ZIP_FILES_EXTENSIONS, ZSTD_FILES_EXTENSIONS,
+};
 use crate::common::create_crash_message;
 use crate::common::dir_traversal::{DirTraversalBuilder, DirTraversalResult};
 use crate::common::model::{ToolType, WorkContinueStatus};
@@ -21,7 +25,74 @@ use crate::common::progress_data::{CurrentStage, ProgressData};
 use crate::common::progress_stop_handler::{check_if_stop_received, prepare_thread_handler_common};
 use crate::common::tool_data::{CommonData, CommonToolData};
 use crate::common::traits::ResultEntry;
-use crate::tools::broken_files::{BrokenEntry, BrokenFiles, BrokenFilesParameters, CheckedTypes, Info, TypeOfFile};
+use crate::tools::broken_files::archive::check_archive;
+use crate::tools::broken_files::font::check_font;
+use crate::tools::broken_files::image_structure::check_image_strictly;
+use crate::tools::broken_files::sqlite::check_sqlite;
+use crate::tools::broken_files::text_formats::{check_json, check_xml, check_yaml};
+use crate::tools::broken_files::video::{check_video_container, decode_video_frames};
+use crate::tools::broken_files::zip_archive::{check_zip, check_zip_document};
+use crate::tools::broken_files::{BrokenEntry, BrokenFiles, BrokenFilesParameters, CheckMode, CheckedTypes, Info, Severity, TypeOfFile};
+
+// Returns None when file cannot be opened, otherwise entry with filled error, if file is broken
+type FileValidator = fn(BrokenEntry, &BrokenFilesParameters, &Arc<AtomicBool>) -> Option<BrokenEntry>;
+
+// Registry of all checked formats - new format requires entry here, with new `TypeOfFile` variant and `CheckedTypes` flag,
+// which also must be added to CLI parser and GUIs
+// When extension is used by multiple checked types, first of them is used
+const BROKEN_FILES_VALIDATORS: &[(CheckedTypes, TypeOfFile, &[&str], FileValidator)] = &[
+    (CheckedTypes::IMAGE, TypeOfFile::Image, IMAGE_RS_BROKEN_FILES_EXTENSIONS, |entry, params, _| {
+        Some(BrokenFiles::check_broken_image(entry, params.strict_image_check))
+    }),
+    (CheckedTypes::DOCUMENT, TypeOfFile::ZipDocument, ZIP_DOCUMENT_FILES_EXTENSIONS, |entry, params, _| {
+        BrokenFiles::check_broken_zip_document(entry, params.verify_zip_content)
+    }),
+    (CheckedTypes::ARCHIVE, TypeOfFile::ArchiveZip, ZIP_FILES_EXTENSIONS, |entry, params, _| {
+        BrokenFiles::check_broken_zip(entry, params.verify_zip_content)
+    }),
+    (CheckedTypes::AUDIO, TypeOfFile::Audio, AUDIO_FILES_EXTENSIONS, |entry, _, _| {
+        BrokenFiles::check_broken_audio(entry)
+    }),
+    (CheckedTypes::PDF, TypeOfFile::PDF, PDF_FILES_EXTENSIONS, |entry, _, _| {
+        Some(BrokenFiles::check_broken_pdf(entry))
+    }),
+    (CheckedTypes::VIDEO, TypeOfFile::Video, VIDEO_BROKEN_FILES_EXTENSIONS, |entry, params, _| {
+        BrokenFiles::check_broken_video(entry, params.decode_video_frames)
+    }),
+    (CheckedTypes::TAR, TypeOfFile::ArchiveTar, TAR_FILES_EXTENSIONS, |entry, _, stop_flag| {
+        BrokenFiles::check_broken_archive(entry, stop_flag)
+    }),
+    (CheckedTypes::GZIP, TypeOfFile::ArchiveGzip, GZIP_FILES_EXTENSIONS, |entry, _, stop_flag| {
+        BrokenFiles::check_broken_archive(entry, stop_flag)
+    }),
+    (CheckedTypes::XZ, TypeOfFile::ArchiveXz, XZ_FILES_EXTENSIONS, |entry, _, stop_flag| {
+        BrokenFiles::check_broken_archive(entry, stop_flag)
+    }),
+    (CheckedTypes::ZSTD, TypeOfFile::ArchiveZstd, ZSTD_FILES_EXTENSIONS, |entry, _, stop_flag| {
+        BrokenFiles::check_broken_archive(entry, stop_flag)
+    }),
+    (CheckedTypes::BZIP2, TypeOfFile::ArchiveBzip2, BZIP2_FILES_EXTENSIONS, |entry, _, stop_flag| {
+        BrokenFiles::check_broken_archive(entry, stop_flag)
+    }),
+    (CheckedTypes::SEVEN_ZIP, TypeOfFile::Archive7z, SEVEN_ZIP_FILES_EXTENSIONS, |entry, _, stop_flag| {
+        BrokenFiles::check_broken_archive(entry, stop_flag)
+    }),
+    (CheckedTypes::FONT, TypeOfFile::Font, FONT_FILES_EXTENSIONS, |entry, _, _| {
+        BrokenFiles::check_broken_font(entry)
+    }),
+    (CheckedTypes::SQLITE, TypeOfFile::Sqlite, SQLITE_FILES_EXTENSIONS, |entry, _, _| {
+        BrokenFiles::check_broken_file_content(entry, check_sqlite)
+    }),
+    (CheckedTypes::JSON, TypeOfFile::Json, JSON_FILES_EXTENSIONS, |entry, _, _| {
+        BrokenFiles::check_broken_file_content(entry, check_json)
+    }),
+    (CheckedTypes::XML, TypeOfFile::Xml, XML_FILES_EXTENSIONS, |entry, _, _| {
+        BrokenFiles::check_broken_file_content(entry, check_xml)
+    }),
+    (CheckedTypes::YAML, TypeOfFile::Yaml, YAML_FILES_EXTENSIONS, |entry, _, _| {
+        BrokenFiles::check_broken_file_content(entry, check_yaml)
+    }),
+];
 
 impl BrokenFiles {
     pub fn new(params: BrokenFilesParameters) -> Self {
@@ -36,21 +107,14 @@ impl BrokenFiles {
 
     #[fun_time(message = "check_files", level = "debug")]
     pub(crate) fn check_files(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
-        let zip_extensions = ZIP_FILES_EXTENSIONS.iter().copied().collect::<IndexSet<_>>();
-        let audio_extensions = AUDIO_FILES_EXTENSIONS.iter().copied().collect::<IndexSet<_>>();
-        let pdf_extensions = PDF_FILES_EXTENSIONS.iter().copied().collect::<IndexSet<_>>();
-        let images_extensions = IMAGE_RS_BROKEN_FILES_EXTENSIONS.iter().copied().collect::<IndexSet<_>>();
-
         let mut extensions = Vec::new();
-        let vec_extensions = [
-            (CheckedTypes::PDF, PDF_FILES_EXTENSIONS),
-            (CheckedTypes::AUDIO, AUDIO_FILES_EXTENSIONS),
-            (CheckedTypes::ARCHIVE, ZIP_FILES_EXTENSIONS),
-            (CheckedTypes::IMAGE, IMAGE_RS_BROKEN_FILES_EXTENSIONS),
-        ];
-        for (checked_type, extensions_to_add) in &vec_extensions {
+        let mut extensions_types: IndexMap<&'static str, TypeOfFile> = IndexMap::new();
+        for (checked_type, type_of_file, type_extensions, _validator) in BROKEN_FILES_VALIDATORS {
             if self.get_params().checked_types.contains(*checked_type) {
-                extensions.extend_from_slice(extensions_to_add);
+                extensions.extend_from_slice(type_extensions);
+                for extension in *type_extensions {
+                    extensions_types.entry(*extension).or_insert(*type_of_file);
+                }
             }
         }
 
@@ -76,7 +140,8 @@ impl BrokenFiles {
                     .flatten()
                     .map(|fe| {
                         let mut broken_entry = fe.into_broken_entry();
-                        broken_entry.type_of_file = check_extension_availability(broken_entry.get_path(), &images_extensions, &zip_extensions, &audio_extensions, &pdf_extensions);
+                        broken_entry.type_of_file = check_extension_availability(broken_entry.get_path(), &extensions_types);
+                        broken_entry.check_mode = CheckMode::new(broken_entry.type_of_file, &self.params);
                         (broken_entry.path.to_string_lossy().to_string(), broken_entry)
                     })
                     .collect();
@@ -89,12 +154,20 @@ impl BrokenFiles {
         }
     }
 
-    fn check_broken_image(mut file_entry: BrokenEntry) -> BrokenEntry {
+    fn check_broken_image(mut file_entry: BrokenEntry, strict: bool) -> BrokenEntry {
         let mut file_entry_clone = file_entry.clone();
 
         panic::catch_unwind(|| {
-            if let Err(e) = image::open(&file_entry.path) {
-                file_entry.error_string = e.to_string();
+            match image::open(&file_entry.path) {
+                Ok(image) => {
+                    if strict && let Err(e) = check_image_strictly(&file_entry.path, &image) {
+                        file_entry.error_string = e;
+                        file_entry.severity = Severity::Warning;
+                    }
+                }
+                Err(e) => {
+                    file_entry.error_string = e.to_string();
+                }
             }
             file_entry
         })
@@ -105,11 +178,23 @@ impl BrokenFiles {
             file_entry_clone
         })
     }
-    fn check_broken_zip(mut file_entry: BrokenEntry) -> Option<BrokenEntry> {
+    fn check_broken_zip(mut file_entry: BrokenEntry, verify_content: bool) -> Option<BrokenEntry> {
         match File::open(&file_entry.path) {
             Ok(file) => {
-                if let Err(e) = zip::ZipArchive::new(file) {
-                    file_entry.error_string = e.to_string();
+                if let Err(e) = check_zip(file, verify_content) {
+                    file_entry.error_string = e;
+                }
+                Some(file_entry)
+            }
+            Err(_inspected) => None,
+        }
+    }
+    fn check_broken_zip_document(mut file_entry: BrokenEntry, verify_content: bool) -> Option<BrokenEntry> {
+        match File::open(&file_entry.path) {
+            Ok(file) => {
+                let extension = file_entry.path.extension().map(|ext| ext.to_string_lossy().to_string()).unwrap_or_default();
+                if let Err(e) = check_zip_document(file, &extension, verify_content) {
+                    file_entry.error_string = e;
                 }
                 Some(file_entry)
             }
@@ -159,6 +244,74 @@ impl BrokenFiles {
             file_entry_clone
         })
     }
+    fn check_broken_video(mut file_entry: BrokenEntry, decode_frames: bool) -> Option<BrokenEntry> {
+        match File::open(&file_entry.path) {
+            Ok(mut file) => {
+                if let Err(e) = check_video_container(&mut file, file_entry.size) {
+                    file_entry.error_string = e;
+                } else if decode_frames && let Err(e) = decode_video_frames(&file_entry.path) {
+                    file_entry.error_string = e;
+                }
+                Some(file_entry)
+            }
+            Err(_inspected) => None,
+        }
+    }
+    fn check_broken_archive(mut file_entry: BrokenEntry, stop_flag: &Arc<AtomicBool>) -> Option<BrokenEntry> {
+        match File::open(&file_entry.path) {
+            Ok(file) => {
+                let file_name = file_entry.path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+                if let Err(e) = check_archive(file, &file_name, file_entry.type_of_file, stop_flag) {
+                    file_entry.error_string = e;
+                }
+                Some(file_entry)
+            }
+            Err(_inspected) => None,
+        }
+    }
+    fn check_broken_file_content(mut file_entry: BrokenEntry, validator: fn(File) -> Result<(), String>) -> Option<BrokenEntry> {
+        match File::open(&file_entry.path) {
+            Ok(file) => {
+                let mut file_entry_clone = file_entry.clone();
+
+                panic::catch_unwind(|| {
+                    if let Err(e) = validator(file) {
+                        file_entry.error_string = e;
+                    }
+                    Some(file_entry)
+                })
+                .unwrap_or_else(|_| {
+                    let message = create_crash_message("Czkawka", &file_entry_clone.path.to_string_lossy(), "https://github.com/qarmin/czkawka");
+                    error!("{message}");
+                    file_entry_clone.error_string = message;
+                    Some(file_entry_clone)
+                })
+            }
+            Err(_inspected) => None,
+        }
+    }
+    fn check_broken_font(mut file_entry: BrokenEntry) -> Option<BrokenEntry> {
+        match File::open(&file_entry.path) {
+            Ok(file) => {
+                let mut file_entry_clone = file_entry.clone();
+
+                panic::catch_unwind(|| {
+                    if let Err((e, severity)) = check_font(file) {
+                        file_entry.error_string = e;
+                        file_entry.severity = severity;
+                    }
+                    Some(file_entry)
+                })
+                .unwrap_or_else(|_| {
+                    let message = create_crash_message("Czkawka", &file_entry_clone.path.to_string_lossy(), "https://github.com/qarmin/czkawka");
+                    error!("{message}");
+                    file_entry_clone.error_string = message;
+                    Some(file_entry_clone)
+                })
+            }
+            Err(_inspected) => None,
+        }
+    }
 
     #[fun_time(message = "load_cache", level = "debug")]
     fn load_cache(&mut self) -> (BTreeMap<String, BrokenEntry>, BTreeMap<String, BrokenEntry>, BTreeMap<String, BrokenEntry>) {
@@ -174,7 +327,16 @@ impl BrokenFiles {
             self.get_text_messages_mut().extend_with_another_messages(messages);
             loaded_hash_map = loaded_items.unwrap_or_default();
 
-            extract_loaded_cache(&loaded_hash_map, files_to_check, &mut records_already_cached, &mut non_cached_files_to_check);
+            for (name, file_entry) in files_to_check {
+                match loaded_hash_map.get(&name) {
+                    Some(cached_entry) if is_cached_entry_usable(cached_entry, &file_entry) => {
+                        records_already_cached.insert(name, cached_entry.clone());
+                    }
+                    _ => {
+                        non_cached_files_to_check.insert(name, file_entry);
+                    }
+                }
+            }
         } else {
             loaded_hash_map = Default::default();
             non_cached_files_to_check = files_to_check;
@@ -182,15 +344,10 @@ impl BrokenFiles {
         (loaded_hash_map, records_already_cached, non_cached_files_to_check)
     }
 
-    fn check_file(file_entry: BrokenEntry) -> Option<BrokenEntry> {
-        match file_entry.type_of_file {
-            TypeOfFile::Image => Some(Self::check_broken_image(file_entry)),
-            TypeOfFile::ArchiveZip => Self::check_broken_zip(file_entry),
-            TypeOfFile::Audio => Self::check_broken_audio(file_entry),
-            TypeOfFile::PDF => Some(Self::check_broken_pdf(file_entry)),
-            // This means that cache read invalid value because maybe cache comes from different czkawka version
-            TypeOfFile::Unknown => None,
-        }
+    fn check_file(file_entry: BrokenEntry, params: &BrokenFilesParameters, stop_flag: &Arc<AtomicBool>) -> Option<BrokenEntry> {
+        // Not found validator means that cache read invalid value because maybe cache comes from different czkawka version
+        let (_, _, _, validator) = BROKEN_FILES_VALIDATORS.iter().find(|(_, type_of_file, _, _)| *type_of_file == file_entry.type_of_file)?;
+        validator(file_entry, params, stop_flag)
     }
 
     #[fun_time(message = "look_for_broken_files", level = "debug")]
@@ -210,6 +367,7 @@ impl BrokenFiles {
         );
 
         let non_cached_files_to_check = non_cached_files_to_check.into_iter().collect::<Vec<_>>();
+        let params = self.get_params();
 
         debug!("look_for_broken_files - started finding for broken files");
         let mut vec_file_entry: Vec<BrokenEntry> = non_cached_files_to_check
@@ -221,7 +379,11 @@ impl BrokenFiles {
                 }
 
                 let size = file_entry.size;
-                let res = Self::check_file(file_entry);
+                let res = Self::check_file(file_entry, params, stop_flag);
+                // Check may be interrupted by stop flag, so its result cannot be trusted
+                if check_if_stop_received(stop_flag) {
+                    return None;
+                }
 
                 progress_handler.increase_items(1);
                 progress_handler.increase_size(size);
@@ -240,7 +402,11 @@ impl BrokenFiles {
 
         self.save_to_cache(&vec_file_entry, loaded_hash_map);
 
-        self.broken_files = vec_file_entry.into_iter().filter_map(|f| if f.error_string.is_empty() { None } else { Some(f) }).collect();
+        let minimal_severity = self.get_params().minimal_severity;
+        self.broken_files = vec_file_entry
+            .into_iter()
+            .filter(|f| !f.error_string.is_empty() && f.severity >= minimal_severity)
+            .collect();
 
         self.information.number_of_broken_files = self.broken_files.len();
         debug!("Found {} broken files.", self.information.number_of_broken_files);
@@ -253,12 +419,13 @@ impl BrokenFiles {
     fn save_to_cache(&mut self, vec_file_entry: &[BrokenEntry], loaded_hash_map: BTreeMap<String, BrokenEntry>) {
         if self.common_data.use_cache {
             // Must save all results to file, old loaded from file with all currently counted results
+            // Files checked again replace their old results
             let mut all_results: BTreeMap<String, BrokenEntry> = Default::default();
 
-            for file_entry in vec_file_entry.iter().cloned() {
+            for (_name, file_entry) in loaded_hash_map {
                 all_results.insert(file_entry.path.to_string_lossy().to_string(), file_entry);
             }
-            for (_name, file_entry) in loaded_hash_map {
+            for file_entry in vec_file_entry.iter().cloned() {
                 all_results.insert(file_entry.path.to_string_lossy().to_string(), file_entry);
             }
 
@@ -268,14 +435,14 @@ impl BrokenFiles {
     }
 }
 
+// Cache is keyed only by path, size and modification date, so files cached as other type or checked with other mode are checked again
+// Stronger check may find errors, which weaker one would not report, so its results cannot be reused either
+fn is_cached_entry_usable(cached_entry: &BrokenEntry, file_entry: &BrokenEntry) -> bool {
+    cached_entry.type_of_file == file_entry.type_of_file && cached_entry.check_mode == file_entry.check_mode
+}
+
 #[expect(clippy::string_slice)] // Valid, because we address go to dot, which is known ascii character
-fn check_extension_availability(
-    full_name: &Path,
-    images_extensions: &IndexSet<&'static str>,
-    zip_extensions: &IndexSet<&'static str>,
-    audio_extensions: &IndexSet<&'static str>,
-    pdf_extensions: &IndexSet<&'static str>,
-) -> TypeOfFile {
+fn check_extension_availability(full_name: &Path, extensions_types: &IndexMap<&'static str, TypeOfFile>) -> TypeOfFile {
     let Some(file_name) = full_name.file_name() else {
         error!("Missing file name in file - \"{}\"", full_name.to_string_lossy());
         debug_assert!(false, "Missing file name in file - \"{}\"", full_name.to_string_lossy());
@@ -287,14 +454,8 @@ fn check_extension_availability(
 
     let extension_lowercase = extension_str.to_ascii_lowercase();
 
-    if images_extensions.contains(&extension_lowercase.as_str()) {
-        TypeOfFile::Image
-    } else if zip_extensions.contains(&extension_lowercase.as_str()) {
-        TypeOfFile::ArchiveZip
-    } else if audio_extensions.contains(&extension_lowercase.as_str()) {
-        TypeOfFile::Audio
-    } else if pdf_extensions.contains(&extension_lowercase.as_str()) {
-        TypeOfFile::PDF
+    if let Some(type_of_file) = extensions_types.get(extension_lowercase.as_str()) {
+        *type_of_file
     } else {
         error!("File with unknown extension: \"{}\" - {extension_lowercase}", full_name.to_string_lossy());
         debug_assert!(false, "File with unknown extension - \"{}\" - {extension_lowercase}", full_name.to_string_lossy());
@@ -303,98 +464,94 @@ fn check_extension_availability(
 }
 
 pub fn get_broken_files_cache_file() -> String {
-    format!("cache_broken_files_{CACHE_VERSION}.bin")
+    format!("cache_broken_files_{CACHE_BROKEN_FILES_VERSION}.bin")
 }
 
 #[cfg(test)]
 mod tests {
     use std::path::Path;
 
-    use indexmap::{IndexSet, indexset};
+    use indexmap::{IndexMap, indexmap};
 
     use super::*;
+    use crate::common::model::FileEntry;
 
     #[test]
     fn test_check_extension_availability_image() {
-        let images_extensions: IndexSet<&str> = indexset! {"jpg", "png", "gif"};
-        let zip_extensions: IndexSet<&str> = IndexSet::new();
-        let audio_extensions: IndexSet<&str> = IndexSet::new();
-        let pdf_extensions: IndexSet<&str> = IndexSet::new();
+        let extensions_types: IndexMap<&str, TypeOfFile> = indexmap! {"jpg" => TypeOfFile::Image, "png" => TypeOfFile::Image, "gif" => TypeOfFile::Image};
 
         let path = Path::new("test.jpg");
-        assert_eq!(
-            check_extension_availability(path, &images_extensions, &zip_extensions, &audio_extensions, &pdf_extensions),
-            TypeOfFile::Image
-        );
+        assert_eq!(check_extension_availability(path, &extensions_types), TypeOfFile::Image);
     }
 
     #[test]
     fn test_check_extension_availability_zip() {
-        let images_extensions: IndexSet<&str> = IndexSet::new();
-        let zip_extensions: IndexSet<&str> = indexset! {"zip", "rar"};
-        let audio_extensions: IndexSet<&str> = IndexSet::new();
-        let pdf_extensions: IndexSet<&str> = IndexSet::new();
+        let extensions_types: IndexMap<&str, TypeOfFile> = indexmap! {"zip" => TypeOfFile::ArchiveZip, "rar" => TypeOfFile::ArchiveZip};
 
         let path = Path::new("test.zip");
-        assert_eq!(
-            check_extension_availability(path, &images_extensions, &zip_extensions, &audio_extensions, &pdf_extensions),
-            TypeOfFile::ArchiveZip
-        );
+        assert_eq!(check_extension_availability(path, &extensions_types), TypeOfFile::ArchiveZip);
     }
 
     #[test]
     fn test_check_extension_availability_audio() {
-        let images_extensions: IndexSet<&str> = IndexSet::new();
-        let zip_extensions: IndexSet<&str> = IndexSet::new();
-        let audio_extensions: IndexSet<&str> = indexset! {"mp3", "wav"};
-        let pdf_extensions: IndexSet<&str> = IndexSet::new();
+        let extensions_types: IndexMap<&str, TypeOfFile> = indexmap! {"mp3" => TypeOfFile::Audio, "wav" => TypeOfFile::Audio};
 
         let path = Path::new("test.mp3");
-        assert_eq!(
-            check_extension_availability(path, &images_extensions, &zip_extensions, &audio_extensions, &pdf_extensions),
-            TypeOfFile::Audio
-        );
+        assert_eq!(check_extension_availability(path, &extensions_types), TypeOfFile::Audio);
     }
 
     #[test]
     fn test_check_extension_availability_pdf() {
-        let images_extensions: IndexSet<&str> = IndexSet::new();
-        let zip_extensions: IndexSet<&str> = IndexSet::new();
-        let audio_extensions: IndexSet<&str> = IndexSet::new();
-        let pdf_extensions: IndexSet<&str> = indexset! {"pdf"};
+        let extensions_types: IndexMap<&str, TypeOfFile> = indexmap! {"pdf" => TypeOfFile::PDF};
 
         let path = Path::new("test.pdf");
-        assert_eq!(
-            check_extension_availability(path, &images_extensions, &zip_extensions, &audio_extensions, &pdf_extensions),
-            TypeOfFile::PDF
-        );
+        assert_eq!(check_extension_availability(path, &extensions_types), TypeOfFile::PDF);
+    }
+
+    #[test]
+    fn test_check_extension_availability_video() {
+        let extensions_types: IndexMap<&str, TypeOfFile> = indexmap! {"mp4" => TypeOfFile::Video, "mkv" => TypeOfFile::Video};
+
+        let path = Path::new("test.MKV");
+        assert_eq!(check_extension_availability(path, &extensions_types), TypeOfFile::Video);
     }
 
     #[test]
     fn test_check_extension_availability_no_extension() {
-        let images_extensions: IndexSet<&str> = IndexSet::new();
-        let zip_extensions: IndexSet<&str> = IndexSet::new();
-        let audio_extensions: IndexSet<&str> = IndexSet::new();
-        let pdf_extensions: IndexSet<&str> = IndexSet::new();
+        let extensions_types: IndexMap<&str, TypeOfFile> = IndexMap::new();
 
         let path = Path::new("test");
-        assert_eq!(
-            check_extension_availability(path, &images_extensions, &zip_extensions, &audio_extensions, &pdf_extensions),
-            TypeOfFile::Unknown
-        );
+        assert_eq!(check_extension_availability(path, &extensions_types), TypeOfFile::Unknown);
     }
 
     #[test]
     fn test_check_no_extension() {
-        let images_extensions: IndexSet<&str> = IndexSet::new();
-        let zip_extensions: IndexSet<&str> = IndexSet::new();
-        let audio_extensions: IndexSet<&str> = indexset! {"mp3", "wav"};
-        let pdf_extensions: IndexSet<&str> = IndexSet::new();
+        let extensions_types: IndexMap<&str, TypeOfFile> = indexmap! {"mp3" => TypeOfFile::Audio, "wav" => TypeOfFile::Audio};
 
         let path = Path::new("/home/.mp3");
-        assert_eq!(
-            check_extension_availability(path, &images_extensions, &zip_extensions, &audio_extensions, &pdf_extensions),
-            TypeOfFile::Audio
-        );
+        assert_eq!(check_extension_availability(path, &extensions_types), TypeOfFile::Audio);
+    }
+
+    #[test]
+    fn test_is_cached_entry_usable() {
+        let params = |verify_zip_content| BrokenFilesParameters::new(CheckedTypes::ARCHIVE | CheckedTypes::DOCUMENT, false, verify_zip_content, false, Severity::Warning);
+        let entry = |type_of_file, verify_zip_content| {
+            let mut entry = FileEntry {
+                path: "test.docx".into(),
+                size: 1,
+                modified_date: 1,
+            }
+            .into_broken_entry();
+            entry.type_of_file = type_of_file;
+            entry.check_mode = CheckMode::new(type_of_file, &params(verify_zip_content));
+            entry
+        };
+
+        assert!(is_cached_entry_usable(&entry(TypeOfFile::ZipDocument, false), &entry(TypeOfFile::ZipDocument, false)));
+        assert!(!is_cached_entry_usable(&entry(TypeOfFile::ZipDocument, true), &entry(TypeOfFile::ZipDocument, false)));
+        assert!(!is_cached_entry_usable(&entry(TypeOfFile::ZipDocument, false), &entry(TypeOfFile::ZipDocument, true)));
+        assert!(!is_cached_entry_usable(&entry(TypeOfFile::ArchiveZip, true), &entry(TypeOfFile::ZipDocument, true)));
+        // Options not affecting type of file do not require checking it again
+        assert_eq!(CheckMode::new(TypeOfFile::Image, &params(true)), CheckMode::default());
     }
 }
@@ -0,0 +1,139 @@
+use std::io::Read;
+
+use crate::tools::broken_files::Severity;
+
+// TrueType and OpenType fonts contain checksum of every table and of whole file, stored in `head` table
+const FONT_CHECKSUM_MAGIC: u32 = 0xB1B0_AFBA;
+const TABLE_DIRECTORY_OFFSET: usize = 12;
+const TABLE_RECORD_SIZE: usize = 16;
+const HEAD_CHECKSUM_ADJUSTMENT_OFFSET: usize = 8;
+
+// Many fonts with invalid checksums are still usable, so only broken structure makes font unreadable
+pub(crate) fn check_font<R: Read>(mut reader: R) -> Result<(), (String, Severity)> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data).map_err(|e| (e.to_string(), Severity::Unreadable))?;
+    check_font_data(&data)
+}
+
+fn check_font_data(data: &[u8]) -> Result<(), (String, Severity)> {
+    let unreadable = |message: &str| Err((message.to_string(), Severity::Unreadable));
+    let Some(&[s1, s2, s3, s4, n1, n2]) = data.get(..6) else {
+        return unreadable("Font file is too small to contain table directory");
+    };
+    match &[s1, s2, s3, s4] {
+        [0x00, 0x01, 0x00, 0x00] | b"OTTO" | b"true" | b"typ1" => {}
+        _ => return unreadable("Font file has invalid signature"),
+    }
+
+    let number_of_tables = usize::from(u16::from_be_bytes([n1, n2]));
+    let Some(table_records) = data.get(TABLE_DIRECTORY_OFFSET..TABLE_DIRECTORY_OFFSET + number_of_tables * TABLE_RECORD_SIZE) else {
+        return unreadable("Font table directory is truncated");
+    };
+
+    let mut head_checksum_adjustment = None;
+    for record in table_records.chunks_exact(TABLE_RECORD_SIZE) {
+        let tag = String::from_utf8_lossy(record.get(..4).unwrap_or_default()).to_string();
+        let checksum = read_u32(record, 4);
+        let offset = read_u32(record, 8) as usize;
+        let length = read_u32(record, 12) as usize;
+
+        let Some(table) = data.get(offset..offset + length) else {
+            return unreadable(&format!("Font table \"{tag}\" points outside of file, file is probably truncated"));
+        };
+
+        let calculated_checksum = if tag == "head" {
+            let Some(adjustment) = table.get(HEAD_CHECKSUM_ADJUSTMENT_OFFSET..HEAD_CHECKSUM_ADJUSTMENT_OFFSET + 4) else {
+                return unreadable("Font table \"head\" is too small");
+            };
+            let adjustment = read_u32(adjustment, 0);
+            head_checksum_adjustment = Some(adjustment);
+            calculate_checksum(table).wrapping_sub(adjustment)
+        } else {
+            calculate_checksum(table)
+        };
+        if calculated_checksum != checksum {
+            return Err((format!("Font table \"{tag}\" has invalid checksum"), Severity::Warning));
+        }
+    }
+
+    // Not every font format requires `head` table e.g. bitmap only fonts
+    if let Some(adjustment) = head_checksum_adjustment {
+        // Tables are aligned to 4 bytes, so adjustment is counted in checksum as single word
+        let file_checksum = calculate_checksum(data).wrapping_sub(adjustment);
+        if FONT_CHECKSUM_MAGIC.wrapping_sub(file_checksum) != adjustment {
+            return Err(("Font file has invalid checksum".to_string(), Severity::Warning));
+        }
+    }
+
+    Ok(())
+}
+
+fn calculate_checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0_u32, |sum, chunk| {
+        let mut word = [0; 4];
+        for (word_byte, byte) in word.iter_mut().zip(chunk) {
+            *word_byte = *byte;
+        }
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    let mut word = [0; 4];
+    if let Some(bytes) = data.get(offset..offset + 4) {
+        word.copy_from_slice(bytes);
+    }
+    u32::from_be_bytes(word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Minimal font with `head` and `name` tables and valid checksums
+    fn create_font() -> Vec<u8> {
+        let head = (0..54).map(|i| if (8..12).contains(&i) { 0 } else { i as u8 }).collect::<Vec<_>>();
+        let name = b"Font name\0\0\0".to_vec();
+        let tables: [(&[u8; 4], &[u8]); 2] = [(b"head", &head), (b"name", &name)];
+
+        let mut data = vec![0x00, 0x01, 0x00, 0x00, 0x00, tables.len() as u8, 0, 0, 0, 0, 0, 0];
+        let mut offset = TABLE_DIRECTORY_OFFSET + tables.len() * TABLE_RECORD_SIZE;
+        let mut content = Vec::new();
+        for (tag, table) in tables {
+            data.extend_from_slice(tag);
+            data.extend_from_slice(&calculate_checksum(table).to_be_bytes());
+            data.extend_from_slice(&(offset as u32).to_be_bytes());
+            data.extend_from_slice(&(table.len() as u32).to_be_bytes());
+            let padded_len = table.len().div_ceil(4) * 4;
+            content.extend_from_slice(table);
+            content.resize(content.len() + padded_len - table.len(), 0);
+            offset += padded_len;
+        }
+        data.extend(content);
+
+        let head_offset = TABLE_DIRECTORY_OFFSET + tables.len() * TABLE_RECORD_SIZE;
+        let adjustment = FONT_CHECKSUM_MAGIC.wrapping_sub(calculate_checksum(&data));
+        data[head_offset + 8..head_offset + 12].copy_from_slice(&adjustment.to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn test_check_font_data() {
+        let font = create_font();
+        check_font_data(&font).unwrap();
+
+        assert_eq!(check_font_data(&font[..font.len() - 8]).unwrap_err().1, Severity::Unreadable);
+        assert_eq!(check_font_data(&font[..20]).unwrap_err().1, Severity::Unreadable);
+        assert_eq!(check_font_data(b"Not a font").unwrap_err().1, Severity::Unreadable);
+
+        let mut corrupted = font.clone();
+        let idx = corrupted.len() - 6;
+        corrupted[idx] ^= 0xFF;
+        assert_eq!(check_font_data(&corrupted).unwrap_err().1, Severity::Warning);
+
+        // Only whole file checksum is invalid
+        let mut corrupted = font;
+        corrupted[TABLE_DIRECTORY_OFFSET + 2 * TABLE_RECORD_SIZE + 9] ^= 0xFF;
+        assert_eq!(check_font_data(&corrupted).unwrap_err().1, Severity::Warning);
+    }
+}
@@ -0,0 +1,248 @@
+use std::fs;
+use std::path::Path;
+
+use image::DynamicImage;
+
+const PNG_SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+// Smaller images may be uniform by design e.g. icons
+const MINIMAL_HEIGHT_FOR_UNIFORM_CHECK: u32 = 64;
+// Part of image height, which must be covered by uniform region to report it
+const UNIFORM_REGION_MINIMAL_RATIO: f32 = 0.1;
+const UNIFORM_PIXEL_TOLERANCE: u8 = 3;
+// Maximal difference between channels, to treat colour as grey or black
+const GREY_CHANNEL_TOLERANCE: u8 = 8;
+// Decoders fill missing part of image with black or mid-grey colour, other uniform regions are probably part of image e.g. white background
+const TRUNCATION_FILL_VALUES: &[u8] = &[0, 128];
+const TRUNCATION_FILL_TOLERANCE: u8 = 8;
+// Minimal average difference of channels between uniform region and row above it, truncated image ends with sharp edge
+const MINIMAL_EDGE_DIFFERENCE: f32 = 10.0;
+
+// Image is already decoded, so any problem found here is only a warning
+pub(crate) fn check_image_strictly(path: &Path, image: &DynamicImage) -> Result<(), String> {
+    let data = fs::read(path).map_err(|e| e.to_string())?;
+    let extension = path.extension().map(|ext| ext.to_string_lossy().to_string()).unwrap_or_default();
+    check_image_structure(&data, &extension)?;
+    check_uniform_bottom_region(image)
+}
+
+// Checks structure of file, which decoders usually ignore when image is still possible to decode
+fn check_image_structure(data: &[u8], extension: &str) -> Result<(), String> {
+    match extension.to_ascii_lowercase().as_str() {
+        "jpg" | "jpeg" | "jfif" | "jpe" | "jif" | "jfi" => check_jpeg_structure(data),
+        "png" => check_png_structure(data),
+        _ => Ok(()),
+    }
+}
+
+fn check_jpeg_structure(data: &[u8]) -> Result<(), String> {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return Err("JPEG file is missing start of image marker".to_string());
+    }
+
+    // Markers before first scan are followed by their length, so can be safely skipped
+    let mut pos = 2;
+    loop {
+        let Some(&[0xFF, marker]) = data.get(pos..pos + 2) else {
+            return Err("JPEG file ended before image data, file is probably truncated".to_string());
+        };
+        match marker {
+            // Fill bytes
+            0xFF => pos += 1,
+            // Start of scan
+            0xDA => break,
+            // Markers without length
+            0x01 | 0xD0..=0xD7 => pos += 2,
+            _ => {
+                let Some(&[high, low]) = data.get(pos + 2..pos + 4) else {
+                    return Err("JPEG file ended before image data, file is probably truncated".to_string());
+                };
+                pos += 2 + usize::from(u16::from_be_bytes([high, low]));
+            }
+        }
+    }
+
+    // Entropy coded data cannot contain end of image marker, because every 0xFF byte is followed there by 0x00 or restart marker
+    if !data.get(pos..).unwrap_or_default().windows(2).any(|window| window == [0xFF, 0xD9]) {
+        return Err("JPEG file is missing end of image marker, file is probably truncated".to_string());
+    }
+    Ok(())
+}
+
+fn check_png_structure(data: &[u8]) -> Result<(), String> {
+    if !data.starts_with(PNG_SIGNATURE) {
+        return Err("PNG file has invalid signature".to_string());
+    }
+
+    let mut pos = PNG_SIGNATURE.len();
+    let mut found_idat = false;
+    let mut chunk_idx = 0;
+    loop {
+        let Some(&[l1, l2, l3, l4, t1, t2, t3, t4]) = data.get(pos..pos + 8) else {
+            return Err("PNG file is missing IEND chunk, file is probably truncated".to_string());
+        };
+        let length = u32::from_be_bytes([l1, l2, l3, l4]) as usize;
+        let chunk_type = [t1, t2, t3, t4];
+        let chunk_name = String::from_utf8_lossy(&chunk_type).to_string();
+
+        // Chunk type, data and CRC
+        let Some(chunk_with_crc) = data.get(pos + 4..pos + 12 + length) else {
+            return Err(format!("PNG chunk {chunk_name} is truncated, file ended prematurely"));
+        };
+        let (chunk_content, crc) = chunk_with_crc.split_at(4 + length);
+        if crc32fast::hash(chunk_content).to_be_bytes() != crc {
+            return Err(format!("PNG chunk {chunk_name} has invalid CRC"));
+        }
+
+        if chunk_idx == 0 && &chunk_type != b"IHDR" {
+            return Err("PNG file doesn't start with IHDR chunk".to_string());
+        }
+        match &chunk_type {
+            b"IDAT" => found_idat = true,
+            b"IEND" => break,
+            _ => {}
+        }
+
+        pos += 12 + length;
+        chunk_idx += 1;
+    }
+
+    if !found_idat {
+        return Err("PNG file doesn't contain any IDAT chunk".to_string());
+    }
+    Ok(())
+}
+
+// Partially downloaded or copied images are usually decoded with remaining rows filled by mid-grey or black colour
+fn check_uniform_bottom_region(image: &DynamicImage) -> Result<(), String> {
+    let image = image.to_rgb8();
+    let (width, height) = image.dimensions();
+    if width == 0 || height < MINIMAL_HEIGHT_FOR_UNIFORM_CHECK {
+        return Ok(());
+    }
+
+    let reference = image.get_pixel(0, height - 1).0;
+    let reference_min = reference.iter().copied().min().unwrap_or_default();
+    let reference_max = reference.iter().copied().max().unwrap_or_default();
+    if reference_max - reference_min > GREY_CHANNEL_TOLERANCE
+        || !TRUNCATION_FILL_VALUES
+            .iter()
+            .any(|fill| reference_min.abs_diff(*fill) <= TRUNCATION_FILL_TOLERANCE && reference_max.abs_diff(*fill) <= TRUNCATION_FILL_TOLERANCE)
+    {
+        return Ok(());
+    }
+
+    let uniform_rows = (0..height)
+        .rev()
+        .take_while(|&y| (0..width).all(|x| image.get_pixel(x, y).0.iter().zip(reference).all(|(c, r)| c.abs_diff(r) <= UNIFORM_PIXEL_TOLERANCE)))
+        .count() as u32;
+
+    let ratio = uniform_rows as f32 / height as f32;
+    if uniform_rows == height || ratio < UNIFORM_REGION_MINIMAL_RATIO {
+        return Ok(());
+    }
+
+    let edge_row = height - uniform_rows - 1;
+    let edge_difference = (0..width)
+        .flat_map(|x| image.get_pixel(x, edge_row).0.into_iter().zip(reference).map(|(c, r)| u64::from(c.abs_diff(r))))
+        .sum::<u64>() as f32
+        / (width as f32 * 3.0);
+    if edge_difference >= MINIMAL_EDGE_DIFFERENCE {
+        return Err(format!(
+            "Bottom {uniform_rows} rows of image({:.0}% of height) are filled with uniform colour, image is probably partially downloaded",
+            ratio * 100.0
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use image::{ImageFormat, Rgb, RgbImage};
+
+    use super::*;
+
+    fn create_image() -> RgbImage {
+        RgbImage::from_fn(100, 100, |x, y| Rgb([(x * 2) as u8, (y * 2) as u8, ((x + y) % 256) as u8]))
+    }
+
+    fn encode(image: &RgbImage, format: ImageFormat) -> Vec<u8> {
+        let mut data = Cursor::new(Vec::new());
+        image.write_to(&mut data, format).expect("Failed to encode image");
+        data.into_inner()
+    }
+
+    #[test]
+    fn test_check_jpeg_structure() {
+        let data = encode(&create_image(), ImageFormat::Jpeg);
+        check_image_structure(&data, "JPG").unwrap();
+        assert!(check_image_structure(&data[..data.len() - 2], "jpg").is_err());
+        assert!(check_image_structure(&data[..data.len() / 2], "jpg").is_err());
+        assert!(check_image_structure(&data[..10], "jpg").is_err());
+        assert!(check_image_structure(b"Not a jpeg", "jpg").is_err());
+    }
+
+    #[test]
+    fn test_check_png_structure() {
+        let data = encode(&create_image(), ImageFormat::Png);
+        check_image_structure(&data, "png").unwrap();
+        assert!(check_image_structure(&data[..data.len() - 12], "png").is_err());
+        assert!(check_image_structure(&data[..data.len() / 2], "png").is_err());
+
+        let mut corrupted = data;
+        let idx = corrupted.len() / 2;
+        if let Some(byte) = corrupted.get_mut(idx) {
+            *byte ^= 0xFF;
+        }
+        assert!(check_image_structure(&corrupted, "png").is_err());
+
+        // Other formats are not checked
+        check_image_structure(&corrupted, "gif").unwrap();
+    }
+
+    #[test]
+    fn test_check_uniform_bottom_region() {
+        let mut image = create_image();
+        check_uniform_bottom_region(&DynamicImage::ImageRgb8(image.clone())).unwrap();
+
+        for y in 95..100 {
+            for x in 0..100 {
+                image.put_pixel(x, y, Rgb([128, 128, 128]));
+            }
+        }
+        check_uniform_bottom_region(&DynamicImage::ImageRgb8(image.clone())).unwrap();
+
+        for y in 60..100 {
+            for x in 0..100 {
+                image.put_pixel(x, y, Rgb([128, 128, 128]));
+            }
+        }
+        assert!(check_uniform_bottom_region(&DynamicImage::ImageRgb8(image.clone())).is_err());
+
+        for y in 60..100 {
+            for x in 0..100 {
+                image.put_pixel(x, y, Rgb([0, 0, 0]));
+            }
+        }
+        assert!(check_uniform_bottom_region(&DynamicImage::ImageRgb8(image.clone())).is_err());
+
+        // Colourful and white regions are not result of truncation
+        for colour in [Rgb([200, 20, 20]), Rgb([255, 255, 255]), Rgb([60, 60, 60])] {
+            for y in 60..100 {
+                for x in 0..100 {
+                    image.put_pixel(x, y, colour);
+                }
+            }
+            check_uniform_bottom_region(&DynamicImage::ImageRgb8(image.clone())).unwrap();
+        }
+
+        // Black region smoothly continuing dark image content, e.g. night sky
+        let dark = RgbImage::from_fn(100, 100, |_x, y| if y < 60 { Rgb([6, 6, 6]) } else { Rgb([0, 0, 0]) });
+        check_uniform_bottom_region(&DynamicImage::ImageRgb8(dark)).unwrap();
+
+        let uniform = RgbImage::from_pixel(100, 100, Rgb([0, 0, 0]));
+        check_uniform_bottom_region(&DynamicImage::ImageRgb8(uniform)).unwrap();
+    }
+}
@@ -1,7 +1,14 @@
 use bitflags::bitflags;
 
+mod archive;
 pub mod core;
+mod font;
+mod image_structure;
+mod sqlite;
+mod text_formats;
 pub mod traits;
+mod video;
+mod zip_archive;
 
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
@@ -19,6 +26,11 @@ pub struct BrokenEntry {
     pub size: u64,
     pub type_of_file: TypeOfFile,
     pub error_string: String,
+    #[serde(default)]
+    pub severity: Severity,
+    // Saved in cache, to check file again when other check mode is requested
+    #[serde(default)]
+    pub check_mode: CheckMode,
 }
 impl ResultEntry for BrokenEntry {
     fn get_path(&self) -> &Path {
@@ -41,6 +53,8 @@ impl FileEntry {
 
             type_of_file: TypeOfFile::Unknown,
             error_string: String::new(),
+            severity: Severity::Unreadable,
+            check_mode: CheckMode::default(),
         }
     }
 }
@@ -52,6 +66,45 @@ pub enum TypeOfFile {
     ArchiveZip,
     Audio,
     PDF,
+    Video,
+    ArchiveTar,
+    ArchiveGzip,
+    ArchiveXz,
+    ArchiveZstd,
+    ArchiveBzip2,
+    Archive7z,
+    ZipDocument,
+    Font,
+    Sqlite,
+    Json,
+    Xml,
+    Yaml,
+}
+
+// Warnings are reported for files, which can be opened, but are probably damaged e.g. truncated images
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Debug)]
+pub enum Severity {
+    Warning,
+    #[default]
+    Unreadable,
+}
+
+// Optional, slower checks used for given file, only ones affecting its type are set
+#[derive(Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct CheckMode {
+    pub decode_video_frames: bool,
+    pub verify_zip_content: bool,
+    pub strict_image_check: bool,
+}
+
+impl CheckMode {
+    pub(crate) fn new(type_of_file: TypeOfFile, params: &BrokenFilesParameters) -> Self {
+        Self {
+            decode_video_frames: type_of_file == TypeOfFile::Video && params.decode_video_frames,
+            verify_zip_content: matches!(type_of_file, TypeOfFile::ArchiveZip | TypeOfFile::ZipDocument) && params.verify_zip_content,
+            strict_image_check: type_of_file == TypeOfFile::Image && params.strict_image_check,
+        }
+    }
 }
 
 bitflags! {
@@ -63,6 +116,19 @@ bitflags! {
         const AUDIO = 0b10;
         const IMAGE = 0b100;
         const ARCHIVE = 0b1000;
+        const VIDEO = 0b10000;
+        const TAR = 0b10_0000;
+        const GZIP = 0b100_0000;
+        const XZ = 0b1000_0000;
+        const ZSTD = 0b1_0000_0000;
+        const BZIP2 = 0b10_0000_0000;
+        const SEVEN_ZIP = 0b100_0000_0000;
+        const DOCUMENT = 0b1000_0000_0000;
+        const FONT = 0b1_0000_0000_0000;
+        const SQLITE = 0b10_0000_0000_0000;
+        const JSON = 0b100_0000_0000_0000;
+        const XML = 0b1000_0000_0000_0000;
+        const YAML = 0b1_0000_0000_0000_0000;
     }
 }
 
@@ -74,11 +140,25 @@ pub struct Info {
 #[derive(Clone)]
 pub struct BrokenFilesParameters {
     pub checked_types: CheckedTypes,
+    // Besides checking container structure, decodes first frames of videos with ffmpeg
+    pub decode_video_frames: bool,
+    // Reads every entry of zip archives and documents, to verify its CRC
+    pub verify_zip_content: bool,
+    // Checks also structure of JPEG and PNG files and looks for uniform region at the bottom of images
+    pub strict_image_check: bool,
+    // Files with lower severity are not reported
+    pub minimal_severity: Severity,
 }
 
 impl BrokenFilesParameters {
-    pub fn new(checked_types: CheckedTypes) -> Self {
-        Self { checked_types }
+    pub fn new(checked_types: CheckedTypes, decode_video_frames: bool, verify_zip_content: bool, strict_image_check: bool, minimal_severity: Severity) -> Self {
+        Self {
+            checked_types,
+            decode_video_frames,
+            verify_zip_content,
+            strict_image_check,
+            minimal_severity,
+        }
     }
 }
 
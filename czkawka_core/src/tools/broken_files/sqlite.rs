@@ -0,0 +1,449 @@
+use std::io::{Read, Seek, SeekFrom};
+
+const SQLITE_MAGIC: &[u8] = b"SQLite format 3\0";
+const SQLITE_HEADER_SIZE: usize = 100;
+// Page containing this offset is never used by SQLite, because it is reserved for file locking
+const LOCK_BYTE_OFFSET: u64 = 0x4000_0000;
+// Real databases never have such deep trees, so this protects only against stack overflow
+const MAX_BTREE_DEPTH: usize = 64;
+
+const PAGE_INDEX_INTERIOR: u8 = 2;
+const PAGE_TABLE_INTERIOR: u8 = 5;
+const PAGE_INDEX_LEAF: u8 = 10;
+const PAGE_TABLE_LEAF: u8 = 13;
+
+// Equivalent of most important parts of `PRAGMA integrity_check` - validates header, freelist and structure of every table and index b-tree
+// and verifies that every page is used exactly once
+pub(crate) fn check_sqlite<R: Read + Seek>(reader: R) -> Result<(), String> {
+    let mut database = SqliteDatabase::open(reader)?;
+    database.check_freelist()?;
+
+    let mut schema_records = Vec::new();
+    database.check_btree(1, Some(true), 0, Some(&mut schema_records))?;
+    for record in schema_records {
+        // Views and triggers don't have their own b-tree
+        if let Some(root_page) = parse_schema_root_page(&record)? {
+            database.check_btree(root_page, None, 0, None)?;
+        }
+    }
+
+    database.check_unused_pages()
+}
+
+struct SqliteDatabase<R> {
+    reader: R,
+    header: [u8; SQLITE_HEADER_SIZE],
+    page_size: usize,
+    usable_size: usize,
+    page_count: u32,
+    used_pages: Vec<bool>,
+}
+
+impl<R: Read + Seek> SqliteDatabase<R> {
+    fn open(mut reader: R) -> Result<Self, String> {
+        let mut header = [0; SQLITE_HEADER_SIZE];
+        reader.read_exact(&mut header).map_err(|_| "File is too small to be SQLite database".to_string())?;
+        if !header.starts_with(SQLITE_MAGIC) {
+            return Err("File has invalid SQLite header".to_string());
+        }
+
+        let page_size = match read_u16(&header, 16)? {
+            1 => 65536,
+            size => usize::from(size),
+        };
+        if !page_size.is_power_of_two() || page_size < 512 {
+            return Err(format!("SQLite database has invalid page size {page_size}"));
+        }
+        if header.get(18..20).is_none_or(|versions| versions.iter().any(|version| !(1..=2).contains(version))) {
+            return Err("SQLite database has invalid file format version".to_string());
+        }
+        if header.get(21..24) != Some(&[64, 32, 32][..]) {
+            return Err("SQLite database has invalid payload fractions".to_string());
+        }
+        let usable_size = page_size - usize::from(header.get(20).copied().unwrap_or_default());
+        if usable_size < 480 {
+            return Err(format!("SQLite database has invalid usable page size {usable_size}"));
+        }
+
+        let file_size = reader.seek(SeekFrom::End(0)).map_err(|e| e.to_string())?;
+        if file_size % page_size as u64 != 0 {
+            return Err(format!("SQLite database size is not multiple of page size {page_size}, file is probably truncated"));
+        }
+        let pages_in_file = file_size / page_size as u64;
+
+        // Page count from header is valid only if it was written by SQLite 3.7.0 or newer
+        let header_page_count = read_u32(&header, 28)?;
+        let page_count = if header_page_count != 0 && read_u32(&header, 24)? == read_u32(&header, 92)? {
+            if u64::from(header_page_count) > pages_in_file {
+                return Err(format!(
+                    "SQLite database should contain {header_page_count} pages, but file contains only {pages_in_file}, file is probably truncated"
+                ));
+            }
+            header_page_count
+        } else {
+            u32::try_from(pages_in_file).map_err(|_| "SQLite database contains too many pages".to_string())?
+        };
+
+        Ok(Self {
+            reader,
+            header,
+            page_size,
+            usable_size,
+            page_count,
+            used_pages: vec![false; page_count as usize + 1],
+        })
+    }
+
+    fn read_page(&mut self, page_number: u32) -> Result<Vec<u8>, String> {
+        let mut page = vec![0; self.page_size];
+        self.reader
+            .seek(SeekFrom::Start(u64::from(page_number - 1) * self.page_size as u64))
+            .and_then(|_| self.reader.read_exact(&mut page))
+            .map_err(|e| format!("Failed to read page {page_number} - {e}"))?;
+        Ok(page)
+    }
+
+    fn mark_used(&mut self, page_number: u32, usage: &str) -> Result<(), String> {
+        match self.used_pages.get_mut(page_number as usize) {
+            Some(used) if page_number != 0 => {
+                if *used {
+                    return Err(format!("Page {page_number} is referenced multiple times, last time as {usage} page"));
+                }
+                *used = true;
+                Ok(())
+            }
+            _ => Err(format!("Page {page_number} used as {usage} page is outside of database")),
+        }
+    }
+
+    fn check_freelist(&mut self) -> Result<(), String> {
+        let expected_free_pages = read_u32(&self.header, 36)?;
+        let max_leaves_in_trunk = self.usable_size / 4 - 2;
+
+        let mut free_pages: u64 = 0;
+        let mut trunk_page = read_u32(&self.header, 32)?;
+        while trunk_page != 0 {
+            self.mark_used(trunk_page, "freelist trunk")?;
+            let page = self.read_page(trunk_page)?;
+            let leaves = read_u32(&page, 4)? as usize;
+            if leaves > max_leaves_in_trunk {
+                return Err(format!("Freelist trunk page {trunk_page} contains invalid number of leaves {leaves}"));
+            }
+            for idx in 0..leaves {
+                self.mark_used(read_u32(&page, 8 + idx * 4)?, "freelist leaf")?;
+            }
+            free_pages += 1 + leaves as u64;
+            trunk_page = read_u32(&page, 0)?;
+        }
+
+        if free_pages != u64::from(expected_free_pages) {
+            return Err(format!("Freelist should contain {expected_free_pages} pages, but contains {free_pages}"));
+        }
+        Ok(())
+    }
+
+    // When table type of b-tree is not known(e.g. `WITHOUT ROWID` tables use index b-trees), it is taken from root page
+    fn check_btree(&mut self, page_number: u32, is_table: Option<bool>, depth: usize, mut payloads: Option<&mut Vec<Vec<u8>>>) -> Result<(), String> {
+        if depth > MAX_BTREE_DEPTH {
+            return Err(format!("B-tree containing page {page_number} is too deep"));
+        }
+        self.mark_used(page_number, "b-tree")?;
+        let page = self.read_page(page_number)?;
+
+        let header_offset = if page_number == 1 { SQLITE_HEADER_SIZE } else { 0 };
+        let page_type = page.get(header_offset).copied().unwrap_or_default();
+        let (page_is_table, is_leaf) = match page_type {
+            PAGE_INDEX_INTERIOR => (false, false),
+            PAGE_TABLE_INTERIOR => (true, false),
+            PAGE_INDEX_LEAF => (false, true),
+            PAGE_TABLE_LEAF => (true, true),
+            _ => return Err(format!("Page {page_number} has invalid b-tree page type {page_type}")),
+        };
+        if is_table.is_some_and(|is_table| is_table != page_is_table) {
+            return Err(format!("Page {page_number} has b-tree page type {page_type}, which doesn't match type of its parent"));
+        }
+
+        let header_size = if is_leaf { 8 } else { 12 };
+        let cell_count = usize::from(read_u16(&page, header_offset + 3)?);
+        let cell_pointers_end = header_offset + header_size + 2 * cell_count;
+        if cell_pointers_end > self.usable_size {
+            return Err(format!("Page {page_number} contains too many cells {cell_count}"));
+        }
+
+        for idx in 0..cell_count {
+            let cell_offset = usize::from(read_u16(&page, header_offset + header_size + 2 * idx)?);
+            let Some(cell) = page.get(cell_offset..self.usable_size).filter(|_| cell_offset >= cell_pointers_end) else {
+                return Err(format!("Cell {idx} of page {page_number} points outside of page content"));
+            };
+
+            let mut pos = if is_leaf {
+                0
+            } else {
+                self.check_btree(read_u32(cell, 0)?, Some(page_is_table), depth + 1, payloads.as_deref_mut())?;
+                // Interior cells of table b-tree contain only row id
+                if page_is_table {
+                    continue;
+                }
+                4
+            };
+
+            let (payload_size, len) = read_varint(cell, pos)?;
+            pos += len;
+            if page_is_table {
+                let (_row_id, len) = read_varint(cell, pos)?;
+                pos += len;
+            }
+            let payload = self.check_payload(cell, pos, payload_size, page_is_table, payloads.is_some())?;
+            if let Some(payloads) = payloads.as_deref_mut() {
+                payloads.push(payload);
+            }
+        }
+
+        if !is_leaf {
+            self.check_btree(read_u32(&page, header_offset + 8)?, Some(page_is_table), depth + 1, payloads)?;
+        }
+        Ok(())
+    }
+
+    // Payload which doesn't fit in page is stored in chain of overflow pages
+    fn check_payload(&mut self, cell: &[u8], pos: usize, payload_size: u64, is_table: bool, collect: bool) -> Result<Vec<u8>, String> {
+        let usable_size = self.usable_size as u64;
+        let max_local = if is_table { usable_size - 35 } else { (usable_size - 12) * 64 / 255 - 23 };
+        let min_local = (usable_size - 12) * 32 / 255 - 23;
+        let local_size = if payload_size <= max_local {
+            payload_size
+        } else {
+            let size = min_local + (payload_size - min_local) % (usable_size - 4);
+            if size <= max_local { size } else { min_local }
+        } as usize;
+
+        let Some(local_payload) = cell.get(pos..pos + local_size) else {
+            return Err("Cell payload exceeds page size".to_string());
+        };
+        let mut payload = if collect { local_payload.to_vec() } else { Vec::new() };
+
+        if payload_size > local_size as u64 {
+            let mut remaining = payload_size - local_size as u64;
+            let mut overflow_page = read_u32(cell, pos + local_size)?;
+            while remaining > 0 {
+                if overflow_page == 0 {
+                    return Err("Overflow chain is shorter than cell payload".to_string());
+                }
+                self.mark_used(overflow_page, "overflow")?;
+                let page = self.read_page(overflow_page)?;
+                let chunk_size = remaining.min(usable_size - 4) as usize;
+                if collect {
+                    payload.extend_from_slice(page.get(4..4 + chunk_size).unwrap_or_default());
+                }
+                remaining -= chunk_size as u64;
+                overflow_page = read_u32(&page, 0)?;
+            }
+            if overflow_page != 0 {
+                return Err("Overflow chain is longer than cell payload".to_string());
+            }
+        }
+        Ok(payload)
+    }
+
+    fn check_unused_pages(&mut self) -> Result<(), String> {
+        // In auto-vacuum mode, pointer map pages are not referenced by any b-tree
+        if read_u32(&self.header, 52)? != 0 {
+            return Ok(());
+        }
+        let lock_byte_page = LOCK_BYTE_OFFSET / self.page_size as u64 + 1;
+        if let Some(used) = self.used_pages.get_mut(lock_byte_page as usize) {
+            *used = true;
+        }
+
+        if let Some(first_unused) = self.used_pages.iter().skip(1).position(|used| !*used) {
+            let unused_pages = self.used_pages.iter().skip(1).filter(|used| !**used).count();
+            return Err(format!(
+                "{unused_pages} of {} pages are never used, first of them is page {}",
+                self.page_count,
+                first_unused + 1
+            ));
+        }
+        Ok(())
+    }
+}
+
+// Schema table contains columns type, name, tbl_name, rootpage and sql
+fn parse_schema_root_page(record: &[u8]) -> Result<Option<u32>, String> {
+    let (header_size, mut header_pos) = read_varint(record, 0)?;
+    let mut serial_types = Vec::new();
+    while (header_pos as u64) < header_size {
+        let (serial_type, len) = read_varint(record, header_pos)?;
+        serial_types.push(serial_type);
+        header_pos += len;
+    }
+
+    let mut value_pos = header_size as usize;
+    for (column, serial_type) in serial_types.into_iter().enumerate() {
+        let size = match serial_type {
+            0 | 8 | 9 => 0,
+            1..=4 => serial_type as usize,
+            5 => 6,
+            6 | 7 => 8,
+            10 | 11 => return Err(format!("Schema record contains invalid serial type {serial_type}")),
+            _ => (serial_type as usize - 12) / 2,
+        };
+        if column == 3 {
+            let root_page = match serial_type {
+                0 | 8 => 0,
+                9 => 1,
+                1..=6 => {
+                    let Some(bytes) = value_pos.checked_add(size).and_then(|value_end| record.get(value_pos..value_end)) else {
+                        return Err("Schema record is truncated".to_string());
+                    };
+                    bytes.iter().fold(0_u64, |value, byte| (value << 8) | u64::from(*byte))
+                }
+                _ => return Err(format!("Schema record contains root page with invalid serial type {serial_type}")),
+            };
+            return Ok(u32::try_from(root_page).ok().filter(|root_page| *root_page != 0));
+        }
+        // Sizes come from file, so they may be arbitrarily big in broken database
+        value_pos = value_pos.checked_add(size).ok_or_else(|| "Schema record contains too big values".to_string())?;
+    }
+    Err("Schema record doesn't contain root page".to_string())
+}
+
+fn read_varint(data: &[u8], pos: usize) -> Result<(u64, usize), String> {
+    let mut value: u64 = 0;
+    // First 8 bytes contain 7 bits of value, 9th byte contains whole 8 bits
+    for idx in 0..8 {
+        let Some(&byte) = data.get(pos + idx) else {
+            return Err("Varint exceeds page size".to_string());
+        };
+        value = (value << 7) | u64::from(byte & 0x7F);
+        if byte & 0x80 == 0 {
+            return Ok((value, idx + 1));
+        }
+    }
+    let Some(&byte) = data.get(pos + 8) else {
+        return Err("Varint exceeds page size".to_string());
+    };
+    Ok(((value << 8) | u64::from(byte), 9))
+}
+
+fn read_u16(data: &[u8], pos: usize) -> Result<u16, String> {
+    match data.get(pos..pos + 2) {
+        Some(&[b1, b2]) => Ok(u16::from_be_bytes([b1, b2])),
+        _ => Err("Value exceeds page size".to_string()),
+    }
+}
+
+fn read_u32(data: &[u8], pos: usize) -> Result<u32, String> {
+    match data.get(pos..pos + 4) {
+        Some(&[b1, b2, b3, b4]) => Ok(u32::from_be_bytes([b1, b2, b3, b4])),
+        _ => Err("Value exceeds page size".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    const PAGE_SIZE: usize = 512;
+
+    // Writes leaf table page with given records, each record as single cell
+    fn write_leaf_page(page: &mut [u8], header_offset: usize, records: &[Vec<u8>]) {
+        let mut content_start = PAGE_SIZE;
+        page[header_offset] = PAGE_TABLE_LEAF;
+        page[header_offset + 3..header_offset + 5].copy_from_slice(&(records.len() as u16).to_be_bytes());
+        for (idx, record) in records.iter().enumerate() {
+            let mut cell = vec![record.len() as u8, idx as u8 + 1];
+            cell.extend_from_slice(record);
+            content_start -= cell.len();
+            page[content_start..content_start + cell.len()].copy_from_slice(&cell);
+            let pointer_pos = header_offset + 8 + idx * 2;
+            page[pointer_pos..pointer_pos + 2].copy_from_slice(&(content_start as u16).to_be_bytes());
+        }
+        page[header_offset + 5..header_offset + 7].copy_from_slice(&(content_start as u16).to_be_bytes());
+    }
+
+    fn create_record(values: &[&[u8]], root_page: u8) -> Vec<u8> {
+        // Columns type, name, tbl_name as text, rootpage as 1 byte integer and sql as text
+        let text_type = |text: &[u8]| (text.len() * 2 + 13) as u8;
+        let mut header = vec![6, text_type(values[0]), text_type(values[1]), text_type(values[2]), 1, text_type(values[3])];
+        header.extend_from_slice(values[0]);
+        header.extend_from_slice(values[1]);
+        header.extend_from_slice(values[2]);
+        header.push(root_page);
+        header.extend_from_slice(values[3]);
+        header
+    }
+
+    fn create_database(page_count: u32) -> Vec<u8> {
+        let mut data = vec![0; PAGE_SIZE * 2];
+        data[..16].copy_from_slice(SQLITE_MAGIC);
+        data[16..18].copy_from_slice(&(PAGE_SIZE as u16).to_be_bytes());
+        data[18] = 1;
+        data[19] = 1;
+        data[21..24].copy_from_slice(&[64, 32, 32]);
+        data[24..28].copy_from_slice(&1_u32.to_be_bytes());
+        data[28..32].copy_from_slice(&page_count.to_be_bytes());
+        data[44..48].copy_from_slice(&4_u32.to_be_bytes());
+        data[56..60].copy_from_slice(&1_u32.to_be_bytes());
+        data[92..96].copy_from_slice(&1_u32.to_be_bytes());
+
+        let schema = create_record(&[b"table", b"t", b"t", b"CREATE TABLE t(a)"], 2);
+        write_leaf_page(&mut data[..PAGE_SIZE], SQLITE_HEADER_SIZE, &[schema]);
+        write_leaf_page(&mut data[PAGE_SIZE..], 0, &[vec![2, 15, b'a'], vec![2, 15, b'b']]);
+        data
+    }
+
+    #[test]
+    fn test_check_sqlite() {
+        let data = create_database(2);
+        check_sqlite(Cursor::new(&data)).unwrap();
+
+        assert!(check_sqlite(Cursor::new(&data[..PAGE_SIZE])).is_err());
+        assert!(check_sqlite(Cursor::new(&data[..PAGE_SIZE + 100])).is_err());
+        assert!(check_sqlite(Cursor::new(b"Not a database")).is_err());
+
+        let mut corrupted = data.clone();
+        corrupted[PAGE_SIZE] = 0xFF;
+        assert!(check_sqlite(Cursor::new(&corrupted)).is_err());
+
+        // Cell pointer outside of page
+        let mut corrupted = data.clone();
+        corrupted[PAGE_SIZE + 8..PAGE_SIZE + 10].copy_from_slice(&600_u16.to_be_bytes());
+        assert!(check_sqlite(Cursor::new(&corrupted)).is_err());
+
+        // Table pointing to schema page
+        let mut corrupted = data;
+        let schema_root_page_pos = PAGE_SIZE - (2 + 6 + 5 + 1 + 1 + 1 + 17) + 2 + 6 + 5 + 1 + 1;
+        corrupted[schema_root_page_pos] = 1;
+        assert!(check_sqlite(Cursor::new(&corrupted)).is_err());
+    }
+
+    #[test]
+    fn test_check_sqlite_unused_pages() {
+        let mut data = create_database(3);
+        data.resize(PAGE_SIZE * 3, 0);
+        assert!(check_sqlite(Cursor::new(&data)).is_err());
+
+        // Page added to freelist
+        data[32..36].copy_from_slice(&3_u32.to_be_bytes());
+        data[36..40].copy_from_slice(&1_u32.to_be_bytes());
+        check_sqlite(Cursor::new(&data)).unwrap();
+    }
+
+    #[test]
+    fn test_read_varint() {
+        assert_eq!(read_varint(&[0x05], 0), Ok((5, 1)));
+        assert_eq!(read_varint(&[0x81, 0x00], 0), Ok((128, 2)));
+        assert_eq!(read_varint(&[0xFF; 9], 0), Ok((u64::MAX, 9)));
+        read_varint(&[0x81], 0).unwrap_err();
+    }
+
+    #[test]
+    fn test_parse_schema_root_page_with_too_big_values() {
+        // Two blobs with maximal size, which together exceed address space
+        let mut record = vec![19];
+        record.extend_from_slice(&[0xFF; 18]);
+        parse_schema_root_page(&record).unwrap_err();
+    }
+}
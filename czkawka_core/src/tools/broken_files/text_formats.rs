@@ -0,0 +1,70 @@
+use std::io::Read;
+
+use serde::de::IgnoredAny;
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+pub(crate) fn check_json<R: Read>(reader: R) -> Result<(), String> {
+    let data = read_all(reader)?;
+    let data = data.strip_prefix(UTF8_BOM).unwrap_or(&data);
+    serde_json::from_slice::<IgnoredAny>(data).map(|_| ()).map_err(|e| e.to_string())
+}
+
+pub(crate) fn check_xml<R: Read>(reader: R) -> Result<(), String> {
+    let data = read_all(reader)?;
+    // Parser supports only UTF-8, so UTF-16 documents are skipped and documents in other encodings are checked after lossy conversion
+    if data.starts_with(&[0xFF, 0xFE]) || data.starts_with(&[0xFE, 0xFF]) {
+        return Ok(());
+    }
+    let content = String::from_utf8_lossy(&data);
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    roxmltree::Document::parse_with_options(&content, options).map(|_| ()).map_err(|e| e.to_string())
+}
+
+pub(crate) fn check_yaml<R: Read>(reader: R) -> Result<(), String> {
+    let data = read_all(reader)?;
+    let content = String::from_utf8_lossy(data.strip_prefix(UTF8_BOM).unwrap_or(&data));
+    yaml_rust2::YamlLoader::load_from_str(&content).map(|_| ()).map_err(|e| e.to_string())
+}
+
+fn read_all<R: Read>(mut reader: R) -> Result<Vec<u8>, String> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data).map_err(|e| e.to_string())?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_json() {
+        check_json(&br#"{"a": [1, 2.5, "text", null, {"b": true}]}"#[..]).unwrap();
+        check_json(&b"\xEF\xBB\xBF[]"[..]).unwrap();
+        assert!(check_json(&br#"{"a": [1, 2"#[..]).is_err());
+        assert!(check_json(&br#"{"a": 1} {"#[..]).is_err());
+        assert!(check_json(&b""[..]).is_err());
+    }
+
+    #[test]
+    fn test_check_xml() {
+        check_xml(&br#"<?xml version="1.0"?><root a="1"><child>Text</child><empty/></root>"#[..]).unwrap();
+        check_xml(&br#"<!DOCTYPE root [<!ENTITY e "value">]><root>&e;</root>"#[..]).unwrap();
+        assert!(check_xml(&b"<root><child></root>"[..]).is_err());
+        assert!(check_xml(&b"<root><child>"[..]).is_err());
+        assert!(check_xml(&b"<root/><second/>"[..]).is_err());
+        assert!(check_xml(&b""[..]).is_err());
+    }
+
+    #[test]
+    fn test_check_yaml() {
+        check_yaml(&b"a: 1\nb:\n  - c\n  - d: [1, 2]\n---\nsecond: document\n"[..]).unwrap();
+        check_yaml(&b""[..]).unwrap();
+        assert!(check_yaml(&b"a: [1, 2\nb: 3\n"[..]).is_err());
+        assert!(check_yaml(&b"a: b: c\n"[..]).is_err());
+        assert!(check_yaml(&b"a: \"unclosed\n"[..]).is_err());
+    }
+}
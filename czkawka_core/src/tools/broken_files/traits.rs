@@ -7,9 +7,11 @@ use fun_time::fun_time;
 
 use crate::common::model::WorkContinueStatus;
 use crate::common::progress_data::ProgressData;
+use crate::common::results_file::retain_unchanged_entries;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteItemType, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
-use crate::tools::broken_files::{BrokenFiles, BrokenFilesParameters, Info};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PersistentResults, PrintResults, Search};
+use crate::flc;
+use crate::tools::broken_files::{BrokenEntry, BrokenFiles, BrokenFilesParameters, CheckedTypes, Info};
 
 impl AllTraits for BrokenFiles {}
 
@@ -17,6 +19,13 @@ impl Search for BrokenFiles {
     #[fun_time(message = "find_broken_files", level = "info")]
     fn search(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) {
         self.prepare_items();
+        // Container structure can be still checked without ffmpeg
+        if self.params.decode_video_frames && self.params.checked_types.contains(CheckedTypes::VIDEO) && !ffmpeg_cmdline_utils::ffmpeg_and_ffprobe_are_callable() {
+            self.common_data.text_messages.errors.push(flc!("core_ffmpeg_not_found"));
+            #[cfg(target_os = "windows")]
+            self.common_data.text_messages.errors.push(flc!("core_ffmpeg_not_found_windows"));
+            self.params.decode_video_frames = false;
+        }
         if self.check_files(stop_flag, progress_sender) == WorkContinueStatus::Stop {
             self.common_data.stopped_search = true;
             return;
@@ -55,7 +64,13 @@ impl PrintResults for BrokenFiles {
         if !self.broken_files.is_empty() {
             writeln!(writer, "Found {} broken files.", self.information.number_of_broken_files)?;
             for file_entry in &self.broken_files {
-                writeln!(writer, "\"{}\" - {}", file_entry.path.to_string_lossy(), file_entry.error_string)?;
+                writeln!(
+                    writer,
+                    "\"{}\" - {:?} - {}",
+                    file_entry.path.to_string_lossy(),
+                    file_entry.severity,
+                    file_entry.error_string
+                )?;
             }
         } else {
             write!(writer, "Not found any broken files.")?;
@@ -99,3 +114,16 @@ impl CommonData for BrokenFiles {
         self.information.number_of_broken_files > 0
     }
 }
+
+impl PersistentResults for BrokenFiles {
+    type Results = Vec<BrokenEntry>;
+
+    fn get_results_to_persist(&self) -> Self::Results {
+        self.broken_files.clone()
+    }
+
+    fn set_persisted_results(&mut self, results: Self::Results) {
+        self.broken_files = retain_unchanged_entries(results, &mut self.common_data.text_messages.warnings);
+        self.information.number_of_broken_files = self.broken_files.len();
+    }
+}
@@ -0,0 +1,434 @@
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::process::Command;
+
+// Number of frames decoded by ffmpeg from the beginning of the first video stream
+const VIDEO_FRAMES_TO_DECODE: u32 = 50;
+// Index atoms bigger than this are not loaded to memory, so chunk offsets are not verified for them
+const MAX_MOOV_SIZE: u64 = 256 * 1024 * 1024;
+const MAX_SEEK_HEAD_SIZE: u64 = 1024 * 1024;
+
+const MKV_EBML_ID: u32 = 0x1A45_DFA3;
+const MKV_SEGMENT_ID: u32 = 0x1853_8067;
+const MKV_SEEK_HEAD_ID: u32 = 0x114D_9B74;
+const MKV_SEEK_ID: u32 = 0x4DBB;
+const MKV_SEEK_POSITION_ID: u32 = 0x53AC;
+
+// Atoms which contain only other atoms and lead to track indexes
+const MP4_CONTAINER_ATOMS: &[&[u8]] = &[b"moov", b"trak", b"mdia", b"minf", b"stbl"];
+const MP4_TOP_LEVEL_ATOMS: &[&[u8]] = &[b"ftyp", b"moov", b"mdat", b"free", b"skip", b"wide", b"pnot"];
+
+#[derive(Debug, PartialEq, Eq)]
+enum ContainerType {
+    Mp4,
+    Matroska,
+    Avi,
+    Unknown,
+}
+
+/// Checks structure of MP4/MOV, Matroska/WebM and AVI containers, without decoding any frames.
+/// Other containers are accepted without checking, because they can be verified only by decoding
+pub(crate) fn check_video_container<R: Read + Seek>(reader: &mut R, file_size: u64) -> Result<(), String> {
+    let header = read_up_to(reader, 0, 12)?;
+
+    match detect_container_type(&header) {
+        ContainerType::Mp4 => check_mp4(reader, file_size),
+        ContainerType::Matroska => check_matroska(reader, file_size),
+        ContainerType::Avi => check_avi(reader, file_size),
+        ContainerType::Unknown => Ok(()),
+    }
+}
+
+/// Decodes first frames of video with ffmpeg, any reported error means that file is broken
+pub(crate) fn decode_video_frames(path: &Path) -> Result<(), String> {
+    let output = Command::new("ffmpeg")
+        .args(["-hide_banner", "-nostdin", "-v", "error", "-i"])
+        .arg(path)
+        .args(["-map", "0:v:0?", "-frames:v", &VIDEO_FRAMES_TO_DECODE.to_string(), "-f", "null", "-"])
+        .output()
+        .map_err(|e| format!("Cannot run ffmpeg - {e}"))?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let errors = stderr.lines().map(str::trim).filter(|line| !line.is_empty()).take(3).collect::<Vec<_>>();
+    if !errors.is_empty() {
+        return Err(format!("Decoding failed - {}", errors.join(", ")));
+    }
+    if !output.status.success() {
+        return Err(format!("Decoding failed - ffmpeg exited with {}", output.status));
+    }
+    Ok(())
+}
+
+fn detect_container_type(header: &[u8]) -> ContainerType {
+    if be_u32(header, 0) == Some(MKV_EBML_ID) {
+        ContainerType::Matroska
+    } else if header.get(0..4) == Some(b"RIFF") && header.get(8..12) == Some(b"AVI ") {
+        ContainerType::Avi
+    } else if header.get(4..8).is_some_and(|atom_type| MP4_TOP_LEVEL_ATOMS.contains(&atom_type)) {
+        ContainerType::Mp4
+    } else {
+        ContainerType::Unknown
+    }
+}
+
+fn be_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)?.try_into().ok().map(u32::from_be_bytes)
+}
+
+fn be_u64(data: &[u8], offset: usize) -> Option<u64> {
+    data.get(offset..offset + 8)?.try_into().ok().map(u64::from_be_bytes)
+}
+
+fn le_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)?.try_into().ok().map(u32::from_le_bytes)
+}
+
+// Reads as much data as possible at given position, so result may be shorter than requested size
+fn read_up_to<R: Read + Seek>(reader: &mut R, position: u64, size: u64) -> Result<Vec<u8>, String> {
+    reader.seek(SeekFrom::Start(position)).map_err(|e| e.to_string())?;
+    let mut data = Vec::new();
+    reader.by_ref().take(size).read_to_end(&mut data).map_err(|e| e.to_string())?;
+    Ok(data)
+}
+
+fn atom_name(atom_type: &[u8]) -> String {
+    String::from_utf8_lossy(atom_type).to_string()
+}
+
+// Returns atom type, header size and full atom size, size 0 means that atom lasts to the end of parent
+fn parse_atom_header(data: &[u8]) -> Option<(&[u8], usize, u64)> {
+    let size = be_u32(data, 0)?;
+    let atom_type = data.get(4..8)?;
+    if size == 1 {
+        return Some((atom_type, 16, be_u64(data, 8)?));
+    }
+    Some((atom_type, 8, size as u64))
+}
+
+fn check_mp4<R: Read + Seek>(reader: &mut R, file_size: u64) -> Result<(), String> {
+    let mut position = 0;
+    let mut moov = None;
+    let mut found_moov = false;
+    let mut found_media_data = false;
+
+    while position < file_size {
+        let header = read_up_to(reader, position, 16)?;
+        let Some((atom_type, header_size, mut atom_size)) = parse_atom_header(&header) else {
+            return Err(format!("File is truncated, atom header at offset {position} is incomplete"));
+        };
+        if atom_size == 0 {
+            atom_size = file_size - position;
+        }
+        if atom_size < header_size as u64 {
+            return Err(format!("Atom \"{}\" at offset {position} has invalid size {atom_size}", atom_name(atom_type)));
+        }
+        if position.saturating_add(atom_size) > file_size {
+            return Err(format!(
+                "File is truncated, atom \"{}\" at offset {position} needs {atom_size} bytes, but only {} are available",
+                atom_name(atom_type),
+                file_size - position
+            ));
+        }
+
+        match atom_type {
+            b"moov" => {
+                found_moov = true;
+                if atom_size <= MAX_MOOV_SIZE {
+                    moov = Some(read_up_to(reader, position + header_size as u64, atom_size - header_size as u64)?);
+                }
+            }
+            // Fragmented files keep media data in multiple mdat atoms placed after moof atoms
+            b"mdat" => found_media_data = true,
+            _ => {}
+        }
+        position += atom_size;
+    }
+
+    if !found_moov {
+        return Err("Missing moov atom, file has no index".to_string());
+    }
+    if !found_media_data {
+        return Err("Missing mdat atom, file contains no media data".to_string());
+    }
+    if let Some(moov) = moov {
+        check_mp4_chunk_offsets(&moov, file_size)?;
+    }
+    Ok(())
+}
+
+// Walks through track indexes and verifies that every chunk offset points inside file
+fn check_mp4_chunk_offsets(data: &[u8], file_size: u64) -> Result<(), String> {
+    let mut position = 0;
+    while position < data.len() {
+        let atom = data.get(position..).unwrap_or_default();
+        let Some((atom_type, header_size, mut atom_size)) = parse_atom_header(atom) else {
+            return Err("Index is broken, atom header inside moov is incomplete".to_string());
+        };
+        if atom_size == 0 {
+            atom_size = atom.len() as u64;
+        }
+        let Some(content) = atom.get(header_size..atom_size as usize) else {
+            return Err(format!("Index is broken, atom \"{}\" does not fit inside its parent", atom_name(atom_type)));
+        };
+
+        if MP4_CONTAINER_ATOMS.contains(&atom_type) {
+            check_mp4_chunk_offsets(content, file_size)?;
+        } else if atom_type == b"stco" || atom_type == b"co64" {
+            let Some(entries) = be_u32(content, 4) else {
+                return Err(format!("Index is broken, \"{}\" atom is too short", atom_name(atom_type)));
+            };
+            for idx in 0..entries as usize {
+                let offset = if atom_type == b"stco" {
+                    be_u32(content, 8 + idx * 4).map(u64::from)
+                } else {
+                    be_u64(content, 8 + idx * 8)
+                };
+                let Some(offset) = offset else {
+                    return Err(format!("Index is broken, \"{}\" atom declares {entries} chunks, but contains less", atom_name(atom_type)));
+                };
+                if offset >= file_size {
+                    return Err(format!("Index is broken, chunk offset {offset} points outside of file with size {file_size}"));
+                }
+            }
+        }
+        position += atom_size as usize;
+    }
+    Ok(())
+}
+
+// Reads EBML variable size integer, returns its value and length, IDs keep length marker bits
+fn parse_vint(data: &[u8], keep_marker: bool) -> Option<(u64, usize)> {
+    let first = *data.first()?;
+    if first == 0 {
+        return None;
+    }
+    let length = first.leading_zeros() as usize + 1;
+    let first_value = if keep_marker { first as u64 } else { first as u64 & (0xFF >> length) };
+    let value = data.get(1..length)?.iter().fold(first_value, |value, byte| (value << 8) | *byte as u64);
+    Some((value, length))
+}
+
+// Returns element ID, header size and content size, None as content size means unknown size
+fn parse_ebml_element_header(data: &[u8]) -> Option<(u32, u64, Option<u64>)> {
+    let (id, id_length) = parse_vint(data, true)?;
+    if id_length > 4 {
+        return None;
+    }
+    let (size, size_length) = parse_vint(data.get(id_length..)?, false)?;
+    let unknown_size = size == (1 << (7 * size_length)) - 1;
+    Some((id as u32, (id_length + size_length) as u64, if unknown_size { None } else { Some(size) }))
+}
+
+fn read_ebml_element_header<R: Read + Seek>(reader: &mut R, position: u64) -> Result<(u32, u64, Option<u64>), String> {
+    let header = read_up_to(reader, position, 12)?;
+    parse_ebml_element_header(&header).ok_or_else(|| format!("File is truncated or broken, invalid EBML element at offset {position}"))
+}
+
+fn check_matroska<R: Read + Seek>(reader: &mut R, file_size: u64) -> Result<(), String> {
+    let (_, ebml_header_size, ebml_size) = read_ebml_element_header(reader, 0)?;
+    let Some(ebml_size) = ebml_size else {
+        return Err("EBML header has unknown size".to_string());
+    };
+    let segment_position = ebml_header_size + ebml_size;
+    if segment_position >= file_size {
+        return Err("File is truncated, segment is missing".to_string());
+    }
+
+    let (segment_id, segment_header_size, segment_size) = read_ebml_element_header(reader, segment_position)?;
+    if segment_id != MKV_SEGMENT_ID {
+        return Err(format!("Expected segment at offset {segment_position}, found element 0x{segment_id:X}"));
+    }
+    let segment_start = segment_position + segment_header_size;
+    let segment_end = match segment_size {
+        Some(size) if segment_start + size > file_size => {
+            return Err(format!(
+                "File is truncated, segment needs {size} bytes, but only {} are available",
+                file_size.saturating_sub(segment_start)
+            ));
+        }
+        Some(size) => segment_start + size,
+        None => file_size,
+    };
+
+    let mut seek_positions = Vec::new();
+    let mut position = segment_start;
+    while position < segment_end {
+        let (id, header_size, size) = read_ebml_element_header(reader, position)?;
+        // Elements with unknown size are used by live streams, their end can be found only by parsing all children
+        let Some(size) = size else {
+            break;
+        };
+        let element_end = position + header_size + size;
+        if element_end > segment_end {
+            return Err(format!(
+                "File is truncated, element 0x{id:X} at offset {position} needs {size} bytes, but only {} are available",
+                segment_end.saturating_sub(position + header_size)
+            ));
+        }
+        if id == MKV_SEEK_HEAD_ID && size <= MAX_SEEK_HEAD_SIZE {
+            let seek_head = read_up_to(reader, position + header_size, size)?;
+            seek_positions.extend(collect_seek_positions(&seek_head)?);
+        }
+        position = element_end;
+    }
+
+    for seek_position in seek_positions {
+        if segment_start.saturating_add(seek_position) >= segment_end {
+            return Err(format!("Index is broken, seek entry points to offset {seek_position} outside of segment"));
+        }
+    }
+    Ok(())
+}
+
+fn collect_seek_positions(seek_head: &[u8]) -> Result<Vec<u64>, String> {
+    let mut positions = Vec::new();
+    for (id, content) in parse_ebml_children(seek_head)? {
+        if id != MKV_SEEK_ID {
+            continue;
+        }
+        for (child_id, child_content) in parse_ebml_children(content)? {
+            if child_id == MKV_SEEK_POSITION_ID && !child_content.is_empty() && child_content.len() <= 8 {
+                positions.push(child_content.iter().fold(0u64, |value, byte| (value << 8) | *byte as u64));
+            }
+        }
+    }
+    Ok(positions)
+}
+
+fn parse_ebml_children(data: &[u8]) -> Result<Vec<(u32, &[u8])>, String> {
+    let mut children = Vec::new();
+    let mut position = 0;
+    while position < data.len() {
+        let Some((id, header_size, Some(size))) = parse_ebml_element_header(data.get(position..).unwrap_or_default()) else {
+            return Err("Index is broken, invalid element inside seek head".to_string());
+        };
+        let content_start = position + header_size as usize;
+        let content_end = content_start + size as usize;
+        let Some(content) = data.get(content_start..content_end) else {
+            return Err("Index is broken, element does not fit inside seek head".to_string());
+        };
+        children.push((id, content));
+        position = content_end;
+    }
+    Ok(children)
+}
+
+fn check_avi<R: Read + Seek>(reader: &mut R, file_size: u64) -> Result<(), String> {
+    let header = read_up_to(reader, 0, 8)?;
+    let riff_size = le_u32(&header, 4).unwrap_or_default() as u64;
+    let riff_end = 8 + riff_size;
+    if riff_end > file_size {
+        return Err(format!("File is truncated, RIFF chunk needs {riff_size} bytes, but only {} are available", file_size - 8));
+    }
+
+    // Skips form type
+    let mut position = 12;
+    let mut found_movi = false;
+    while position + 8 <= riff_end {
+        let chunk_header = read_up_to(reader, position, 12)?;
+        let (Some(chunk_id), Some(chunk_size)) = (chunk_header.get(0..4), le_u32(&chunk_header, 4)) else {
+            break;
+        };
+        let chunk_size = chunk_size as u64;
+        if position + 8 + chunk_size > riff_end {
+            return Err(format!("File is truncated, chunk \"{}\" at offset {position} does not fit in file", atom_name(chunk_id)));
+        }
+        if chunk_id == b"LIST" && chunk_header.get(8..12) == Some(b"movi") {
+            found_movi = true;
+        }
+        // Chunks are padded to even size
+        position += 8 + chunk_size + chunk_size % 2;
+    }
+
+    if !found_movi {
+        return Err("Missing movi list, file contains no media data".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn atom(atom_type: [u8; 4], content: &[u8]) -> Vec<u8> {
+        let mut data = ((content.len() + 8) as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(&atom_type);
+        data.extend_from_slice(content);
+        data
+    }
+
+    fn mp4_with_chunk_offset(chunk_offset: u32) -> Vec<u8> {
+        let mut stco_content = vec![0, 0, 0, 0, 0, 0, 0, 1];
+        stco_content.extend_from_slice(&chunk_offset.to_be_bytes());
+        let stbl = atom(*b"stbl", &atom(*b"stco", &stco_content));
+        let moov = atom(*b"moov", &atom(*b"trak", &atom(*b"mdia", &atom(*b"minf", &stbl))));
+
+        let mut data = atom(*b"ftyp", b"isom\0\0\0\0");
+        data.extend(moov);
+        data.extend(atom(*b"mdat", &[0; 32]));
+        data
+    }
+
+    fn check(data: &[u8]) -> Result<(), String> {
+        check_video_container(&mut Cursor::new(data), data.len() as u64)
+    }
+
+    #[test]
+    fn test_mp4_structure() {
+        let data = mp4_with_chunk_offset(100);
+        assert_eq!(check(&data), Ok(()));
+
+        // Truncated mdat
+        let mut truncated = data.clone();
+        truncated.truncate(data.len() - 10);
+        assert!(check(&truncated).expect_err("Truncated file").contains("truncated"));
+
+        let data = mp4_with_chunk_offset(10_000);
+        assert!(check(&data).expect_err("Broken index").contains("chunk offset"));
+
+        let mut data = atom(*b"ftyp", b"isom\0\0\0\0");
+        data.extend(atom(*b"mdat", &[0; 32]));
+        assert!(check(&data).expect_err("Missing index").contains("moov"));
+    }
+
+    #[test]
+    fn test_matroska_structure() {
+        // EBML header with empty content, segment with single void element of 4 bytes
+        let mut data = vec![0x1A, 0x45, 0xDF, 0xA3, 0x80];
+        data.extend_from_slice(&[0x18, 0x53, 0x80, 0x67, 0x86, 0xEC, 0x84, 0, 0, 0, 0]);
+        assert_eq!(check(&data), Ok(()));
+
+        let mut truncated = data.clone();
+        truncated.truncate(data.len() - 2);
+        assert!(check(&truncated).expect_err("Truncated file").contains("truncated"));
+
+        // Size written on all 8 bytes
+        assert_eq!(parse_vint(&[0x01, 0, 0, 0, 0, 0, 0, 0x05], false), Some((5, 8)));
+
+        // Segment with unknown size cannot be checked for truncation at the end
+        let mut data = vec![0x1A, 0x45, 0xDF, 0xA3, 0x80];
+        data.extend_from_slice(&[0x18, 0x53, 0x80, 0x67, 0xFF, 0xEC, 0x84, 0, 0, 0, 0]);
+        assert_eq!(check(&data), Ok(()));
+    }
+
+    #[test]
+    fn test_seek_head_outside_of_segment() {
+        // Seek head with single entry pointing to position 0x40
+        let seek = [0x4D, 0xBB, 0x84, 0x53, 0xAC, 0x81, 0x40];
+        let mut seek_head = vec![0x11, 0x4D, 0x9B, 0x74, 0x80 | seek.len() as u8];
+        seek_head.extend_from_slice(&seek);
+
+        let mut data = vec![0x1A, 0x45, 0xDF, 0xA3, 0x80];
+        data.extend_from_slice(&[0x18, 0x53, 0x80, 0x67, 0x80 | seek_head.len() as u8]);
+        data.extend(seek_head);
+        assert!(check(&data).expect_err("Broken index").contains("seek entry"));
+    }
+
+    #[test]
+    fn test_unknown_container_is_accepted() {
+        assert_eq!(check(b"not a video file"), Ok(()));
+        assert_eq!(detect_container_type(b"RIFF\0\0\0\0AVI "), ContainerType::Avi);
+    }
+}
@@ -0,0 +1,143 @@
+use std::io::{self, Read, Seek};
+
+use zip::ZipArchive;
+use zip::result::ZipError;
+
+// Extension, entries which must be present in archive and expected content of `mimetype` entry
+// JAR files are checked as normal zip archives, because their manifest is optional
+const ZIP_DOCUMENT_REQUIRED_ENTRIES: &[(&str, &[&str], Option<&str>)] = &[
+    ("docx", &["[Content_Types].xml", "_rels/.rels"], None),
+    ("xlsx", &["[Content_Types].xml", "_rels/.rels"], None),
+    ("pptx", &["[Content_Types].xml", "_rels/.rels"], None),
+    ("odt", &["mimetype", "META-INF/manifest.xml"], Some("application/vnd.oasis.opendocument.text")),
+    ("ods", &["mimetype", "META-INF/manifest.xml"], Some("application/vnd.oasis.opendocument.spreadsheet")),
+    ("odp", &["mimetype", "META-INF/manifest.xml"], Some("application/vnd.oasis.opendocument.presentation")),
+    ("epub", &["mimetype", "META-INF/container.xml"], Some("application/epub+zip")),
+    ("apk", &["AndroidManifest.xml"], None),
+];
+
+pub(crate) fn check_zip<R: Read + Seek>(reader: R, verify_content: bool) -> Result<(), String> {
+    let mut archive = ZipArchive::new(reader).map_err(|e| e.to_string())?;
+    if verify_content {
+        verify_zip_entries(&mut archive)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn check_zip_document<R: Read + Seek>(reader: R, extension: &str, verify_content: bool) -> Result<(), String> {
+    let mut archive = ZipArchive::new(reader).map_err(|e| e.to_string())?;
+
+    let extension = extension.to_ascii_lowercase();
+    if let Some((_, required_entries, mimetype)) = ZIP_DOCUMENT_REQUIRED_ENTRIES.iter().find(|(ext, _, _)| *ext == extension) {
+        for required_entry in *required_entries {
+            if archive.index_for_name(required_entry).is_none() {
+                return Err(format!("Document is missing mandatory entry \"{required_entry}\""));
+            }
+        }
+        if let Some(mimetype) = mimetype {
+            check_mimetype(&mut archive, mimetype)?;
+        }
+    }
+
+    if verify_content {
+        verify_zip_entries(&mut archive)?;
+    }
+    Ok(())
+}
+
+fn check_mimetype<R: Read + Seek>(archive: &mut ZipArchive<R>, expected_mimetype: &str) -> Result<(), String> {
+    let mut mimetype = String::new();
+    archive
+        .by_name("mimetype")
+        .and_then(|mut entry| entry.read_to_string(&mut mimetype).map_err(ZipError::from))
+        .map_err(|e| format!("Failed to read \"mimetype\" entry - {e}"))?;
+    if mimetype.trim_end() != expected_mimetype {
+        return Err(format!("Document has invalid mimetype \"{mimetype}\", expected \"{expected_mimetype}\""));
+    }
+    Ok(())
+}
+
+// Zip crate verifies CRC of entry only after reading it to the end
+fn verify_zip_entries<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Result<(), String> {
+    for idx in 0..archive.len() {
+        let mut entry = match archive.by_index(idx) {
+            Ok(entry) => entry,
+            // Encrypted entries and not supported compression methods cannot be verified
+            Err(ZipError::UnsupportedArchive(_)) => continue,
+            Err(e) => return Err(e.to_string()),
+        };
+        if let Err(e) = io::copy(&mut entry, &mut io::sink()) {
+            return Err(format!("Entry \"{}\" is corrupted - {e}", entry.name()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use zip::ZipWriter;
+    use zip::write::SimpleFileOptions;
+
+    use super::*;
+
+    const CONTENT: &[u8] = b"Content of file, which will be corrupted later";
+
+    fn create_zip(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        for (name, content) in entries {
+            writer.start_file(*name, options).expect("Failed to start zip entry");
+            writer.write_all(content).expect("Failed to write zip entry");
+        }
+        writer.finish().expect("Failed to finish zip").into_inner()
+    }
+
+    fn corrupt_content(data: &mut [u8]) {
+        let idx = data.windows(CONTENT.len()).position(|window| window == CONTENT).expect("Content not found");
+        if let Some(byte) = data.get_mut(idx + 5) {
+            *byte ^= 0xFF;
+        }
+    }
+
+    #[test]
+    fn test_check_zip() {
+        let mut data = create_zip(&[("file.txt", CONTENT)]);
+        check_zip(Cursor::new(&data), false).unwrap();
+        check_zip(Cursor::new(&data), true).unwrap();
+
+        // Only reading content of entry finds invalid CRC
+        corrupt_content(&mut data);
+        check_zip(Cursor::new(&data), false).unwrap();
+        assert!(check_zip(Cursor::new(&data), true).is_err());
+
+        assert!(check_zip(Cursor::new(b"Not a zip file"), false).is_err());
+    }
+
+    #[test]
+    fn test_check_zip_document() {
+        let data = create_zip(&[("[Content_Types].xml", b"<Types/>"), ("_rels/.rels", b"<Relationships/>"), ("word/document.xml", CONTENT)]);
+        check_zip_document(Cursor::new(&data), "docx", true).unwrap();
+        check_zip_document(Cursor::new(&data), "DOCX", true).unwrap();
+        assert!(check_zip_document(Cursor::new(&data), "epub", true).is_err());
+
+        let mut corrupted = data;
+        corrupt_content(&mut corrupted);
+        check_zip_document(Cursor::new(&corrupted), "docx", false).unwrap();
+        assert!(check_zip_document(Cursor::new(&corrupted), "docx", true).is_err());
+
+        let data = create_zip(&[("word/document.xml", CONTENT)]);
+        assert!(check_zip_document(Cursor::new(&data), "docx", false).is_err());
+    }
+
+    #[test]
+    fn test_check_zip_document_mimetype() {
+        let data = create_zip(&[("mimetype", b"application/epub+zip"), ("META-INF/container.xml", b"<container/>")]);
+        check_zip_document(Cursor::new(&data), "epub", false).unwrap();
+        assert!(check_zip_document(Cursor::new(&data), "odt", false).is_err());
+
+        let data = create_zip(&[("mimetype", b"application/zip"), ("META-INF/container.xml", b"<container/>")]);
+        assert!(check_zip_document(Cursor::new(&data), "epub", false).is_err());
+    }
+}
@@ -1,8 +1,9 @@
-use std::collections::BTreeMap;
-use std::path::Path;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
-use std::{mem, thread};
+use std::{fs, mem, thread};
 
 use crossbeam_channel::Sender;
 use fun_time::fun_time;
@@ -10,6 +11,7 @@ use humansize::{BINARY, format_size};
 use indexmap::IndexMap;
 use log::debug;
 use rayon::prelude::*;
+use xxhash_rust::xxh3::Xxh3;
 
 use crate::common::cache::{CACHE_DUPLICATE_VERSION, load_cache_from_file_generalized_by_size, save_cache_to_file_generalized};
 use crate::common::dir_traversal::{DirTraversalBuilder, DirTraversalResult};
@@ -19,7 +21,8 @@ use crate::common::progress_stop_handler::{check_if_stop_received, prepare_threa
 use crate::common::tool_data::{CommonData, CommonToolData};
 use crate::common::traits::ResultEntry;
 use crate::tools::duplicate::{
-    DuplicateEntry, DuplicateFinder, DuplicateFinderParameters, Info, PREHASHING_BUFFER_SIZE, THREAD_BUFFER, filter_hard_links, hash_calculation, hash_calculation_limit,
+    DirectoryEntry, DuplicateEntry, DuplicateFinder, DuplicateFinderParameters, Info, PREHASHING_BUFFER_SIZE, SimilarDirectories, THREAD_BUFFER, filter_hard_links,
+    get_sampled_hash_size, hash_calculation, hash_calculation_limit, sampled_hash_calculation, split_group_by_content,
 };
 
 impl DuplicateFinder {
@@ -35,6 +38,11 @@ impl DuplicateFinder {
             files_with_identical_size_names_referenced: Default::default(),
             files_with_identical_size_referenced: Default::default(),
             files_with_identical_hashes_referenced: Default::default(),
+            directories_files_number: Default::default(),
+            duplicated_directories: Default::default(),
+            directories_contained_in_other: Default::default(),
+            similar_directories: Default::default(),
+            groups_verified_by_content: false,
             params,
         }
     }
@@ -73,7 +81,7 @@ impl DuplicateFinder {
                 self.common_data.text_messages.warnings.extend(warnings);
 
                 // Create new BTreeMap without single size entries(files have not duplicates)
-                self.files_with_identical_names = grouped_file_entries
+                let files_with_identical_names: BTreeMap<String, Vec<DuplicateEntry>> = grouped_file_entries
                     .into_iter()
                     .filter_map(|(name, vector)| {
                         if vector.len() > 1 {
@@ -86,7 +94,7 @@ impl DuplicateFinder {
 
                 // Reference - only use in size, because later hash will be counted differently
                 if self.common_data.use_reference_folders {
-                    let vec = mem::take(&mut self.files_with_identical_names)
+                    let vec = files_with_identical_names
                         .into_iter()
                         .filter_map(|(_name, vec_file_entry)| {
                             let (mut files_from_referenced_folders, normal_files): (Vec<_>, Vec<_>) = vec_file_entry
@@ -103,6 +111,9 @@ impl DuplicateFinder {
                     for (fe, vec_fe) in vec {
                         self.files_with_identical_names_referenced.insert(fe.path.to_string_lossy().to_string(), (fe, vec_fe));
                     }
+                    self.remove_ignored_pairs_with_reference_files(CheckingMethod::Name);
+                } else {
+                    self.files_with_identical_names = self.split_groups_by_ignored_pairs(files_with_identical_names);
                 }
                 self.calculate_name_stats();
 
@@ -119,7 +130,7 @@ impl DuplicateFinder {
                 self.information.number_of_groups_by_name += 1;
             }
         } else {
-            for vector in self.files_with_identical_names.values() {
+            for vector in self.files_with_identical_names.values().flatten() {
                 self.information.number_of_duplicated_files_by_name += vector.len() - 1;
                 self.information.number_of_groups_by_name += 1;
             }
@@ -165,7 +176,7 @@ impl DuplicateFinder {
             DirTraversalResult::SuccessFiles { grouped_file_entries, warnings } => {
                 self.common_data.text_messages.warnings.extend(warnings);
 
-                self.files_with_identical_size_names = grouped_file_entries
+                let files_with_identical_size_names: BTreeMap<(u64, String), Vec<DuplicateEntry>> = grouped_file_entries
                     .into_iter()
                     .filter_map(|(size_name, vector)| {
                         if vector.len() > 1 {
@@ -178,7 +189,7 @@ impl DuplicateFinder {
 
                 // Reference - only use in size, because later hash will be counted differently
                 if self.common_data.use_reference_folders {
-                    let vec = mem::take(&mut self.files_with_identical_size_names)
+                    let vec = files_with_identical_size_names
                         .into_iter()
                         .filter_map(|(_size, vec_file_entry)| {
                             let (mut files_from_referenced_folders, normal_files): (Vec<_>, Vec<_>) = vec_file_entry
@@ -196,6 +207,9 @@ impl DuplicateFinder {
                         self.files_with_identical_size_names_referenced
                             .insert((fe.size, fe.path.to_string_lossy().to_string()), (fe, vec_fe));
                     }
+                    self.remove_ignored_pairs_with_reference_files(CheckingMethod::SizeName);
+                } else {
+                    self.files_with_identical_size_names = self.split_groups_by_ignored_pairs(files_with_identical_size_names);
                 }
                 self.calculate_size_name_stats();
 
@@ -214,10 +228,12 @@ impl DuplicateFinder {
                 self.information.lost_space_by_size += (vector.len() as u64) * size;
             }
         } else {
-            for ((size, _name), vector) in &self.files_with_identical_size_names {
-                self.information.number_of_duplicated_files_by_size_name += vector.len() - 1;
-                self.information.number_of_groups_by_size_name += 1;
-                self.information.lost_space_by_size += (vector.len() as u64 - 1) * size;
+            for ((size, _name), vector_vectors) in &self.files_with_identical_size_names {
+                for vector in vector_vectors {
+                    self.information.number_of_duplicated_files_by_size_name += vector.len() - 1;
+                    self.information.number_of_groups_by_size_name += 1;
+                    self.information.lost_space_by_size += (vector.len() as u64 - 1) * size;
+                }
             }
         }
     }
@@ -230,6 +246,7 @@ impl DuplicateFinder {
             .stop_flag(stop_flag)
             .progress_sender(progress_sender)
             .checking_method(self.get_params().check_method)
+            .has_optional_stage(self.has_optional_stage())
             .build()
             .run();
 
@@ -238,8 +255,11 @@ impl DuplicateFinder {
                 self.common_data.text_messages.warnings.extend(warnings);
 
                 let grouped_file_entries: Vec<(u64, Vec<FileEntry>)> = grouped_file_entries.into_iter().collect();
+                if self.should_analyze_directories() {
+                    self.count_files_in_directories(&grouped_file_entries);
+                }
                 let rayon_max_len = if self.get_params().ignore_hard_links { 3 } else { 100 };
-                self.files_with_identical_size = grouped_file_entries
+                let files_with_identical_size: BTreeMap<u64, Vec<DuplicateEntry>> = grouped_file_entries
                     .into_par_iter()
                     .with_max_len(rayon_max_len)
                     .filter_map(|(size, vec)| {
@@ -257,13 +277,28 @@ impl DuplicateFinder {
                     })
                     .collect();
 
-                self.filter_reference_folders_by_size();
+                if self.get_params().check_method == CheckingMethod::Size {
+                    if self.common_data.use_reference_folders {
+                        self.filter_reference_folders_by_size(files_with_identical_size);
+                    } else {
+                        self.files_with_identical_size = self.split_groups_by_ignored_pairs(files_with_identical_size);
+                    }
+                } else {
+                    // Files from ignored group cannot be in any hash group, so they don't need to be hashed
+                    // Other groups are split by dismissed pairs only after hashing, because files may be grouped differently by hash
+                    let ignored_groups_filter = self.common_data.get_ignored_groups_filter();
+                    self.files_with_identical_size = files_with_identical_size
+                        .into_iter()
+                        .filter(|(_size, vector)| !ignored_groups_filter.is_group_ignored(vector))
+                        .map(|(size, vector)| (size, vec![vector]))
+                        .collect();
+                }
                 self.calculate_size_stats();
 
                 debug!(
                     "check_file_size - after calculating size stats/duplicates, found in {} groups, {} files with same size | referenced {} groups, {} files",
-                    self.files_with_identical_size.len(),
                     self.files_with_identical_size.values().map(Vec::len).sum::<usize>(),
+                    self.files_with_identical_size.values().flatten().map(Vec::len).sum::<usize>(),
                     self.files_with_identical_size_referenced.len(),
                     self.files_with_identical_size_referenced.values().map(|(_fe, vec)| vec.len()).sum::<usize>()
                 );
@@ -275,6 +310,26 @@ impl DuplicateFinder {
         }
     }
 
+    fn should_analyze_directories(&self) -> bool {
+        (self.get_params().find_duplicate_directories || self.get_params().minimal_directories_similarity.is_some())
+            && matches!(self.get_params().check_method, CheckingMethod::Hash | CheckingMethod::SampledHash)
+    }
+
+    // Directory can be duplicate of another only when all files inside it have duplicates, so number of all files in it must be known
+    fn count_files_in_directories(&mut self, grouped_file_entries: &[(u64, Vec<FileEntry>)]) {
+        let included_directories = &self.common_data.directories.included_directories;
+        for file_entry in grouped_file_entries.iter().flat_map(|(_size, vec)| vec) {
+            for directory in file_entry.path.ancestors().skip(1) {
+                if !included_directories.iter().any(|included| directory.starts_with(included)) {
+                    break;
+                }
+                let (files_number, size) = self.directories_files_number.entry(directory.to_path_buf()).or_default();
+                *files_number += 1;
+                *size += file_entry.size;
+            }
+        }
+    }
+
     fn calculate_size_stats(&mut self) {
         if self.common_data.use_reference_folders {
             for (size, (_fe, vector)) in &self.files_with_identical_size_referenced {
@@ -283,18 +338,20 @@ impl DuplicateFinder {
                 self.information.lost_space_by_size += (vector.len() as u64) * size;
             }
         } else {
-            for (size, vector) in &self.files_with_identical_size {
-                self.information.number_of_duplicated_files_by_size += vector.len() - 1;
-                self.information.number_of_groups_by_size += 1;
-                self.information.lost_space_by_size += (vector.len() as u64 - 1) * size;
+            for (size, vector_vectors) in &self.files_with_identical_size {
+                for vector in vector_vectors {
+                    self.information.number_of_duplicated_files_by_size += vector.len() - 1;
+                    self.information.number_of_groups_by_size += 1;
+                    self.information.lost_space_by_size += (vector.len() as u64 - 1) * size;
+                }
             }
         }
     }
 
     #[fun_time(message = "filter_reference_folders_by_size", level = "debug")]
-    fn filter_reference_folders_by_size(&mut self) {
+    fn filter_reference_folders_by_size(&mut self, files_with_identical_size: BTreeMap<u64, Vec<DuplicateEntry>>) {
         if self.common_data.use_reference_folders && self.get_params().check_method == CheckingMethod::Size {
-            let vec = mem::take(&mut self.files_with_identical_size)
+            let vec = files_with_identical_size
                 .into_iter()
                 .filter_map(|(_size, vec_file_entry)| {
                     let (mut files_from_referenced_folders, normal_files): (Vec<_>, Vec<_>) = vec_file_entry
@@ -311,6 +368,41 @@ impl DuplicateFinder {
             for (fe, vec_fe) in vec {
                 self.files_with_identical_size_referenced.insert(fe.size, (fe, vec_fe));
             }
+            self.remove_ignored_pairs_with_reference_files(CheckingMethod::Size);
+        }
+    }
+
+    // Dismissed pairs of files cannot be in same group, so groups are split by them
+    fn split_groups_by_ignored_pairs<K: Ord>(&self, groups: BTreeMap<K, Vec<DuplicateEntry>>) -> BTreeMap<K, Vec<Vec<DuplicateEntry>>> {
+        let ignored_groups_filter = self.common_data.get_ignored_groups_filter();
+        groups
+            .into_iter()
+            .filter_map(|(key, vector)| {
+                let vectors = ignored_groups_filter.split_group(vector);
+                (!vectors.is_empty()).then_some((key, vectors))
+            })
+            .collect()
+    }
+
+    // With reference folders, files are compared only with reference file, so only files dismissed together with it are removed
+    fn remove_ignored_pairs_with_reference_files(&mut self, check_method: CheckingMethod) {
+        let ignored_groups_filter = self.common_data.get_ignored_groups_filter();
+        if ignored_groups_filter.is_empty() {
+            return;
+        }
+        let retain_not_ignored = |(reference, vector): &mut (DuplicateEntry, Vec<DuplicateEntry>)| {
+            vector.retain(|e| !ignored_groups_filter.is_pair_ignored(&reference.path, &e.path));
+            !vector.is_empty()
+        };
+        match check_method {
+            CheckingMethod::Name => self.files_with_identical_names_referenced.retain(|_name, group| retain_not_ignored(group)),
+            CheckingMethod::SizeName => self.files_with_identical_size_names_referenced.retain(|_size_name, group| retain_not_ignored(group)),
+            CheckingMethod::Size => self.files_with_identical_size_referenced.retain(|_size, group| retain_not_ignored(group)),
+            CheckingMethod::Hash | CheckingMethod::SampledHash => self.files_with_identical_hashes_referenced.retain(|_size, groups| {
+                groups.retain_mut(|group| retain_not_ignored(group));
+                !groups.is_empty()
+            }),
+            CheckingMethod::None | CheckingMethod::AudioTags | CheckingMethod::AudioContent => {}
         }
     }
 
@@ -324,25 +416,29 @@ impl DuplicateFinder {
         let mut records_already_cached: BTreeMap<u64, Vec<DuplicateEntry>> = Default::default();
         let mut non_cached_files_to_check: BTreeMap<u64, Vec<DuplicateEntry>> = Default::default();
 
+        let files_with_identical_size: BTreeMap<u64, Vec<DuplicateEntry>> = mem::take(&mut self.files_with_identical_size)
+            .into_iter()
+            .map(|(size, vectors)| (size, vectors.into_iter().flatten().collect()))
+            .collect();
         if self.get_params().use_prehash_cache {
             let (messages, loaded_items) = load_cache_from_file_generalized_by_size::<DuplicateEntry>(
                 &get_duplicate_cache_file(&self.get_params().hash_type, true),
                 self.get_delete_outdated_cache(),
-                &self.files_with_identical_size,
+                &files_with_identical_size,
             );
             self.get_text_messages_mut().extend_with_another_messages(messages);
             loaded_hash_map = loaded_items.unwrap_or_default();
 
             Self::diff_loaded_and_prechecked_files(
                 "prehash_load_cache_at_start",
-                mem::take(&mut self.files_with_identical_size),
+                files_with_identical_size,
                 &loaded_hash_map,
                 &mut records_already_cached,
                 &mut non_cached_files_to_check,
             );
         } else {
             loaded_hash_map = Default::default();
-            mem::swap(&mut self.files_with_identical_size, &mut non_cached_files_to_check);
+            non_cached_files_to_check = files_with_identical_size;
         }
         (loaded_hash_map, records_already_cached, non_cached_files_to_check)
     }
@@ -533,11 +629,8 @@ impl DuplicateFinder {
 
         if self.common_data.use_cache {
             debug!("full_hashing_load_cache_at_start - using cache");
-            let (messages, loaded_items) = load_cache_from_file_generalized_by_size::<DuplicateEntry>(
-                &get_duplicate_cache_file(&self.get_params().hash_type, false),
-                self.get_delete_outdated_cache(),
-                &pre_checked_map,
-            );
+            let (messages, loaded_items) =
+                load_cache_from_file_generalized_by_size::<DuplicateEntry>(&self.get_hash_cache_file(), self.get_delete_outdated_cache(), &pre_checked_map);
             self.get_text_messages_mut().extend_with_another_messages(messages);
             loaded_hash_map = loaded_items.unwrap_or_default();
 
@@ -600,7 +693,7 @@ impl DuplicateFinder {
         }
 
         let messages = save_cache_to_file_generalized(
-            &get_duplicate_cache_file(&self.get_params().hash_type, false),
+            &self.get_hash_cache_file(),
             &all_results,
             self.common_data.save_also_as_json,
             self.get_params().minimal_cache_file_size,
@@ -608,6 +701,14 @@ impl DuplicateFinder {
         self.get_text_messages_mut().extend_with_another_messages(messages);
     }
 
+    fn get_hash_cache_file(&self) -> String {
+        if self.get_params().check_method == CheckingMethod::SampledHash {
+            get_duplicate_sampled_cache_file(&self.get_params().hash_type)
+        } else {
+            get_duplicate_cache_file(&self.get_params().hash_type, false)
+        }
+    }
+
     #[fun_time(message = "full_hashing", level = "debug")]
     fn full_hashing(
         &mut self,
@@ -628,12 +729,28 @@ impl DuplicateFinder {
             return WorkContinueStatus::Stop;
         }
 
+        // Sampled hashing uses same stage and cache logic, only amount of data read from each file is different
+        let sampled = self.get_params().check_method == CheckingMethod::SampledHash;
+        let (hashing_stage, bytes_to_check) = if sampled {
+            (
+                CurrentStage::DuplicateSampledHashing,
+                non_cached_files_to_check
+                    .iter()
+                    .map(|(size, items)| get_sampled_hash_size(*size) * items.len() as u64)
+                    .sum::<u64>(),
+            )
+        } else {
+            (
+                CurrentStage::DuplicateFullHashing,
+                non_cached_files_to_check.iter().map(|(size, items)| (*size) * items.len() as u64).sum::<u64>(),
+            )
+        };
         let progress_handler = prepare_thread_handler_common(
             progress_sender,
-            CurrentStage::DuplicateFullHashing,
+            hashing_stage,
             non_cached_files_to_check.values().map(Vec::len).sum(),
             self.get_test_type(),
-            non_cached_files_to_check.iter().map(|(size, items)| (*size) * items.len() as u64).sum::<u64>(),
+            bytes_to_check,
         );
 
         let non_cached_files_to_check: Vec<(u64, Vec<DuplicateEntry>)> = non_cached_files_to_check.into_iter().collect();
@@ -656,7 +773,12 @@ impl DuplicateFinder {
                             return None;
                         }
 
-                        match hash_calculation(buffer, &file_entry, check_type, progress_handler.size_counter(), stop_flag) {
+                        let hash_result = if sampled {
+                            sampled_hash_calculation(buffer, &file_entry, check_type, progress_handler.size_counter()).map(Some)
+                        } else {
+                            hash_calculation(buffer, &file_entry, check_type, progress_handler.size_counter(), stop_flag)
+                        };
+                        match hash_result {
                             Ok(hash_string) => {
                                 if let Some(hash_string) = hash_string {
                                     file_entry.hash = hash_string.clone();
@@ -699,6 +821,61 @@ impl DuplicateFinder {
         WorkContinueStatus::Continue
     }
 
+    // Hash may have collisions(especially crc32 and xxh3), so before deleting files, content can be compared byte by byte
+    #[fun_time(message = "verify_content", level = "debug")]
+    fn verify_content(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
+        if self.files_with_identical_hashes.is_empty() {
+            return WorkContinueStatus::Continue;
+        }
+
+        let groups_to_check: Vec<(u64, &Vec<DuplicateEntry>)> = self
+            .files_with_identical_hashes
+            .iter()
+            .flat_map(|(size, vec_vec_file_entry)| vec_vec_file_entry.iter().map(|vec_file_entry| (*size, vec_file_entry)))
+            .collect();
+
+        let progress_handler = prepare_thread_handler_common(
+            progress_sender,
+            CurrentStage::DuplicateComparingContent,
+            groups_to_check.iter().map(|(_size, vec_file_entry)| vec_file_entry.len()).sum(),
+            self.get_test_type(),
+            groups_to_check.iter().map(|(size, vec_file_entry)| size * vec_file_entry.len() as u64).sum(),
+        );
+
+        let verified_results: Vec<(u64, Vec<Vec<DuplicateEntry>>, Vec<String>)> = groups_to_check
+            .into_par_iter()
+            .map(|(size, vec_file_entry)| {
+                let (split_groups, errors) =
+                    THREAD_BUFFER.with_borrow_mut(|buffer| split_group_by_content(buffer, vec_file_entry.clone(), progress_handler.size_counter(), stop_flag))?;
+                progress_handler.increase_items(vec_file_entry.len());
+                Some((size, split_groups, errors))
+            })
+            .while_some()
+            .collect();
+
+        progress_handler.join_thread();
+        // Partially verified results cannot be trusted, so hash groups are left untouched
+        if check_if_stop_received(stop_flag) {
+            return WorkContinueStatus::Stop;
+        }
+
+        self.files_with_identical_hashes.clear();
+        for (size, split_groups, errors) in verified_results {
+            self.common_data.text_messages.warnings.extend(errors);
+            if split_groups.len() > 1 {
+                self.information.number_of_groups_split_by_content += 1;
+            }
+            for group in split_groups {
+                if group.len() > 1 {
+                    self.information.number_of_groups_verified_by_content += 1;
+                    self.files_with_identical_hashes.entry(size).or_default().push(group);
+                }
+            }
+        }
+
+        WorkContinueStatus::Continue
+    }
+
     #[fun_time(message = "hash_reference_folders", level = "debug")]
     fn hash_reference_folders(&mut self) {
         // Reference - only use in size, because later hash will be counted differently
@@ -730,8 +907,118 @@ impl DuplicateFinder {
             for vec_of_vec in vec {
                 self.files_with_identical_hashes_referenced.insert(vec_of_vec[0].0.size, vec_of_vec);
             }
+            self.remove_ignored_pairs_with_reference_files(self.get_params().check_method);
         }
 
+        self.calculate_hash_stats();
+    }
+
+    // Directories are compared by relative paths and hashes of all duplicated files inside them
+    #[fun_time(message = "analyze_directories", level = "debug")]
+    fn analyze_directories(&mut self) {
+        let directories_files_number = mem::take(&mut self.directories_files_number);
+        if !self.should_analyze_directories() || self.files_with_identical_hashes.is_empty() {
+            return;
+        }
+        if self.common_data.use_reference_folders {
+            self.common_data
+                .text_messages
+                .warnings
+                .push("Comparing directories is not supported when reference folders are used".to_string());
+            return;
+        }
+
+        let mut directories_content = get_directories_content(&self.files_with_identical_hashes, &directories_files_number);
+        if self.get_params().find_duplicate_directories {
+            (self.duplicated_directories, self.directories_contained_in_other) = find_duplicate_directories(&mut directories_content);
+        }
+        if let Some(minimal_similarity) = self.get_params().minimal_directories_similarity {
+            self.similar_directories = find_similar_directories(&directories_content, minimal_similarity);
+        }
+        drop(directories_content);
+        self.collect_files_of_reported_directories();
+        self.remove_groups_explained_by_directories();
+    }
+
+    // Files must be remembered before removing groups explained by directories, because they are needed to delete directories
+    fn collect_files_of_reported_directories(&mut self) {
+        let mut files_in_directories: HashMap<PathBuf, Vec<DuplicateEntry>> = self
+            .duplicated_directories
+            .iter()
+            .flatten()
+            .chain(self.directories_contained_in_other.iter().flat_map(|(_container, contained)| contained))
+            .map(|e| (e.path.clone(), Vec::new()))
+            .collect();
+        if files_in_directories.is_empty() {
+            return;
+        }
+
+        for file_entry in self.files_with_identical_hashes.values().flatten().flatten() {
+            if let Some(directory) = file_entry.path.ancestors().skip(1).find(|directory| files_in_directories.contains_key(*directory))
+                && let Some(files) = files_in_directories.get_mut(directory)
+            {
+                files.push(file_entry.clone());
+            }
+        }
+
+        for directory_entry in self
+            .duplicated_directories
+            .iter_mut()
+            .flatten()
+            .chain(self.directories_contained_in_other.iter_mut().flat_map(|(_container, contained)| contained))
+        {
+            directory_entry.files = files_in_directories.remove(&directory_entry.path).unwrap_or_default();
+        }
+    }
+
+    // Groups of files are not needed, when they only contain same file from every directory in one result
+    fn remove_groups_explained_by_directories(&mut self) {
+        let mut reported_directories: HashMap<&Path, usize> = Default::default();
+        for (idx, group) in self.duplicated_directories.iter().enumerate() {
+            reported_directories.extend(group.iter().map(|e| (e.path.as_path(), idx)));
+        }
+        for (idx, (container, contained)) in self.directories_contained_in_other.iter().enumerate() {
+            let idx = idx + self.duplicated_directories.len();
+            reported_directories.insert(container.path.as_path(), idx);
+            reported_directories.extend(contained.iter().map(|e| (e.path.as_path(), idx)));
+        }
+        if reported_directories.is_empty() {
+            return;
+        }
+
+        let explained_by = |file_entry: &DuplicateEntry| {
+            let (directory, idx) = file_entry
+                .path
+                .ancestors()
+                .skip(1)
+                .find_map(|directory| Some((directory, *reported_directories.get(directory)?)))?;
+            Some((idx, file_entry.path.strip_prefix(directory).ok()?.to_path_buf()))
+        };
+        let mut files_with_identical_hashes = mem::take(&mut self.files_with_identical_hashes);
+        for vec_vec_file_entry in files_with_identical_hashes.values_mut() {
+            vec_vec_file_entry.retain(|vec_file_entry| {
+                let first = vec_file_entry.first().and_then(explained_by);
+                first.is_none() || vec_file_entry.iter().any(|file_entry| explained_by(file_entry) != first)
+            });
+        }
+        files_with_identical_hashes.retain(|_size, vec_vec_file_entry| !vec_vec_file_entry.is_empty());
+        self.files_with_identical_hashes = files_with_identical_hashes;
+    }
+
+    fn calculate_directories_stats(&mut self) {
+        for group in &self.duplicated_directories {
+            self.information.number_of_groups_of_duplicated_directories += 1;
+            self.information.number_of_duplicated_directories += group.len() - 1;
+            self.information.lost_space_by_directories += group.iter().skip(1).map(|e| e.size).sum::<u64>();
+        }
+        for (_container, contained) in &self.directories_contained_in_other {
+            self.information.number_of_directories_contained_in_other += contained.len();
+            self.information.lost_space_by_directories += contained.iter().map(|e| e.size).sum::<u64>();
+        }
+        self.information.number_of_similar_directories = self.similar_directories.len();
+    }
+
+    fn calculate_hash_stats(&mut self) {
         if self.common_data.use_reference_folders {
             for (size, vector_vectors) in &self.files_with_identical_hashes_referenced {
                 for (_fe, vector) in vector_vectors {
@@ -749,11 +1036,25 @@ impl DuplicateFinder {
                 }
             }
         }
+        self.calculate_directories_stats();
+    }
+
+    // Used when results are not found by scanning, but loaded from file
+    pub(crate) fn recalculate_stats(&mut self) {
+        self.information = Info::default();
+        match self.get_params().check_method {
+            CheckingMethod::Name => self.calculate_name_stats(),
+            CheckingMethod::SizeName => self.calculate_size_name_stats(),
+            CheckingMethod::Size => self.calculate_size_stats(),
+            CheckingMethod::Hash | CheckingMethod::SampledHash => self.calculate_hash_stats(),
+            // Other checking methods are not used by duplicate finder, so there are no results to count
+            CheckingMethod::None | CheckingMethod::AudioTags | CheckingMethod::AudioContent => {}
+        }
     }
 
     #[fun_time(message = "check_files_hash", level = "debug")]
     pub(crate) fn check_files_hash(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
-        assert_eq!(self.get_params().check_method, CheckingMethod::Hash);
+        assert!(matches!(self.get_params().check_method, CheckingMethod::Hash | CheckingMethod::SampledHash));
 
         let mut pre_checked_map: BTreeMap<u64, Vec<DuplicateEntry>> = Default::default();
         if self.prehashing(stop_flag, progress_sender, &mut pre_checked_map) == WorkContinueStatus::Stop {
@@ -764,6 +1065,23 @@ impl DuplicateFinder {
             return WorkContinueStatus::Stop;
         }
 
+        if self.get_params().verify_content && self.verify_content(stop_flag, progress_sender) == WorkContinueStatus::Stop {
+            return WorkContinueStatus::Stop;
+        }
+        self.groups_verified_by_content = self.get_params().verify_content;
+
+        if !self.common_data.use_reference_folders {
+            let ignored_groups_filter = self.common_data.get_ignored_groups_filter();
+            self.files_with_identical_hashes = mem::take(&mut self.files_with_identical_hashes)
+                .into_iter()
+                .filter_map(|(size, vec_vec_file_entry)| {
+                    let vec_vec_file_entry = ignored_groups_filter.filter_groups(vec_vec_file_entry);
+                    (!vec_vec_file_entry.is_empty()).then_some((size, vec_vec_file_entry))
+                })
+                .collect();
+        }
+
+        self.analyze_directories();
         self.hash_reference_folders();
 
         // Clean unused data
@@ -774,7 +1092,253 @@ impl DuplicateFinder {
     }
 }
 
+// Directory -> (entry, (path relative to directory, size, hash) of all duplicated files inside it)
+type DirectoriesContent<'a> = BTreeMap<&'a Path, (DirectoryEntry, BTreeSet<(&'a Path, u64, &'a str)>)>;
+
+fn get_directories_content<'a>(
+    files_with_identical_hashes: &'a BTreeMap<u64, Vec<Vec<DuplicateEntry>>>,
+    directories_files_number: &BTreeMap<PathBuf, (usize, u64)>,
+) -> DirectoriesContent<'a> {
+    let mut directories_content: DirectoriesContent<'_> = Default::default();
+    for file_entry in files_with_identical_hashes.values().flatten().flatten() {
+        for directory in file_entry.path.ancestors().skip(1) {
+            let Some(&(files_number, size)) = directories_files_number.get(directory) else {
+                break;
+            };
+            let Ok(relative_path) = file_entry.path.strip_prefix(directory) else {
+                continue;
+            };
+            let (directory_entry, content) = directories_content.entry(directory).or_insert_with(|| {
+                (
+                    DirectoryEntry {
+                        path: directory.to_path_buf(),
+                        files_number,
+                        size,
+                        ..Default::default()
+                    },
+                    BTreeSet::new(),
+                )
+            });
+            directory_entry.modified_date = directory_entry.modified_date.max(file_entry.modified_date);
+            content.insert((relative_path, file_entry.size, &file_entry.hash));
+        }
+    }
+
+    directories_content
+}
+
+// Counts all files inside directory tree, without using any filters
+// None is returned when any part of directory cannot be read, because it may contain unknown files
+fn count_all_files_in_directory(directory: &Path) -> Option<(usize, u64)> {
+    let mut folders_to_check = vec![directory.to_path_buf()];
+    let (mut files_number, mut size) = (0, 0);
+    while let Some(folder) = folders_to_check.pop() {
+        for entry in fs::read_dir(&folder).ok()? {
+            let entry = entry.ok()?;
+            if entry.file_type().ok()?.is_dir() {
+                folders_to_check.push(entry.path());
+            } else {
+                files_number += 1;
+                size += entry.metadata().ok()?.len();
+            }
+        }
+    }
+    Some((files_number, size))
+}
+
+// Hash of directory is calculated from relative paths and hashes of all files inside it, so identical directory trees have same hash
+fn find_duplicate_directories(directories_content: &mut DirectoriesContent<'_>) -> (Vec<Vec<DirectoryEntry>>, Vec<(DirectoryEntry, Vec<DirectoryEntry>)>) {
+    // Only directories in which all files have duplicates, can be compared
+    let mut complete_directories: BTreeSet<&Path> = Default::default();
+    let mut directories_by_hash: BTreeMap<String, Vec<&Path>> = Default::default();
+    for (directory, (directory_entry, content)) in directories_content.iter_mut() {
+        if content.len() != directory_entry.files_number {
+            continue;
+        }
+        // Files skipped by search filters(e.g. too small ones) are not counted while searching, so directory must be checked again without them
+        let Some((files_number, size)) = count_all_files_in_directory(directory) else {
+            continue;
+        };
+        directory_entry.files_number = files_number;
+        directory_entry.size = size;
+        if content.len() != files_number {
+            continue;
+        }
+        let mut hasher = Xxh3::new();
+        for (relative_path, size, hash) in content.iter() {
+            hasher.update(relative_path.as_os_str().as_encoded_bytes());
+            hasher.update(&size.to_le_bytes());
+            hasher.update(hash.as_bytes());
+            hasher.update(&[0]);
+        }
+        directory_entry.hash = hasher.digest().to_string();
+        complete_directories.insert(directory);
+        directories_by_hash.entry(directory_entry.hash.clone()).or_default().push(directory);
+    }
+
+    let duplicated: BTreeSet<&Path> = directories_by_hash.values().filter(|group| group.len() > 1).flatten().copied().collect();
+
+    // Directory whose every file can be found in bigger directory, under same relative path
+    let mut directories_with_item: HashMap<(&Path, u64, &str), Vec<&Path>> = Default::default();
+    for (directory, (_directory_entry, content)) in directories_content.iter() {
+        for item in content {
+            directories_with_item.entry(*item).or_default().push(directory);
+        }
+    }
+    let mut contained_in: BTreeMap<&Path, &Path> = Default::default();
+    for (directory, (directory_entry, content)) in directories_content
+        .iter()
+        .filter(|(directory, _)| complete_directories.contains(*directory) && !duplicated.contains(*directory))
+    {
+        let Some(candidates) = content.iter().filter_map(|item| directories_with_item.get(item)).min_by_key(|candidates| candidates.len()) else {
+            continue;
+        };
+        // The smallest directory is chosen, because it is the closest copy
+        let container = candidates
+            .iter()
+            .filter_map(|candidate| Some((*candidate, directories_content.get(candidate)?)))
+            .filter(|(_candidate, (candidate_entry, candidate_content))| candidate_entry.files_number > directory_entry.files_number && content.is_subset(candidate_content))
+            .min_by_key(|(candidate, (candidate_entry, _candidate_content))| (candidate_entry.files_number, *candidate))
+            .map(|(candidate, _)| candidate);
+        if let Some(container) = container {
+            contained_in.insert(directory, container);
+        }
+    }
+
+    // Directories inside already reported directory are not shown, because they are duplicates also
+    let is_inside_reported = |directory: &Path| directory.parent().is_some_and(|parent| duplicated.contains(parent) || contained_in.contains_key(parent));
+
+    let mut duplicated_directories: Vec<Vec<DirectoryEntry>> = directories_by_hash
+        .into_values()
+        .filter_map(|group| {
+            let group: Vec<DirectoryEntry> = group
+                .into_iter()
+                .filter(|directory| !is_inside_reported(directory))
+                .filter_map(|directory| Some(directories_content.get(directory)?.0.clone()))
+                .collect();
+            (group.len() > 1).then_some(group)
+        })
+        .collect();
+    duplicated_directories.sort_by_key(|group| group.first().map(|e| (Reverse(e.size), e.path.clone())));
+
+    let mut directories_contained_in_other: BTreeMap<&Path, Vec<DirectoryEntry>> = Default::default();
+    for (directory, container) in &contained_in {
+        if !is_inside_reported(directory)
+            && let Some((directory_entry, _content)) = directories_content.get(directory)
+        {
+            directories_contained_in_other.entry(container).or_default().push(directory_entry.clone());
+        }
+    }
+    let mut directories_contained_in_other: Vec<(DirectoryEntry, Vec<DirectoryEntry>)> = directories_contained_in_other
+        .into_iter()
+        .filter_map(|(container, contained)| Some((directories_content.get(container)?.0.clone(), contained)))
+        .collect();
+    directories_contained_in_other.sort_by_key(|(container, _contained)| (Reverse(container.size), container.path.clone()));
+
+    (duplicated_directories, directories_contained_in_other)
+}
+
+// Files, which can be found in more directories, are usually generic files like licenses or icons, and checking all pairs of such directories would be too slow
+const MAX_DIRECTORIES_WITH_SAME_FILE: usize = 50;
+
+// Only directories with at least one common file are compared, so most of pairs is never checked
+fn find_similar_directories(directories_content: &DirectoriesContent<'_>, minimal_similarity: f64) -> Vec<SimilarDirectories> {
+    let mut directories_with_item: HashMap<(&Path, u64, &str), Vec<&Path>> = Default::default();
+    for (directory, (_directory_entry, content)) in directories_content {
+        for item in content {
+            directories_with_item.entry(*item).or_default().push(directory);
+        }
+    }
+
+    let mut candidate_pairs: BTreeSet<(&Path, &Path)> = Default::default();
+    for directories in directories_with_item.values().filter(|directories| directories.len() <= MAX_DIRECTORIES_WITH_SAME_FILE) {
+        for (idx, first) in directories.iter().enumerate() {
+            for second in directories.iter().skip(idx + 1) {
+                // Directory inside other directory, may contain same files only by accident
+                if first.starts_with(second) || second.starts_with(first) {
+                    continue;
+                }
+                candidate_pairs.insert(if first < second { (*first, *second) } else { (*second, *first) });
+            }
+        }
+    }
+    drop(directories_with_item);
+
+    // Files skipped by search filters are not counted while searching, so similarity is checked again with all files, when it is high enough without them
+    let mut all_files_in_directories: HashMap<PathBuf, Option<(usize, u64)>> = Default::default();
+    let mut similar_directories: BTreeMap<(&Path, &Path), SimilarDirectories> = Default::default();
+    for (first, second) in candidate_pairs {
+        let (Some((first_entry, first_content)), Some((second_entry, second_content))) = (directories_content.get(first), directories_content.get(second)) else {
+            continue;
+        };
+        let (shared_files_number, shared_size) = first_content
+            .intersection(second_content)
+            .fold((0, 0), |(files_number, size), (_relative_path, file_size, _hash)| (files_number + 1, size + file_size));
+        let get_similarity =
+            |first_files_number: usize, second_files_number: usize| shared_files_number as f64 / (first_files_number + second_files_number - shared_files_number) as f64;
+        if get_similarity(first_entry.files_number, second_entry.files_number) < minimal_similarity {
+            continue;
+        }
+
+        let mut count_all_files = |directory: &Path, directory_entry: &DirectoryEntry| {
+            let (files_number, size) = (*all_files_in_directories
+                .entry(directory.to_path_buf())
+                .or_insert_with(|| count_all_files_in_directory(directory)))?;
+            let mut directory_entry = directory_entry.clone();
+            // Directory may change since searching, but number of files cannot be smaller than number of shared files
+            directory_entry.files_number = files_number.max(shared_files_number);
+            directory_entry.size = size.max(shared_size);
+            Some(directory_entry)
+        };
+        let (Some(first_entry), Some(second_entry)) = (count_all_files(first, first_entry), count_all_files(second, second_entry)) else {
+            continue;
+        };
+        let similarity = get_similarity(first_entry.files_number, second_entry.files_number);
+        if similarity < minimal_similarity {
+            continue;
+        }
+        similar_directories.insert(
+            (first, second),
+            SimilarDirectories {
+                shared_files_number,
+                shared_size,
+                unique_files_number_first: first_entry.files_number - shared_files_number,
+                unique_size_first: first_entry.size - shared_size,
+                unique_files_number_second: second_entry.files_number - shared_files_number,
+                unique_size_second: second_entry.size - shared_size,
+                first: first_entry,
+                second: second_entry,
+                similarity,
+            },
+        );
+    }
+
+    // Subdirectories of similar directories are usually also similar, so only the topmost pair is shown
+    let is_parent_pair_similar = |first: &Path, second: &Path| {
+        let (Some(first_parent), Some(second_parent)) = (first.parent(), second.parent()) else {
+            return false;
+        };
+        similar_directories.contains_key(&(first_parent, second_parent)) || similar_directories.contains_key(&(second_parent, first_parent))
+    };
+    let mut result: Vec<SimilarDirectories> = similar_directories
+        .iter()
+        .filter(|((first, second), _)| !is_parent_pair_similar(first, second))
+        .map(|(_, similar)| similar.clone())
+        .collect();
+    result.sort_by(|a, b| {
+        b.similarity
+            .total_cmp(&a.similarity)
+            .then_with(|| b.shared_size.cmp(&a.shared_size))
+            .then_with(|| a.first.path.cmp(&b.first.path))
+    });
+    result
+}
+
 pub fn get_duplicate_cache_file(type_of_hash: &HashType, is_prehash: bool) -> String {
     let prehash_str = if is_prehash { "_prehash" } else { "" };
     format!("cache_duplicates_{type_of_hash:?}{prehash_str}_{CACHE_DUPLICATE_VERSION}.bin")
 }
+
+pub fn get_duplicate_sampled_cache_file(type_of_hash: &HashType) -> String {
+    format!("cache_duplicates_{type_of_hash:?}_sampled_{CACHE_DUPLICATE_VERSION}.bin")
+}
@@ -8,6 +8,7 @@ use std::fmt::Debug;
 use std::fs;
 use std::fs::File;
 use std::hash::Hasher;
+use std::io::SeekFrom;
 use std::io::prelude::*;
 #[cfg(target_family = "unix")]
 use std::os::unix::fs::MetadataExt;
@@ -26,13 +27,14 @@ use crate::common::tool_data::CommonToolData;
 use crate::common::traits::ResultEntry;
 
 pub const PREHASHING_BUFFER_SIZE: u64 = 4 * 1024;
+pub const SAMPLED_HASH_REGION_SIZE: u64 = 64 * 1024;
 pub const THREAD_BUFFER_SIZE: usize = 2 * 1024 * 1024;
 
 thread_local! {
     static THREAD_BUFFER: RefCell<Vec<u8>> = RefCell::new(vec![0u8; THREAD_BUFFER_SIZE]);
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
 pub struct DuplicateEntry {
     pub path: PathBuf,
     pub modified_date: u64,
@@ -51,6 +53,47 @@ impl ResultEntry for DuplicateEntry {
     }
 }
 
+// Directory in which all files have duplicates in other directory with same relative paths
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct DirectoryEntry {
+    pub path: PathBuf,
+    // Newest modification date of duplicated files inside
+    pub modified_date: u64,
+    // Size of all files inside
+    pub size: u64,
+    pub files_number: usize,
+    pub hash: String,
+    // Duplicated files inside directory, only they are removed when deleting directory
+    #[serde(default)]
+    pub files: Vec<DuplicateEntry>,
+}
+impl ResultEntry for DirectoryEntry {
+    fn get_path(&self) -> &Path {
+        &self.path
+    }
+    fn get_modified_date(&self) -> u64 {
+        self.modified_date
+    }
+    fn get_size(&self) -> u64 {
+        self.size
+    }
+}
+
+// Pair of directories, which share part of their files(with same relative paths and content)
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct SimilarDirectories {
+    pub first: DirectoryEntry,
+    pub second: DirectoryEntry,
+    pub shared_files_number: usize,
+    pub shared_size: u64,
+    pub unique_files_number_first: usize,
+    pub unique_size_first: u64,
+    pub unique_files_number_second: usize,
+    pub unique_size_second: u64,
+    // Jaccard index of files in both directories - number of shared files divided by number of all files, in range 0.0..=1.0
+    pub similarity: f64,
+}
+
 impl FileEntry {
     fn into_duplicate_entry(self) -> DuplicateEntry {
         DuplicateEntry {
@@ -74,6 +117,13 @@ pub struct Info {
     pub number_of_duplicated_files_by_size_name: usize,
     pub lost_space_by_size: u64,
     pub lost_space_by_hash: u64,
+    pub number_of_groups_verified_by_content: usize,
+    pub number_of_groups_split_by_content: usize,
+    pub number_of_groups_of_duplicated_directories: usize,
+    pub number_of_duplicated_directories: usize,
+    pub number_of_directories_contained_in_other: usize,
+    pub lost_space_by_directories: u64,
+    pub number_of_similar_directories: usize,
     pub scanning_time: u128,
 }
 
@@ -86,6 +136,10 @@ pub struct DuplicateFinderParameters {
     pub minimal_cache_file_size: u64,
     pub minimal_prehash_cache_file_size: u64,
     pub case_sensitive_name_comparison: bool,
+    pub verify_content: bool,
+    pub find_duplicate_directories: bool,
+    // When set, pairs of directories with at least this similarity(0.0..=1.0) are found
+    pub minimal_directories_similarity: Option<f64>,
 }
 
 impl DuplicateFinderParameters {
@@ -97,6 +151,9 @@ impl DuplicateFinderParameters {
         minimal_cache_file_size: u64,
         minimal_prehash_cache_file_size: u64,
         case_sensitive_name_comparison: bool,
+        verify_content: bool,
+        find_duplicate_directories: bool,
+        minimal_directories_similarity: Option<f64>,
     ) -> Self {
         Self {
             check_method,
@@ -106,6 +163,9 @@ impl DuplicateFinderParameters {
             minimal_cache_file_size,
             minimal_prehash_cache_file_size,
             case_sensitive_name_comparison,
+            verify_content,
+            find_duplicate_directories,
+            minimal_directories_similarity,
         }
     }
 }
@@ -113,12 +173,12 @@ impl DuplicateFinderParameters {
 pub struct DuplicateFinder {
     common_data: CommonToolData,
     information: Info,
-    // File Size, File Entry
-    files_with_identical_names: BTreeMap<String, Vec<DuplicateEntry>>,
-    // File (Size, Name), File Entry
-    files_with_identical_size_names: BTreeMap<(u64, String), Vec<DuplicateEntry>>,
-    // File Size, File Entry
-    files_with_identical_size: BTreeMap<u64, Vec<DuplicateEntry>>,
+    // File Name, next grouped by dismissed pairs of files
+    files_with_identical_names: BTreeMap<String, Vec<Vec<DuplicateEntry>>>,
+    // File (Size, Name), next grouped by dismissed pairs of files
+    files_with_identical_size_names: BTreeMap<(u64, String), Vec<Vec<DuplicateEntry>>>,
+    // File Size, next grouped by dismissed pairs of files
+    files_with_identical_size: BTreeMap<u64, Vec<Vec<DuplicateEntry>>>,
     // File Size, next grouped by file size, next grouped by hash
     files_with_identical_hashes: BTreeMap<u64, Vec<Vec<DuplicateEntry>>>,
     // File Size, File Entry
@@ -129,9 +189,39 @@ pub struct DuplicateFinder {
     files_with_identical_size_referenced: BTreeMap<u64, (DuplicateEntry, Vec<DuplicateEntry>)>,
     // File Size, next grouped by file size, next grouped by hash
     files_with_identical_hashes_referenced: BTreeMap<u64, Vec<(DuplicateEntry, Vec<DuplicateEntry>)>>,
+    // Directory, (number, size) of all files found inside it(also in subdirectories) - used only when searching for duplicated directories
+    directories_files_number: BTreeMap<PathBuf, (usize, u64)>,
+    // Groups of directories with identical content
+    duplicated_directories: Vec<Vec<DirectoryEntry>>,
+    // Directory, directories whose whole content is also inside it
+    directories_contained_in_other: Vec<(DirectoryEntry, Vec<DirectoryEntry>)>,
+    similar_directories: Vec<SimilarDirectories>,
+    // Whether groups were verified byte by byte, so sampled hash results can be safely deleted or linked
+    groups_verified_by_content: bool,
     params: DuplicateFinderParameters,
 }
 
+// Content of results file, keys are saved as pairs, because json allows only string keys in maps
+#[derive(Default, Serialize, Deserialize)]
+pub struct DuplicateResults {
+    files_with_identical_names: Vec<(String, Vec<Vec<DuplicateEntry>>)>,
+    files_with_identical_size_names: Vec<((u64, String), Vec<Vec<DuplicateEntry>>)>,
+    files_with_identical_size: Vec<(u64, Vec<Vec<DuplicateEntry>>)>,
+    files_with_identical_hashes: Vec<(u64, Vec<Vec<DuplicateEntry>>)>,
+    files_with_identical_names_referenced: Vec<(String, (DuplicateEntry, Vec<DuplicateEntry>))>,
+    files_with_identical_size_names_referenced: Vec<((u64, String), (DuplicateEntry, Vec<DuplicateEntry>))>,
+    files_with_identical_size_referenced: Vec<(u64, (DuplicateEntry, Vec<DuplicateEntry>))>,
+    files_with_identical_hashes_referenced: Vec<(u64, Vec<(DuplicateEntry, Vec<DuplicateEntry>)>)>,
+    #[serde(default)]
+    duplicated_directories: Vec<Vec<DirectoryEntry>>,
+    #[serde(default)]
+    directories_contained_in_other: Vec<(DirectoryEntry, Vec<DirectoryEntry>)>,
+    #[serde(default)]
+    similar_directories: Vec<SimilarDirectories>,
+    #[serde(default)]
+    groups_verified_by_content: bool,
+}
+
 #[cfg(target_family = "windows")]
 fn filter_hard_links(vec_file_entry: &[FileEntry]) -> Vec<FileEntry> {
     let mut inodes: IndexSet<u128> = IndexSet::with_capacity(vec_file_entry.len());
@@ -174,15 +264,15 @@ impl DuplicateFinder {
         &self.params
     }
 
-    pub const fn get_files_sorted_by_names(&self) -> &BTreeMap<String, Vec<DuplicateEntry>> {
+    pub const fn get_files_sorted_by_names(&self) -> &BTreeMap<String, Vec<Vec<DuplicateEntry>>> {
         &self.files_with_identical_names
     }
 
-    pub const fn get_files_sorted_by_size(&self) -> &BTreeMap<u64, Vec<DuplicateEntry>> {
+    pub const fn get_files_sorted_by_size(&self) -> &BTreeMap<u64, Vec<Vec<DuplicateEntry>>> {
         &self.files_with_identical_size
     }
 
-    pub const fn get_files_sorted_by_size_name(&self) -> &BTreeMap<(u64, String), Vec<DuplicateEntry>> {
+    pub const fn get_files_sorted_by_size_name(&self) -> &BTreeMap<(u64, String), Vec<Vec<DuplicateEntry>>> {
         &self.files_with_identical_size_names
     }
 
@@ -190,6 +280,23 @@ impl DuplicateFinder {
         &self.files_with_identical_hashes
     }
 
+    /// Groups found by sampled hash, which were not verified byte by byte, may contain different files
+    pub fn contains_only_sampled_groups(&self) -> bool {
+        self.get_params().check_method == CheckingMethod::SampledHash && !self.groups_verified_by_content
+    }
+
+    pub const fn get_duplicated_directories(&self) -> &Vec<Vec<DirectoryEntry>> {
+        &self.duplicated_directories
+    }
+
+    pub const fn get_directories_contained_in_other(&self) -> &Vec<(DirectoryEntry, Vec<DirectoryEntry>)> {
+        &self.directories_contained_in_other
+    }
+
+    pub const fn get_similar_directories(&self) -> &Vec<SimilarDirectories> {
+        &self.similar_directories
+    }
+
     pub const fn get_information(&self) -> &Info {
         &self.information
     }
@@ -277,6 +384,139 @@ pub fn hash_calculation(
     Ok(Some(hasher.finalize()))
 }
 
+// Hashes only beginning, middle and end of file, so different files may have same hash
+// Smaller files are hashed entirely, because regions would overlap
+pub(crate) fn sampled_hash_calculation(buffer: &mut [u8], file_entry: &DuplicateEntry, hash_type: HashType, size_counter: &Arc<AtomicU64>) -> Result<String, String> {
+    const_assert!(SAMPLED_HASH_REGION_SIZE <= THREAD_BUFFER_SIZE as u64);
+
+    let mut file_handler = match File::open(&file_entry.path) {
+        Ok(t) => t,
+        Err(e) => {
+            size_counter.fetch_add(get_sampled_hash_size(file_entry.size), Ordering::Relaxed);
+            return Err(format!("Unable to check hash of file {:?}, reason {e}", file_entry.path));
+        }
+    };
+    let hasher = &mut *hash_type.hasher();
+
+    let offsets = if file_entry.size <= 3 * SAMPLED_HASH_REGION_SIZE {
+        vec![0]
+    } else {
+        vec![0, file_entry.size / 2 - SAMPLED_HASH_REGION_SIZE / 2, file_entry.size - SAMPLED_HASH_REGION_SIZE]
+    };
+    let region_size = get_sampled_hash_size(file_entry.size) / offsets.len() as u64;
+
+    for offset in offsets {
+        let read_result = file_handler.seek(SeekFrom::Start(offset)).and_then(|_| {
+            #[expect(clippy::indexing_slicing)] // Safe, because region size is always <= buffer size
+            read_to_fill(&mut file_handler, &mut buffer[..region_size as usize])
+        });
+        let n = match read_result {
+            Ok(t) => t,
+            Err(e) => return Err(format!("Error happened when checking hash of file {:?}, reason {}", file_entry.path, e)),
+        };
+
+        #[expect(clippy::indexing_slicing)] // Safe, because we read only n bytes, which is always <= buffer size
+        hasher.update(&buffer[..n]);
+        size_counter.fetch_add(n as u64, Ordering::Relaxed);
+    }
+    Ok(hasher.finalize())
+}
+
+// Number of bytes read by sampled hash calculation
+pub(crate) fn get_sampled_hash_size(file_size: u64) -> u64 {
+    file_size.min(3 * SAMPLED_HASH_REGION_SIZE)
+}
+
+// Same as read_exact, but end of file is not treated as error
+fn read_to_fill(file_handler: &mut File, buffer: &mut [u8]) -> std::io::Result<usize> {
+    let mut read_bytes = 0;
+    while read_bytes < buffer.len() {
+        #[expect(clippy::indexing_slicing)] // Safe, because read_bytes is always < buffer size
+        match file_handler.read(&mut buffer[read_bytes..]) {
+            Ok(0) => break,
+            Ok(n) => read_bytes += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(read_bytes)
+}
+
+// Tells which of compared files cannot be read
+pub(crate) enum CompareContentError {
+    First(String),
+    Second(String),
+}
+
+// Returns None when stop was requested
+pub(crate) fn compare_files_content(buffer: &mut [u8], first_path: &Path, second_path: &Path, stop_flag: &Arc<AtomicBool>) -> Result<Option<bool>, CompareContentError> {
+    let open_file = |path: &Path| File::open(path).map_err(|e| format!("Unable to compare content of file {path:?}, reason {e}"));
+    let mut first_handler = open_file(first_path).map_err(CompareContentError::First)?;
+    let mut second_handler = open_file(second_path).map_err(CompareContentError::Second)?;
+
+    let (first_buffer, second_buffer) = buffer.split_at_mut(buffer.len() / 2);
+    loop {
+        let first_n = read_to_fill(&mut first_handler, first_buffer)
+            .map_err(|e| CompareContentError::First(format!("Error happened when comparing content of file {first_path:?}, reason {e}")))?;
+        let second_n = read_to_fill(&mut second_handler, second_buffer)
+            .map_err(|e| CompareContentError::Second(format!("Error happened when comparing content of file {second_path:?}, reason {e}")))?;
+
+        #[expect(clippy::indexing_slicing)] // Safe, because read bytes are always <= buffer size
+        if first_buffer[..first_n] != second_buffer[..second_n] {
+            return Ok(Some(false));
+        }
+        if first_n == 0 {
+            return Ok(Some(true));
+        }
+        if check_if_stop_received(stop_flag) {
+            return Ok(None);
+        }
+    }
+}
+
+// Files with same hash are compared with first file of each already found group, so usually every file is read only once or twice
+// Files which cannot be read are removed from group, because it is not possible to confirm that they are duplicates
+// When first file of group cannot be read, next file from this group is used instead, so other files are not dropped
+pub(crate) fn split_group_by_content(
+    buffer: &mut [u8],
+    group: Vec<DuplicateEntry>,
+    size_counter: &Arc<AtomicU64>,
+    stop_flag: &Arc<AtomicBool>,
+) -> Option<(Vec<Vec<DuplicateEntry>>, Vec<String>)> {
+    let mut split_groups: Vec<Vec<DuplicateEntry>> = Vec::new();
+    let mut errors = Vec::new();
+
+    'next_file: for file_entry in group {
+        size_counter.fetch_add(file_entry.size, Ordering::Relaxed);
+        let mut group_idx = 0;
+        while let Some(split_group) = split_groups.get_mut(group_idx) {
+            let Some(representative) = split_group.first() else {
+                split_groups.remove(group_idx);
+                continue;
+            };
+            match compare_files_content(buffer, &representative.path, &file_entry.path, stop_flag) {
+                Ok(Some(true)) => {
+                    split_group.push(file_entry);
+                    continue 'next_file;
+                }
+                Ok(Some(false)) => group_idx += 1,
+                Ok(None) => return None,
+                Err(CompareContentError::First(e)) => {
+                    errors.push(e);
+                    split_group.remove(0);
+                }
+                Err(CompareContentError::Second(e)) => {
+                    errors.push(e);
+                    continue 'next_file;
+                }
+            }
+        }
+        split_groups.push(vec![file_entry]);
+    }
+
+    Some((split_groups, errors))
+}
+
 impl MyHasher for blake3::Hasher {
     fn update(&mut self, bytes: &[u8]) {
         self.update(bytes);
@@ -310,6 +550,9 @@ mod tests {
     use std::io;
 
     use super::*;
+    use crate::common::model::{ToolType, WorkContinueStatus};
+    use crate::common::tool_data::{CommonData, DeleteMethod};
+    use crate::common::traits::Search;
 
     #[test]
     fn test_filter_hard_links_empty() {
@@ -385,6 +628,368 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_sampled_hash_calculation() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        let mut buf = vec![0u8; THREAD_BUFFER_SIZE];
+        let size = 4 * SAMPLED_HASH_REGION_SIZE;
+
+        let mut create_entry = |name: &str, changed_byte: u64| -> io::Result<(DuplicateEntry, String)> {
+            let mut content = vec![0u8; size as usize];
+            content[changed_byte as usize] = 1;
+            let path = dir.path().join(name);
+            File::create(&path)?.write_all(&content)?;
+            let e = DuplicateEntry { path, size, ..Default::default() };
+            let size_counter = Arc::new(AtomicU64::new(0));
+            let hash = sampled_hash_calculation(&mut buf, &e, HashType::Blake3, &size_counter).expect("sampled_hash_calculation failed");
+            assert_eq!(size_counter.load(Ordering::Relaxed), 3 * SAMPLED_HASH_REGION_SIZE);
+            Ok((e, hash))
+        };
+
+        let (_, not_sampled_1) = create_entry("a", SAMPLED_HASH_REGION_SIZE + 1)?;
+        let (_, not_sampled_2) = create_entry("b", size / 2 + SAMPLED_HASH_REGION_SIZE / 2 + 1)?;
+        let (_, head) = create_entry("c", 0)?;
+        let (_, middle) = create_entry("d", size / 2)?;
+        let (_, tail) = create_entry("e", size - 1)?;
+
+        // Changes outside sampled regions are not visible
+        assert_eq!(not_sampled_1, not_sampled_2);
+        let mut hashes = vec![not_sampled_1, head, middle, tail];
+        hashes.dedup();
+        assert_eq!(hashes.len(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_group_by_content() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        let mut entries = vec![DuplicateEntry {
+            path: dir.path().join("removed"),
+            size: 4,
+            ..Default::default()
+        }];
+        for (name, content) in [("a", b"abcd"), ("b", b"abce"), ("c", b"abcd"), ("d", b"abce"), ("e", b"xxxx")] {
+            let path = dir.path().join(name);
+            File::create(&path)?.write_all(content)?;
+            entries.push(DuplicateEntry {
+                path,
+                size: 4,
+                ..Default::default()
+            });
+        }
+        entries.push(DuplicateEntry {
+            path: dir.path().join("not_existing"),
+            size: 4,
+            ..Default::default()
+        });
+
+        // Small buffer, to check that files are compared in multiple chunks
+        let mut buf = [0u8; 2];
+        let size_counter = Arc::new(AtomicU64::new(0));
+        let (groups, errors) = split_group_by_content(&mut buf, entries, &size_counter, &Arc::default()).expect("split_group_by_content was stopped");
+        let names = groups
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|e| e.path.file_name().expect("No file name").to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec![vec!["a", "c"], vec!["b", "d"], vec!["e"]]);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(size_counter.load(Ordering::Relaxed), 28);
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_group_by_content_with_unreadable_first_file() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        let mut entries = Vec::new();
+        for name in ["a", "b", "c"] {
+            let path = dir.path().join(name);
+            File::create(&path)?.write_all(b"abcd")?;
+            entries.push(DuplicateEntry {
+                path,
+                size: 4,
+                ..Default::default()
+            });
+        }
+
+        // First file cannot be read, so next files must be compared with each other
+        fs::remove_file(dir.path().join("a"))?;
+        let mut buf = [0u8; 2];
+        let (groups, errors) = split_group_by_content(&mut buf, entries, &Arc::new(AtomicU64::new(0)), &Arc::default()).expect("split_group_by_content was stopped");
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            groups[0].iter().map(|e| e.path.clone()).collect::<Vec<_>>(),
+            vec![dir.path().join("b"), dir.path().join("c")]
+        );
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains(&format!("{:?}", dir.path().join("a"))), "{errors:?}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_duplicate_directories() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        for (path, content) in [
+            ("Photos/a.txt", b"aaa"),
+            ("Photos/c.txt", b"ccc"),
+            ("Photos/sub/b.txt", b"bbb"),
+            ("Photos (backup)/a.txt", b"aaa"),
+            ("Photos (backup)/c.txt", b"ccc"),
+            ("Photos (backup)/sub/b.txt", b"bbb"),
+            ("Partial/a.txt", b"aaa"),
+            ("Big/a.txt", b"aaa"),
+            ("Big/sub/b.txt", b"bbb"),
+            ("Big/unique.txt", b"uuu"),
+        ] {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().expect("No parent"))?;
+            File::create(&path)?.write_all(content)?;
+        }
+
+        let params = DuplicateFinderParameters::new(CheckingMethod::Hash, HashType::Blake3, false, false, 0, 0, false, false, true, None);
+        let mut finder = DuplicateFinder::new(params);
+        finder.set_included_directory(vec![dir.path().to_path_buf()]);
+        finder.set_minimal_file_size(1);
+        finder.set_use_cache(false);
+        finder.set_delete_method(DeleteMethod::AllExceptNewest);
+        finder.search(&Arc::default(), None);
+
+        let duplicated_directories = finder
+            .get_duplicated_directories()
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|e| (e.path.strip_prefix(dir.path()).expect("Invalid path"), e.files_number))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(duplicated_directories, vec![vec![(Path::new("Photos"), 3), (Path::new("Photos (backup)"), 3)]]);
+
+        let contained = finder.get_directories_contained_in_other();
+        assert_eq!(contained.len(), 1);
+        assert_eq!(contained[0].0.path, dir.path().join("Big"));
+        assert_eq!((contained[0].0.files_number, contained[0].0.size), (3, 9));
+        assert_eq!(contained[0].1.iter().map(|e| e.path.clone()).collect::<Vec<_>>(), vec![dir.path().join("Partial")]);
+
+        // Group with c.txt is fully explained by duplicated directories
+        let groups = finder.get_files_sorted_by_hash().values().flatten().map(Vec::len).collect::<Vec<_>>();
+        assert_eq!(groups, vec![4, 3]);
+
+        assert!(!dir.path().join("Partial").exists());
+        assert!(dir.path().join("Photos").exists() != dir.path().join("Photos (backup)").exists());
+        assert!(dir.path().join("Big/sub/b.txt").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_directory_with_filtered_files_is_not_duplicate() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        // Notes are smaller than default minimal file size, so they are not found while searching
+        for (path, content) in [
+            ("Photos/a.jpg", vec![1; 10_000]),
+            ("Photos/b.jpg", vec![2; 10_000]),
+            ("Photos/notes.txt", b"unique notes".to_vec()),
+            ("Backup/a.jpg", vec![1; 10_000]),
+            ("Backup/b.jpg", vec![2; 10_000]),
+        ] {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().expect("No parent"))?;
+            File::create(&path)?.write_all(&content)?;
+        }
+
+        let params = DuplicateFinderParameters::new(CheckingMethod::Hash, HashType::Blake3, false, false, 0, 0, false, false, true, None);
+        let mut finder = DuplicateFinder::new(params);
+        finder.set_included_directory(vec![dir.path().to_path_buf()]);
+        finder.set_use_cache(false);
+        finder.set_delete_method(DeleteMethod::AllExceptOldest);
+        finder.search(&Arc::default(), None);
+
+        assert!(finder.get_duplicated_directories().is_empty());
+        let contained = finder.get_directories_contained_in_other();
+        assert_eq!(contained.len(), 1);
+        assert_eq!(contained[0].0.path, dir.path().join("Photos"));
+        assert_eq!(contained[0].0.files_number, 3);
+        assert_eq!(contained[0].1.iter().map(|e| e.path.clone()).collect::<Vec<_>>(), vec![dir.path().join("Backup")]);
+
+        assert!(dir.path().join("Photos/notes.txt").exists());
+        assert!(dir.path().join("Photos/a.jpg").exists() && dir.path().join("Photos/b.jpg").exists());
+        assert!(!dir.path().join("Backup").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_quarantine_folder_is_not_scanned() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        for path in ["data/a.bin", "data/b.bin", "quarantine/1/data/c.bin"] {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().expect("No parent"))?;
+            File::create(&path)?.write_all(&[1; 10_000])?;
+        }
+
+        let params = DuplicateFinderParameters::new(CheckingMethod::Hash, HashType::Blake3, false, false, 0, 0, false, false, false, None);
+        let mut finder = DuplicateFinder::new(params);
+        finder.set_included_directory(vec![dir.path().to_path_buf()]);
+        finder.set_quarantine_folder(Some(dir.path().join("quarantine")));
+        finder.set_use_cache(false);
+        finder.search(&Arc::default(), None);
+
+        let groups = finder.get_files_sorted_by_hash().values().flatten().collect::<Vec<_>>();
+        assert_eq!(groups.len(), 1);
+        assert!(groups[0].iter().all(|e| e.path.starts_with(dir.path().join("data"))));
+        assert_eq!(groups[0].len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_similar_directories() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        let mut files = vec![("Photos/only_photos.txt", "photos".to_string()), ("Backup/only_backup.txt", "backup".to_string())];
+        for idx in 0..8 {
+            files.extend(["Photos/", "Backup/"].map(|folder| (folder, format!("file{idx}"))));
+        }
+        files.extend([("Photos/sub/", "sub".to_string()), ("Backup/sub/", "sub".to_string()), ("Other/", "file0".to_string())]);
+        for (path, content) in files {
+            let path = if path.ends_with('/') {
+                dir.path().join(path).join(&content)
+            } else {
+                dir.path().join(path)
+            };
+            fs::create_dir_all(path.parent().expect("No parent"))?;
+            File::create(&path)?.write_all(content.as_bytes())?;
+        }
+
+        let params = DuplicateFinderParameters::new(CheckingMethod::Hash, HashType::Blake3, false, false, 0, 0, false, false, false, Some(0.8));
+        let mut finder = DuplicateFinder::new(params);
+        finder.set_included_directory(vec![dir.path().to_path_buf()]);
+        finder.set_minimal_file_size(1);
+        finder.set_use_cache(false);
+        finder.search(&Arc::default(), None);
+
+        // Photos/sub and Backup/sub are identical, but they are hidden, because their parents are similar
+        let similar_directories = finder.get_similar_directories();
+        assert_eq!(similar_directories.len(), 1, "{similar_directories:?}");
+        let similar = &similar_directories[0];
+        assert_eq!(similar.first.path, dir.path().join("Backup"));
+        assert_eq!(similar.second.path, dir.path().join("Photos"));
+        assert_eq!((similar.shared_files_number, similar.shared_size), (9, 8 * 5 + 3));
+        assert_eq!((similar.unique_files_number_first, similar.unique_size_first), (1, 6));
+        assert_eq!((similar.unique_files_number_second, similar.unique_size_second), (1, 6));
+        assert!((similar.similarity - 9.0 / 11.0).abs() < 0.0001);
+        Ok(())
+    }
+
+    #[test]
+    fn test_similar_directories_count_filtered_files() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        for idx in 0..4 {
+            for folder in ["Photos", "Backup"] {
+                let path = dir.path().join(folder).join(format!("{idx}.jpg"));
+                fs::create_dir_all(path.parent().expect("No parent"))?;
+                File::create(&path)?.write_all(&[idx; 10_000])?;
+            }
+        }
+        // Small files are skipped while searching, but they make directories different
+        for idx in 0..4 {
+            File::create(dir.path().join("Photos").join(format!("{idx}.txt")))?.write_all(b"note")?;
+        }
+
+        let params = DuplicateFinderParameters::new(CheckingMethod::Hash, HashType::Blake3, false, false, 0, 0, false, false, false, Some(0.6));
+        let mut finder = DuplicateFinder::new(params);
+        finder.set_included_directory(vec![dir.path().to_path_buf()]);
+        finder.set_use_cache(false);
+        finder.search(&Arc::default(), None);
+
+        assert!(finder.get_similar_directories().is_empty(), "{:?}", finder.get_similar_directories());
+        Ok(())
+    }
+
+    #[test]
+    fn test_verification_stage_is_counted_only_when_enabled() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        for name in ["a", "b"] {
+            File::create(dir.path().join(name))?.write_all(b"abcd")?;
+        }
+
+        for verify_content in [false, true] {
+            let params = DuplicateFinderParameters::new(CheckingMethod::Hash, HashType::Blake3, false, false, 0, 0, false, verify_content, false, None);
+            let mut finder = DuplicateFinder::new(params);
+            finder.set_included_directory(vec![dir.path().to_path_buf()]);
+            finder.set_minimal_file_size(1);
+            finder.set_use_cache(false);
+            let (progress_sender, progress_receiver) = crossbeam_channel::unbounded();
+            finder.search(&Arc::default(), Some(&progress_sender));
+
+            let max_stages = progress_receiver.try_iter().map(|progress| progress.max_stage_idx).collect::<Vec<_>>();
+            assert!(!max_stages.is_empty());
+            assert!(max_stages.iter().all(|max_stage| *max_stage == 6 + u8::from(verify_content)), "{max_stages:?}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_sampled_groups_are_not_deleted_without_verification() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        // Files differ only outside of sampled regions
+        let mut content = vec![0u8; 4 * SAMPLED_HASH_REGION_SIZE as usize];
+        File::create(dir.path().join("a"))?.write_all(&content)?;
+        content[SAMPLED_HASH_REGION_SIZE as usize + 1] = 1;
+        File::create(dir.path().join("b"))?.write_all(&content)?;
+
+        for verify_content in [false, true] {
+            let params = DuplicateFinderParameters::new(CheckingMethod::SampledHash, HashType::Blake3, false, false, 0, 0, false, verify_content, false, None);
+            let mut finder = DuplicateFinder::new(params);
+            finder.set_included_directory(vec![dir.path().to_path_buf()]);
+            finder.set_use_cache(false);
+            finder.set_delete_method(DeleteMethod::AllExceptNewest);
+            finder.search(&Arc::default(), None);
+
+            assert_eq!(finder.contains_only_sampled_groups(), !verify_content);
+            assert_eq!(finder.get_files_sorted_by_hash().len(), usize::from(!verify_content));
+            assert!(dir.path().join("a").exists() && dir.path().join("b").exists());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_dismissed_pair_is_not_grouped_with_third_copy() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        for name in ["a", "b", "c"] {
+            File::create(dir.path().join(name))?.write_all(b"same content")?;
+        }
+
+        for check_method in [CheckingMethod::Size, CheckingMethod::Hash] {
+            let params = DuplicateFinderParameters::new(check_method, HashType::Blake3, false, false, 0, 0, false, false, false, None);
+            let mut finder = DuplicateFinder::new(params);
+            finder.set_included_directory(vec![dir.path().to_path_buf()]);
+            finder.set_minimal_file_size(1);
+            finder.set_use_cache(false);
+            finder.prepare_items();
+            finder
+                .common_data
+                .ignored_groups
+                .add_group(ToolType::Duplicate, ["a", "b"].map(|name| dir.path().join(name)));
+
+            assert_eq!(finder.check_files_size(&Arc::default(), None), WorkContinueStatus::Continue);
+            let groups = if check_method == CheckingMethod::Hash {
+                assert_eq!(finder.check_files_hash(&Arc::default(), None), WorkContinueStatus::Continue);
+                finder.get_files_sorted_by_hash().values().flatten().cloned().collect::<Vec<_>>()
+            } else {
+                finder.get_files_sorted_by_size().values().flatten().cloned().collect::<Vec<_>>()
+            };
+            assert_eq!(groups.len(), 1, "{check_method:?}");
+            let mut paths = groups[0].iter().map(|e| e.path.clone()).collect::<Vec<_>>();
+            paths.sort();
+            assert_eq!(paths.len(), 2, "{check_method:?}");
+            assert_eq!(paths[1], dir.path().join("c"), "{check_method:?}");
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_hash_calculation_invalid_file() -> io::Result<()> {
         let dir = tempfile::Builder::new().tempdir()?;
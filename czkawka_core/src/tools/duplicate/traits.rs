@@ -1,5 +1,6 @@
 use std::io::prelude::*;
 use std::io::{self};
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::Instant;
@@ -10,9 +11,11 @@ use humansize::{BINARY, format_size};
 
 use crate::common::model::{CheckingMethod, WorkContinueStatus};
 use crate::common::progress_data::ProgressData;
-use crate::common::tool_data::{CommonData, CommonToolData, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
-use crate::tools::duplicate::{DuplicateFinder, DuplicateFinderParameters, Info};
+use crate::common::remove_folder_if_contains_only_empty_folders;
+use crate::common::results_file::{check_if_entry_is_unchanged, retain_unchanged_entries, retain_unchanged_groups, retain_unchanged_referenced_groups};
+use crate::common::tool_data::{CommonData, CommonToolData, DeleteItemType, DeleteMethod, get_entries_to_delete};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PersistentResults, PrintResults, Search};
+use crate::tools::duplicate::{DirectoryEntry, DuplicateEntry, DuplicateFinder, DuplicateFinderParameters, DuplicateResults, Info};
 
 impl AllTraits for DuplicateFinder {}
 
@@ -22,18 +25,191 @@ impl DeletingItems for DuplicateFinder {
         if self.common_data.delete_method == DeleteMethod::None {
             return WorkContinueStatus::Continue;
         }
+        // Files matched only by sampled hash may still differ, so removing them could lose data
+        if self.contains_only_sampled_groups() {
+            self.common_data
+                .text_messages
+                .warnings
+                .push("Files compared only by sampled hashes may still differ, so they were left untouched - verify content of groups to delete or link them".to_string());
+            return WorkContinueStatus::Continue;
+        }
+
+        let deleted_directories = self.delete_directories(stop_flag, progress_sender);
+        if deleted_directories.is_none() {
+            return WorkContinueStatus::Stop;
+        }
 
-        let files_to_delete = match self.get_params().check_method {
-            CheckingMethod::Name => self.files_with_identical_names.values().cloned().collect::<Vec<_>>(),
-            CheckingMethod::SizeName => self.files_with_identical_size_names.values().cloned().collect::<Vec<_>>(),
-            CheckingMethod::Hash => self.files_with_identical_hashes.values().flatten().cloned().collect::<Vec<_>>(),
-            CheckingMethod::Size => self.files_with_identical_size.values().cloned().collect::<Vec<_>>(),
+        let mut files_to_delete = match self.get_params().check_method {
+            CheckingMethod::Name => self.files_with_identical_names.values().flatten().cloned().collect::<Vec<_>>(),
+            CheckingMethod::SizeName => self.files_with_identical_size_names.values().flatten().cloned().collect::<Vec<_>>(),
+            CheckingMethod::Hash | CheckingMethod::SampledHash => self.files_with_identical_hashes.values().flatten().cloned().collect::<Vec<_>>(),
+            CheckingMethod::Size => self.files_with_identical_size.values().flatten().cloned().collect::<Vec<_>>(),
             _ => panic!(),
         };
+        // Files from already removed directories, cannot be processed again
+        if let Some(deleted_directories) = deleted_directories.filter(|e| !e.is_empty()) {
+            files_to_delete = files_to_delete
+                .into_iter()
+                .map(|group| {
+                    group
+                        .into_iter()
+                        .filter(|e| !deleted_directories.iter().any(|directory| e.path.starts_with(directory)))
+                        .collect::<Vec<_>>()
+                })
+                .filter(|group| group.len() > 1)
+                .collect();
+        }
         self.delete_advanced_elements_and_add_to_messages(stop_flag, progress_sender, files_to_delete)
     }
 }
 
+impl DuplicateFinder {
+    // Returns removed directories, or None when stop was requested
+    fn delete_directories(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> Option<Vec<PathBuf>> {
+        if self.duplicated_directories.is_empty() && self.directories_contained_in_other.is_empty() {
+            return Some(Vec::new());
+        }
+        let delete_method = self.common_data.delete_method;
+        if matches!(delete_method, DeleteMethod::HardLink | DeleteMethod::Reflink | DeleteMethod::Symlink { .. }) {
+            self.common_data
+                .text_messages
+                .warnings
+                .push("Linking is not supported for duplicated directories, so they were left untouched".to_string());
+            return Some(Vec::new());
+        }
+
+        let mut directories_to_delete = self
+            .duplicated_directories
+            .iter()
+            .flat_map(|group| get_entries_to_delete(delete_method, group.clone()))
+            .collect::<Vec<_>>();
+        // Container is never removed, because it contains also unique files, so it is the kept copy of contained directories
+        for (_container, contained) in &self.directories_contained_in_other {
+            match delete_method {
+                DeleteMethod::OneOldest | DeleteMethod::OneNewest | DeleteMethod::OneBiggest | DeleteMethod::OneSmallest => {
+                    directories_to_delete.extend(get_entries_to_delete(delete_method, contained.clone()));
+                }
+                _ => directories_to_delete.extend(contained.iter().cloned()),
+            }
+        }
+        let deleted_directories: Vec<PathBuf> = directories_to_delete.iter().map(|e| e.path.clone()).collect();
+
+        // Only files known to be duplicates are removed, directories are removed later only when nothing else is left inside them
+        let files_to_delete = directories_to_delete.into_iter().flat_map(|e| e.files).collect::<Vec<_>>();
+        if self.delete_simple_elements_and_add_to_messages(stop_flag, progress_sender, DeleteItemType::DeletingFiles(files_to_delete)) == WorkContinueStatus::Stop {
+            return None;
+        }
+
+        if !self.common_data.dry_run {
+            for directory in &deleted_directories {
+                if let Err(e) = remove_folder_if_contains_only_empty_folders(directory, false) {
+                    self.common_data.text_messages.warnings.push(format!(
+                        "Directory \"{}\" was not removed, because it still contains files - {e}",
+                        directory.to_string_lossy()
+                    ));
+                }
+            }
+        }
+        Some(deleted_directories)
+    }
+
+    fn write_directories_results<T: Write>(&self, writer: &mut T) -> io::Result<()> {
+        if !self.duplicated_directories.is_empty() {
+            writeln!(
+                writer,
+                "-------------------------------------------------Directories with same content-------------------------------------------------"
+            )?;
+            writeln!(
+                writer,
+                "Found {} duplicated directories which in {} groups which takes {}.",
+                self.information.number_of_duplicated_directories,
+                self.information.number_of_groups_of_duplicated_directories,
+                format_size(self.information.lost_space_by_directories, BINARY)
+            )?;
+            for group in &self.duplicated_directories {
+                if let Some(first) = group.first() {
+                    writeln!(
+                        writer,
+                        "\n---- Size {} ({}) - {} files - {} directories",
+                        format_size(first.size, BINARY),
+                        first.size,
+                        first.files_number,
+                        group.len()
+                    )?;
+                }
+                for directory in group {
+                    writeln!(writer, "\"{}\"", directory.path.to_string_lossy())?;
+                }
+            }
+            writeln!(writer)?;
+        }
+        if !self.directories_contained_in_other.is_empty() {
+            writeln!(
+                writer,
+                "-------------------------------------------------Directories contained in other directories-------------------------------------------------"
+            )?;
+            writeln!(
+                writer,
+                "Found {} directories, whose whole content can be found in other directories.",
+                self.information.number_of_directories_contained_in_other
+            )?;
+            for (container, contained) in &self.directories_contained_in_other {
+                writeln!(
+                    writer,
+                    "\n---- \"{}\" - {} files, {}",
+                    container.path.to_string_lossy(),
+                    container.files_number,
+                    format_size(container.size, BINARY)
+                )?;
+                for directory in contained {
+                    writeln!(
+                        writer,
+                        "\"{}\" - {} files, {}",
+                        directory.path.to_string_lossy(),
+                        directory.files_number,
+                        format_size(directory.size, BINARY)
+                    )?;
+                }
+            }
+            writeln!(writer)?;
+        }
+        if !self.similar_directories.is_empty() {
+            writeln!(
+                writer,
+                "-------------------------------------------------Similar directories-------------------------------------------------"
+            )?;
+            writeln!(
+                writer,
+                "Found {} pairs of directories with similar content.",
+                self.information.number_of_similar_directories
+            )?;
+            for similar in &self.similar_directories {
+                writeln!(
+                    writer,
+                    "\n---- Similarity {:.2}% - {} shared files({})",
+                    similar.similarity * 100.0,
+                    similar.shared_files_number,
+                    format_size(similar.shared_size, BINARY)
+                )?;
+                for (directory, unique_files_number, unique_size) in [
+                    (&similar.first, similar.unique_files_number_first, similar.unique_size_first),
+                    (&similar.second, similar.unique_files_number_second, similar.unique_size_second),
+                ] {
+                    writeln!(
+                        writer,
+                        "\"{}\" - {} unique files({})",
+                        directory.path.to_string_lossy(),
+                        unique_files_number,
+                        format_size(unique_size, BINARY)
+                    )?;
+                }
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+}
+
 impl Search for DuplicateFinder {
     #[fun_time(message = "find_duplicates", level = "info")]
     fn search(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) {
@@ -64,7 +240,7 @@ impl Search for DuplicateFinder {
                     return;
                 }
             }
-            CheckingMethod::Hash => {
+            CheckingMethod::Hash | CheckingMethod::SampledHash => {
                 self.common_data.stopped_search = self.check_files_size(stop_flag, progress_sender) == WorkContinueStatus::Stop;
                 if self.common_data.stopped_search {
                     self.information.scanning_time = start_time.elapsed().as_millis();
@@ -103,10 +279,21 @@ impl DebugPrint for DuplicateFinder {
             "Number of duplicated files by hash(in groups) - {} ({})",
             self.information.number_of_duplicated_files_by_hash, self.information.number_of_groups_by_hash
         );
+        println!(
+            "Number of groups verified by content - {} ({} hash groups were split)",
+            self.information.number_of_groups_verified_by_content, self.information.number_of_groups_split_by_content
+        );
         println!(
             "Number of duplicated files by name(in groups) - {} ({})",
             self.information.number_of_duplicated_files_by_name, self.information.number_of_groups_by_name
         );
+        println!(
+            "Number of duplicated directories(in groups) - {} ({}), contained in other directories - {}, pairs of similar directories - {}",
+            self.information.number_of_duplicated_directories,
+            self.information.number_of_groups_of_duplicated_directories,
+            self.information.number_of_directories_contained_in_other,
+            self.information.number_of_similar_directories
+        );
         println!(
             "Lost space by size - {} ({} bytes)",
             format_size(self.information.lost_space_by_size, BINARY),
@@ -157,12 +344,14 @@ impl PrintResults for DuplicateFinder {
                         "Found {} files in {} groups with same name(may have different content)",
                         self.information.number_of_duplicated_files_by_name, self.information.number_of_groups_by_name,
                     )?;
-                    for (name, vector) in self.files_with_identical_names.iter().rev() {
-                        writeln!(writer, "Name - {} - {} files ", name, vector.len())?;
-                        for j in vector {
-                            writeln!(writer, "\"{}\"", j.path.to_string_lossy())?;
+                    for (name, vectors_vector) in self.files_with_identical_names.iter().rev() {
+                        for vector in vectors_vector {
+                            writeln!(writer, "Name - {} - {} files ", name, vector.len())?;
+                            for j in vector {
+                                writeln!(writer, "\"{}\"", j.path.to_string_lossy())?;
+                            }
+                            writeln!(writer)?;
                         }
-                        writeln!(writer)?;
                     }
                 } else if !self.files_with_identical_names_referenced.is_empty() {
                     writeln!(
@@ -197,12 +386,14 @@ impl PrintResults for DuplicateFinder {
                         "Found {} files in {} groups with same size and name(may have different content)",
                         self.information.number_of_duplicated_files_by_size_name, self.information.number_of_groups_by_size_name,
                     )?;
-                    for ((size, name), vector) in self.files_with_identical_size_names.iter().rev() {
-                        writeln!(writer, "Name - {}, {} - {} files ", name, format_size(*size, BINARY), vector.len())?;
-                        for j in vector {
-                            writeln!(writer, "\"{}\"", j.path.to_string_lossy())?;
+                    for ((size, name), vectors_vector) in self.files_with_identical_size_names.iter().rev() {
+                        for vector in vectors_vector {
+                            writeln!(writer, "Name - {}, {} - {} files ", name, format_size(*size, BINARY), vector.len())?;
+                            for j in vector {
+                                writeln!(writer, "\"{}\"", j.path.to_string_lossy())?;
+                            }
+                            writeln!(writer)?;
                         }
-                        writeln!(writer)?;
                     }
                 } else if !self.files_with_identical_names_referenced.is_empty() {
                     writeln!(
@@ -239,10 +430,12 @@ impl PrintResults for DuplicateFinder {
                         self.information.number_of_groups_by_size,
                         format_size(self.information.lost_space_by_size, BINARY)
                     )?;
-                    for (size, vector) in self.files_with_identical_size.iter().rev() {
-                        write!(writer, "\n---- Size {} ({}) - {} files \n", format_size(*size, BINARY), size, vector.len())?;
-                        for file_entry in vector {
-                            writeln!(writer, "\"{}\"", file_entry.path.to_string_lossy())?;
+                    for (size, vectors_vector) in self.files_with_identical_size.iter().rev() {
+                        for vector in vectors_vector {
+                            write!(writer, "\n---- Size {} ({}) - {} files \n", format_size(*size, BINARY), size, vector.len())?;
+                            for file_entry in vector {
+                                writeln!(writer, "\"{}\"", file_entry.path.to_string_lossy())?;
+                            }
                         }
                     }
                 } else if !self.files_with_identical_size_referenced.is_empty() {
@@ -268,7 +461,14 @@ impl PrintResults for DuplicateFinder {
                     write!(writer, "Not found any duplicates.")?;
                 }
             }
-            CheckingMethod::Hash => {
+            CheckingMethod::Hash | CheckingMethod::SampledHash => {
+                if self.contains_only_sampled_groups() {
+                    writeln!(
+                        writer,
+                        "Results are based on sampled hashes - only beginning, middle and end of files were compared, so files in groups may still differ."
+                    )?;
+                }
+                self.write_directories_results(writer)?;
                 if !self.files_with_identical_hashes.is_empty() {
                     writeln!(
                         writer,
@@ -310,7 +510,7 @@ impl PrintResults for DuplicateFinder {
                             }
                         }
                     }
-                } else {
+                } else if self.duplicated_directories.is_empty() && self.directories_contained_in_other.is_empty() && self.similar_directories.is_empty() {
                     write!(writer, "Not found any duplicates.")?;
                 }
             }
@@ -329,7 +529,9 @@ impl PrintResults for DuplicateFinder {
                     self.save_results_to_file_as_json_internal(file_name, &self.files_with_identical_size_names_referenced.values().collect::<Vec<_>>(), pretty_print)
                 }
                 CheckingMethod::Size => self.save_results_to_file_as_json_internal(file_name, &self.files_with_identical_size_referenced, pretty_print),
-                CheckingMethod::Hash => self.save_results_to_file_as_json_internal(file_name, &self.files_with_identical_hashes_referenced, pretty_print),
+                CheckingMethod::Hash | CheckingMethod::SampledHash => {
+                    self.save_results_to_file_as_json_internal(file_name, &self.files_with_identical_hashes_referenced, pretty_print)
+                }
                 _ => panic!(),
             }
         } else {
@@ -337,13 +539,112 @@ impl PrintResults for DuplicateFinder {
                 CheckingMethod::Name => self.save_results_to_file_as_json_internal(file_name, &self.files_with_identical_names, pretty_print),
                 CheckingMethod::SizeName => self.save_results_to_file_as_json_internal(file_name, &self.files_with_identical_size_names.values().collect::<Vec<_>>(), pretty_print),
                 CheckingMethod::Size => self.save_results_to_file_as_json_internal(file_name, &self.files_with_identical_size, pretty_print),
-                CheckingMethod::Hash => self.save_results_to_file_as_json_internal(file_name, &self.files_with_identical_hashes, pretty_print),
+                CheckingMethod::Hash | CheckingMethod::SampledHash => self.save_results_to_file_as_json_internal(file_name, &self.files_with_identical_hashes, pretty_print),
                 _ => panic!(),
             }
         }
     }
 }
 
+impl PersistentResults for DuplicateFinder {
+    type Results = DuplicateResults;
+
+    fn get_results_to_persist(&self) -> Self::Results {
+        DuplicateResults {
+            files_with_identical_names: self.files_with_identical_names.clone().into_iter().collect(),
+            files_with_identical_size_names: self.files_with_identical_size_names.clone().into_iter().collect(),
+            files_with_identical_size: self.files_with_identical_size.clone().into_iter().collect(),
+            files_with_identical_hashes: self.files_with_identical_hashes.clone().into_iter().collect(),
+            files_with_identical_names_referenced: self.files_with_identical_names_referenced.clone().into_iter().collect(),
+            files_with_identical_size_names_referenced: self.files_with_identical_size_names_referenced.clone().into_iter().collect(),
+            files_with_identical_size_referenced: self.files_with_identical_size_referenced.clone().into_iter().collect(),
+            files_with_identical_hashes_referenced: self.files_with_identical_hashes_referenced.clone().into_iter().collect(),
+            duplicated_directories: self.duplicated_directories.clone(),
+            directories_contained_in_other: self.directories_contained_in_other.clone(),
+            similar_directories: self.similar_directories.clone(),
+            groups_verified_by_content: self.groups_verified_by_content,
+        }
+    }
+
+    fn set_persisted_results(&mut self, results: Self::Results) {
+        self.groups_verified_by_content = results.groups_verified_by_content;
+        let warnings = &mut self.common_data.text_messages.warnings;
+
+        let mut retain_groups = |groups: Vec<Vec<DuplicateEntry>>| {
+            let groups = retain_unchanged_groups(groups, warnings);
+            (!groups.is_empty()).then_some(groups)
+        };
+        self.files_with_identical_names = results.files_with_identical_names.into_iter().filter_map(|(k, v)| Some((k, retain_groups(v)?))).collect();
+        self.files_with_identical_size_names = results
+            .files_with_identical_size_names
+            .into_iter()
+            .filter_map(|(k, v)| Some((k, retain_groups(v)?)))
+            .collect();
+        self.files_with_identical_size = results.files_with_identical_size.into_iter().filter_map(|(k, v)| Some((k, retain_groups(v)?))).collect();
+        self.files_with_identical_hashes = results.files_with_identical_hashes.into_iter().filter_map(|(k, v)| Some((k, retain_groups(v)?))).collect();
+
+        let mut retain_referenced_group = |(reference, group): (DuplicateEntry, Vec<DuplicateEntry>)| {
+            if let Err(e) = check_if_entry_is_unchanged(&reference) {
+                warnings.push(e);
+                return None;
+            }
+            let group = retain_unchanged_entries(group, warnings);
+            (!group.is_empty()).then_some((reference, group))
+        };
+        self.files_with_identical_names_referenced = results
+            .files_with_identical_names_referenced
+            .into_iter()
+            .filter_map(|(k, v)| Some((k, retain_referenced_group(v)?)))
+            .collect();
+        self.files_with_identical_size_names_referenced = results
+            .files_with_identical_size_names_referenced
+            .into_iter()
+            .filter_map(|(k, v)| Some((k, retain_referenced_group(v)?)))
+            .collect();
+        self.files_with_identical_size_referenced = results
+            .files_with_identical_size_referenced
+            .into_iter()
+            .filter_map(|(k, v)| Some((k, retain_referenced_group(v)?)))
+            .collect();
+
+        // Directory is used only when none of its duplicated files changed
+        let retain_existing_directories = |group: Vec<DirectoryEntry>| {
+            group
+                .into_iter()
+                .filter(|e| e.path.is_dir() && e.files.iter().all(|file_entry| check_if_entry_is_unchanged(file_entry).is_ok()))
+                .collect::<Vec<_>>()
+        };
+        self.duplicated_directories = results
+            .duplicated_directories
+            .into_iter()
+            .map(retain_existing_directories)
+            .filter(|group| group.len() > 1)
+            .collect();
+        self.directories_contained_in_other = results
+            .directories_contained_in_other
+            .into_iter()
+            .filter(|(container, _contained)| container.path.is_dir())
+            .map(|(container, contained)| (container, retain_existing_directories(contained)))
+            .filter(|(_container, contained)| !contained.is_empty())
+            .collect();
+        self.similar_directories = results
+            .similar_directories
+            .into_iter()
+            .filter(|e| e.first.path.is_dir() && e.second.path.is_dir())
+            .collect();
+        self.files_with_identical_hashes_referenced = results
+            .files_with_identical_hashes_referenced
+            .into_iter()
+            .filter_map(|(k, v)| {
+                let v = retain_unchanged_referenced_groups(v, warnings);
+                (!v.is_empty()).then_some((k, v))
+            })
+            .collect();
+
+        self.recalculate_stats();
+    }
+}
+
 impl CommonData for DuplicateFinder {
     type Info = Info;
     type Parameters = DuplicateFinderParameters;
@@ -363,10 +664,17 @@ impl CommonData for DuplicateFinder {
     fn get_check_method(&self) -> CheckingMethod {
         self.get_params().check_method
     }
+    // Content is verified only after hashing
+    fn has_optional_stage(&self) -> bool {
+        self.get_params().verify_content && [CheckingMethod::Hash, CheckingMethod::SampledHash].contains(&self.get_params().check_method)
+    }
     fn found_any_broken_files(&self) -> bool {
         self.get_information().number_of_duplicated_files_by_hash > 0
             || self.get_information().number_of_duplicated_files_by_name > 0
             || self.get_information().number_of_duplicated_files_by_size > 0
             || self.get_information().number_of_duplicated_files_by_size_name > 0
+            || self.get_information().number_of_duplicated_directories > 0
+            || self.get_information().number_of_directories_contained_in_other > 0
+            || self.get_information().number_of_similar_directories > 0
     }
 }
@@ -5,10 +5,11 @@ use std::sync::atomic::AtomicBool;
 use crossbeam_channel::Sender;
 use fun_time::fun_time;
 
-use crate::common::model::WorkContinueStatus;
+use crate::common::model::{FileEntry, WorkContinueStatus};
 use crate::common::progress_data::ProgressData;
+use crate::common::results_file::retain_unchanged_entries;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteItemType, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PersistentResults, PrintResults, Search};
 use crate::tools::empty_files::{EmptyFiles, Info};
 
 impl AllTraits for EmptyFiles {}
@@ -96,3 +97,16 @@ impl DeletingItems for EmptyFiles {
         }
     }
 }
+
+impl PersistentResults for EmptyFiles {
+    type Results = Vec<FileEntry>;
+
+    fn get_results_to_persist(&self) -> Self::Results {
+        self.empty_files.clone()
+    }
+
+    fn set_persisted_results(&mut self, results: Self::Results) {
+        self.empty_files = retain_unchanged_entries(results, &mut self.common_data.text_messages.warnings);
+        self.information.number_of_empty_files = self.empty_files.len();
+    }
+}
@@ -12,6 +12,7 @@ use rayon::prelude::*;
 use crate::common::dir_traversal::{common_get_entry_data, common_get_metadata_dir, common_read_dir, get_modified_time};
 use crate::common::directories::Directories;
 use crate::common::items::ExcludedItems;
+use crate::common::metadata_filter::MetadataFilter;
 use crate::common::model::{ToolType, WorkContinueStatus};
 use crate::common::progress_data::{CurrentStage, ProgressData};
 use crate::common::progress_stop_handler::{check_if_stop_received, prepare_thread_handler_common};
@@ -56,18 +57,20 @@ impl EmptyFolder {
 
     #[fun_time(message = "check_for_empty_folders", level = "debug")]
     pub(crate) fn check_for_empty_folders(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
-        let mut folders_to_check: Vec<PathBuf> = self.common_data.directories.included_directories.clone();
+        let mut folders_to_check: Vec<(PathBuf, usize)> = self.common_data.directories.included_directories.iter().map(|dir| (dir.clone(), 0)).collect();
 
         let progress_handler = prepare_thread_handler_common(progress_sender, CurrentStage::CollectingFiles, 0, self.get_test_type(), 0);
 
         let excluded_items = self.common_data.excluded_items.clone();
         let directories = self.common_data.directories.clone();
+        let metadata_filter = self.common_data.metadata_filter.clone();
+        let max_depth = self.common_data.max_depth;
 
         let mut non_empty_folders: Vec<String> = vec![];
 
         let mut start_folder_entries = Vec::with_capacity(folders_to_check.len());
         let mut new_folder_entries_list = Vec::new();
-        for dir in &folders_to_check {
+        for (dir, _) in &folders_to_check {
             start_folder_entries.push(FolderEntry {
                 path: dir.clone(),
                 parent_path: None,
@@ -84,16 +87,17 @@ impl EmptyFolder {
 
             let segments: Vec<_> = folders_to_check
                 .into_par_iter()
-                .map(|current_folder| {
+                .map(|(current_folder, depth)| {
                     let mut dir_result = vec![];
                     let mut warnings = vec![];
                     let mut non_empty_folder = None;
                     let mut folder_entries_list = vec![];
+                    let check_subfolders = max_depth.is_none_or(|max_depth| depth < max_depth);
 
                     let current_folder_as_string = current_folder.to_string_lossy().to_string();
 
                     let Some(read_dir) = common_read_dir(&current_folder, &mut warnings) else {
-                        return (dir_result, warnings, Some(current_folder_as_string), folder_entries_list);
+                        return (dir_result, depth, warnings, Some(current_folder_as_string), folder_entries_list);
                     };
 
                     let mut counter = 0;
@@ -104,7 +108,12 @@ impl EmptyFolder {
                         };
                         let Ok(file_type) = entry_data.file_type() else { continue };
 
-                        if file_type.is_dir() {
+                        // Content of folders below maximal depth is unknown, so their parent cannot be reported as empty
+                        if file_type.is_dir() && !check_subfolders {
+                            if non_empty_folder.is_none() {
+                                non_empty_folder = Some(current_folder_as_string.clone());
+                            }
+                        } else if file_type.is_dir() {
                             counter += 1;
                             Self::process_dir_in_dir_mode(
                                 &current_folder,
@@ -114,6 +123,7 @@ impl EmptyFolder {
                                 &mut dir_result,
                                 &mut warnings,
                                 &excluded_items,
+                                &metadata_filter,
                                 &mut non_empty_folder,
                                 &mut folder_entries_list,
                             );
@@ -126,16 +136,16 @@ impl EmptyFolder {
                         progress_handler.increase_items(counter);
                     }
 
-                    (dir_result, warnings, non_empty_folder, folder_entries_list)
+                    (dir_result, depth, warnings, non_empty_folder, folder_entries_list)
                 })
                 .collect();
 
-            let required_size = segments.iter().map(|(segment, _, _, _)| segment.len()).sum::<usize>();
+            let required_size = segments.iter().map(|(segment, _, _, _, _)| segment.len()).sum::<usize>();
             folders_to_check = Vec::with_capacity(required_size);
 
             // Process collected data
-            for (segment, warnings, non_empty_folder, fe_list) in segments {
-                folders_to_check.extend(segment);
+            for (segment, depth, warnings, non_empty_folder, fe_list) in segments {
+                folders_to_check.extend(segment.into_iter().map(|folder| (folder, depth + 1)));
                 if !warnings.is_empty() {
                     self.common_data.text_messages.warnings.extend(warnings);
                 }
@@ -204,6 +214,7 @@ impl EmptyFolder {
         dir_result: &mut Vec<PathBuf>,
         warnings: &mut Vec<String>,
         excluded_items: &ExcludedItems,
+        metadata_filter: &MetadataFilter,
         non_empty_folder: &mut Option<String>,
         folder_entries_list: &mut Vec<FolderEntry>,
     ) {
@@ -231,12 +242,56 @@ impl EmptyFolder {
             return;
         };
 
+        let modified_date = get_modified_time(&metadata, warnings, current_folder, true);
+        // Folder not matching filters is never reported and, as with excluded folders, its parent cannot be removed
+        // Its subfolders are still checked, because they may match filters
+        let is_allowed = metadata_filter.is_allowed(&metadata, modified_date);
+        if !is_allowed && non_empty_folder.is_none() {
+            *non_empty_folder = Some(current_folder_as_str.to_string());
+        }
+
         dir_result.push(next_folder.clone());
         folder_entries_list.push(FolderEntry {
             path: next_folder,
             parent_path: Some(current_folder_as_str.to_string()),
-            is_empty: FolderEmptiness::Maybe,
-            modified_date: get_modified_time(&metadata, warnings, current_folder, true),
+            is_empty: if is_allowed { FolderEmptiness::Maybe } else { FolderEmptiness::No },
+            modified_date,
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io;
+
+    use super::*;
+    use crate::common::traits::Search;
+
+    #[test]
+    fn test_max_depth() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        fs::create_dir_all(dir.path().join("a").join("b").join("c"))?;
+        fs::create_dir_all(dir.path().join("x"))?;
+        fs::write(dir.path().join("file.txt"), b"a")?;
+
+        let find = |max_depth: Option<usize>| {
+            let mut empty_folder = EmptyFolder::new();
+            empty_folder.set_included_directory(vec![dir.path().to_path_buf()]);
+            empty_folder.set_max_depth(max_depth);
+            empty_folder.search(&Arc::default(), None);
+            let mut paths: Vec<_> = empty_folder
+                .get_empty_folder_list()
+                .values()
+                .map(|fe| fe.path.strip_prefix(dir.path()).unwrap().to_path_buf())
+                .collect();
+            paths.sort();
+            paths
+        };
+
+        assert_eq!(find(None), vec![PathBuf::from("a"), PathBuf::from("x")]);
+        // Content of "a/b" is not checked, so "a" cannot be reported as empty
+        assert_eq!(find(Some(1)), vec![PathBuf::from("x")]);
+        Ok(())
+    }
+}
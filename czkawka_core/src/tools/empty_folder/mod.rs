@@ -4,11 +4,12 @@ pub mod traits;
 use std::path::{Path, PathBuf};
 
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 
 use crate::common::tool_data::CommonToolData;
 use crate::common::traits::ResultEntry;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FolderEntry {
     pub path: PathBuf,
     pub(crate) parent_path: Option<String>,
@@ -39,7 +40,7 @@ pub struct EmptyFolder {
 
 /// Enum with values which show if folder is empty.
 /// In function "`optimize_folders`" automatically "Maybe" is changed to "Yes", so it is not necessary to put it here
-#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
 pub(crate) enum FolderEmptiness {
     No,
     Maybe,
@@ -8,9 +8,10 @@ use rayon::prelude::*;
 
 use crate::common::model::WorkContinueStatus;
 use crate::common::progress_data::ProgressData;
+use crate::common::results_file::retain_unchanged_entries;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteItemType, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
-use crate::tools::empty_folder::{EmptyFolder, Info};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PersistentResults, PrintResults, Search};
+use crate::tools::empty_folder::{EmptyFolder, FolderEntry, Info};
 
 impl AllTraits for EmptyFolder {}
 
@@ -102,3 +103,19 @@ impl DeletingItems for EmptyFolder {
         }
     }
 }
+
+impl PersistentResults for EmptyFolder {
+    type Results = Vec<FolderEntry>;
+
+    fn get_results_to_persist(&self) -> Self::Results {
+        self.empty_folder_list.values().cloned().collect()
+    }
+
+    fn set_persisted_results(&mut self, results: Self::Results) {
+        self.empty_folder_list = retain_unchanged_entries(results, &mut self.common_data.text_messages.warnings)
+            .into_iter()
+            .map(|folder_entry| (folder_entry.path.to_string_lossy().to_string(), folder_entry))
+            .collect();
+        self.information.number_of_empty_folders = self.empty_folder_list.len();
+    }
+}
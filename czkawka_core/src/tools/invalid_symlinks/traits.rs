@@ -7,9 +7,10 @@ use fun_time::fun_time;
 
 use crate::common::model::WorkContinueStatus;
 use crate::common::progress_data::ProgressData;
+use crate::common::results_file::retain_unchanged_entries;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteItemType, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
-use crate::tools::invalid_symlinks::{ErrorType, Info, InvalidSymlinks};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PersistentResults, PrintResults, Search};
+use crate::tools::invalid_symlinks::{ErrorType, Info, InvalidSymlinks, SymlinksFileEntry};
 
 impl AllTraits for InvalidSymlinks {}
 
@@ -98,3 +99,16 @@ impl DeletingItems for InvalidSymlinks {
         }
     }
 }
+
+impl PersistentResults for InvalidSymlinks {
+    type Results = Vec<SymlinksFileEntry>;
+
+    fn get_results_to_persist(&self) -> Self::Results {
+        self.invalid_symlinks.clone()
+    }
+
+    fn set_persisted_results(&mut self, results: Self::Results) {
+        self.invalid_symlinks = retain_unchanged_entries(results, &mut self.common_data.text_messages.warnings);
+        self.information.number_of_invalid_symlinks = self.invalid_symlinks.len();
+    }
+}
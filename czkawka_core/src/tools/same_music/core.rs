@@ -355,23 +355,13 @@ impl SameMusic {
 
         progress_handler.join_thread();
 
-        self.duplicated_music_entries = old_duplicates;
+        self.duplicated_music_entries = self.common_data.get_ignored_groups_filter().filter_groups(old_duplicates);
 
         if self.common_data.use_reference_folders {
             self.duplicated_music_entries_referenced = self.common_data.directories.filter_reference_folders(mem::take(&mut self.duplicated_music_entries));
         }
 
-        if self.common_data.use_reference_folders {
-            for (_fe, vector) in &self.duplicated_music_entries_referenced {
-                self.information.number_of_duplicates += vector.len();
-                self.information.number_of_groups += 1;
-            }
-        } else {
-            for vector in &self.duplicated_music_entries {
-                self.information.number_of_duplicates += vector.len() - 1;
-                self.information.number_of_groups += 1;
-            }
-        }
+        self.calculate_stats();
 
         // Clear unused data
         self.music_entries.clear();
@@ -445,6 +435,8 @@ impl SameMusic {
         let maximum_difference = self.params.maximum_difference;
 
         let mut duplicated_music_entries = Vec::new();
+        // Borrowed directly from field, because errors are added to messages while filter is used
+        let ignored_groups_filter = self.common_data.ignored_groups.get_filter(self.common_data.tool_type);
 
         for f_entry in base_files {
             items_counter.fetch_add(1, Ordering::Relaxed);
@@ -461,7 +453,7 @@ impl SameMusic {
                 .par_iter()
                 .map(|e_entry| {
                     let e_string = e_entry.path.to_string_lossy().to_string();
-                    if used_paths.contains(&e_string) || e_string == f_string {
+                    if used_paths.contains(&e_string) || e_string == f_string || ignored_groups_filter.is_pair_ignored(&f_entry.path, &e_entry.path) {
                         return None;
                     }
                     let mut segments = match match_fingerprints(&f_entry.fingerprint, &e_entry.fingerprint, configuration) {
@@ -517,12 +509,23 @@ impl SameMusic {
 
         progress_handler.join_thread();
 
-        self.duplicated_music_entries = duplicated_music_entries;
+        self.duplicated_music_entries = self.common_data.get_ignored_groups_filter().filter_groups(duplicated_music_entries);
 
         if self.common_data.use_reference_folders {
             self.duplicated_music_entries_referenced = self.common_data.directories.filter_reference_folders(mem::take(&mut self.duplicated_music_entries));
         }
 
+        self.calculate_stats();
+
+        // Clear unused data
+        self.music_entries.clear();
+
+        WorkContinueStatus::Continue
+    }
+
+    pub(crate) fn calculate_stats(&mut self) {
+        self.information.number_of_duplicates = 0;
+        self.information.number_of_groups = 0;
         if self.common_data.use_reference_folders {
             for (_fe, vector) in &self.duplicated_music_entries_referenced {
                 self.information.number_of_duplicates += vector.len();
@@ -534,11 +537,6 @@ impl SameMusic {
                 self.information.number_of_groups += 1;
             }
         }
-
-        // Clear unused data
-        self.music_entries.clear();
-
-        WorkContinueStatus::Continue
     }
 
     #[fun_time(message = "check_music_item", level = "debug")]
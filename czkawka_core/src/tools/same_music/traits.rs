@@ -7,8 +7,9 @@ use fun_time::fun_time;
 
 use crate::common::model::{CheckingMethod, WorkContinueStatus};
 use crate::common::progress_data::ProgressData;
+use crate::common::results_file::{GroupedResults, retain_unchanged_groups, retain_unchanged_referenced_groups};
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PersistentResults, PrintResults, Search};
 use crate::tools::same_music::{Info, MusicEntry, SameMusic, SameMusicParameters};
 
 impl AllTraits for SameMusic {}
@@ -148,6 +149,23 @@ impl CommonData for SameMusic {
     }
 }
 
+impl PersistentResults for SameMusic {
+    type Results = GroupedResults<MusicEntry>;
+
+    fn get_results_to_persist(&self) -> Self::Results {
+        GroupedResults {
+            groups: self.duplicated_music_entries.clone(),
+            referenced_groups: self.duplicated_music_entries_referenced.clone(),
+        }
+    }
+
+    fn set_persisted_results(&mut self, results: Self::Results) {
+        self.duplicated_music_entries = retain_unchanged_groups(results.groups, &mut self.common_data.text_messages.warnings);
+        self.duplicated_music_entries_referenced = retain_unchanged_referenced_groups(results.referenced_groups, &mut self.common_data.text_messages.warnings);
+        self.calculate_stats();
+    }
+}
+
 impl DeletingItems for SameMusic {
     #[fun_time(message = "delete_files", level = "debug")]
     fn delete_files(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
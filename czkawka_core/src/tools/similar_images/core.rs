@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
@@ -7,8 +7,9 @@ use std::{mem, panic};
 use bk_tree::BKTree;
 use crossbeam_channel::Sender;
 use fun_time::fun_time;
+use hamming_bitwise_fast::hamming_bitwise_fast;
 use humansize::{BINARY, format_size};
-use image::GenericImageView;
+use image::{DynamicImage, GenericImageView};
 use image_hasher::{FilterType, HashAlg, HasherConfig};
 use indexmap::{IndexMap, IndexSet};
 use log::{debug, error};
@@ -17,6 +18,7 @@ use rayon::prelude::*;
 use crate::common::cache::{CACHE_IMAGE_VERSION, extract_loaded_cache, load_cache_from_file_generalized_by_path, save_cache_to_file_generalized};
 use crate::common::consts::{HEIC_EXTENSIONS, IMAGE_RS_SIMILAR_IMAGES_EXTENSIONS, JXL_IMAGE_EXTENSIONS, RAW_IMAGE_EXTENSIONS};
 use crate::common::dir_traversal::{DirTraversalBuilder, DirTraversalResult, inode, take_1_per_inode};
+use crate::common::ignored_groups::IgnoredGroupsFilter;
 use crate::common::image::get_dynamic_image_from_path;
 use crate::common::model::{ToolType, WorkContinueStatus};
 use crate::common::progress_data::{CurrentStage, ProgressData};
@@ -24,7 +26,10 @@ use crate::common::progress_stop_handler::{check_if_stop_received, prepare_threa
 use crate::common::tool_data::{CommonData, CommonToolData};
 use crate::common::traits::ResultEntry;
 use crate::flc;
-use crate::tools::similar_images::{Hamming, ImHash, ImagesEntry, SIMILAR_VALUES, SimilarImages, SimilarImagesParameters, SimilarityPreset};
+use crate::tools::similar_images::{
+    CroppedImage, Hamming, HashCombineMode, ImHash, ImageCropHashesEntry, ImageTransform, ImagesEntry, ImagesGroupingMode, SIMILAR_VALUES, SimilarImages, SimilarImagesParameters,
+    SimilarityPreset,
+};
 
 impl SimilarImages {
     pub fn new(params: SimilarImagesParameters) -> Self {
@@ -34,6 +39,7 @@ impl SimilarImages {
             bktree: BKTree::new(Hamming),
             similar_vectors: vec![],
             similar_referenced_vectors: vec![],
+            cropped_images: vec![],
             params,
             images_to_check: Default::default(),
             image_hashes: Default::default(),
@@ -95,12 +101,21 @@ impl SimilarImages {
 
         if self.common_data.use_cache {
             let (messages, loaded_items) = load_cache_from_file_generalized_by_path::<ImagesEntry>(
-                &get_similar_images_cache_file(&self.get_params().hash_size, &self.get_params().hash_alg, &self.get_params().image_filter),
+                &get_similar_images_cache_file(
+                    &self.get_params().hash_size,
+                    &self.get_params().hash_alg,
+                    &self.get_params().image_filter,
+                    &self.get_params().get_additional_hash_algs(),
+                ),
                 self.get_delete_outdated_cache(),
                 &self.images_to_check,
             );
             self.get_text_messages_mut().extend_with_another_messages(messages);
-            loaded_hash_map = loaded_items.unwrap_or_default();
+            let mut loaded_items = loaded_items.unwrap_or_default();
+            if self.get_params().find_cropped_images {
+                self.load_crop_hashes_from_cache(&mut loaded_items);
+            }
+            loaded_hash_map = loaded_items;
 
             debug!("hash_images-load_cache - starting calculating diff");
             extract_loaded_cache(
@@ -109,6 +124,24 @@ impl SimilarImages {
                 &mut records_already_cached,
                 &mut non_cached_files_to_check,
             );
+            // Entries saved when rotations or crops were not checked, don't contain hashes of transformed images or image parts
+            let check_rotations = self.get_params().match_rotations_and_flips;
+            let check_crops = self.get_params().find_cropped_images;
+            if check_rotations || check_crops {
+                let crop_windows_number = get_crop_windows().len();
+                let check_transformed_additional_hashes = check_rotations && !self.get_params().additional_hash_algs.is_empty();
+                records_already_cached.retain(|path, entry| {
+                    if (!check_rotations || entry.transformed_hashes.len() == ImageTransform::TRANSFORMS.len())
+                        && (!check_transformed_additional_hashes || entry.transformed_additional_hashes.len() == ImageTransform::TRANSFORMS.len())
+                        && (!check_crops || entry.crop_hashes.len() == crop_windows_number)
+                    {
+                        true
+                    } else {
+                        non_cached_files_to_check.insert(path.clone(), entry.clone());
+                        false
+                    }
+                });
+            }
             debug!(
                 "hash_images_load_cache - completed diff between loaded and prechecked files, {}({}) - non cached, {}({}) - already cached",
                 non_cached_files_to_check.len(),
@@ -123,6 +156,25 @@ impl SimilarImages {
         (loaded_hash_map, records_already_cached, non_cached_files_to_check)
     }
 
+    // Hashes of image parts are loaded only into entries, which are still valid in main cache
+    fn load_crop_hashes_from_cache(&mut self, loaded_hash_map: &mut BTreeMap<String, ImagesEntry>) {
+        let (messages, loaded_items) = load_cache_from_file_generalized_by_path::<ImageCropHashesEntry>(
+            &get_similar_images_crop_cache_file(&self.get_params().hash_size, &self.get_params().hash_alg, &self.get_params().image_filter),
+            self.get_delete_outdated_cache(),
+            &BTreeMap::new(),
+        );
+        self.get_text_messages_mut().extend_with_another_messages(messages);
+
+        for (path, crop_entry) in loaded_items.unwrap_or_default() {
+            if let Some(file_entry) = loaded_hash_map.get_mut(&path)
+                && file_entry.size == crop_entry.size
+                && file_entry.modified_date == crop_entry.modified_date
+            {
+                file_entry.crop_hashes = crop_entry.crop_hashes;
+            }
+        }
+    }
+
     // Cache algorithm:
     // - Load data from file
     // - Remove from data to search, already loaded entries from cache(size and modified date must match)
@@ -179,7 +231,11 @@ impl SimilarImages {
         // All valid entries are used to create bktree used to check for hash similarity
         for file_entry in &vec_file_entry {
             // Only use to comparing, non broken hashes(all 0 or 255 hashes means that algorithm fails to decode them because e.g. contains a lot of alpha channel)
-            if !(file_entry.hash.is_empty() || file_entry.hash.iter().all(|e| *e == 0) || file_entry.hash.iter().all(|e| *e == 255)) {
+            if !(file_entry.hash.is_empty()
+                || file_entry.hash.iter().all(|e| *e == 0)
+                || file_entry.hash.iter().all(|e| *e == 255)
+                || file_entry.additional_hashes.len() != self.get_params().additional_hash_algs.len())
+            {
                 self.image_hashes.entry(file_entry.hash.clone()).or_default().push(file_entry.clone());
             }
         }
@@ -204,32 +260,102 @@ impl SimilarImages {
             }
 
             let messages = save_cache_to_file_generalized(
-                &get_similar_images_cache_file(&self.get_params().hash_size, &self.get_params().hash_alg, &self.get_params().image_filter),
+                &get_similar_images_cache_file(
+                    &self.get_params().hash_size,
+                    &self.get_params().hash_alg,
+                    &self.get_params().image_filter,
+                    &self.get_params().get_additional_hash_algs(),
+                ),
                 &all_results,
                 self.common_data.save_also_as_json,
                 0,
             );
             self.get_text_messages_mut().extend_with_another_messages(messages);
+
+            if self.get_params().find_cropped_images {
+                let crop_results: BTreeMap<String, ImageCropHashesEntry> = all_results
+                    .into_iter()
+                    .filter(|(_path, file_entry)| !file_entry.crop_hashes.is_empty())
+                    .map(|(path, file_entry)| {
+                        let crop_entry = ImageCropHashesEntry {
+                            path: file_entry.path,
+                            size: file_entry.size,
+                            modified_date: file_entry.modified_date,
+                            crop_hashes: file_entry.crop_hashes,
+                        };
+                        (path, crop_entry)
+                    })
+                    .collect();
+                let messages = save_cache_to_file_generalized(
+                    &get_similar_images_crop_cache_file(&self.get_params().hash_size, &self.get_params().hash_alg, &self.get_params().image_filter),
+                    &crop_results,
+                    self.common_data.save_also_as_json,
+                    0,
+                );
+                self.get_text_messages_mut().extend_with_another_messages(messages);
+            }
         }
     }
 
     fn collect_image_file_entry(&self, mut file_entry: ImagesEntry) -> Result<ImagesEntry, String> {
         let img = get_dynamic_image_from_path(&file_entry.path.to_string_lossy())?;
 
+        self.fill_image_hashes(&mut file_entry, &img);
+
+        Ok(file_entry)
+    }
+
+    fn fill_image_hashes(&self, file_entry: &mut ImagesEntry, img: &DynamicImage) {
         let dimensions = img.dimensions();
 
         file_entry.width = dimensions.0;
         file_entry.height = dimensions.1;
 
+        file_entry.hash = self.calculate_hash(img, self.get_params().hash_alg);
+        file_entry.additional_hashes = self
+            .get_params()
+            .get_additional_hash_algs()
+            .into_iter()
+            .map(|hash_alg| self.calculate_hash(img, hash_alg))
+            .collect();
+        if self.get_params().match_rotations_and_flips {
+            let additional_hash_algs = self.get_params().get_additional_hash_algs();
+            (file_entry.transformed_hashes, file_entry.transformed_additional_hashes) = ImageTransform::TRANSFORMS
+                .iter()
+                .map(|transform| {
+                    let transformed_img = transform.apply(img);
+                    let additional_hashes = additional_hash_algs.iter().map(|hash_alg| self.calculate_hash(&transformed_img, *hash_alg)).collect();
+                    (self.calculate_hash(&transformed_img, self.get_params().hash_alg), additional_hashes)
+                })
+                .unzip();
+            if additional_hash_algs.is_empty() {
+                file_entry.transformed_additional_hashes = Vec::new();
+            }
+        }
+        if self.get_params().find_cropped_images {
+            // Parts are hashed from smaller image, because hashing hundreds of crops of big image would be too slow
+            let thumbnail = if img.width().max(img.height()) > CROP_THUMBNAIL_SIZE {
+                img.thumbnail(CROP_THUMBNAIL_SIZE, CROP_THUMBNAIL_SIZE)
+            } else {
+                img.clone()
+            };
+            file_entry.crop_hashes = get_crop_windows()
+                .iter()
+                .map(|window| {
+                    let (x, y, width, height) = window.to_pixels(thumbnail.width(), thumbnail.height());
+                    self.calculate_hash(&thumbnail.crop_imm(x, y, width, height), self.get_params().hash_alg)
+                })
+                .collect();
+        }
+    }
+
+    fn calculate_hash(&self, img: &DynamicImage, hash_alg: HashAlg) -> ImHash {
         let hasher_config = HasherConfig::new()
             .hash_size(self.get_params().hash_size as u32, self.get_params().hash_size as u32)
-            .hash_alg(self.get_params().hash_alg)
+            .hash_alg(hash_alg)
             .resize_filter(self.get_params().image_filter);
         let hasher = hasher_config.to_hasher();
-        let hash = hasher.hash_image(&img);
-        file_entry.hash = hash.as_bytes().to_vec();
-
-        Ok(file_entry)
+        hasher.hash_image(img).as_bytes().to_vec()
     }
 
     // Split hashes at 2 parts, base hashes and hashes to compare, 3 argument is set of hashes with multiple images
@@ -295,8 +421,12 @@ impl SimilarImages {
 
         for (child_hash, (parent_hash, similarity)) in hashes_similarity {
             let mut vec_fe = all_hashed_images[&child_hash].clone();
+            let parent_fe = all_hashed_images[&parent_hash].first();
             for fe in &mut vec_fe {
                 fe.similarity = similarity;
+                if let Some(parent_fe) = parent_fe {
+                    fe.transform = get_distance_and_transform(parent_fe, fe).1;
+                }
             }
             collected_similar_images
                 .get_mut(&parent_hash)
@@ -318,6 +448,7 @@ impl SimilarImages {
         let (base_hashes, hashes_with_multiple_images) = self.split_hashes(all_hashed_images);
 
         let progress_handler = prepare_thread_handler_common(progress_sender, CurrentStage::SimilarImagesComparingHashes, base_hashes.len(), self.get_test_type(), 0);
+        let ignored_groups_filter = self.common_data.get_ignored_groups_filter();
 
         let mut hashes_parents: IndexMap<ImHash, u32> = Default::default(); // Hashes used as parent (hash, children_number_of_hash)
         let mut hashes_similarity: IndexMap<ImHash, (ImHash, u32)> = Default::default(); // Hashes used as child, (parent_hash, similarity)
@@ -335,10 +466,10 @@ impl SimilarImages {
                         return None;
                     }
                     let mut found_items = self
-                        .bktree
-                        .find(hash_to_check, tolerance)
-                        .filter(|(similarity, compared_hash)| {
-                            *similarity != 0 && !hashes_parents.contains_key(*compared_hash) && !hashes_with_multiple_images.contains(*compared_hash)
+                        .find_in_bktree(hash_to_check, all_hashed_images, tolerance, &ignored_groups_filter)
+                        .into_iter()
+                        .filter(|(_similarity, compared_hash)| {
+                            *compared_hash != hash_to_check && !hashes_parents.contains_key(*compared_hash) && !hashes_with_multiple_images.contains(*compared_hash)
                         })
                         .filter(|(similarity, compared_hash)| {
                             if let Some((_, other_similarity_with_parent)) = hashes_similarity.get(*compared_hash) {
@@ -380,6 +511,171 @@ impl SimilarImages {
         WorkContinueStatus::Continue
     }
 
+    // Groups are created from graph of similar hashes, so results not depends on order of checking hashes
+    #[fun_time(message = "compare_hashes_with_clustering", level = "debug")]
+    fn compare_hashes_with_clustering(
+        &mut self,
+        all_hashed_images: &IndexMap<ImHash, Vec<ImagesEntry>>,
+        collected_similar_images: &mut IndexMap<ImHash, Vec<ImagesEntry>>,
+        progress_sender: Option<&Sender<ProgressData>>,
+        stop_flag: &Arc<AtomicBool>,
+        tolerance: u32,
+    ) -> WorkContinueStatus {
+        let mut hashes = all_hashed_images.keys().collect::<Vec<_>>();
+        hashes.sort_unstable();
+        let hash_indexes: IndexMap<&ImHash, usize> = hashes.iter().enumerate().map(|(idx, hash)| (*hash, idx)).collect();
+        for hash in &hashes {
+            self.bktree.add((*hash).clone());
+        }
+
+        // With reference folders, only images similar to images from reference folders are interesting
+        let use_reference_folders = self.common_data.use_reference_folders;
+        let contains_reference_image = hashes
+            .iter()
+            .map(|hash| {
+                use_reference_folders
+                    && all_hashed_images
+                        .get(*hash)
+                        .is_some_and(|vec_fe| vec_fe.iter().any(|fe| self.common_data.directories.is_in_referenced_directory(fe.get_path())))
+            })
+            .collect::<Vec<_>>();
+
+        let progress_handler = prepare_thread_handler_common(progress_sender, CurrentStage::SimilarImagesComparingHashes, hashes.len(), self.get_test_type(), 0);
+        let ignored_groups_filter = self.common_data.get_ignored_groups_filter();
+
+        let found_neighbours = hashes
+            .par_iter()
+            .enumerate()
+            .map(|(idx, hash)| {
+                progress_handler.increase_items(1);
+                if check_if_stop_received(stop_flag) {
+                    return None;
+                }
+                let is_reference = contains_reference_image.get(idx) == Some(&true);
+                let found_items = self
+                    .find_in_bktree(hash, all_hashed_images, tolerance, &ignored_groups_filter)
+                    .into_iter()
+                    .filter_map(|(distance, other_hash)| hash_indexes.get(other_hash).map(|other_idx| (*other_idx, distance)))
+                    .filter(|(other_idx, _distance)| *other_idx != idx && (!use_reference_folders || is_reference || contains_reference_image.get(*other_idx) == Some(&true)))
+                    .collect::<Vec<_>>();
+                Some(found_items)
+            })
+            .while_some()
+            .collect::<Vec<_>>();
+
+        progress_handler.join_thread();
+        if check_if_stop_received(stop_flag) {
+            return WorkContinueStatus::Stop;
+        }
+
+        // Searching with rotations may find only one direction of connection, so graph is made symmetric
+        let mut neighbours: Vec<BTreeMap<usize, u32>> = vec![BTreeMap::new(); hashes.len()];
+        for (idx, found_items) in found_neighbours.into_iter().enumerate() {
+            for (other_idx, distance) in found_items {
+                for (first, second) in [(idx, other_idx), (other_idx, idx)] {
+                    if let Some(node_neighbours) = neighbours.get_mut(first) {
+                        let current_distance = node_neighbours.entry(second).or_insert(distance);
+                        *current_distance = (*current_distance).min(distance);
+                    }
+                }
+            }
+        }
+
+        let clusters = match self.get_params().grouping_mode {
+            ImagesGroupingMode::ConnectedComponents => connected_components(&neighbours),
+            ImagesGroupingMode::StrictCliques => strict_cliques(&neighbours),
+            ImagesGroupingMode::FirstHit => unreachable!("First hit grouping is handled in compare_hashes_with_non_zero_tolerance"),
+        };
+
+        for cluster in clusters {
+            let cluster_entries = cluster
+                .iter()
+                .filter_map(|idx| hashes.get(*idx).and_then(|hash| all_hashed_images.get(*hash)))
+                .collect::<Vec<_>>();
+            if cluster_entries.iter().map(|vec_fe| vec_fe.len()).sum::<usize>() < 2 {
+                continue;
+            }
+
+            // Representative is hash with most similar hashes in group, then with most images, then first one
+            let Some(representative_idx) = cluster
+                .iter()
+                .enumerate()
+                .max_by_key(|(position, idx)| {
+                    let degree = neighbours
+                        .get(**idx)
+                        .map_or(0, |node_neighbours| cluster.iter().filter(|e| node_neighbours.contains_key(e)).count());
+                    let images_number = cluster_entries.get(*position).map_or(0, |vec_fe| vec_fe.len());
+                    (degree, images_number, std::cmp::Reverse(*position))
+                })
+                .map(|(position, _idx)| position)
+            else {
+                continue;
+            };
+            let (Some(representative_hash), Some(representative_entries)) =
+                (cluster.get(representative_idx).and_then(|idx| hashes.get(*idx)), cluster_entries.get(representative_idx))
+            else {
+                continue;
+            };
+            let mut group = (*representative_entries).clone();
+            group.sort_by(|a, b| a.path.cmp(&b.path));
+            let Some(representative) = group.first().cloned() else {
+                continue;
+            };
+
+            let mut other_entries = Vec::new();
+            for (position, vec_fe) in cluster_entries.iter().enumerate() {
+                if position == representative_idx {
+                    continue;
+                }
+                // Connected components may contain images similar to representative only through other images,
+                // so their distance to representative may be bigger than tolerance
+                for fe in *vec_fe {
+                    let mut fe = fe.clone();
+                    (fe.similarity, fe.transform) = get_distance_and_transform(&representative, &fe);
+                    other_entries.push(fe);
+                }
+            }
+            other_entries.sort_by(|a, b| a.similarity.cmp(&b.similarity).then_with(|| a.path.cmp(&b.path)));
+
+            group.extend(other_entries);
+            collected_similar_images.insert((*representative_hash).clone(), group);
+        }
+
+        WorkContinueStatus::Continue
+    }
+
+    // Finds hashes similar to checked hash, and when enabled also to its rotated and flipped variants
+    // Only the lowest distance to each found hash is returned
+    // Hashes, whose all images were dismissed by user as not similar to images of checked hash, are skipped
+    fn find_in_bktree<'a>(
+        &'a self,
+        hash_to_check: &ImHash,
+        all_hashed_images: &IndexMap<ImHash, Vec<ImagesEntry>>,
+        tolerance: u32,
+        ignored_groups_filter: &IgnoredGroupsFilter<'_>,
+    ) -> Vec<(u32, &'a ImHash)> {
+        let mut found_items = self.bktree.find(hash_to_check, tolerance).collect::<Vec<_>>();
+        if self.get_params().match_rotations_and_flips
+            && let Some(file_entry) = all_hashed_images.get(hash_to_check).and_then(|vec_fe| vec_fe.first())
+        {
+            for transformed_hash in &file_entry.transformed_hashes {
+                found_items.extend(self.bktree.find(transformed_hash, tolerance));
+            }
+            found_items.sort_unstable_by(|a, b| a.1.cmp(b.1).then(a.0.cmp(&b.0)));
+            found_items.dedup_by(|a, b| a.1 == b.1);
+        }
+        if !ignored_groups_filter.is_empty()
+            && let Some(images_to_check) = all_hashed_images.get(hash_to_check)
+        {
+            found_items.retain(|(_distance, other_hash)| {
+                all_hashed_images
+                    .get(*other_hash)
+                    .is_none_or(|other_images| !ignored_groups_filter.are_all_pairs_ignored(images_to_check, other_images))
+            });
+        }
+        found_items
+    }
+
     #[fun_time(message = "connect_results", level = "debug")]
     fn connect_results(
         &self,
@@ -457,13 +753,22 @@ impl SimilarImages {
 
         let all_hashed_images = mem::take(&mut self.image_hashes);
 
+        if self.find_cropped_images(&all_hashed_images, progress_sender, stop_flag, tolerance) == WorkContinueStatus::Stop {
+            return WorkContinueStatus::Stop;
+        }
+
         // Checking entries with tolerance 0 is really easy and fast, because only entries with same hashes needs to be checked
-        if tolerance == 0 {
+        // Transformed images have different hashes, so they must be searched in bktree
+        if tolerance == 0 && !self.get_params().match_rotations_and_flips {
             for (hash, vec_file_entry) in all_hashed_images {
                 if vec_file_entry.len() >= 2 {
                     collected_similar_images.insert(hash, vec_file_entry);
                 }
             }
+        } else if self.get_params().grouping_mode != ImagesGroupingMode::FirstHit {
+            if self.compare_hashes_with_clustering(&all_hashed_images, &mut collected_similar_images, progress_sender, stop_flag, tolerance) == WorkContinueStatus::Stop {
+                return WorkContinueStatus::Stop;
+            }
         } else if self.compare_hashes_with_non_zero_tolerance(&all_hashed_images, &mut collected_similar_images, progress_sender, stop_flag, tolerance) == WorkContinueStatus::Stop
         {
             return WorkContinueStatus::Stop;
@@ -473,11 +778,195 @@ impl SimilarImages {
 
         // Info about hashes is not needed anymore, so we drop this info
         self.similar_vectors = collected_similar_images.into_values().collect();
+        // Hashes of image parts are big and not needed in results
+        if self.get_params().find_cropped_images {
+            for file_entry in self.similar_vectors.iter_mut().flatten() {
+                file_entry.crop_hashes = Vec::new();
+            }
+        }
+
+        self.filter_by_additional_hashes();
+
+        self.remove_ignored_pairs();
 
         self.exclude_items_with_same_size();
 
         self.remove_multiple_records_from_reference_folders();
 
+        self.remove_cropped_images_from_same_group();
+
+        self.calculate_stats();
+
+        // Clean unused data to save ram
+        self.image_hashes = Default::default();
+        self.images_to_check = Default::default();
+        self.bktree = BKTree::new(Hamming);
+
+        WorkContinueStatus::Continue
+    }
+
+    // Groups may still contain pairs dismissed by user, e.g. images connected only through other images or images with same hash,
+    // so such groups are split and similarity in new groups is computed against their new first image
+    // Images not similar enough to new first image are moved to next groups
+    fn remove_ignored_pairs(&mut self) {
+        let ignored_groups_filter = self.common_data.get_ignored_groups_filter();
+        if ignored_groups_filter.is_empty() {
+            return;
+        }
+        let tolerance = self.get_params().similarity;
+        let mut new_similar_vectors = Vec::new();
+        for vec_file_entry in mem::take(&mut self.similar_vectors) {
+            let original_first_path = vec_file_entry.first().map(|e| e.path.clone());
+            for group in ignored_groups_filter.split_group(vec_file_entry) {
+                if group.first().map(|e| &e.path) == original_first_path.as_ref() {
+                    new_similar_vectors.push(group);
+                } else {
+                    new_similar_vectors.extend(regroup_by_first_image(group, tolerance));
+                }
+            }
+        }
+        self.similar_vectors = new_similar_vectors;
+        self.cropped_images.retain(|e| !ignored_groups_filter.is_pair_ignored(&e.parent.path, &e.child.path));
+    }
+
+    #[fun_time(message = "find_cropped_images", level = "debug")]
+    fn find_cropped_images(
+        &mut self,
+        all_hashed_images: &IndexMap<ImHash, Vec<ImagesEntry>>,
+        progress_sender: Option<&Sender<ProgressData>>,
+        stop_flag: &Arc<AtomicBool>,
+        tolerance: u32,
+    ) -> WorkContinueStatus {
+        if !self.get_params().find_cropped_images {
+            return WorkContinueStatus::Continue;
+        }
+
+        let crop_windows = get_crop_windows();
+        let all_entries = all_hashed_images.values().flatten().collect::<Vec<_>>();
+
+        // Hash of image part -> (index of image, index of window)
+        let mut crop_hashes: IndexMap<&ImHash, Vec<(usize, usize)>> = IndexMap::new();
+        for (entry_idx, file_entry) in all_entries.iter().enumerate() {
+            for (window_idx, crop_hash) in file_entry.crop_hashes.iter().enumerate() {
+                // Uniform parts of images gives broken hashes, which would match a lot of unrelated images
+                if crop_hash.iter().all(|e| *e == 0) || crop_hash.iter().all(|e| *e == 255) {
+                    continue;
+                }
+                crop_hashes.entry(crop_hash).or_default().push((entry_idx, window_idx));
+            }
+        }
+        let mut bktree = BKTree::new(Hamming);
+        for crop_hash in crop_hashes.keys() {
+            bktree.add((*crop_hash).clone());
+        }
+
+        let progress_handler = prepare_thread_handler_common(progress_sender, CurrentStage::SimilarImagesComparingHashes, all_entries.len(), self.get_test_type(), 0);
+
+        let use_reference_folders = self.common_data.use_reference_folders;
+        let cropped_images = all_entries
+            .par_iter()
+            .enumerate()
+            .map(|(child_idx, child)| {
+                progress_handler.increase_items(1);
+                if check_if_stop_received(stop_flag) {
+                    return None;
+                }
+                if child.width == 0 || child.height == 0 || (use_reference_folders && self.common_data.directories.is_in_referenced_directory(child.get_path())) {
+                    return Some(Vec::new());
+                }
+                let child_aspect_ratio = child.width as f32 / child.height as f32;
+
+                // Parent index -> (similarity, window index), only best window of each parent is used
+                let mut best_windows: BTreeMap<usize, (u32, usize)> = BTreeMap::new();
+                for (similarity, crop_hash) in bktree.find(&child.hash, tolerance) {
+                    for &(parent_idx, window_idx) in crop_hashes.get(crop_hash).into_iter().flatten() {
+                        let (Some(parent), Some(window)) = (all_entries.get(parent_idx), crop_windows.get(window_idx)) else {
+                            continue;
+                        };
+                        if parent_idx == child_idx || parent.height == 0 {
+                            continue;
+                        }
+                        // Hash is calculated on resized image, so aspect ratio must be checked separately
+                        let window_aspect_ratio = (window.width * parent.width as f32) / (window.height * parent.height as f32);
+                        if (window_aspect_ratio / child_aspect_ratio).ln().abs() > CROP_MAX_ASPECT_RATIO_DIFFERENCE {
+                            continue;
+                        }
+                        let best_window = best_windows.entry(parent_idx).or_insert((similarity, window_idx));
+                        if similarity < best_window.0 {
+                            *best_window = (similarity, window_idx);
+                        }
+                    }
+                }
+
+                let pairs = best_windows
+                    .into_iter()
+                    .filter_map(|(parent_idx, (similarity, window_idx))| {
+                        let parent = all_entries.get(parent_idx)?;
+                        let window = crop_windows.get(window_idx)?;
+                        Some(CroppedImage {
+                            parent: ImagesEntry {
+                                crop_hashes: Vec::new(),
+                                ..(*parent).clone()
+                            },
+                            child: ImagesEntry {
+                                crop_hashes: Vec::new(),
+                                similarity,
+                                ..(*child).clone()
+                            },
+                            crop_ratio: window.width * window.height,
+                            similarity,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                Some(pairs)
+            })
+            .while_some()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        progress_handler.join_thread();
+        if check_if_stop_received(stop_flag) {
+            return WorkContinueStatus::Stop;
+        }
+
+        self.cropped_images = cropped_images;
+        self.cropped_images
+            .sort_by(|a, b| a.parent.path.cmp(&b.parent.path).then_with(|| a.child.path.cmp(&b.child.path)));
+
+        WorkContinueStatus::Continue
+    }
+
+    // Images from same group are almost identical, so there is no point to show them also as crops
+    fn remove_cropped_images_from_same_group(&mut self) {
+        if self.cropped_images.is_empty() {
+            return;
+        }
+        let mut group_of_image: HashMap<&Path, usize> = HashMap::new();
+        for (group_idx, vec_file_entry) in self.similar_vectors.iter().enumerate() {
+            for file_entry in vec_file_entry {
+                group_of_image.insert(&file_entry.path, group_idx);
+            }
+        }
+        for (group_idx, (reference_entry, vec_file_entry)) in self.similar_referenced_vectors.iter().enumerate() {
+            for file_entry in std::iter::once(reference_entry).chain(vec_file_entry) {
+                group_of_image.insert(&file_entry.path, group_idx);
+            }
+        }
+
+        let cropped_images = mem::take(&mut self.cropped_images)
+            .into_iter()
+            .filter(|cropped_image| {
+                let parent_group = group_of_image.get(cropped_image.parent.path.as_path());
+                parent_group.is_none() || parent_group != group_of_image.get(cropped_image.child.path.as_path())
+            })
+            .collect();
+        self.cropped_images = cropped_images;
+    }
+
+    pub(crate) fn calculate_stats(&mut self) {
+        self.information.number_of_cropped_images = self.cropped_images.len();
+        self.information.number_of_duplicates = 0;
+        self.information.number_of_groups = 0;
         if self.common_data.use_reference_folders {
             for (_fe, vector) in &self.similar_referenced_vectors {
                 self.information.number_of_duplicates += vector.len();
@@ -489,13 +978,45 @@ impl SimilarImages {
                 self.information.number_of_groups += 1;
             }
         }
+    }
 
-        // Clean unused data to save ram
-        self.image_hashes = Default::default();
-        self.images_to_check = Default::default();
-        self.bktree = BKTree::new(Hamming);
+    // Groups are found only with main hash, so here images, which are not similar to first image in group
+    // when taking into account also additional hashes, are removed from group
+    #[fun_time(message = "filter_by_additional_hashes", level = "debug")]
+    fn filter_by_additional_hashes(&mut self) {
+        if self.get_params().additional_hash_algs.is_empty() {
+            return;
+        }
+        let threshold = self.get_params().similarity;
+        let weights = self.get_params().additional_hash_algs.iter().map(|(_, weight)| *weight).collect::<Vec<_>>();
+        let combine_mode = self.get_params().hash_combine_mode;
 
-        WorkContinueStatus::Continue
+        let mut rejected_images = 0;
+        for mut vec_file_entry in mem::take(&mut self.similar_vectors) {
+            if vec_file_entry.is_empty() {
+                continue;
+            }
+            let base = vec_file_entry.remove(0);
+            let mut new_group = Vec::with_capacity(vec_file_entry.len() + 1);
+            for mut file_entry in vec_file_entry {
+                // Transformed images are compared with hashes of base image after same transformation
+                let Some((base_hash, base_additional_hashes)) = get_transformed_hashes(&base, file_entry.transform) else {
+                    rejected_images += 1;
+                    continue;
+                };
+                if let Some(similarity) = combined_similarity(base_hash, base_additional_hashes, &file_entry, &weights, combine_mode, threshold) {
+                    file_entry.similarity = similarity;
+                    new_group.push(file_entry);
+                } else {
+                    rejected_images += 1;
+                }
+            }
+            if !new_group.is_empty() {
+                new_group.insert(0, base);
+                self.similar_vectors.push(new_group);
+            }
+        }
+        self.information.number_of_images_rejected_by_additional_hashes = rejected_images;
     }
 
     #[fun_time(message = "exclude_items_with_same_size", level = "debug")]
@@ -570,18 +1091,203 @@ impl SimilarImages {
     }
 }
 
+const CROP_WINDOW_SCALES: [f32; 5] = [0.5, 0.625, 0.75, 0.875, 1.0];
+const CROP_WINDOW_STEP: f32 = 0.125;
+const CROP_THUMBNAIL_SIZE: u32 = 256;
+// Maximal difference of aspect ratio(in logarithmic scale) between crop window and child image
+const CROP_MAX_ASPECT_RATIO_DIFFERENCE: f32 = 0.15;
+
+// Part of image, all values are fractions of image size
+pub(crate) struct CropWindow {
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+}
+
+impl CropWindow {
+    fn to_pixels(&self, image_width: u32, image_height: u32) -> (u32, u32, u32, u32) {
+        let convert = |position: f32, size: f32, image_size: u32| {
+            let size = ((size * image_size as f32).round() as u32).clamp(1, image_size.max(1));
+            let position = ((position * image_size as f32).round() as u32).min(image_size.saturating_sub(size));
+            (position, size)
+        };
+        let (x, width) = convert(self.x, self.width, image_width);
+        let (y, height) = convert(self.y, self.height, image_height);
+        (x, y, width, height)
+    }
+}
+
+// Windows with different scales in both axes, moved by fixed step, without window covering whole image
+pub(crate) fn get_crop_windows() -> Vec<CropWindow> {
+    let positions = |scale: f32| (0..=((1.0 - scale) / CROP_WINDOW_STEP).round() as u32).map(move |idx| idx as f32 * CROP_WINDOW_STEP);
+    let mut windows = Vec::new();
+    for width in CROP_WINDOW_SCALES {
+        for height in CROP_WINDOW_SCALES {
+            if width >= 1.0 && height >= 1.0 {
+                continue;
+            }
+            for x in positions(width) {
+                for y in positions(height) {
+                    windows.push(CropWindow { x, y, width, height });
+                }
+            }
+        }
+    }
+    windows
+}
+
+// Returns distance between images and transformation of parent image, which is the most similar to child image
+fn get_distance_and_transform(parent: &ImagesEntry, child: &ImagesEntry) -> (u32, ImageTransform) {
+    let mut best_transform = ImageTransform::None;
+    let mut best_distance = hamming_bitwise_fast(&parent.hash, &child.hash);
+    for (transform, transformed_hash) in ImageTransform::TRANSFORMS.iter().zip(&parent.transformed_hashes) {
+        let distance = hamming_bitwise_fast(transformed_hash, &child.hash);
+        if distance < best_distance {
+            best_distance = distance;
+            best_transform = *transform;
+        }
+    }
+    (best_distance, best_transform)
+}
+
+// Creates groups from images, where every image is similar to first image of its group at most by tolerance
+// Images are checked in order, so first image of each group is the first not yet grouped image
+fn regroup_by_first_image(mut remaining: Vec<ImagesEntry>, tolerance: u32) -> Vec<Vec<ImagesEntry>> {
+    let mut groups = Vec::new();
+    while remaining.len() > 1 {
+        let mut entries = remaining.into_iter();
+        let Some(mut first) = entries.next() else {
+            break;
+        };
+        first.similarity = 0;
+        first.transform = ImageTransform::None;
+
+        let mut group = Vec::new();
+        remaining = Vec::new();
+        for mut fe in entries {
+            (fe.similarity, fe.transform) = get_distance_and_transform(&first, &fe);
+            if fe.similarity <= tolerance {
+                group.push(fe);
+            } else {
+                remaining.push(fe);
+            }
+        }
+        if !group.is_empty() {
+            group.sort_by(|a, b| a.similarity.cmp(&b.similarity).then_with(|| a.path.cmp(&b.path)));
+            group.insert(0, first);
+            groups.push(group);
+        }
+    }
+    groups
+}
+
+// Neighbours are symmetric, so in each map of neighbours, there is also key of node which points to it
+fn connected_components(neighbours: &[BTreeMap<usize, u32>]) -> Vec<Vec<usize>> {
+    fn find_root(parents: &mut [usize], mut node: usize) -> usize {
+        #[expect(clippy::indexing_slicing)] // Safe, because parents contains all nodes
+        while parents[node] != node {
+            parents[node] = parents[parents[node]];
+            node = parents[node];
+        }
+        node
+    }
+
+    let mut parents = (0..neighbours.len()).collect::<Vec<_>>();
+    for (node, node_neighbours) in neighbours.iter().enumerate() {
+        for neighbour in node_neighbours.keys() {
+            let first_root = find_root(&mut parents, node);
+            let second_root = find_root(&mut parents, *neighbour);
+            // Smaller index is always root, so components are deterministic
+            if let Some(parent) = parents.get_mut(first_root.max(second_root)) {
+                *parent = first_root.min(second_root);
+            }
+        }
+    }
+
+    let mut components: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for node in 0..neighbours.len() {
+        let root = find_root(&mut parents, node);
+        components.entry(root).or_default().push(node);
+    }
+    components.into_values().collect()
+}
+
+// Greedily creates cliques, starting from not assigned node with smallest index and adding its closest neighbours,
+// which are similar to all already added nodes
+fn strict_cliques(neighbours: &[BTreeMap<usize, u32>]) -> Vec<Vec<usize>> {
+    let mut assigned = vec![false; neighbours.len()];
+    let mut cliques = Vec::new();
+    for (node, node_neighbours) in neighbours.iter().enumerate() {
+        if assigned.get(node) != Some(&false) {
+            continue;
+        }
+        let mut candidates = node_neighbours.iter().map(|(neighbour, distance)| (*distance, *neighbour)).collect::<Vec<_>>();
+        candidates.sort_unstable();
+
+        let mut clique = vec![node];
+        for (_distance, candidate) in candidates {
+            if assigned.get(candidate) == Some(&false) && clique.iter().all(|member| neighbours.get(*member).is_some_and(|e| e.contains_key(&candidate))) {
+                clique.push(candidate);
+            }
+        }
+        for member in &clique {
+            if let Some(is_assigned) = assigned.get_mut(*member) {
+                *is_assigned = true;
+            }
+        }
+        clique.sort_unstable();
+        cliques.push(clique);
+    }
+    cliques
+}
+
+// Returns main and additional hashes of image after transformation
+fn get_transformed_hashes(image: &ImagesEntry, transform: ImageTransform) -> Option<(&ImHash, &[ImHash])> {
+    if transform == ImageTransform::None {
+        return Some((&image.hash, &image.additional_hashes));
+    }
+    let idx = ImageTransform::TRANSFORMS.iter().position(|e| *e == transform)?;
+    Some((image.transformed_hashes.get(idx)?, image.transformed_additional_hashes.get(idx)?))
+}
+
+// Returns combined similarity of two images, or None if images are not similar enough
+fn combined_similarity(base_hash: &ImHash, base_additional_hashes: &[ImHash], other: &ImagesEntry, weights: &[f32], combine_mode: HashCombineMode, threshold: u32) -> Option<u32> {
+    let main_distance = hamming_bitwise_fast(base_hash, &other.hash);
+    let additional_distances = base_additional_hashes
+        .iter()
+        .zip(other.additional_hashes.iter())
+        .map(|(a, b)| hamming_bitwise_fast(a, b))
+        .collect::<Vec<_>>();
+
+    match combine_mode {
+        HashCombineMode::AllUnderThreshold => {
+            let max_distance = additional_distances.into_iter().fold(main_distance, u32::max);
+            (max_distance <= threshold).then_some(max_distance)
+        }
+        HashCombineMode::WeightedSum => {
+            let weighted_sum = main_distance as f32 + additional_distances.iter().zip(weights).map(|(distance, weight)| *distance as f32 * weight).sum::<f32>();
+            let sum_of_weights = 1.0 + weights.iter().sum::<f32>();
+            let combined = weighted_sum / sum_of_weights;
+            (combined <= threshold as f32).then_some((combined.round() as u32).min(threshold))
+        }
+    }
+}
+
 fn is_in_reference_folder(reference_directories: &[PathBuf], path: &Path) -> bool {
     reference_directories.iter().any(|e| path.starts_with(e))
 }
 
-#[expect(clippy::indexing_slicing)] // Because hash size is validated before
+// Images grouped by connected components may be similar to first image of group only through other images,
+// so similarity bigger than any preset is also valid
+#[expect(clippy::indexing_slicing)] // Index is always in range of presets
 pub fn get_string_from_similarity(similarity: &u32, hash_size: u8) -> String {
     let index_preset = match hash_size {
         8 => 0,
         16 => 1,
         32 => 2,
         64 => 3,
-        _ => panic!("Invalid hash size {hash_size}"),
+        _ => return similarity.to_string(),
     };
 
     if *similarity == 0 {
@@ -599,7 +1305,7 @@ pub fn get_string_from_similarity(similarity: &u32, hash_size: u8) -> String {
     } else if *similarity <= SIMILAR_VALUES[index_preset][5] {
         flc!("core_similarity_minimal")
     } else {
-        panic!("Invalid similarity value {similarity} for hash size {hash_size} (index {index_preset})");
+        flc!("core_similarity_indirect")
     }
 }
 
@@ -711,9 +1417,23 @@ fn debug_check_for_duplicated_things(
     assert!(!found_broken_thing);
 }
 
-pub fn get_similar_images_cache_file(hash_size: &u8, hash_alg: &HashAlg, image_filter: &FilterType) -> String {
+// Additional algorithms are part of file name, because entries contain also their hashes
+pub fn get_similar_images_cache_file(hash_size: &u8, hash_alg: &HashAlg, image_filter: &FilterType, additional_hash_algs: &[HashAlg]) -> String {
+    let algorithms = std::iter::once(hash_alg)
+        .chain(additional_hash_algs)
+        .map(|alg| convert_algorithm_to_string(*alg))
+        .collect::<Vec<_>>()
+        .join("+");
+    format!(
+        "cache_similar_images_{hash_size}_{algorithms}_{}_{CACHE_IMAGE_VERSION}.bin",
+        convert_filters_to_string(*image_filter),
+    )
+}
+
+// Parts of images are hashed only with main algorithm
+pub fn get_similar_images_crop_cache_file(hash_size: &u8, hash_alg: &HashAlg, image_filter: &FilterType) -> String {
     format!(
-        "cache_similar_images_{hash_size}_{}_{}_{CACHE_IMAGE_VERSION}.bin",
+        "cache_similar_images_crops_{hash_size}_{}_{}_{CACHE_IMAGE_VERSION}.bin",
         convert_algorithm_to_string(*hash_alg),
         convert_filters_to_string(*image_filter),
     )
@@ -740,6 +1460,11 @@ mod tests {
             image_filter: FilterType::Lanczos3,
             exclude_images_with_same_size: false,
             ignore_hard_links: false,
+            additional_hash_algs: Vec::new(),
+            hash_combine_mode: HashCombineMode::AllUnderThreshold,
+            match_rotations_and_flips: false,
+            find_cropped_images: false,
+            grouping_mode: ImagesGroupingMode::FirstHit,
         }
     }
 
@@ -1104,7 +1829,378 @@ mod tests {
             height: 100,
             modified_date: 0,
             hash,
+            additional_hashes: Vec::new(),
+            transformed_hashes: Vec::new(),
+            transformed_additional_hashes: Vec::new(),
+            crop_hashes: Vec::new(),
             similarity: 0,
+            transform: ImageTransform::None,
+        }
+    }
+
+    fn get_groups_with_grouping_mode(grouping_mode: ImagesGroupingMode, reversed: bool) -> Vec<Vec<(String, u32)>> {
+        let mut parameters = get_default_parameters();
+        parameters.similarity = 2;
+        parameters.grouping_mode = grouping_mode;
+        let mut similar_images = SimilarImages::new(parameters);
+        similar_images.set_use_reference_folders(false);
+
+        // A - B - C are chain of similar images, A and C are not similar
+        let mut entries = vec![
+            create_random_file_entry(vec![1, 1, 1, 1, 1, 1, 1, 0b0000_0001], "a.txt"),
+            create_random_file_entry(vec![1, 1, 1, 1, 1, 1, 1, 0b0000_0111], "b.txt"),
+            create_random_file_entry(vec![1, 1, 1, 1, 1, 1, 1, 0b0001_1111], "c.txt"),
+            create_random_file_entry(vec![255, 1, 1, 1, 1, 1, 1, 1], "d.txt"),
+            create_random_file_entry(vec![255, 1, 1, 1, 1, 1, 1, 1], "e.txt"),
+        ];
+        if reversed {
+            entries.reverse();
+        }
+        add_hashes(&mut similar_images.image_hashes, entries);
+
+        similar_images.find_similar_hashes(&Arc::default(), None);
+        let mut groups = similar_images
+            .get_similar_images()
+            .iter()
+            .map(|group| group.iter().map(|e| (e.path.to_string_lossy().to_string(), e.similarity)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        groups.sort();
+        groups
+    }
+
+    #[test]
+    fn test_connected_components_grouping() {
+        for reversed in [false, true] {
+            let groups = get_groups_with_grouping_mode(ImagesGroupingMode::ConnectedComponents, reversed);
+            let expected = vec![
+                vec![("b.txt".to_string(), 0), ("a.txt".to_string(), 2), ("c.txt".to_string(), 2)],
+                vec![("d.txt".to_string(), 0), ("e.txt".to_string(), 0)],
+            ];
+            assert_eq!(groups, expected);
+        }
+    }
+
+    #[test]
+    fn test_ignored_pairs_are_not_grouped() {
+        let mut parameters = get_default_parameters();
+        parameters.similarity = 2;
+        parameters.grouping_mode = ImagesGroupingMode::ConnectedComponents;
+        let mut similar_images = SimilarImages::new(parameters);
+        similar_images.set_use_reference_folders(false);
+        similar_images
+            .common_data
+            .ignored_groups
+            .add_group(ToolType::SimilarImages, ["a.txt", "b.txt"].map(PathBuf::from));
+        similar_images
+            .common_data
+            .ignored_groups
+            .add_group(ToolType::SimilarImages, ["d.txt", "e.txt"].map(PathBuf::from));
+
+        let entries = vec![
+            create_random_file_entry(vec![1, 1, 1, 1, 1, 1, 1, 0b0000_0001], "a.txt"),
+            create_random_file_entry(vec![1, 1, 1, 1, 1, 1, 1, 0b0000_0111], "b.txt"),
+            create_random_file_entry(vec![1, 1, 1, 1, 1, 1, 1, 0b0001_1111], "c.txt"),
+            create_random_file_entry(vec![255, 1, 1, 1, 1, 1, 1, 1], "d.txt"),
+            create_random_file_entry(vec![255, 1, 1, 1, 1, 1, 1, 1], "e.txt"),
+        ];
+        add_hashes(&mut similar_images.image_hashes, entries);
+
+        similar_images.find_similar_hashes(&Arc::default(), None);
+        let groups = similar_images
+            .get_similar_images()
+            .iter()
+            .map(|group| group.iter().map(|e| (e.path.to_string_lossy().to_string(), e.similarity)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        // Dismissed pair a - b breaks chain, so a is not connected with c through b
+        assert_eq!(groups, vec![vec![("b.txt".to_string(), 0), ("c.txt".to_string(), 2)]]);
+    }
+
+    #[test]
+    fn test_split_groups_contain_only_images_similar_to_first_image() {
+        let mut parameters = get_default_parameters();
+        parameters.similarity = 2;
+        let mut similar_images = SimilarImages::new(parameters);
+        similar_images.set_use_reference_folders(false);
+        for path in ["c.txt", "d.txt", "e.txt"] {
+            similar_images
+                .common_data
+                .ignored_groups
+                .add_group(ToolType::SimilarImages, ["a.txt", path].map(PathBuf::from));
+        }
+
+        let mut entries = vec![
+            create_random_file_entry(vec![1, 1, 1, 1, 1, 1, 1, 0b0000_0000], "a.txt"),
+            create_random_file_entry(vec![1, 1, 1, 1, 1, 1, 1, 0b0000_0001], "b.txt"),
+            create_random_file_entry(vec![1, 1, 1, 1, 1, 1, 1, 0b0000_0011], "c.txt"),
+            create_random_file_entry(vec![1, 1, 1, 1, 1, 1, 1, 0b1111_0000], "d.txt"),
+            create_random_file_entry(vec![1, 1, 1, 1, 1, 1, 1, 0b1111_0001], "e.txt"),
+        ];
+        entries[1].similarity = 1;
+        similar_images.similar_vectors = vec![entries];
+
+        similar_images.remove_ignored_pairs();
+        let groups = similar_images
+            .get_similar_images()
+            .iter()
+            .map(|group| group.iter().map(|e| (e.path.to_string_lossy().to_string(), e.similarity)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        // C is too different from D and E to be first image of their group
+        assert_eq!(
+            groups,
+            vec![
+                vec![("a.txt".to_string(), 0), ("b.txt".to_string(), 1)],
+                vec![("d.txt".to_string(), 0), ("e.txt".to_string(), 1)]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_connected_components_similarity_is_distance_to_representative() {
+        let mut parameters = get_default_parameters();
+        parameters.similarity = 2;
+        parameters.grouping_mode = ImagesGroupingMode::ConnectedComponents;
+        let mut similar_images = SimilarImages::new(parameters);
+        similar_images.set_use_reference_folders(false);
+
+        // Each image is similar only to its neighbours in chain, B is representative, because it is first with most neighbours
+        let entries = vec![
+            create_random_file_entry(vec![1, 1, 1, 1, 1, 1, 1, 0b0000_0001], "a.txt"),
+            create_random_file_entry(vec![1, 1, 1, 1, 1, 1, 1, 0b0000_0111], "b.txt"),
+            create_random_file_entry(vec![1, 1, 1, 1, 1, 1, 1, 0b0001_1111], "c.txt"),
+            create_random_file_entry(vec![1, 1, 1, 1, 1, 1, 1, 0b0111_1111], "d.txt"),
+        ];
+        add_hashes(&mut similar_images.image_hashes, entries);
+
+        similar_images.find_similar_hashes(&Arc::default(), None);
+        let groups = similar_images.get_similar_images();
+        assert_eq!(groups.len(), 1);
+        let similarities = groups[0].iter().map(|e| (e.path.to_string_lossy().to_string(), e.similarity)).collect::<Vec<_>>();
+        assert_eq!(
+            similarities,
+            [("b.txt", 0), ("a.txt", 2), ("c.txt", 2), ("d.txt", 4)].map(|(path, similarity)| (path.to_string(), similarity))
+        );
+    }
+
+    #[test]
+    fn test_get_string_from_similarity() {
+        assert_eq!(get_string_from_similarity(&0, 8), flc!("core_similarity_original"));
+        assert_eq!(get_string_from_similarity(&SIMILAR_VALUES[0][5], 8), flc!("core_similarity_minimal"));
+        assert_eq!(get_string_from_similarity(&64, 8), flc!("core_similarity_indirect"));
+        assert_eq!(get_string_from_similarity(&256, 64), flc!("core_similarity_indirect"));
+        assert_eq!(get_string_from_similarity(&5, 12), "5");
+    }
+
+    #[test]
+    fn test_strict_cliques_grouping() {
+        for reversed in [false, true] {
+            let groups = get_groups_with_grouping_mode(ImagesGroupingMode::StrictCliques, reversed);
+            let expected = vec![
+                vec![("a.txt".to_string(), 0), ("b.txt".to_string(), 2)],
+                vec![("d.txt".to_string(), 0), ("e.txt".to_string(), 0)],
+            ];
+            assert_eq!(groups, expected);
         }
     }
+
+    #[test]
+    fn test_cropped_images() {
+        let mut parameters = get_default_parameters();
+        parameters.hash_size = 16;
+        parameters.similarity = 10;
+        parameters.find_cropped_images = true;
+        let mut similar_images = SimilarImages::new(parameters);
+        similar_images.set_use_reference_folders(false);
+
+        // Smooth image, similar to photos, because resizing noise would give completely different image
+        let create_image = |fx: f32, fy: f32| {
+            DynamicImage::ImageLuma8(image::GrayImage::from_fn(200, 160, |x, y| {
+                let (x, y) = (x as f32, y as f32);
+                image::Luma([(128.0 + 60.0 * (x / fx).sin() + 60.0 * (y / fy + x / 23.0).cos()) as u8])
+            }))
+        };
+        let image = create_image(9.0, 7.0);
+        let other_image = create_image(5.0, 13.0);
+        let mut entries = Vec::new();
+        for (img, name) in [
+            (image.clone(), "parent.png"),
+            (image.crop_imm(50, 40, 100, 80), "cropped.png"),
+            (image.crop_imm(0, 80, 150, 80).thumbnail(75, 40), "cropped_resized.png"),
+            (other_image, "other.png"),
+        ] {
+            let mut fe = create_random_file_entry(Vec::new(), name);
+            similar_images.fill_image_hashes(&mut fe, &img);
+            entries.push(fe);
+        }
+        add_hashes(&mut similar_images.image_hashes, entries);
+
+        similar_images.find_similar_hashes(&Arc::default(), None);
+        let cropped_images = similar_images.get_cropped_images();
+        assert!(similar_images.get_similar_images().is_empty());
+        assert_eq!(cropped_images.len(), 2, "{cropped_images:?}");
+        assert!(cropped_images.iter().all(|e| e.parent.path == Path::new("parent.png") && e.parent.crop_hashes.is_empty()));
+        assert_eq!(cropped_images[0].child.path, Path::new("cropped.png"));
+        assert!((cropped_images[0].crop_ratio - 0.25).abs() < 0.001);
+        assert_eq!(cropped_images[1].child.path, Path::new("cropped_resized.png"));
+        assert!((cropped_images[1].crop_ratio - 0.375).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_crop_hashes_are_not_saved_in_image_entry() {
+        let mut file_entry = create_random_file_entry(vec![1, 2, 3], "image.png");
+        file_entry.crop_hashes = vec![vec![4, 5, 6]; get_crop_windows().len()];
+
+        let serialized = serde_json::to_string(&file_entry).unwrap();
+        let deserialized: ImagesEntry = serde_json::from_str(&serialized).unwrap();
+        assert!(deserialized.crop_hashes.is_empty());
+        assert_eq!(deserialized.hash, vec![1, 2, 3]);
+
+        assert_ne!(
+            get_similar_images_cache_file(&16, &HashAlg::Gradient, &FilterType::Lanczos3, &[]),
+            get_similar_images_crop_cache_file(&16, &HashAlg::Gradient, &FilterType::Lanczos3)
+        );
+    }
+
+    #[test]
+    fn test_crop_windows() {
+        let windows = get_crop_windows();
+        assert_eq!(windows.len(), 15 * 15 - 1);
+        assert!(windows.iter().all(|w| w.x + w.width <= 1.0 && w.y + w.height <= 1.0));
+        assert_eq!(
+            CropWindow {
+                x: 0.5,
+                y: 0.0,
+                width: 0.5,
+                height: 1.0
+            }
+            .to_pixels(201, 100),
+            (100, 0, 101, 100)
+        );
+    }
+
+    #[test]
+    fn test_rotated_and_flipped_images() {
+        let mut parameters = get_default_parameters();
+        parameters.hash_size = 16;
+        parameters.similarity = 0;
+        parameters.match_rotations_and_flips = true;
+        let mut similar_images = SimilarImages::new(parameters);
+        similar_images.set_use_reference_folders(false);
+
+        let image = DynamicImage::ImageLuma8(image::GrayImage::from_fn(64, 48, |x, y| image::Luma([((x * 37 + y * y * 11 + x * y) % 251) as u8])));
+        let create_entry = |transform: ImageTransform, name: &str| {
+            let transformed_image = transform.apply(&image);
+            let mut fe = create_random_file_entry(similar_images.calculate_hash(&transformed_image, HashAlg::Gradient), name);
+            fe.transformed_hashes = ImageTransform::TRANSFORMS
+                .iter()
+                .map(|t| similar_images.calculate_hash(&t.apply(&transformed_image), HashAlg::Gradient))
+                .collect();
+            fe
+        };
+        let entries = vec![
+            create_entry(ImageTransform::None, "original.png"),
+            create_entry(ImageTransform::Rotate90, "rotated.png"),
+            create_entry(ImageTransform::FlipHorizontal, "flipped.png"),
+        ];
+
+        add_hashes(&mut similar_images.image_hashes, entries);
+
+        similar_images.find_similar_hashes(&Arc::default(), None);
+        let res = similar_images.get_similar_images();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].len(), 3);
+        assert_eq!(res[0][0].transform, ImageTransform::None);
+        assert!(res[0].iter().all(|e| e.similarity == 0));
+        for fe in &res[0] {
+            let expected_transform = match (res[0][0].path.to_string_lossy().as_ref(), fe.path.to_string_lossy().as_ref()) {
+                (parent, child) if parent == child => ImageTransform::None,
+                ("original.png", "rotated.png") => ImageTransform::Rotate90,
+                ("rotated.png", "original.png") => ImageTransform::Rotate270,
+                ("original.png", "flipped.png") | ("flipped.png", "original.png") => ImageTransform::FlipHorizontal,
+                ("rotated.png", "flipped.png") | ("flipped.png", "rotated.png") => ImageTransform::Transverse,
+                _ => unreachable!(),
+            };
+            assert_eq!(fe.transform, expected_transform, "{:?} -> {:?}", res[0][0].path, fe.path);
+        }
+    }
+
+    #[test]
+    fn test_additional_hashes_filter_group() {
+        for combine_mode in [HashCombineMode::AllUnderThreshold, HashCombineMode::WeightedSum] {
+            let mut parameters = get_default_parameters();
+            parameters.similarity = 2;
+            parameters.additional_hash_algs = vec![(HashAlg::DoubleGradient, 1.0)];
+            parameters.hash_combine_mode = combine_mode;
+            let mut similar_images = SimilarImages::new(parameters);
+            similar_images.set_use_reference_folders(false);
+
+            let mut fe1 = create_random_file_entry(vec![1, 1, 1, 1, 1, 1, 1, 1], "abc.txt");
+            fe1.additional_hashes = vec![vec![0, 0, 0, 0, 0, 0, 0, 0]];
+            let mut fe2 = create_random_file_entry(vec![1, 1, 1, 1, 1, 1, 1, 3], "bcd.txt");
+            fe2.additional_hashes = vec![vec![0, 0, 0, 0, 0, 0, 0, 1]];
+            // Main hash is identical, but additional hash is completely different
+            let mut fe3 = create_random_file_entry(vec![1, 1, 1, 1, 1, 1, 1, 1], "rrd.txt");
+            fe3.additional_hashes = vec![vec![255, 255, 0, 0, 0, 0, 0, 0]];
+
+            add_hashes(&mut similar_images.image_hashes, vec![fe1, fe2, fe3]);
+
+            similar_images.find_similar_hashes(&Arc::default(), None);
+            let res = similar_images.get_similar_images();
+            assert_eq!(res.len(), 1);
+            assert_eq!(res[0].len(), 2);
+            assert!(res[0].iter().all(|e| e.path != Path::new("rrd.txt")));
+            assert_eq!(similar_images.get_information().number_of_images_rejected_by_additional_hashes, 1);
+        }
+    }
+
+    #[test]
+    fn test_additional_hashes_filter_transformed_images() {
+        let mut parameters = get_default_parameters();
+        parameters.hash_size = 16;
+        parameters.similarity = 0;
+        parameters.match_rotations_and_flips = true;
+        parameters.additional_hash_algs = vec![(HashAlg::DoubleGradient, 1.0)];
+        let mut similar_images = SimilarImages::new(parameters);
+        similar_images.set_use_reference_folders(false);
+
+        let image = DynamicImage::ImageLuma8(image::GrayImage::from_fn(64, 48, |x, y| image::Luma([((x * 37 + y * y * 11 + x * y) % 251) as u8])));
+        let create_entry = |transform: ImageTransform, name: &str| {
+            let mut fe = create_random_file_entry(Vec::new(), name);
+            similar_images.fill_image_hashes(&mut fe, &transform.apply(&image));
+            fe
+        };
+        // Main hash matches after rotation, but additional hash is completely different
+        let mut fake_rotated = create_entry(ImageTransform::Rotate90, "fake_rotated.png");
+        for byte in fake_rotated.additional_hashes.iter_mut().flatten() {
+            *byte = !*byte;
+        }
+        let entries = vec![
+            create_entry(ImageTransform::None, "original.png"),
+            create_entry(ImageTransform::Rotate90, "rotated.png"),
+            fake_rotated,
+        ];
+
+        add_hashes(&mut similar_images.image_hashes, entries);
+
+        similar_images.find_similar_hashes(&Arc::default(), None);
+        let res = similar_images.get_similar_images();
+        assert_eq!(res.len(), 1);
+        let paths = res[0].iter().map(|e| e.path.to_string_lossy().to_string()).collect::<BTreeSet<_>>();
+        assert_eq!(paths, BTreeSet::from(["original.png".to_string(), "rotated.png".to_string()]));
+        assert_eq!(similar_images.get_information().number_of_images_rejected_by_additional_hashes, 1);
+    }
+
+    #[test]
+    fn test_combined_similarity() {
+        let mut base = create_random_file_entry(vec![0, 0], "a.txt");
+        base.additional_hashes = vec![vec![0, 0]];
+        let mut other = create_random_file_entry(vec![0, 0b11], "b.txt");
+        other.additional_hashes = vec![vec![0, 0b1111]];
+
+        let combined =
+            |weight: f32, combine_mode: HashCombineMode, threshold: u32| combined_similarity(&base.hash, &base.additional_hashes, &other, &[weight], combine_mode, threshold);
+        assert_eq!(combined(1.0, HashCombineMode::AllUnderThreshold, 4), Some(4));
+        assert_eq!(combined(1.0, HashCombineMode::AllUnderThreshold, 3), None);
+        assert_eq!(combined(1.0, HashCombineMode::WeightedSum, 3), Some(3));
+        assert_eq!(combined(3.0, HashCombineMode::WeightedSum, 3), None);
+    }
 }
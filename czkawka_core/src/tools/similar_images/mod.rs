@@ -6,6 +6,7 @@ use std::path::{Path, PathBuf};
 
 use bk_tree::BKTree;
 use hamming_bitwise_fast::hamming_bitwise_fast;
+use image::DynamicImage;
 use image_hasher::{FilterType, HashAlg};
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
@@ -34,7 +35,23 @@ pub struct ImagesEntry {
     pub height: u32,
     pub modified_date: u64,
     pub hash: ImHash,
+    // Hashes calculated with additional algorithms, in same order as in parameters
+    #[serde(default)]
+    pub additional_hashes: Vec<ImHash>,
+    // Hashes of rotated and flipped image, in same order as in ImageTransform::TRANSFORMS
+    #[serde(default)]
+    pub transformed_hashes: Vec<ImHash>,
+    // Hashes calculated with additional algorithms for each rotated and flipped image
+    #[serde(default)]
+    pub transformed_additional_hashes: Vec<Vec<ImHash>>,
+    // Hashes of parts of image, in same order as windows from get_crop_windows
+    // They are big, so are kept in separate cache file and are never saved with results
+    #[serde(skip)]
+    pub crop_hashes: Vec<ImHash>,
     pub similarity: u32,
+    // Transformation of first image in group, which gives this image
+    #[serde(default)]
+    pub transform: ImageTransform,
 }
 
 impl ResultEntry for ImagesEntry {
@@ -58,7 +75,95 @@ impl FileEntry {
             width: 0,
             height: 0,
             hash: Vec::new(),
+            additional_hashes: Vec::new(),
+            transformed_hashes: Vec::new(),
+            transformed_additional_hashes: Vec::new(),
+            crop_hashes: Vec::new(),
             similarity: 0,
+            transform: ImageTransform::None,
+        }
+    }
+}
+
+// Entry of cache with hashes of image parts, used only when searching for cropped images
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ImageCropHashesEntry {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified_date: u64,
+    pub crop_hashes: Vec<ImHash>,
+}
+
+impl ResultEntry for ImageCropHashesEntry {
+    fn get_path(&self) -> &Path {
+        &self.path
+    }
+    fn get_modified_date(&self) -> u64 {
+        self.modified_date
+    }
+    fn get_size(&self) -> u64 {
+        self.size
+    }
+}
+
+// Child image is similar to part of parent image
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CroppedImage {
+    pub parent: ImagesEntry,
+    pub child: ImagesEntry,
+    // Part of parent image area, which is visible in child image
+    pub crop_ratio: f32,
+    pub similarity: u32,
+}
+
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ImageTransform {
+    #[default]
+    None,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+    Transpose,
+    Transverse,
+}
+
+impl ImageTransform {
+    // All transformations except None, order of transformed hashes in ImagesEntry
+    pub const TRANSFORMS: [Self; 7] = [
+        Self::Rotate90,
+        Self::Rotate180,
+        Self::Rotate270,
+        Self::FlipHorizontal,
+        Self::FlipVertical,
+        Self::Transpose,
+        Self::Transverse,
+    ];
+
+    pub fn apply(self, image: &DynamicImage) -> DynamicImage {
+        match self {
+            Self::None => image.clone(),
+            Self::Rotate90 => image.rotate90(),
+            Self::Rotate180 => image.rotate180(),
+            Self::Rotate270 => image.rotate270(),
+            Self::FlipHorizontal => image.fliph(),
+            Self::FlipVertical => image.flipv(),
+            Self::Transpose => image.rotate90().fliph(),
+            Self::Transverse => image.rotate270().fliph(),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::Rotate90 => "Rotated 90°",
+            Self::Rotate180 => "Rotated 180°",
+            Self::Rotate270 => "Rotated 270°",
+            Self::FlipHorizontal => "Flipped horizontally",
+            Self::FlipVertical => "Flipped vertically",
+            Self::Transpose => "Transposed",
+            Self::Transverse => "Transversed",
         }
     }
 }
@@ -87,6 +192,28 @@ impl bk_tree::Metric<ImHash> for Hamming {
     }
 }
 
+// How distances of main and additional hashes are combined, when additional hash algorithms are used
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub enum HashCombineMode {
+    // Distance of every hash must be below threshold
+    #[default]
+    AllUnderThreshold,
+    // Weighted average of distances must be below threshold, main hash always have weight 1
+    WeightedSum,
+}
+
+// How similar hashes are connected into groups
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub enum ImagesGroupingMode {
+    // Each image is added to group of first similar image found, so groups depends on order of checking
+    #[default]
+    FirstHit,
+    // Images are in same group, when are connected by chain of similar images
+    ConnectedComponents,
+    // Every pair of images in group is similar
+    StrictCliques,
+}
+
 #[derive(Clone)]
 pub struct SimilarImagesParameters {
     pub similarity: u32,
@@ -95,10 +222,30 @@ pub struct SimilarImagesParameters {
     pub image_filter: FilterType,
     pub exclude_images_with_same_size: bool,
     pub ignore_hard_links: bool,
+    // Algorithms with weights, used to verify groups found with main hash algorithm
+    pub additional_hash_algs: Vec<(HashAlg, f32)>,
+    pub hash_combine_mode: HashCombineMode,
+    // Hashes also rotated and flipped variants of images, to find transformed copies
+    pub match_rotations_and_flips: bool,
+    // Hashes also parts of images, to find images which are crops of other images
+    pub find_cropped_images: bool,
+    pub grouping_mode: ImagesGroupingMode,
 }
 
 impl SimilarImagesParameters {
-    pub fn new(similarity: u32, hash_size: u8, hash_alg: HashAlg, image_filter: FilterType, exclude_images_with_same_size: bool, ignore_hard_links: bool) -> Self {
+    pub fn new(
+        similarity: u32,
+        hash_size: u8,
+        hash_alg: HashAlg,
+        image_filter: FilterType,
+        exclude_images_with_same_size: bool,
+        ignore_hard_links: bool,
+        additional_hash_algs: Vec<(HashAlg, f32)>,
+        hash_combine_mode: HashCombineMode,
+        match_rotations_and_flips: bool,
+        find_cropped_images: bool,
+        grouping_mode: ImagesGroupingMode,
+    ) -> Self {
         assert!([8, 16, 32, 64].contains(&hash_size));
         Self {
             similarity,
@@ -107,8 +254,17 @@ impl SimilarImagesParameters {
             image_filter,
             exclude_images_with_same_size,
             ignore_hard_links,
+            additional_hash_algs: additional_hash_algs.into_iter().filter(|(alg, _)| *alg != hash_alg).collect(),
+            hash_combine_mode,
+            match_rotations_and_flips,
+            find_cropped_images,
+            grouping_mode,
         }
     }
+
+    pub fn get_additional_hash_algs(&self) -> Vec<HashAlg> {
+        self.additional_hash_algs.iter().map(|(alg, _)| *alg).collect()
+    }
 }
 
 pub struct SimilarImages {
@@ -117,16 +273,28 @@ pub struct SimilarImages {
     bktree: BKTree<ImHash, Hamming>,
     similar_vectors: Vec<Vec<ImagesEntry>>,
     similar_referenced_vectors: Vec<(ImagesEntry, Vec<ImagesEntry>)>,
+    cropped_images: Vec<CroppedImage>,
     // Hashmap with image hashes and Vector with names of files
     image_hashes: IndexMap<ImHash, Vec<ImagesEntry>>,
     images_to_check: BTreeMap<String, ImagesEntry>,
     params: SimilarImagesParameters,
 }
 
+// Content of results file
+#[derive(Default, Serialize, Deserialize)]
+pub struct SimilarImagesResults {
+    groups: Vec<Vec<ImagesEntry>>,
+    referenced_groups: Vec<(ImagesEntry, Vec<ImagesEntry>)>,
+    #[serde(default)]
+    cropped_images: Vec<CroppedImage>,
+}
+
 #[derive(Default, Clone)]
 pub struct Info {
     pub number_of_duplicates: usize,
     pub number_of_groups: u64,
+    pub number_of_images_rejected_by_additional_hashes: usize,
+    pub number_of_cropped_images: usize,
 }
 
 impl SimilarImages {
@@ -142,6 +310,10 @@ impl SimilarImages {
         &self.similar_referenced_vectors
     }
 
+    pub fn get_cropped_images(&self) -> &Vec<CroppedImage> {
+        &self.cropped_images
+    }
+
     pub fn get_use_reference(&self) -> bool {
         self.common_data.use_reference_folders
     }
@@ -5,13 +5,15 @@ use std::sync::atomic::AtomicBool;
 use crossbeam_channel::Sender;
 use fun_time::fun_time;
 use humansize::{BINARY, format_size};
+use serde::Serialize;
 
 use crate::common::model::WorkContinueStatus;
 use crate::common::progress_data::ProgressData;
+use crate::common::results_file::{check_if_entry_is_unchanged, retain_unchanged_groups, retain_unchanged_referenced_groups};
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PersistentResults, PrintResults, Search};
 use crate::tools::similar_images::core::get_string_from_similarity;
-use crate::tools::similar_images::{Info, SimilarImages, SimilarImagesParameters};
+use crate::tools::similar_images::{CroppedImage, ImageTransform, ImagesEntry, Info, SimilarImages, SimilarImagesParameters, SimilarImagesResults};
 
 impl AllTraits for SimilarImages {}
 
@@ -53,6 +55,14 @@ impl DebugPrint for SimilarImages {
     }
 }
 
+fn get_transform_suffix(file_entry: &ImagesEntry) -> String {
+    if file_entry.transform == ImageTransform::None {
+        String::new()
+    } else {
+        format!(" - {}", file_entry.transform.as_str())
+    }
+}
+
 impl PrintResults for SimilarImages {
     fn write_results<T: Write>(&self, writer: &mut T) -> std::io::Result<()> {
         if !self.similar_vectors.is_empty() {
@@ -63,12 +73,13 @@ impl PrintResults for SimilarImages {
                 for file_entry in struct_similar {
                     writeln!(
                         writer,
-                        "\"{}\" - {}x{} - {} - {}",
+                        "\"{}\" - {}x{} - {} - {}{}",
                         file_entry.path.to_string_lossy(),
                         file_entry.width,
                         file_entry.height,
                         format_size(file_entry.size, BINARY),
-                        get_string_from_similarity(&file_entry.similarity, self.get_params().hash_size)
+                        get_string_from_similarity(&file_entry.similarity, self.get_params().hash_size),
+                        get_transform_suffix(file_entry)
                     )?;
                 }
                 writeln!(writer)?;
@@ -81,22 +92,24 @@ impl PrintResults for SimilarImages {
                 writeln!(writer)?;
                 writeln!(
                     writer,
-                    "\"{}\" - {}x{} - {} - {}",
+                    "\"{}\" - {}x{} - {} - {}{}",
                     file_entry.path.to_string_lossy(),
                     file_entry.width,
                     file_entry.height,
                     format_size(file_entry.size, BINARY),
-                    get_string_from_similarity(&file_entry.similarity, self.get_params().hash_size)
+                    get_string_from_similarity(&file_entry.similarity, self.get_params().hash_size),
+                    get_transform_suffix(file_entry)
                 )?;
                 for file_entry in vec_file_entry {
                     writeln!(
                         writer,
-                        "{:?} - {}x{} - {} - {}",
+                        "{:?} - {}x{} - {} - {}{}",
                         file_entry.path,
                         file_entry.width,
                         file_entry.height,
                         format_size(file_entry.size, BINARY),
-                        get_string_from_similarity(&file_entry.similarity, self.get_params().hash_size)
+                        get_string_from_similarity(&file_entry.similarity, self.get_params().hash_size),
+                        get_transform_suffix(file_entry)
                     )?;
                 }
                 writeln!(writer)?;
@@ -105,17 +118,82 @@ impl PrintResults for SimilarImages {
             write!(writer, "Not found any similar images.")?;
         }
 
+        if !self.cropped_images.is_empty() {
+            write!(writer, "\n\n{} images which are cropped from other images\n\n", self.cropped_images.len())?;
+            for cropped_image in &self.cropped_images {
+                writeln!(
+                    writer,
+                    "\"{}\" - {}x{} is cropped from \"{}\" - {}x{} - {:.0}% of area - {}",
+                    cropped_image.child.path.to_string_lossy(),
+                    cropped_image.child.width,
+                    cropped_image.child.height,
+                    cropped_image.parent.path.to_string_lossy(),
+                    cropped_image.parent.width,
+                    cropped_image.parent.height,
+                    cropped_image.crop_ratio * 100.0,
+                    get_string_from_similarity(&cropped_image.similarity, self.get_params().hash_size)
+                )?;
+            }
+        }
+
         Ok(())
     }
 
     fn save_results_to_file_as_json(&self, file_name: &str, pretty_print: bool) -> std::io::Result<()> {
-        if self.get_use_reference() {
+        // Layout of file without crops is kept unchanged, to not break existing scripts
+        if self.get_params().find_cropped_images {
+            let results = SimilarImagesWithCropsJson {
+                similar_images: &self.similar_vectors,
+                similar_images_referenced: &self.similar_referenced_vectors,
+                cropped_images: &self.cropped_images,
+            };
+            self.save_results_to_file_as_json_internal(file_name, &results, pretty_print)
+        } else if self.get_use_reference() {
             self.save_results_to_file_as_json_internal(file_name, &self.similar_referenced_vectors, pretty_print)
         } else {
             self.save_results_to_file_as_json_internal(file_name, &self.similar_vectors, pretty_print)
         }
     }
 }
+#[derive(Serialize, Debug)]
+struct SimilarImagesWithCropsJson<'a> {
+    similar_images: &'a Vec<Vec<ImagesEntry>>,
+    similar_images_referenced: &'a Vec<(ImagesEntry, Vec<ImagesEntry>)>,
+    cropped_images: &'a Vec<CroppedImage>,
+}
+
+impl PersistentResults for SimilarImages {
+    type Results = SimilarImagesResults;
+
+    fn get_results_to_persist(&self) -> Self::Results {
+        SimilarImagesResults {
+            groups: self.similar_vectors.clone(),
+            referenced_groups: self.similar_referenced_vectors.clone(),
+            cropped_images: self.cropped_images.clone(),
+        }
+    }
+
+    fn set_persisted_results(&mut self, results: Self::Results) {
+        let warnings = &mut self.common_data.text_messages.warnings;
+        self.similar_vectors = retain_unchanged_groups(results.groups, warnings);
+        self.similar_referenced_vectors = retain_unchanged_referenced_groups(results.referenced_groups, warnings);
+        self.cropped_images = results
+            .cropped_images
+            .into_iter()
+            .filter(
+                |cropped_image| match check_if_entry_is_unchanged(&cropped_image.parent).and_then(|()| check_if_entry_is_unchanged(&cropped_image.child)) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        warnings.push(e);
+                        false
+                    }
+                },
+            )
+            .collect();
+        self.calculate_stats();
+    }
+}
+
 impl CommonData for SimilarImages {
     type Info = Info;
     type Parameters = SimilarImagesParameters;
@@ -133,7 +211,7 @@ impl CommonData for SimilarImages {
         &mut self.common_data
     }
     fn found_any_broken_files(&self) -> bool {
-        self.information.number_of_duplicates > 0
+        self.information.number_of_duplicates > 0 || self.information.number_of_cropped_images > 0
     }
 }
 
@@ -174,6 +174,18 @@ impl SimilarVideos {
         self.match_groups_of_videos(vector_of_hashes, &hashmap_with_file_entries);
         self.remove_from_reference_folders();
 
+        self.calculate_stats();
+
+        // Clean unused data
+        self.videos_hashes = Default::default();
+        self.videos_to_check = Default::default();
+
+        WorkContinueStatus::Continue
+    }
+
+    pub(crate) fn calculate_stats(&mut self) {
+        self.information.number_of_duplicates = 0;
+        self.information.number_of_groups = 0;
         if self.common_data.use_reference_folders {
             for (_fe, vector) in &self.similar_referenced_vectors {
                 self.information.number_of_duplicates += vector.len();
@@ -185,12 +197,6 @@ impl SimilarVideos {
                 self.information.number_of_groups += 1;
             }
         }
-
-        // Clean unused data
-        self.videos_hashes = Default::default();
-        self.videos_to_check = Default::default();
-
-        WorkContinueStatus::Continue
     }
 
     #[fun_time(message = "save_cache", level = "debug")]
@@ -239,7 +245,7 @@ impl SimilarVideos {
             }
         }
 
-        self.similar_vectors = collected_similar_videos;
+        self.similar_vectors = self.common_data.get_ignored_groups_filter().filter_groups(collected_similar_videos);
     }
 
     #[fun_time(message = "remove_from_reference_folders", level = "debug")]
@@ -8,10 +8,11 @@ use humansize::{BINARY, format_size};
 
 use crate::common::model::WorkContinueStatus;
 use crate::common::progress_data::ProgressData;
+use crate::common::results_file::{GroupedResults, retain_unchanged_groups, retain_unchanged_referenced_groups};
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PersistentResults, PrintResults, Search};
 use crate::flc;
-use crate::tools::similar_videos::{Info, SimilarVideos, SimilarVideosParameters};
+use crate::tools::similar_videos::{Info, SimilarVideos, SimilarVideosParameters, VideosEntry};
 
 impl AllTraits for SimilarVideos {}
 
@@ -53,6 +54,23 @@ impl DeletingItems for SimilarVideos {
     }
 }
 
+impl PersistentResults for SimilarVideos {
+    type Results = GroupedResults<VideosEntry>;
+
+    fn get_results_to_persist(&self) -> Self::Results {
+        GroupedResults {
+            groups: self.similar_vectors.clone(),
+            referenced_groups: self.similar_referenced_vectors.clone(),
+        }
+    }
+
+    fn set_persisted_results(&mut self, results: Self::Results) {
+        self.similar_vectors = retain_unchanged_groups(results.groups, &mut self.common_data.text_messages.warnings);
+        self.similar_referenced_vectors = retain_unchanged_referenced_groups(results.referenced_groups, &mut self.common_data.text_messages.warnings);
+        self.calculate_stats();
+    }
+}
+
 impl DebugPrint for SimilarVideos {
     #[expect(clippy::print_stdout)]
     fn debug_print(&self) {
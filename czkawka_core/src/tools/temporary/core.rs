@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+use std::fs;
 use std::fs::DirEntry;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -7,7 +9,7 @@ use crossbeam_channel::Sender;
 use fun_time::fun_time;
 use rayon::prelude::*;
 
-use crate::common::dir_traversal::{common_read_dir, get_modified_time};
+use crate::common::dir_traversal::{common_read_dir, get_folder_id, get_modified_time};
 use crate::common::directories::Directories;
 use crate::common::items::ExcludedItems;
 use crate::common::model::{ToolType, WorkContinueStatus};
@@ -27,7 +29,13 @@ impl Temporary {
 
     #[fun_time(message = "check_files", level = "debug")]
     pub(crate) fn check_files(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
-        let mut folders_to_check: Vec<PathBuf> = self.common_data.directories.included_directories.clone();
+        let mut folders_to_check: Vec<(PathBuf, usize)> = self.common_data.directories.included_directories.iter().map(|dir| (dir.clone(), 0)).collect();
+
+        // Symlinked folders may point to its parents, so every visited folder is remembered to avoid infinite loops
+        let mut visited_folders = HashSet::new();
+        if self.common_data.follow_symlinks {
+            visited_folders.extend(folders_to_check.iter().filter_map(|(folder, _)| get_folder_id(folder)));
+        }
 
         let progress_handler = prepare_thread_handler_common(progress_sender, CurrentStage::CollectingFiles, 0, self.get_test_type(), 0);
 
@@ -39,13 +47,14 @@ impl Temporary {
 
             let segments: Vec<_> = folders_to_check
                 .into_par_iter()
-                .map(|current_folder| {
+                .map(|(current_folder, depth)| {
                     let mut dir_result = vec![];
                     let mut warnings = vec![];
                     let mut fe_result = vec![];
+                    let check_subfolders = self.common_data.recursive_search && self.common_data.max_depth.is_none_or(|max_depth| depth < max_depth);
 
                     let Some(read_dir) = common_read_dir(&current_folder, &mut warnings) else {
-                        return (dir_result, warnings, fe_result);
+                        return (dir_result, depth, warnings, fe_result);
                     };
 
                     // Check every sub folder/file/link etc.
@@ -57,12 +66,15 @@ impl Temporary {
                             continue;
                         };
 
-                        if file_type.is_dir() {
+                        // Only symlinks pointing to folders are followed
+                        let is_followed_symlink =
+                            file_type.is_symlink() && self.common_data.follow_symlinks && fs::metadata(entry_data.path()).is_ok_and(|metadata| metadata.is_dir());
+                        if file_type.is_dir() || is_followed_symlink {
                             check_folder_children(
                                 &mut dir_result,
                                 &mut warnings,
                                 &entry_data,
-                                self.common_data.recursive_search,
+                                check_subfolders,
                                 &self.common_data.directories,
                                 &self.common_data.excluded_items,
                             );
@@ -72,16 +84,21 @@ impl Temporary {
                             fe_result.push(file_entry);
                         }
                     }
-                    (dir_result, warnings, fe_result)
+                    (dir_result, depth, warnings, fe_result)
                 })
                 .collect();
 
-            let required_size = segments.iter().map(|(segment, _, _)| segment.len()).sum::<usize>();
+            let required_size = segments.iter().map(|(segment, _, _, _)| segment.len()).sum::<usize>();
             folders_to_check = Vec::with_capacity(required_size);
 
             // Process collected data
-            for (segment, warnings, fe_result) in segments {
-                folders_to_check.extend(segment);
+            for (segment, depth, warnings, fe_result) in segments {
+                let segment = segment.into_iter().map(|folder| (folder, depth + 1));
+                if self.common_data.follow_symlinks {
+                    folders_to_check.extend(segment.filter(|(folder, _)| get_folder_id(folder).is_none_or(|id| visited_folders.insert(id))));
+                } else {
+                    folders_to_check.extend(segment);
+                }
                 self.common_data.text_messages.warnings.extend(warnings);
                 for fe in fe_result {
                     self.temporary_files.push(fe);
@@ -113,9 +130,14 @@ impl Temporary {
             return None;
         };
 
+        let modified_date = get_modified_time(&metadata, warnings, &current_file_name, false);
+        if !self.common_data.metadata_filter.is_allowed(&metadata, modified_date) {
+            return None;
+        }
+
         // Creating new file entry
         Some(TemporaryFileEntry {
-            modified_date: get_modified_time(&metadata, warnings, &current_file_name, false),
+            modified_date,
             size: metadata.len(),
             path: current_file_name,
         })
@@ -126,11 +148,11 @@ pub(crate) fn check_folder_children(
     dir_result: &mut Vec<PathBuf>,
     warnings: &mut Vec<String>,
     entry_data: &DirEntry,
-    recursive_search: bool,
+    check_subfolders: bool,
     directories: &Directories,
     excluded_items: &ExcludedItems,
 ) {
-    if !recursive_search {
+    if !check_subfolders {
         return;
     }
 
@@ -157,3 +179,53 @@ pub(crate) fn check_folder_children(
 
     dir_result.push(next_item);
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+    use crate::common::traits::Search;
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn test_max_depth_and_follow_symlinks() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        let outside = tempfile::Builder::new().tempdir()?;
+        fs::create_dir_all(dir.path().join("sub").join("deep"))?;
+        fs::write(dir.path().join("a.tmp"), b"a")?;
+        fs::write(dir.path().join("sub").join("b.tmp"), b"b")?;
+        fs::write(dir.path().join("sub").join("deep").join("c.tmp"), b"c")?;
+        fs::write(outside.path().join("d.tmp"), b"d")?;
+        std::os::unix::fs::symlink(outside.path(), dir.path().join("linked"))?;
+        // Loop pointing to root folder must not be followed again
+        std::os::unix::fs::symlink(dir.path(), dir.path().join("sub").join("loop"))?;
+
+        let find = |max_depth: Option<usize>, follow_symlinks: bool| {
+            let mut temporary = Temporary::new();
+            temporary.set_included_directory(vec![dir.path().to_path_buf()]);
+            temporary.set_max_depth(max_depth);
+            temporary.set_follow_symlinks(follow_symlinks);
+            temporary.search(&Arc::default(), None);
+            let mut paths: Vec<_> = temporary
+                .get_temporary_files()
+                .iter()
+                .map(|fe| fe.path.strip_prefix(dir.path()).unwrap().to_path_buf())
+                .collect();
+            paths.sort();
+            paths
+        };
+
+        assert_eq!(find(Some(1), false), vec![PathBuf::from("a.tmp"), PathBuf::from("sub/b.tmp")]);
+        assert_eq!(
+            find(None, true),
+            vec![
+                PathBuf::from("a.tmp"),
+                PathBuf::from("linked/d.tmp"),
+                PathBuf::from("sub/b.tmp"),
+                PathBuf::from("sub/deep/c.tmp")
+            ]
+        );
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@ pub mod traits;
 
 use std::path::{Path, PathBuf};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::common::tool_data::CommonToolData;
 use crate::common::traits::ResultEntry;
@@ -24,7 +24,7 @@ const TEMP_EXTENSIONS: &[&str] = &[
     ".partial",
 ];
 
-#[derive(Clone, Serialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct TemporaryFileEntry {
     pub path: PathBuf,
     pub modified_date: u64,
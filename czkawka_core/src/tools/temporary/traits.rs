@@ -7,9 +7,10 @@ use fun_time::fun_time;
 
 use crate::common::model::WorkContinueStatus;
 use crate::common::progress_data::ProgressData;
+use crate::common::results_file::retain_unchanged_entries;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteItemType, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
-use crate::tools::temporary::{Info, Temporary};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PersistentResults, PrintResults, Search};
+use crate::tools::temporary::{Info, Temporary, TemporaryFileEntry};
 
 impl AllTraits for Temporary {}
 
@@ -93,3 +94,16 @@ impl DebugPrint for Temporary {
         self.debug_print_common();
     }
 }
+
+impl PersistentResults for Temporary {
+    type Results = Vec<TemporaryFileEntry>;
+
+    fn get_results_to_persist(&self) -> Self::Results {
+        self.temporary_files.clone()
+    }
+
+    fn set_persisted_results(&mut self, results: Self::Results) {
+        self.temporary_files = retain_unchanged_entries(results, &mut self.common_data.text_messages.warnings);
+        self.information.number_of_temporary_files = self.temporary_files.len();
+    }
+}
@@ -51,10 +51,17 @@ duplicate_case_sensitive_name_tooltip =
 
         Disabling such option will group names without checking if each letter is same size e.g. żoŁD <-> Żołd
 
+duplicate_verify_content = Verify content
+duplicate_verify_content_tooltip =
+        When enabled, files in groups found by hash are compared byte by byte, so groups created by hash collisions are split.
+
+        Groups found by sampled hash can be deleted, linked or symlinked only after such verification.
+
 duplicate_mode_size_name_combo_box = Size and Name
 duplicate_mode_name_combo_box = Name
 duplicate_mode_size_combo_box = Size
 duplicate_mode_hash_combo_box = Hash
+duplicate_mode_sampled_hash_combo_box = Sampled Hash
 
 duplicate_hash_type_tooltip = 
         Czkawka offers 3 types of hashes:
@@ -149,10 +156,22 @@ main_label_similarity = Similarity{"   "}
 main_check_box_broken_files_audio = Audio
 main_check_box_broken_files_pdf = Pdf
 main_check_box_broken_files_archive = Archive
+main_check_box_broken_files_compressed_archive = TAR/GZIP/XZ/ZSTD/BZIP2/7Z
 main_check_box_broken_files_image = Image
+main_check_box_broken_files_video = Video
+main_check_box_broken_files_decode_video = Decode video frames
+main_check_box_broken_files_document = Document
+main_check_box_broken_files_font = Font
+main_check_box_broken_files_sqlite = SQLite
+main_check_box_broken_files_text_formats = JSON/XML/YAML
+main_check_box_broken_files_verify_zip_content = Verify zip content
+main_check_box_broken_files_strict_image = Strict image check
+main_check_box_broken_files_show_warnings = Show warnings
 
 check_button_general_same_size = Ignore same size
 check_button_general_same_size_tooltip = Ignore files with identical size in results - usually these are 1:1 duplicates
+check_button_image_find_cropped = Find cropped images
+check_button_image_find_cropped_tooltip = Search also for images which are cropped parts of other images, each pair is shown as separate group with parent image first. Slows down hashing and uses additional cache file.
 
 main_label_size_bytes_tooltip = Size of files which will be used in scan
 
@@ -204,10 +223,15 @@ upper_excluded_extensions_tooltip =
         When using both allowed and disabled extensions, this one has higher priority, so file will not be checked.
 
 upper_excluded_items_tooltip = 
-        Excluded items must contain * wildcard and should be separated by commas.
+        Excluded items are matched against full path and should be separated by commas.
+        Globs support *, **, ? and [abc] wildcards(use [?] and [[] to match these characters literally), regular expressions must be prefixed with "regex:".
         This is slower than Excluded Directories, so use it carefully.
 
 upper_excluded_items = Excluded Items:
+upper_included_items_tooltip =
+        When not empty, only files which full path matches at least one of items are checked.
+        Items should be separated by commas, globs and regular expressions prefixed with "regex:" are supported.
+upper_included_items = Included Items:
 upper_allowed_extensions = Allowed Extensions:
 upper_excluded_extensions = Disabled Extensions:
 
@@ -219,6 +243,7 @@ popover_select_all_except_oldest = Select all except oldest
 popover_select_all_except_newest = Select all except newest
 popover_select_one_oldest = Select one oldest
 popover_select_one_newest = Select one newest
+popover_select_all_except_kept_by_rules = Select all except kept by rules
 popover_select_custom = Select custom
 popover_unselect_custom = Unselect custom
 popover_select_all_images_except_biggest = Select all except biggest
@@ -283,6 +308,7 @@ bottom_delete_button = Delete
 bottom_save_button = Save
 bottom_symlink_button = Symlink
 bottom_hardlink_button = Hardlink
+bottom_ignore_button = Ignore
 bottom_move_button = Move
 bottom_sort_button = Sort
 bottom_compare_button = Compare
@@ -304,6 +330,9 @@ bottom_hardlink_button_not_available_tooltip =
         Button is disabled, because hardlinks cannot be created.
         Hardlinks only works with administrator privileges on Windows, so be sure to run app as administrator.
         If app already works with such privileges check for similar issues on Github.
+bottom_ignore_button_tooltip =
+        Marks groups with selected results as not duplicates.
+        Ignored groups are hidden from results and will not be shown again in next scans.
 bottom_move_button_tooltip =
         Moves files to chosen directory.
         It copies all files to the directory without preserving the directory tree.
@@ -348,6 +377,34 @@ settings_use_rust_preview_tooltip =
 
         On non-linux systems, it is recommended to use this option, because gtk-pixbuf are not always available there so disabling this option will not load previews of some images.
 
+settings_keeper_rules = Keeper rules
+settings_keeper_rules_tooltip =
+        Rules choosing file kept in group by "Select all except kept by rules", separated by ";" and checked in order.
+
+        prefer_path <folder>, prefer_shortest_path, prefer_longest_path, avoid_name <text>, prefer_exif, never_delete_read_only, prefer_newest, prefer_oldest, prefer_biggest, prefer_smallest
+
+        e.g. prefer_path /home/user/archive; avoid_name " (1)"; prefer_shortest_path
+
+settings_min_modified_date = Modified after
+settings_min_modified_date_tooltip = Skips files modified before this date, accepts YYYY-MM-DD(UTC) or unix timestamp. Leave empty to disable.
+settings_max_modified_date = Modified before
+settings_max_modified_date_tooltip = Skips files modified after this date, accepts YYYY-MM-DD(UTC) or unix timestamp. Leave empty to disable.
+settings_max_depth = Maximal folder depth
+settings_max_depth_tooltip = Limits how many levels of subfolders are checked, 0 checks only files placed directly inside included directories. Leave empty to disable.
+settings_not_accessed_for_days = Not accessed for days
+settings_not_accessed_for_days_tooltip =
+        Checks only files not accessed for given number of days. Leave empty to disable.
+
+        Files on filesystems mounted with noatime may have outdated access time.
+settings_owner_uids = Owner user ids
+settings_owner_uids_tooltip = Checks only files owned by users with given ids, separated by ",". Works only on Linux and macOS.
+settings_owner_gids = Owner group ids
+settings_owner_gids_tooltip = Checks only files owned by groups with given ids, separated by ",". Works only on Linux and macOS.
+settings_quarantine_folder = Quarantine folder
+settings_quarantine_folder_tooltip =
+        When set, deleted files are moved to this folder instead of trash or being permanently removed.
+        Files can be restored with undo command of CLI. Folder is never scanned.
+
 settings_label_restart = You need to restart app to apply settings!
 
 settings_ignore_other_filesystems = Ignore other filesystems (only Linux)
@@ -356,6 +413,20 @@ settings_ignore_other_filesystems_tooltip =
 
         Works same like -xdev option in find command on Linux
 
+settings_use_ignore_files = Honour .gitignore and .czkawkaignore files
+settings_use_ignore_files_tooltip =
+        Skips files and folders matched by .gitignore and .czkawkaignore files found inside scanned folders.
+
+        Patterns use gitignore syntax, rules from .czkawkaignore are more important than from .gitignore in the same folder.
+
+settings_follow_symlinks = Follow symlinked folders
+settings_follow_symlinks_tooltip =
+        Enters folders pointed by symbolic links like normal folders.
+
+        Every folder is checked only once, even if it can be reached by multiple paths, so symlink loops are safe.
+
+        Not used when searching for empty folders, because folder containing symlink is not empty.
+
 settings_save_at_exit_button_tooltip = Save configuration to file when closing app.
 settings_load_at_start_button_tooltip = 
         Load configuration from file when opening app.
@@ -464,11 +535,14 @@ compute_stopped_by_user = Searching was stopped by user
 
 compute_found_duplicates_hash_size = Found { $number_files } duplicates in { $number_groups } groups which took { $size }, **searching took { $time }**
 compute_found_duplicates_name = Found { $number_files } duplicates in { $number_groups } groups, **searching took { $time }**
+compute_duplicates_sampled_unverified = groups found by sampled hash are not verified, so deleting and linking is disabled
 compute_found_empty_folders = Found { $number_files } empty folders
 compute_found_empty_files = Found { $number_files } empty files
 compute_found_big_files = Found { $number_files } big files
 compute_found_temporary_files = Found { $number_files } temporary files
 compute_found_images = Found { $number_files } similar images in { $number_groups } groups
+compute_found_images_with_cropped = Found { $number_files } similar images in { $number_groups } groups and { $number_cropped } cropped images
+similar_images_cropped_part = { $similarity } - cropped { $percent }% of area
 compute_found_videos = Found { $number_files } similar videos in { $number_groups } groups
 compute_found_music = Found { $number_files } similar music files in { $number_groups } groups
 compute_found_invalid_symlinks = Found { $number_files } invalid symlinks
@@ -499,6 +573,8 @@ progress_scanning_size_name = Scanned name and size of {$file_number} file
 progress_scanning_name = Scanned name of {$file_number} file
 progress_analyzed_partial_hash = Analyzed partial hash of {$file_checked}/{$all_files} files ({$data_checked}/{$all_data})
 progress_analyzed_full_hash = Analyzed full hash of {$file_checked}/{$all_files} files ({$data_checked}/{$all_data})
+progress_analyzed_sampled_hash = Analyzed sampled hash of {$file_checked}/{$all_files} files ({$data_checked}/{$all_data})
+progress_compared_content_of_files = Compared content of {$file_checked}/{$all_files} files ({$data_checked}/{$all_data})
 progress_prehash_cache_loading = Loading prehash cache
 progress_prehash_cache_saving = Saving prehash cache
 progress_hash_cache_loading = Loading hash cache
@@ -550,6 +626,8 @@ hard_sym_invalid_selection_label_3 = First in group is recognized as original an
 hard_sym_link_title_dialog = Link confirmation
 hard_sym_link_label = Are you sure that you want to link these files?
 
+ignore_groups_added = Marked { $groups } groups as not duplicates, they will be hidden in next scans
+
 move_folder_failed = Failed to move folder {$name}, reason {$reason}
 move_file_failed = Failed to move file {$name}, reason {$reason}
 move_files_title_dialog = Choose folder to which you want to move duplicated files
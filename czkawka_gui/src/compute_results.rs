@@ -137,6 +137,7 @@ pub(crate) fn connect_compute_results(gui_data: &GuiData, result_receiver: Recei
 
                 let msg_type = msg.get_message_type();
                 let subview = common_tree_views.get_subview(msg_type);
+                let sampled_groups_unverified = matches!(&msg, Message::Duplicates(df) if df.contains_only_sampled_groups());
 
                 let found_duplicates: Option<bool> = match msg {
                     Message::Duplicates(df) => compute_duplicate_finder(df, &entry_info, &text_view_errors, subview),
@@ -154,6 +155,14 @@ pub(crate) fn connect_compute_results(gui_data: &GuiData, result_receiver: Recei
 
                 if let Some(found_duplicates) = found_duplicates {
                     set_specific_buttons_as_active(&shared_buttons, msg_type, found_duplicates);
+                    // Files matched only by sampled hash may still differ, so they can be moved, but not removed
+                    if sampled_groups_unverified {
+                        set_buttons_as_inactive(
+                            &shared_buttons,
+                            msg_type,
+                            &[BottomButtonsEnum::Delete, BottomButtonsEnum::Hardlink, BottomButtonsEnum::Symlink],
+                        );
+                    }
 
                     set_buttons(
                         &mut *shared_buttons.borrow_mut().get_mut(&msg_type).expect("Failed to borrow buttons"),
@@ -470,16 +479,28 @@ fn compute_similar_images(sf: SimilarImages, entry_info: &Entry, text_view_error
     let information = sf.get_information();
     let text_messages = sf.get_text_messages();
 
-    let found_any_duplicates = information.number_of_duplicates > 0;
+    let found_any_duplicates = information.number_of_duplicates > 0 || information.number_of_cropped_images > 0;
 
-    entry_info.set_text(
-        flg!(
-            "compute_found_images",
-            number_files = information.number_of_duplicates,
-            number_groups = information.number_of_groups
-        )
-        .as_str(),
-    );
+    if information.number_of_cropped_images > 0 {
+        entry_info.set_text(
+            flg!(
+                "compute_found_images_with_cropped",
+                number_files = information.number_of_duplicates,
+                number_groups = information.number_of_groups,
+                number_cropped = information.number_of_cropped_images
+            )
+            .as_str(),
+        );
+    } else {
+        entry_info.set_text(
+            flg!(
+                "compute_found_images",
+                number_files = information.number_of_duplicates,
+                number_groups = information.number_of_groups
+            )
+            .as_str(),
+        );
+    }
 
     // Create GUI
     {
@@ -499,8 +520,7 @@ fn compute_similar_images(sf: SimilarImages, entry_info: &Entry, text_view_error
                     base_file_entry.size,
                     base_file_entry.modified_date,
                     &format!("{}x{}", base_file_entry.width, base_file_entry.height),
-                    0,
-                    hash_size,
+                    "",
                     true,
                     true,
                 );
@@ -513,8 +533,7 @@ fn compute_similar_images(sf: SimilarImages, entry_info: &Entry, text_view_error
                         file_entry.size,
                         file_entry.modified_date,
                         &format!("{}x{}", file_entry.width, file_entry.height),
-                        file_entry.similarity,
-                        hash_size,
+                        &get_string_from_similarity(&file_entry.similarity, hash_size),
                         false,
                         true,
                     );
@@ -525,7 +544,7 @@ fn compute_similar_images(sf: SimilarImages, entry_info: &Entry, text_view_error
             for mut vec_file_entry in vec_struct_similar {
                 vec_file_entry.sort_by_key(|e| e.similarity);
 
-                similar_images_add_to_list_store(&list_store, "", "", 0, 0, "", 0, 0, true, false);
+                similar_images_add_to_list_store(&list_store, "", "", 0, 0, "", "", true, false);
                 for file_entry in &vec_file_entry {
                     let (directory, file) = split_path(&file_entry.path);
                     similar_images_add_to_list_store(
@@ -535,8 +554,7 @@ fn compute_similar_images(sf: SimilarImages, entry_info: &Entry, text_view_error
                         file_entry.size,
                         file_entry.modified_date,
                         &format!("{}x{}", file_entry.width, file_entry.height),
-                        file_entry.similarity,
-                        hash_size,
+                        &get_string_from_similarity(&file_entry.similarity, hash_size),
                         false,
                         false,
                     );
@@ -544,6 +562,44 @@ fn compute_similar_images(sf: SimilarImages, entry_info: &Entry, text_view_error
             }
         }
 
+        // Each cropped image is shown as separate group, with parent image first
+        for cropped_image in sf.get_cropped_images() {
+            let use_reference = sf.get_use_reference();
+            if !use_reference {
+                similar_images_add_to_list_store(&list_store, "", "", 0, 0, "", "", true, false);
+            }
+            let parent = &cropped_image.parent;
+            let (directory, file) = split_path(&parent.path);
+            similar_images_add_to_list_store(
+                &list_store,
+                &file,
+                &directory,
+                parent.size,
+                parent.modified_date,
+                &format!("{}x{}", parent.width, parent.height),
+                "",
+                use_reference,
+                use_reference,
+            );
+            let child = &cropped_image.child;
+            let (directory, file) = split_path(&child.path);
+            similar_images_add_to_list_store(
+                &list_store,
+                &file,
+                &directory,
+                child.size,
+                child.modified_date,
+                &format!("{}x{}", child.width, child.height),
+                &flg!(
+                    "similar_images_cropped_part",
+                    similarity = get_string_from_similarity(&cropped_image.similarity, hash_size),
+                    percent = format!("{:.0}", cropped_image.crop_ratio * 100.0)
+                ),
+                false,
+                use_reference,
+            );
+        }
+
         print_text_messages_to_text_view(text_messages, text_view_errors);
     }
 
@@ -696,7 +752,7 @@ fn compute_duplicate_finder(df: DuplicateFinder, entry_info: &Entry, text_view_e
             duplicates_size = 0;
             duplicates_group = information.number_of_groups_by_name;
         }
-        CheckingMethod::Hash => {
+        CheckingMethod::Hash | CheckingMethod::SampledHash => {
             duplicates_number = information.number_of_duplicated_files_by_hash;
             duplicates_size = information.lost_space_by_hash;
             duplicates_group = information.number_of_groups_by_hash;
@@ -736,6 +792,9 @@ fn compute_duplicate_finder(df: DuplicateFinder, entry_info: &Entry, text_view_e
             .as_str(),
         );
     }
+    if df.contains_only_sampled_groups() && duplicates_group > 0 {
+        entry_info.set_text(&format!("{} - {}", entry_info.text(), flg!("compute_duplicates_sampled_unverified")));
+    }
 
     // Create GUI
     {
@@ -757,7 +816,7 @@ fn compute_duplicate_finder(df: DuplicateFinder, entry_info: &Entry, text_view_e
                         }
                     }
                 }
-                CheckingMethod::Hash => {
+                CheckingMethod::Hash | CheckingMethod::SampledHash => {
                     let btreemap = df.get_files_with_identical_hashes_referenced();
 
                     for (_size, vectors_vector) in btreemap.iter().rev() {
@@ -805,16 +864,18 @@ fn compute_duplicate_finder(df: DuplicateFinder, entry_info: &Entry, text_view_e
                 CheckingMethod::Name => {
                     let btreemap = df.get_files_sorted_by_names();
 
-                    for (_name, vector) in btreemap.iter().rev() {
-                        let vector = vector_sort_unstable_entry_by_path(vector);
-                        duplicates_add_to_list_store(&list_store, "", "", 0, 0, true, false);
-                        for entry in vector {
-                            let (directory, file) = split_path(&entry.path);
-                            duplicates_add_to_list_store(&list_store, &file, &directory, entry.size, entry.modified_date, false, false);
+                    for (_name, vectors_vector) in btreemap.iter().rev() {
+                        for vector in vectors_vector {
+                            let vector = vector_sort_unstable_entry_by_path(vector);
+                            duplicates_add_to_list_store(&list_store, "", "", 0, 0, true, false);
+                            for entry in vector {
+                                let (directory, file) = split_path(&entry.path);
+                                duplicates_add_to_list_store(&list_store, &file, &directory, entry.size, entry.modified_date, false, false);
+                            }
                         }
                     }
                 }
-                CheckingMethod::Hash => {
+                CheckingMethod::Hash | CheckingMethod::SampledHash => {
                     let btreemap = df.get_files_sorted_by_hash();
 
                     for (_size, vectors_vector) in btreemap.iter().rev() {
@@ -832,26 +893,30 @@ fn compute_duplicate_finder(df: DuplicateFinder, entry_info: &Entry, text_view_e
                 CheckingMethod::Size => {
                     let btreemap = df.get_files_sorted_by_size();
 
-                    for (_size, vector) in btreemap.iter().rev() {
-                        let vector = vector_sort_unstable_entry_by_path(vector);
-                        duplicates_add_to_list_store(&list_store, "", "", 0, 0, true, false);
+                    for (_size, vectors_vector) in btreemap.iter().rev() {
+                        for vector in vectors_vector {
+                            let vector = vector_sort_unstable_entry_by_path(vector);
+                            duplicates_add_to_list_store(&list_store, "", "", 0, 0, true, false);
 
-                        for entry in vector {
-                            let (directory, file) = split_path(&entry.path);
-                            duplicates_add_to_list_store(&list_store, &file, &directory, entry.size, entry.modified_date, false, false);
+                            for entry in vector {
+                                let (directory, file) = split_path(&entry.path);
+                                duplicates_add_to_list_store(&list_store, &file, &directory, entry.size, entry.modified_date, false, false);
+                            }
                         }
                     }
                 }
                 CheckingMethod::SizeName => {
                     let btreemap = df.get_files_sorted_by_size_name();
 
-                    for (_size, vector) in btreemap.iter().rev() {
-                        let vector = vector_sort_unstable_entry_by_path(vector);
-                        duplicates_add_to_list_store(&list_store, "", "", 0, 0, true, false);
+                    for (_size, vectors_vector) in btreemap.iter().rev() {
+                        for vector in vectors_vector {
+                            let vector = vector_sort_unstable_entry_by_path(vector);
+                            duplicates_add_to_list_store(&list_store, "", "", 0, 0, true, false);
 
-                        for entry in vector {
-                            let (directory, file) = split_path(&entry.path);
-                            duplicates_add_to_list_store(&list_store, &file, &directory, entry.size, entry.modified_date, false, false);
+                            for entry in vector {
+                                let (directory, file) = split_path(&entry.path);
+                                duplicates_add_to_list_store(&list_store, &file, &directory, entry.size, entry.modified_date, false, false);
+                            }
                         }
                     }
                 }
@@ -905,15 +970,13 @@ fn similar_images_add_to_list_store(
     size: u64,
     modified_date: u64,
     dimensions: &str,
-    similarity: u32,
-    hash_size: u8,
+    similarity_string: &str,
     is_header: bool,
     is_reference_folder: bool,
 ) {
     const COLUMNS_NUMBER: usize = 13;
     let (size_str, string_date) = format_size_and_date(size, modified_date, is_header, is_reference_folder);
     let color = get_row_color(is_header);
-    let similarity_string = if is_header { String::new() } else { get_string_from_similarity(&similarity, hash_size) };
 
     let values: [(u32, &dyn ToValue); COLUMNS_NUMBER] = [
         (ColumnsSimilarImages::ActivatableSelectButton as u32, &(!is_header)),
@@ -1005,6 +1068,18 @@ fn get_dt_timestamp_string(timestamp: u64) -> String {
         .to_string()
 }
 
+fn set_buttons_as_inactive(
+    buttons_array: &Rc<RefCell<HashMap<NotebookMainEnum, HashMap<BottomButtonsEnum, bool>>>>,
+    notebook_enum: NotebookMainEnum,
+    buttons: &[BottomButtonsEnum],
+) {
+    let mut b_mut = buttons_array.borrow_mut();
+    let butt = b_mut.get_mut(&notebook_enum).expect("Failed to borrow buttons");
+    for i in buttons {
+        *butt.get_mut(i).expect("Failed to borrow buttons") = false;
+    }
+}
+
 fn set_specific_buttons_as_active(buttons_array: &Rc<RefCell<HashMap<NotebookMainEnum, HashMap<BottomButtonsEnum, bool>>>>, notebook_enum: NotebookMainEnum, value_to_set: bool) {
     let mut b_mut = buttons_array.borrow_mut();
     let butt = b_mut.get_mut(&notebook_enum).expect("Failed to borrow buttons");
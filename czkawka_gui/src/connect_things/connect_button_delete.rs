@@ -1,3 +1,7 @@
+use std::path::Path;
+
+use czkawka_core::common::deletion_journal::{DeletionJournal, JournalAction};
+use czkawka_core::common::quarantine::Quarantine;
 use czkawka_core::common::{remove_folder_if_contains_only_empty_folders, remove_single_file};
 use gtk4::prelude::*;
 use gtk4::{Align, CheckButton, Dialog, Orientation, ResponseType, TextView};
@@ -7,7 +11,8 @@ use rayon::prelude::*;
 use crate::flg;
 use crate::gui_structs::common_tree_view::SubView;
 use crate::gui_structs::gui_data::GuiData;
-use crate::help_functions::get_full_name_from_path_name;
+use crate::help_functions::{get_full_name_from_path_name, get_quarantine_folder};
+use crate::helpers::enums::BottomButtonsEnum;
 use crate::helpers::list_store_operations::{check_how_much_elements_is_selected, clean_invalid_headers};
 use crate::helpers::model_iter::iter_list;
 use crate::notebook_enums::NotebookMainEnum;
@@ -30,12 +35,23 @@ pub async fn delete_things(gui_data: GuiData) {
     let check_button_settings_confirm_group_deletion = gui_data.settings.check_button_settings_confirm_group_deletion.clone();
 
     let check_button_settings_use_trash = gui_data.settings.check_button_settings_use_trash.clone();
+    let quarantine_folder = get_quarantine_folder(&gui_data.settings.entry_settings_quarantine_folder);
 
     let text_view_errors = gui_data.text_view_errors.clone();
 
     let common_tree_views = gui_data.main_notebook.common_tree_views.clone();
     let sv = gui_data.main_notebook.common_tree_views.get_current_subview();
 
+    // Delete key works even when delete button is hidden, e.g. for duplicates matched only by sampled hash
+    if !gui_data
+        .shared_buttons
+        .borrow()
+        .get(&sv.enum_value)
+        .is_some_and(|buttons| buttons.get(&BottomButtonsEnum::Delete) == Some(&true))
+    {
+        return;
+    }
+
     let (number_of_selected_items, number_of_selected_groups) = check_how_much_elements_is_selected(sv);
 
     // Nothing is selected
@@ -50,12 +66,12 @@ pub async fn delete_things(gui_data: GuiData) {
     if let Some(column_header) = sv.nb_object.column_header {
         if !check_button_settings_confirm_group_deletion.is_active() || !check_if_deleting_all_files_in_group(sv, &window_main, &check_button_settings_confirm_group_deletion).await
         {
-            tree_remove(sv, column_header, &check_button_settings_use_trash, &text_view_errors);
+            tree_remove(sv, column_header, &check_button_settings_use_trash, quarantine_folder.as_deref(), &text_view_errors);
         }
     } else if sv.nb_object.notebook_type == NotebookMainEnum::EmptyDirectories {
-        empty_folder_remover(sv, &check_button_settings_use_trash, &text_view_errors);
+        empty_folder_remover(sv, &check_button_settings_use_trash, quarantine_folder.as_deref(), &text_view_errors);
     } else {
-        basic_remove(sv, &check_button_settings_use_trash, &text_view_errors);
+        basic_remove(sv, &check_button_settings_use_trash, quarantine_folder.as_deref(), &text_view_errors);
     }
 
     common_tree_views.hide_preview();
@@ -201,21 +217,28 @@ pub async fn check_if_deleting_all_files_in_group(sv: &SubView, window_main: &gt
     false
 }
 
-pub(crate) fn empty_folder_remover(sv: &SubView, check_button_settings_use_trash: &CheckButton, text_view_errors: &TextView) {
-    common_file_remove(sv, check_button_settings_use_trash, text_view_errors, None, false);
+pub(crate) fn empty_folder_remover(sv: &SubView, check_button_settings_use_trash: &CheckButton, quarantine_folder: Option<&Path>, text_view_errors: &TextView) {
+    common_file_remove(sv, check_button_settings_use_trash, quarantine_folder, text_view_errors, None, false);
 }
 
-pub(crate) fn basic_remove(sv: &SubView, check_button_settings_use_trash: &CheckButton, text_view_errors: &TextView) {
-    common_file_remove(sv, check_button_settings_use_trash, text_view_errors, None, true);
+pub(crate) fn basic_remove(sv: &SubView, check_button_settings_use_trash: &CheckButton, quarantine_folder: Option<&Path>, text_view_errors: &TextView) {
+    common_file_remove(sv, check_button_settings_use_trash, quarantine_folder, text_view_errors, None, true);
 }
 
-pub(crate) fn tree_remove(sv: &SubView, column_header: i32, check_button_settings_use_trash: &CheckButton, text_view_errors: &TextView) {
-    common_file_remove(sv, check_button_settings_use_trash, text_view_errors, Some(column_header), true);
+pub(crate) fn tree_remove(sv: &SubView, column_header: i32, check_button_settings_use_trash: &CheckButton, quarantine_folder: Option<&Path>, text_view_errors: &TextView) {
+    common_file_remove(sv, check_button_settings_use_trash, quarantine_folder, text_view_errors, Some(column_header), true);
 
     clean_invalid_headers(&sv.get_model(), column_header, sv.nb_object.column_path);
 }
 
-pub(crate) fn common_file_remove(sv: &SubView, check_button_settings_use_trash: &CheckButton, text_view_errors: &TextView, column_header: Option<i32>, file_remove: bool) {
+pub(crate) fn common_file_remove(
+    sv: &SubView,
+    check_button_settings_use_trash: &CheckButton,
+    quarantine_folder: Option<&Path>,
+    text_view_errors: &TextView,
+    column_header: Option<i32>,
+    file_remove: bool,
+) {
     let use_trash = check_button_settings_use_trash.is_active();
 
     let model = sv.get_model();
@@ -258,13 +281,28 @@ pub(crate) fn common_file_remove(sv: &SubView, check_button_settings_use_trash:
         })
         .collect::<Vec<_>>();
 
-    let (mut removed, failed_to_remove): (Vec<usize>, Vec<String>) = to_remove
+    let journal = DeletionJournal::new();
+    let journal_action = if use_trash { JournalAction::MoveToTrash } else { JournalAction::Delete };
+    // Quarantine, when configured, is used instead of trash or permanent deletion
+    let quarantine = quarantine_folder.map(Quarantine::new);
+
+    let (mut removed, mut failed_to_remove): (Vec<usize>, Vec<String>) = to_remove
         .into_par_iter()
-        .map(|(idx, path)| {
-            if file_remove {
-                remove_single_file(&path, use_trash)?;
+        .map(|(idx, full_path)| {
+            let path = Path::new(&full_path);
+            if let Some(quarantine) = &quarantine {
+                let action = JournalAction::Quarantine {
+                    destination: quarantine.get_destination(path),
+                };
+                journal.record(path, action, || quarantine.move_item(path, !file_remove).map(|_| ()))?;
             } else {
-                remove_folder_if_contains_only_empty_folders(&path, use_trash)?;
+                journal.record(path, journal_action.clone(), || {
+                    if file_remove {
+                        remove_single_file(&full_path, use_trash)
+                    } else {
+                        remove_folder_if_contains_only_empty_folders(path, use_trash)
+                    }
+                })?;
             }
             Ok(idx)
         })
@@ -272,6 +310,14 @@ pub(crate) fn common_file_remove(sv: &SubView, check_button_settings_use_trash:
             Ok(entry) => itertools::Either::Left(entry),
             Err(err) => itertools::Either::Right(err),
         });
+    if let Err(e) = journal.save() {
+        failed_to_remove.push(e);
+    }
+    if let Some(quarantine) = &quarantine
+        && let Err(e) = quarantine.save_manifest()
+    {
+        failed_to_remove.push(e);
+    }
 
     for failed in &failed_to_remove {
         messages += failed;
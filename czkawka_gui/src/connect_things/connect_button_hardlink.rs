@@ -1,3 +1,6 @@
+use std::path::Path;
+
+use czkawka_core::common::deletion_journal::{DeletionJournal, JournalAction};
 use czkawka_core::common::{make_file_symlink, make_hard_link};
 use gtk4::prelude::*;
 use gtk4::{Align, CheckButton, Dialog, Orientation, ResponseType, TextView, TreeIter, TreePath};
@@ -155,35 +158,48 @@ fn hardlink_symlink(sv: &SubView, hardlinking: TypeOfTool, text_view_errors: &Te
         }
     }
 
-    let errors = vec_symhardlink_data
+    let journal = DeletionJournal::new();
+    let mut errors = vec_symhardlink_data
         .into_par_iter()
         .flat_map(|symhardlink_data| {
             let mut err = vec![];
             for file_to_be_replaced in symhardlink_data.files_to_symhardlink {
-                if hardlinking == TypeOfTool::Symlinking {
-                    if let Err(e) = make_file_symlink(&symhardlink_data.original_data, &file_to_be_replaced) {
-                        err.push(flg!(
-                            "symlink_failed",
-                            name = symhardlink_data.original_data.clone(),
-                            target = file_to_be_replaced,
-                            reason = e.to_string()
-                        ));
-                    }
+                let original = Path::new(&symhardlink_data.original_data).to_path_buf();
+                let res = if hardlinking == TypeOfTool::Symlinking {
+                    journal.record(Path::new(&file_to_be_replaced), JournalAction::Symlink { original }, || {
+                        make_file_symlink(&symhardlink_data.original_data, &file_to_be_replaced).map_err(|e| {
+                            flg!(
+                                "symlink_failed",
+                                name = symhardlink_data.original_data.clone(),
+                                target = file_to_be_replaced.clone(),
+                                reason = e.to_string()
+                            )
+                        })
+                    })
                 } else {
-                    if let Err(e) = make_hard_link(&symhardlink_data.original_data, &file_to_be_replaced) {
-                        err.push(flg!(
-                            "hardlink_failed",
-                            name = symhardlink_data.original_data.clone(),
-                            target = file_to_be_replaced,
-                            reason = e.to_string()
-                        ));
-                    }
+                    journal.record(Path::new(&file_to_be_replaced), JournalAction::Hardlink { original }, || {
+                        make_hard_link(&symhardlink_data.original_data, &file_to_be_replaced).map_err(|e| {
+                            flg!(
+                                "hardlink_failed",
+                                name = symhardlink_data.original_data.clone(),
+                                target = file_to_be_replaced.clone(),
+                                reason = e.to_string()
+                            )
+                        })
+                    })
+                };
+                if let Err(e) = res {
+                    err.push(e);
                 }
             }
             err
         })
         .collect::<Vec<_>>();
 
+    if let Err(e) = journal.save() {
+        errors.push(e);
+    }
+
     for error in errors {
         add_text_to_text_view(text_view_errors, &error);
     }
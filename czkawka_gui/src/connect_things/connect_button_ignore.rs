@@ -0,0 +1,109 @@
+use std::path::PathBuf;
+
+use czkawka_core::common::ignored_groups::IgnoredGroups;
+use czkawka_core::common::model::ToolType;
+use gtk4::prelude::*;
+use gtk4::{TextView, TreePath};
+
+use crate::flg;
+use crate::gui_structs::common_tree_view::SubView;
+use crate::gui_structs::gui_data::GuiData;
+use crate::help_functions::{add_text_to_text_view, get_full_name_from_path_name, reset_text_view};
+use crate::helpers::model_iter::iter_list;
+use crate::notebook_enums::NotebookMainEnum;
+
+pub(crate) fn connect_button_ignore(gui_data: &GuiData) {
+    let buttons_ignore = gui_data.bottom_buttons.buttons_ignore.clone();
+
+    let gui_data = gui_data.clone();
+
+    buttons_ignore.connect_clicked(move |_| {
+        let common_tree_views = &gui_data.main_notebook.common_tree_views;
+        let sv = common_tree_views.get_current_subview();
+
+        ignore_selected_groups(sv, &gui_data.text_view_errors);
+
+        common_tree_views.hide_preview();
+    });
+}
+
+fn ignore_selected_groups(sv: &SubView, text_view_errors: &TextView) {
+    let tool_type = match sv.enum_value {
+        NotebookMainEnum::Duplicate => ToolType::Duplicate,
+        NotebookMainEnum::SimilarImages => ToolType::SimilarImages,
+        NotebookMainEnum::SimilarVideos => ToolType::SimilarVideos,
+        NotebookMainEnum::SameMusic => ToolType::SameMusic,
+        _ => panic!("Ignoring groups is not supported for {:?}", sv.enum_value),
+    };
+
+    reset_text_view(text_view_errors);
+
+    let column_header = sv.nb_object.column_header.expect("Ignoring can be only used for tree views with grouped results");
+    let model = sv.get_model();
+
+    // Whole group is removed from view, when at least one of its records is selected and group was saved as ignored
+    let mut groups_to_ignore: Vec<(Vec<PathBuf>, Vec<TreePath>)> = Vec::new();
+
+    let mut current_group: Vec<PathBuf> = Vec::new();
+    let mut current_group_tree_paths: Vec<TreePath> = Vec::new();
+    let mut current_group_selected = false;
+    let mut finish_group = |group: &mut Vec<PathBuf>, tree_paths: &mut Vec<TreePath>, selected: bool| {
+        if selected {
+            groups_to_ignore.push((std::mem::take(group), std::mem::take(tree_paths)));
+        } else {
+            group.clear();
+            tree_paths.clear();
+        }
+    };
+
+    iter_list(&model, |m, i| {
+        let is_header = m.get::<bool>(i, column_header);
+        if is_header {
+            finish_group(&mut current_group, &mut current_group_tree_paths, current_group_selected);
+            current_group_selected = false;
+        } else if m.get::<bool>(i, sv.nb_object.column_selection) {
+            current_group_selected = true;
+        }
+
+        // Header contains file only when reference folders are used
+        let path = m.get::<String>(i, sv.nb_object.column_path);
+        if !is_header || !path.is_empty() {
+            let file_name = m.get::<String>(i, sv.nb_object.column_name);
+            current_group.push(PathBuf::from(get_full_name_from_path_name(&path, &file_name)));
+        }
+        current_group_tree_paths.push(m.path(i));
+    });
+    finish_group(&mut current_group, &mut current_group_tree_paths, current_group_selected);
+
+    if groups_to_ignore.is_empty() {
+        return;
+    }
+
+    let mut warnings = Vec::new();
+    let mut ignored_groups = IgnoredGroups::load(&mut warnings);
+    let mut vec_tree_path_to_remove: Vec<TreePath> = Vec::new();
+    let mut ignored_groups_number = 0;
+    for (group, tree_paths) in groups_to_ignore {
+        if ignored_groups.add_group(tool_type, group) {
+            ignored_groups_number += 1;
+            vec_tree_path_to_remove.extend(tree_paths);
+        }
+    }
+
+    // View is changed only when groups are really saved, otherwise user would think, that they will be hidden in next scans
+    if ignored_groups_number > 0 {
+        if let Err(e) = ignored_groups.save() {
+            add_text_to_text_view(text_view_errors, &e);
+            return;
+        }
+
+        for tree_path in vec_tree_path_to_remove.iter().rev() {
+            model.remove(&model.iter(tree_path).expect("Using invalid tree_path"));
+        }
+    }
+
+    add_text_to_text_view(text_view_errors, &flg!("ignore_groups_added", groups = ignored_groups_number));
+    for warning in warnings {
+        add_text_to_text_view(text_view_errors, &warning);
+    }
+}
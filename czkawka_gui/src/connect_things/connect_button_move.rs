@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use czkawka_core::common::deletion_journal::{DeletionJournal, JournalAction};
 use fs_extra::dir::CopyOptions;
 use gtk4::prelude::*;
 use gtk4::{ResponseType, TreePath};
@@ -131,8 +132,10 @@ fn move_files_common(
     debug!("Starting to move {} files", selected_rows.len());
     let start_time = std::time::Instant::now();
 
+    let journal = DeletionJournal::new();
+
     // Save to variable paths of files, and remove it when not removing all occurrences.
-    'next_result: for tree_path in selected_rows.iter().rev() {
+    for tree_path in selected_rows.iter().rev() {
         let iter = model.iter(tree_path).expect("Using invalid tree_path");
 
         let file_name = model.get::<String>(&iter, column_file_name);
@@ -140,22 +143,34 @@ fn move_files_common(
 
         let thing = get_full_name_from_path_name(&path, &file_name);
         let destination_file = destination_folder.join(&file_name);
-        if Path::new(&thing).is_dir() {
-            if let Err(e) = fs_extra::dir::move_dir(&thing, &destination_file, &CopyOptions::new()) {
-                messages += flg!("move_folder_failed", name = thing, reason = e.to_string()).as_str();
-                messages += "\n";
-                continue 'next_result;
+        let action = JournalAction::Move {
+            destination: destination_file.clone(),
+        };
+        let move_result = journal.record(Path::new(&thing), action, || {
+            if Path::new(&thing).is_dir() {
+                fs_extra::dir::move_dir(&thing, &destination_file, &CopyOptions::new())
+                    .map(|_| ())
+                    .map_err(|e| flg!("move_folder_failed", name = thing.clone(), reason = e.to_string()))
+            } else {
+                fs_extra::file::move_file(&thing, &destination_file, &fs_extra::file::CopyOptions::new())
+                    .map(|_| ())
+                    .map_err(|e| flg!("move_file_failed", name = thing.clone(), reason = e.to_string()))
             }
-        } else if let Err(e) = fs_extra::file::move_file(&thing, &destination_file, &fs_extra::file::CopyOptions::new()) {
-            messages += flg!("move_file_failed", name = thing, reason = e.to_string()).as_str();
+        });
+        if let Err(e) = move_result {
+            messages += e.as_str();
             messages += "\n";
-
-            continue 'next_result;
+            continue;
         }
         model.remove(&iter);
         moved_files += 1;
     }
 
+    if let Err(e) = journal.save() {
+        messages += e.as_str();
+        messages += "\n";
+    }
+
     debug!("Moved {moved_files} files in {:?}", start_time.elapsed());
 
     entry_info.set_text(flg!("move_stats", num_files = moved_files, all_files = selected_rows.len()).as_str());
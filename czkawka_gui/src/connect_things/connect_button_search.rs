@@ -5,19 +5,20 @@ use std::thread;
 
 use crossbeam_channel::Sender;
 use czkawka_core::common::consts::DEFAULT_THREAD_SIZE;
+use czkawka_core::common::metadata_filter::{parse_date, parse_ids};
 use czkawka_core::common::model::CheckingMethod;
 use czkawka_core::common::progress_data::ProgressData;
 use czkawka_core::common::tool_data::CommonData;
 use czkawka_core::common::traits::Search;
 use czkawka_core::tools::bad_extensions::{BadExtensions, BadExtensionsParameters};
 use czkawka_core::tools::big_file::{BigFile, BigFileParameters};
-use czkawka_core::tools::broken_files::{BrokenFiles, BrokenFilesParameters, CheckedTypes};
+use czkawka_core::tools::broken_files::{BrokenFiles, BrokenFilesParameters, CheckedTypes, Severity};
 use czkawka_core::tools::duplicate::{DuplicateFinder, DuplicateFinderParameters};
 use czkawka_core::tools::empty_files::EmptyFiles;
 use czkawka_core::tools::empty_folder::EmptyFolder;
 use czkawka_core::tools::invalid_symlinks::InvalidSymlinks;
 use czkawka_core::tools::same_music::{MusicSimilarity, SameMusic, SameMusicParameters};
-use czkawka_core::tools::similar_images::{SimilarImages, SimilarImagesParameters};
+use czkawka_core::tools::similar_images::{HashCombineMode, ImagesGroupingMode, SimilarImages, SimilarImagesParameters};
 use czkawka_core::tools::similar_videos::{DEFAULT_CROP_DETECT, DEFAULT_SKIP_FORWARD_AMOUNT, DEFAULT_VID_HASH_DURATION, SimilarVideos, SimilarVideosParameters};
 use czkawka_core::tools::temporary::Temporary;
 use fun_time::fun_time;
@@ -31,7 +32,7 @@ use crate::help_combo_box::{
     AUDIO_TYPE_CHECK_METHOD_COMBO_BOX, BIG_FILES_CHECK_METHOD_COMBO_BOX, DUPLICATES_CHECK_METHOD_COMBO_BOX, DUPLICATES_HASH_TYPE_COMBO_BOX, IMAGES_HASH_SIZE_COMBO_BOX,
     IMAGES_HASH_TYPE_COMBO_BOX, IMAGES_RESIZE_ALGORITHM_COMBO_BOX,
 };
-use crate::help_functions::{get_path_buf_from_vector_of_strings, hide_all_buttons, reset_text_view, set_buttons};
+use crate::help_functions::{get_path_buf_from_vector_of_strings, get_quarantine_folder, hide_all_buttons, reset_text_view, set_buttons};
 use crate::helpers::enums::{ColumnsExcludedDirectory, ColumnsIncludedDirectory, Message};
 use crate::helpers::list_store_operations::{check_if_list_store_column_have_all_same_values, get_string_from_list_store};
 use crate::helpers::model_iter::iter_list;
@@ -132,6 +133,7 @@ struct LoadedCommonItems {
     reference_directories: Vec<PathBuf>,
     recursive_search: bool,
     excluded_items: Vec<String>,
+    included_items: Vec<String>,
     allowed_extensions: String,
     excluded_extensions: String,
     hide_hard_links: bool,
@@ -141,6 +143,15 @@ struct LoadedCommonItems {
     minimal_file_size: u64,
     maximal_file_size: u64,
     ignore_other_filesystems: bool,
+    use_ignore_files: bool,
+    max_depth: Option<usize>,
+    follow_symlinks: bool,
+    minimal_modified_date: Option<u64>,
+    maximal_modified_date: Option<u64>,
+    not_accessed_for_days: Option<u64>,
+    owner_uids: Result<Vec<u32>, String>,
+    owner_gids: Result<Vec<u32>, String>,
+    quarantine_folder: Option<PathBuf>,
 }
 
 impl LoadedCommonItems {
@@ -176,6 +187,14 @@ impl LoadedCommonItems {
         ));
         let recursive_search = check_button_recursive.is_active();
         let excluded_items = entry_excluded_items.text().as_str().split(',').map(ToString::to_string).collect::<Vec<String>>();
+        let included_items = gui_data
+            .upper_notebook
+            .entry_included_items
+            .text()
+            .as_str()
+            .split(',')
+            .map(ToString::to_string)
+            .collect::<Vec<String>>();
         let allowed_extensions = entry_allowed_extensions.text().as_str().to_string();
         let excluded_extensions = entry_excluded_extensions.text().as_str().to_string();
         let hide_hard_links = check_button_settings_hide_hard_links.is_active();
@@ -201,6 +220,17 @@ impl LoadedCommonItems {
             .parse::<u64>()
             .unwrap_or_else(|_| DEFAULT_MAXIMAL_FILE_SIZE.parse::<u64>().expect("Failed to parse maximal_file_size"));
         let ignore_other_filesystems = check_button_settings_one_filesystem.is_active();
+        let use_ignore_files = gui_data.settings.check_button_settings_use_ignore_files.is_active();
+        let follow_symlinks = gui_data.settings.check_button_settings_follow_symlinks.is_active();
+
+        // Invalid or empty values disable given filter
+        let minimal_modified_date = parse_date(&gui_data.settings.entry_settings_min_modified_date.text()).ok();
+        let maximal_modified_date = parse_date(&gui_data.settings.entry_settings_max_modified_date.text()).ok();
+        let max_depth = gui_data.settings.entry_settings_max_depth.text().trim().parse::<usize>().ok();
+        let not_accessed_for_days = gui_data.settings.entry_settings_not_accessed_for_days.text().trim().parse::<u64>().ok();
+        let owner_uids = parse_ids(&gui_data.settings.entry_settings_owner_uids.text());
+        let owner_gids = parse_ids(&gui_data.settings.entry_settings_owner_gids.text());
+        let quarantine_folder = get_quarantine_folder(&gui_data.settings.entry_settings_quarantine_folder);
 
         Self {
             included_directories,
@@ -208,6 +238,7 @@ impl LoadedCommonItems {
             reference_directories,
             recursive_search,
             excluded_items,
+            included_items,
             allowed_extensions,
             excluded_extensions,
             hide_hard_links,
@@ -217,6 +248,15 @@ impl LoadedCommonItems {
             minimal_file_size,
             maximal_file_size,
             ignore_other_filesystems,
+            use_ignore_files,
+            max_depth,
+            follow_symlinks,
+            minimal_modified_date,
+            maximal_modified_date,
+            not_accessed_for_days,
+            owner_uids,
+            owner_gids,
+            quarantine_folder,
         }
     }
 }
@@ -235,6 +275,7 @@ fn duplicate_search(
     let combo_box_duplicate_hash_type = gui_data.main_notebook.combo_box_duplicate_hash_type.clone();
     let check_button_duplicates_use_prehash_cache = gui_data.settings.check_button_duplicates_use_prehash_cache.clone();
     let check_button_duplicate_case_sensitive_name: gtk4::CheckButton = gui_data.main_notebook.check_button_duplicate_case_sensitive_name.clone();
+    let check_button_duplicate_verify_content: gtk4::CheckButton = gui_data.main_notebook.check_button_duplicate_verify_content.clone();
     let check_button_settings_duplicates_delete_outdated_cache = gui_data.settings.check_button_settings_duplicates_delete_outdated_cache.clone();
     let entry_settings_prehash_cache_file_minimal_size = gui_data.settings.entry_settings_prehash_cache_file_minimal_size.clone();
     let image_preview_duplicates = gui_data.main_notebook.image_preview_duplicates.clone();
@@ -255,6 +296,8 @@ fn duplicate_search(
 
     let delete_outdated_cache = check_button_settings_duplicates_delete_outdated_cache.is_active();
 
+    let verify_content = check_button_duplicate_verify_content.is_active();
+
     // Find duplicates
     thread::Builder::new()
         .stack_size(DEFAULT_THREAD_SIZE)
@@ -267,6 +310,10 @@ fn duplicate_search(
                 loaded_commons.minimal_cache_file_size,
                 minimal_prehash_cache_file_size,
                 case_sensitive_name_comparison,
+                verify_content,
+                // Duplicated and similar directories are not shown in GUI, they are available only in CLI
+                false,
+                None,
             );
             let mut tool = DuplicateFinder::new(params);
 
@@ -493,9 +540,23 @@ fn broken_files_search(
     grid_progress.set_visible(true);
 
     let check_button_broken_files_archive: gtk4::CheckButton = gui_data.main_notebook.check_button_broken_files_archive.clone();
+    let check_button_broken_files_compressed_archive: gtk4::CheckButton = gui_data.main_notebook.check_button_broken_files_compressed_archive.clone();
     let check_button_broken_files_pdf: gtk4::CheckButton = gui_data.main_notebook.check_button_broken_files_pdf.clone();
     let check_button_broken_files_audio: gtk4::CheckButton = gui_data.main_notebook.check_button_broken_files_audio.clone();
     let check_button_broken_files_image: gtk4::CheckButton = gui_data.main_notebook.check_button_broken_files_image.clone();
+    let check_button_broken_files_video: gtk4::CheckButton = gui_data.main_notebook.check_button_broken_files_video.clone();
+    let check_button_broken_files_document: gtk4::CheckButton = gui_data.main_notebook.check_button_broken_files_document.clone();
+    let check_button_broken_files_font: gtk4::CheckButton = gui_data.main_notebook.check_button_broken_files_font.clone();
+    let check_button_broken_files_sqlite: gtk4::CheckButton = gui_data.main_notebook.check_button_broken_files_sqlite.clone();
+    let check_button_broken_files_text_formats: gtk4::CheckButton = gui_data.main_notebook.check_button_broken_files_text_formats.clone();
+    let decode_video_frames = gui_data.main_notebook.check_button_broken_files_decode_video.is_active();
+    let verify_zip_content = gui_data.main_notebook.check_button_broken_files_verify_zip_content.is_active();
+    let strict_image_check = gui_data.main_notebook.check_button_broken_files_strict_image.is_active();
+    let minimal_severity = if gui_data.main_notebook.check_button_broken_files_show_warnings.is_active() {
+        Severity::Warning
+    } else {
+        Severity::Unreadable
+    };
 
     clean_tree_view(&gui_data.main_notebook.common_tree_views.get_current_subview().tree_view);
 
@@ -513,12 +574,30 @@ fn broken_files_search(
     if check_button_broken_files_archive.is_active() {
         checked_types |= CheckedTypes::ARCHIVE;
     }
+    if check_button_broken_files_compressed_archive.is_active() {
+        checked_types |= CheckedTypes::TAR | CheckedTypes::GZIP | CheckedTypes::XZ | CheckedTypes::ZSTD | CheckedTypes::BZIP2 | CheckedTypes::SEVEN_ZIP;
+    }
+    if check_button_broken_files_video.is_active() {
+        checked_types |= CheckedTypes::VIDEO;
+    }
+    if check_button_broken_files_document.is_active() {
+        checked_types |= CheckedTypes::DOCUMENT;
+    }
+    if check_button_broken_files_font.is_active() {
+        checked_types |= CheckedTypes::FONT;
+    }
+    if check_button_broken_files_sqlite.is_active() {
+        checked_types |= CheckedTypes::SQLITE;
+    }
+    if check_button_broken_files_text_formats.is_active() {
+        checked_types |= CheckedTypes::JSON | CheckedTypes::XML | CheckedTypes::YAML;
+    }
 
     if checked_types != CheckedTypes::NONE {
         thread::Builder::new()
             .stack_size(DEFAULT_THREAD_SIZE)
             .spawn(move || {
-                let params = BrokenFilesParameters::new(checked_types);
+                let params = BrokenFilesParameters::new(checked_types, decode_video_frames, verify_zip_content, strict_image_check, minimal_severity);
                 let mut tool = BrokenFiles::new(params);
 
                 set_common_settings(&mut tool, &loaded_commons);
@@ -568,6 +647,7 @@ fn similar_image_search(
     let combo_box_image_hash_algorithm = gui_data.main_notebook.combo_box_image_hash_algorithm.clone();
     let combo_box_image_resize_algorithm = gui_data.main_notebook.combo_box_image_resize_algorithm.clone();
     let check_button_image_ignore_same_size = gui_data.main_notebook.check_button_image_ignore_same_size.clone();
+    let check_button_image_find_cropped = gui_data.main_notebook.check_button_image_find_cropped.clone();
     let check_button_settings_similar_images_delete_outdated_cache = gui_data.settings.check_button_settings_similar_images_delete_outdated_cache.clone();
     let image_preview_similar_images = gui_data.main_notebook.image_preview_similar_images.clone();
     let scale_similarity_similar_images = gui_data.main_notebook.scale_similarity_similar_images.clone();
@@ -585,6 +665,7 @@ fn similar_image_search(
     let hash_alg = IMAGES_HASH_TYPE_COMBO_BOX[hash_alg_index].hash_alg;
 
     let ignore_same_size = check_button_image_ignore_same_size.is_active();
+    let find_cropped_images = check_button_image_find_cropped.is_active();
 
     let similarity = scale_similarity_similar_images.value() as u32;
 
@@ -593,7 +674,19 @@ fn similar_image_search(
     thread::Builder::new()
         .stack_size(DEFAULT_THREAD_SIZE)
         .spawn(move || {
-            let params = SimilarImagesParameters::new(similarity, hash_size, hash_alg, image_filter, ignore_same_size, loaded_commons.hide_hard_links);
+            let params = SimilarImagesParameters::new(
+                similarity,
+                hash_size,
+                hash_alg,
+                image_filter,
+                ignore_same_size,
+                loaded_commons.hide_hard_links,
+                Vec::new(),
+                HashCombineMode::default(),
+                false,
+                find_cropped_images,
+                ImagesGroupingMode::default(),
+            );
             let mut tool = SimilarImages::new(params);
 
             set_common_settings(&mut tool, &loaded_commons);
@@ -706,11 +799,28 @@ where
     component.set_allowed_extensions(loaded_commons.allowed_extensions.clone());
     component.set_excluded_extensions(loaded_commons.excluded_extensions.clone());
     component.set_excluded_items(loaded_commons.excluded_items.clone());
+    component.set_included_items(loaded_commons.included_items.clone());
     component.set_exclude_other_filesystems(loaded_commons.ignore_other_filesystems);
+    component.set_use_ignore_files(loaded_commons.use_ignore_files);
+    component.set_max_depth(loaded_commons.max_depth);
+    component.set_follow_symlinks(loaded_commons.follow_symlinks);
     component.set_use_cache(loaded_commons.use_cache);
     component.set_save_also_as_json(loaded_commons.save_also_as_json);
     component.set_minimal_file_size(loaded_commons.minimal_file_size);
     component.set_maximal_file_size(loaded_commons.maximal_file_size);
+    component.set_minimal_modified_date(loaded_commons.minimal_modified_date);
+    component.set_maximal_modified_date(loaded_commons.maximal_modified_date);
+    component.set_not_accessed_for_days(loaded_commons.not_accessed_for_days);
+    // Invalid ids are reported, because silently ignoring them would show files of all owners
+    match &loaded_commons.owner_uids {
+        Ok(owner_uids) => component.set_owner_uids(owner_uids.clone()),
+        Err(e) => component.get_text_messages_mut().warnings.push(e.clone()),
+    }
+    match &loaded_commons.owner_gids {
+        Ok(owner_gids) => component.set_owner_gids(owner_gids.clone()),
+        Err(e) => component.get_text_messages_mut().warnings.push(e.clone()),
+    }
+    component.set_quarantine_folder(loaded_commons.quarantine_folder.clone());
 }
 
 #[fun_time(message = "clean_tree_view", level = "debug")]
@@ -23,6 +23,7 @@ fn show_required_popovers(popovers_select: &GuiSelectPopovers, sv: &SubView) {
     let buttons_popover_select_all_except_newest = popovers_select.buttons_popover_select_all_except_newest.clone();
     let buttons_popover_select_one_oldest = popovers_select.buttons_popover_select_one_oldest.clone();
     let buttons_popover_select_one_newest = popovers_select.buttons_popover_select_one_newest.clone();
+    let buttons_popover_select_all_except_kept_by_rules = popovers_select.buttons_popover_select_all_except_kept_by_rules.clone();
     let buttons_popover_select_custom = popovers_select.buttons_popover_select_custom.clone();
     let buttons_popover_unselect_custom = popovers_select.buttons_popover_unselect_custom.clone();
     let buttons_popover_select_all_images_except_biggest = popovers_select.buttons_popover_select_all_images_except_biggest.clone();
@@ -76,12 +77,14 @@ fn show_required_popovers(popovers_select: &GuiSelectPopovers, sv: &SubView) {
         buttons_popover_select_all_except_newest.set_visible(true);
         buttons_popover_select_one_oldest.set_visible(true);
         buttons_popover_select_one_newest.set_visible(true);
+        buttons_popover_select_all_except_kept_by_rules.set_visible(true);
         separator_select_date.set_visible(true);
     } else {
         buttons_popover_select_all_except_oldest.set_visible(false);
         buttons_popover_select_all_except_newest.set_visible(false);
         buttons_popover_select_one_oldest.set_visible(false);
         buttons_popover_select_one_newest.set_visible(false);
+        buttons_popover_select_all_except_kept_by_rules.set_visible(false);
         separator_select_date.set_visible(false);
     }
 }
@@ -1,5 +1,9 @@
+use std::path::PathBuf;
+
 use czkawka_core::common::items::new_excluded_item;
+use czkawka_core::common::model::FileEntry;
 use czkawka_core::common::regex_check;
+use czkawka_core::common::tool_data::KeeperRules;
 use gtk4::prelude::*;
 use gtk4::{ResponseType, TreeIter, Window};
 use log::error;
@@ -9,7 +13,7 @@ use crate::flg;
 use crate::gtk_traits::DialogTraits;
 use crate::gui_structs::common_tree_view::{SubView, TreeViewListStoreTrait};
 use crate::gui_structs::gui_data::GuiData;
-use crate::help_functions::{change_dimension_to_krotka, get_full_name_from_path_name};
+use crate::help_functions::{add_text_to_text_view, change_dimension_to_krotka, get_full_name_from_path_name};
 use crate::helpers::model_iter::iter_list;
 
 // File length variable allows users to choose duplicates which have shorter file name
@@ -148,6 +152,57 @@ fn popover_all_except_oldest_newest(
     popover.popdown();
 }
 
+// Selects all files in group, except the one chosen by keeper rules and files protected by them
+fn popover_all_except_kept_by_rules(popover: &gtk4::Popover, sv: &SubView, keeper_rules: &KeeperRules) {
+    let model = sv.get_model();
+    let column_header = sv.nb_object.column_header.expect("Keeper rules can't be used without headers");
+    let column_size_as_bytes = sv.nb_object.column_size_as_bytes.expect("Keeper rules needs size as bytes column");
+    let column_modification_as_secs = sv.nb_object.column_modification_as_secs.expect("Keeper rules needs modification as secs column");
+
+    if let Some(iter) = model.iter_first() {
+        let mut end: bool = false;
+        loop {
+            let mut tree_iter_array: Vec<TreeIter> = Vec::new();
+            let mut entries: Vec<FileEntry> = Vec::new();
+
+            loop {
+                if model.get::<bool>(&iter, column_header) {
+                    if !model.iter_next(&iter) {
+                        end = true;
+                    }
+                    break;
+                }
+                tree_iter_array.push(iter);
+                let path = model.get::<String>(&iter, sv.nb_object.column_path);
+                let name = model.get::<String>(&iter, sv.nb_object.column_name);
+                entries.push(FileEntry {
+                    path: PathBuf::from(get_full_name_from_path_name(&path, &name)),
+                    size: model.get::<u64>(&iter, column_size_as_bytes),
+                    modified_date: model.get::<u64>(&iter, column_modification_as_secs),
+                });
+
+                if !model.iter_next(&iter) {
+                    end = true;
+                    break;
+                }
+            }
+            if !entries.is_empty() {
+                let kept_index = keeper_rules.select_keeper(&entries);
+                for (index, (tree_iter, entry)) in tree_iter_array.iter().zip(&entries).enumerate() {
+                    let selected = index != kept_index && !keeper_rules.is_protected(entry);
+                    model.set_value(tree_iter, sv.nb_object.column_selection as u32, &selected.to_value());
+                }
+            }
+
+            if end {
+                break;
+            }
+        }
+    }
+
+    popover.popdown();
+}
+
 fn popover_one_oldest_newest(
     popover: &gtk4::Popover,
     sv: &SubView,
@@ -676,6 +731,24 @@ pub(crate) fn connect_popover_select(gui_data: &GuiData) {
         popover_one_oldest_newest(&popover_select, sv, false);
     });
 
+    let popover_select = gui_data.popovers_select.popover_select.clone();
+    let buttons_popover_select_all_except_kept_by_rules = gui_data.popovers_select.buttons_popover_select_all_except_kept_by_rules.clone();
+
+    let entry_settings_keeper_rules = gui_data.settings.entry_settings_keeper_rules.clone();
+    let text_view_errors = gui_data.text_view_errors.clone();
+    let common_tree_views = gui_data.main_notebook.common_tree_views.clone();
+    buttons_popover_select_all_except_kept_by_rules.connect_clicked(move |_| {
+        let sv = common_tree_views.get_current_subview();
+
+        match KeeperRules::parse(entry_settings_keeper_rules.text().as_str()) {
+            Ok(keeper_rules) => popover_all_except_kept_by_rules(&popover_select, sv, &keeper_rules),
+            Err(e) => {
+                add_text_to_text_view(&text_view_errors, &e);
+                popover_select.popdown();
+            }
+        }
+    });
+
     let popover_select = gui_data.popovers_select.popover_select.clone();
     let buttons_popover_select_custom = gui_data.popovers_select.buttons_popover_select_custom.clone();
 
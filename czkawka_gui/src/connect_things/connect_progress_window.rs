@@ -151,6 +151,12 @@ fn progress_default(gui_data: &GuiData, item: &ProgressData) {
         CurrentStage::DuplicateFullHashing => {
             label_stage.set_text(&flg!("progress_analyzed_full_hash", progress_ratio_tm(item)));
         }
+        CurrentStage::DuplicateSampledHashing => {
+            label_stage.set_text(&flg!("progress_analyzed_sampled_hash", progress_ratio_tm(item)));
+        }
+        CurrentStage::DuplicateComparingContent => {
+            label_stage.set_text(&flg!("progress_compared_content_of_files", progress_ratio_tm(item)));
+        }
         _ => unreachable!("Invalid stage {:?}", item.sstage),
     }
 }
@@ -6,7 +6,7 @@ use czkawka_core::common::config_cache_path::get_config_cache_path;
 use czkawka_core::common::model::HashType;
 use czkawka_core::helpers::messages::Messages;
 use czkawka_core::tools::duplicate::DuplicateEntry;
-use czkawka_core::tools::duplicate::core::get_duplicate_cache_file;
+use czkawka_core::tools::duplicate::core::{get_duplicate_cache_file, get_duplicate_sampled_cache_file};
 use czkawka_core::tools::similar_images::core::get_similar_images_cache_file;
 use czkawka_core::tools::similar_videos::core::get_similar_videos_cache_file;
 use czkawka_core::tools::similar_videos::{DEFAULT_CROP_DETECT, DEFAULT_SKIP_FORWARD_AMOUNT, DEFAULT_VID_HASH_DURATION};
@@ -125,9 +125,12 @@ pub(crate) fn connect_settings(gui_data: &GuiData) {
                 dialog.connect_response(move |dialog, response_type| {
                     if response_type == ResponseType::Ok {
                         let mut messages: Messages = Messages::new();
-                        for use_prehash in [true, false] {
-                            for type_of_hash in &[HashType::Xxh3, HashType::Blake3, HashType::Crc32] {
-                                let file_name = get_duplicate_cache_file(type_of_hash, use_prehash);
+                        for type_of_hash in &[HashType::Xxh3, HashType::Blake3, HashType::Crc32] {
+                            for file_name in [
+                                get_duplicate_cache_file(type_of_hash, true),
+                                get_duplicate_cache_file(type_of_hash, false),
+                                get_duplicate_sampled_cache_file(type_of_hash),
+                            ] {
                                 let (mut messages, loaded_items) = load_cache_from_file_generalized_by_size::<DuplicateEntry>(&file_name, true, &Default::default());
 
                                 if let Some(cache_entries) = loaded_items {
@@ -183,7 +186,7 @@ pub(crate) fn connect_settings(gui_data: &GuiData) {
                                     HashAlg::Mean,
                                     HashAlg::Median,
                                 ] {
-                                    let file_name = get_similar_images_cache_file(hash_size, hash_alg, image_filter);
+                                    let file_name = get_similar_images_cache_file(hash_size, hash_alg, image_filter, &[]);
                                     let (mut messages, loaded_items) =
                                         load_cache_from_file_generalized_by_path::<czkawka_core::tools::similar_images::ImagesEntry>(&file_name, true, &Default::default());
 
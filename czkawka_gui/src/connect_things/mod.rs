@@ -2,6 +2,7 @@ pub mod connect_about_buttons;
 pub mod connect_button_compare;
 pub mod connect_button_delete;
 pub mod connect_button_hardlink;
+pub mod connect_button_ignore;
 pub mod connect_button_move;
 pub mod connect_button_save;
 pub mod connect_button_search;
@@ -2,7 +2,7 @@ use gtk4::prelude::*;
 use gtk4::{GestureClick, Label, Widget};
 
 use crate::gtk_traits::WidgetTraits;
-use crate::gui_structs::gui_data::CZK_ICON_SORT;
+use crate::gui_structs::gui_data::{CZK_ICON_IGNORE, CZK_ICON_SORT};
 use crate::helpers::enums::BottomButtonsEnum;
 use crate::helpers::image_operations::set_icon_of_button;
 use crate::{
@@ -18,6 +18,7 @@ pub struct GuiBottomButtons {
     pub buttons_save: gtk4::Button,
     pub buttons_symlink: gtk4::Button,
     pub buttons_hardlink: gtk4::Button,
+    pub buttons_ignore: gtk4::Button,
     pub buttons_move: gtk4::Button,
     pub buttons_compare: gtk4::Button,
     pub buttons_sort: gtk4::MenuButton,
@@ -27,8 +28,8 @@ pub struct GuiBottomButtons {
     pub label_buttons_select: gtk4::Label,
     pub label_buttons_sort: gtk4::Label,
 
-    pub buttons_names: [BottomButtonsEnum; 9],
-    pub buttons_array: [Widget; 9],
+    pub buttons_names: [BottomButtonsEnum; 10],
+    pub buttons_array: [Widget; 10],
 
     pub gc_buttons_select: GestureClick,
     pub gc_buttons_sort: GestureClick,
@@ -42,6 +43,7 @@ impl GuiBottomButtons {
         let buttons_save: gtk4::Button = builder.object("buttons_save").expect("Cambalache");
         let buttons_symlink: gtk4::Button = builder.object("buttons_symlink").expect("Cambalache");
         let buttons_hardlink: gtk4::Button = builder.object("buttons_hardlink").expect("Cambalache");
+        let buttons_ignore: gtk4::Button = builder.object("buttons_ignore").expect("Cambalache");
         let buttons_move: gtk4::Button = builder.object("buttons_move").expect("Cambalache");
         let buttons_compare: gtk4::Button = builder.object("buttons_compare").expect("Cambalache");
         let buttons_sort: gtk4::MenuButton = builder.object("buttons_sort").expect("Cambalache");
@@ -64,6 +66,7 @@ impl GuiBottomButtons {
         set_icon_of_button(&buttons_save, CZK_ICON_SAVE);
         set_icon_of_button(&buttons_symlink, CZK_ICON_SYMLINK);
         set_icon_of_button(&buttons_hardlink, CZK_ICON_HARDLINK);
+        set_icon_of_button(&buttons_ignore, CZK_ICON_IGNORE);
         set_icon_of_button(&buttons_move, CZK_ICON_MOVE);
         set_icon_of_button(&buttons_compare, CZK_ICON_COMPARE);
         set_icon_of_button(&buttons_sort, CZK_ICON_SORT);
@@ -77,6 +80,7 @@ impl GuiBottomButtons {
             BottomButtonsEnum::Save,
             BottomButtonsEnum::Symlink,
             BottomButtonsEnum::Hardlink,
+            BottomButtonsEnum::Ignore,
             BottomButtonsEnum::Move,
             BottomButtonsEnum::Compare,
             BottomButtonsEnum::Sort,
@@ -88,6 +92,7 @@ impl GuiBottomButtons {
             buttons_save.clone().upcast::<Widget>(),
             buttons_symlink.clone().upcast::<Widget>(),
             buttons_hardlink.clone().upcast::<Widget>(),
+            buttons_ignore.clone().upcast::<Widget>(),
             buttons_move.clone().upcast::<Widget>(),
             buttons_compare.clone().upcast::<Widget>(),
             buttons_sort.clone().upcast::<Widget>(),
@@ -106,6 +111,7 @@ impl GuiBottomButtons {
             buttons_save,
             buttons_symlink,
             buttons_hardlink,
+            buttons_ignore,
             buttons_move,
             buttons_compare,
             buttons_sort,
@@ -127,6 +133,7 @@ impl GuiBottomButtons {
         self.buttons_symlink.get_widget_of_type::<Label>(true).set_text(&flg!("bottom_symlink_button"));
         self.buttons_move.get_widget_of_type::<Label>(true).set_text(&flg!("bottom_move_button"));
         self.buttons_hardlink.get_widget_of_type::<Label>(true).set_text(&flg!("bottom_hardlink_button"));
+        self.buttons_ignore.get_widget_of_type::<Label>(true).set_text(&flg!("bottom_ignore_button"));
         self.buttons_compare.get_widget_of_type::<Label>(true).set_text(&flg!("bottom_compare_button"));
         self.label_buttons_sort.set_text(&flg!("bottom_sort_button"));
 
@@ -137,6 +144,7 @@ impl GuiBottomButtons {
         self.buttons_symlink.set_tooltip_text(Some(&flg!("bottom_symlink_button_tooltip")));
         self.buttons_move.set_tooltip_text(Some(&flg!("bottom_move_button_tooltip")));
         self.buttons_sort.set_tooltip_text(Some(&flg!("bottom_sort_button_tooltip")));
+        self.buttons_ignore.set_tooltip_text(Some(&flg!("bottom_ignore_button_tooltip")));
         self.buttons_compare.set_tooltip_text(Some(&flg!("bottom_compare_button_tooltip")));
         if self.buttons_hardlink.is_sensitive() {
             self.buttons_hardlink.set_tooltip_text(Some(&flg!("bottom_hardlink_button_tooltip")));
@@ -29,6 +29,7 @@ pub const CZK_ICON_ADD: &[u8] = include_bytes!("../../icons/czk_add.svg");
 pub const CZK_ICON_COMPARE: &[u8] = include_bytes!("../../icons/czk_compare.svg");
 pub const CZK_ICON_DELETE: &[u8] = include_bytes!("../../icons/czk_delete.svg");
 pub const CZK_ICON_HARDLINK: &[u8] = include_bytes!("../../icons/czk_hardlink.svg");
+pub const CZK_ICON_IGNORE: &[u8] = include_bytes!("../../icons/czk_ignore.svg");
 pub const CZK_ICON_HIDE_DOWN: &[u8] = include_bytes!("../../icons/czk_hide_down.svg");
 pub const CZK_ICON_HIDE_UP: &[u8] = include_bytes!("../../icons/czk_hide_up.svg");
 pub const CZK_ICON_INFO: &[u8] = include_bytes!("../../icons/czk_info.svg");
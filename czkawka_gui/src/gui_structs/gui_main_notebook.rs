@@ -31,6 +31,7 @@ pub struct GuiMainNotebook {
     pub label_duplicate_check_method: Label,
     pub label_duplicate_hash_type: Label,
     pub check_button_duplicate_case_sensitive_name: CheckButton,
+    pub check_button_duplicate_verify_content: CheckButton,
 
     pub image_preview_duplicates: Picture,
 
@@ -52,6 +53,7 @@ pub struct GuiMainNotebook {
     pub combo_box_image_hash_size: ComboBoxText,
 
     pub check_button_image_ignore_same_size: CheckButton,
+    pub check_button_image_find_cropped: CheckButton,
     pub check_button_video_ignore_same_size: CheckButton,
 
     pub label_image_similarity: Label,
@@ -71,7 +73,17 @@ pub struct GuiMainNotebook {
     pub check_button_broken_files_audio: CheckButton,
     pub check_button_broken_files_pdf: CheckButton,
     pub check_button_broken_files_archive: CheckButton,
+    pub check_button_broken_files_compressed_archive: CheckButton,
     pub check_button_broken_files_image: CheckButton,
+    pub check_button_broken_files_video: CheckButton,
+    pub check_button_broken_files_decode_video: CheckButton,
+    pub check_button_broken_files_document: CheckButton,
+    pub check_button_broken_files_font: CheckButton,
+    pub check_button_broken_files_sqlite: CheckButton,
+    pub check_button_broken_files_text_formats: CheckButton,
+    pub check_button_broken_files_verify_zip_content: CheckButton,
+    pub check_button_broken_files_strict_image: CheckButton,
+    pub check_button_broken_files_show_warnings: CheckButton,
 
     // Music
     pub check_button_music_title: CheckButton,
@@ -104,6 +116,7 @@ impl GuiMainNotebook {
 
         //// Check Buttons
         let check_button_duplicate_case_sensitive_name: CheckButton = builder.object("check_button_duplicate_case_sensitive_name").expect("Cambalache");
+        let check_button_duplicate_verify_content: CheckButton = builder.object("check_button_duplicate_verify_content").expect("Cambalache");
         let check_button_music_title: CheckButton = builder.object("check_button_music_title").expect("Cambalache");
         let check_button_music_artist: CheckButton = builder.object("check_button_music_artist").expect("Cambalache");
         let check_button_music_year: CheckButton = builder.object("check_button_music_year").expect("Cambalache");
@@ -116,7 +129,17 @@ impl GuiMainNotebook {
         let check_button_broken_files_audio: CheckButton = builder.object("check_button_broken_files_audio").expect("Cambalache");
         let check_button_broken_files_pdf: CheckButton = builder.object("check_button_broken_files_pdf").expect("Cambalache");
         let check_button_broken_files_archive: CheckButton = builder.object("check_button_broken_files_archive").expect("Cambalache");
+        let check_button_broken_files_compressed_archive: CheckButton = builder.object("check_button_broken_files_compressed_archive").expect("Cambalache");
         let check_button_broken_files_image: CheckButton = builder.object("check_button_broken_files_image").expect("Cambalache");
+        let check_button_broken_files_video: CheckButton = builder.object("check_button_broken_files_video").expect("Cambalache");
+        let check_button_broken_files_decode_video: CheckButton = builder.object("check_button_broken_files_decode_video").expect("Cambalache");
+        let check_button_broken_files_document: CheckButton = builder.object("check_button_broken_files_document").expect("Cambalache");
+        let check_button_broken_files_font: CheckButton = builder.object("check_button_broken_files_font").expect("Cambalache");
+        let check_button_broken_files_sqlite: CheckButton = builder.object("check_button_broken_files_sqlite").expect("Cambalache");
+        let check_button_broken_files_text_formats: CheckButton = builder.object("check_button_broken_files_text_formats").expect("Cambalache");
+        let check_button_broken_files_verify_zip_content: CheckButton = builder.object("check_button_broken_files_verify_zip_content").expect("Cambalache");
+        let check_button_broken_files_strict_image: CheckButton = builder.object("check_button_broken_files_strict_image").expect("Cambalache");
+        let check_button_broken_files_show_warnings: CheckButton = builder.object("check_button_broken_files_show_warnings").expect("Cambalache");
 
         let scale_similarity_similar_images: Scale = builder.object("scale_similarity_similar_images").expect("Cambalache");
         let scale_similarity_similar_videos: Scale = builder.object("scale_similarity_similar_videos").expect("Cambalache");
@@ -127,6 +150,7 @@ impl GuiMainNotebook {
         let combo_box_big_files_mode: ComboBoxText = builder.object("combo_box_big_files_mode").expect("Cambalache");
 
         let check_button_image_ignore_same_size: CheckButton = builder.object("check_button_image_ignore_same_size").expect("Cambalache");
+        let check_button_image_find_cropped: CheckButton = builder.object("check_button_image_find_cropped").expect("Cambalache");
         let check_button_video_ignore_same_size: CheckButton = builder.object("check_button_video_ignore_same_size").expect("Cambalache");
 
         let label_similar_images_minimal_similarity: Label = builder.object("label_similar_images_minimal_similarity").expect("Cambalache");
@@ -184,6 +208,7 @@ impl GuiMainNotebook {
             label_duplicate_check_method,
             label_duplicate_hash_type,
             check_button_duplicate_case_sensitive_name,
+            check_button_duplicate_verify_content,
             image_preview_duplicates,
             label_big_shown_files,
             entry_big_files_number,
@@ -197,6 +222,7 @@ impl GuiMainNotebook {
             combo_box_image_hash_algorithm,
             combo_box_image_hash_size,
             check_button_image_ignore_same_size,
+            check_button_image_find_cropped,
             check_button_video_ignore_same_size,
             label_image_similarity,
             label_image_similarity_max,
@@ -209,7 +235,17 @@ impl GuiMainNotebook {
             check_button_broken_files_audio,
             check_button_broken_files_pdf,
             check_button_broken_files_archive,
+            check_button_broken_files_compressed_archive,
             check_button_broken_files_image,
+            check_button_broken_files_video,
+            check_button_broken_files_decode_video,
+            check_button_broken_files_document,
+            check_button_broken_files_font,
+            check_button_broken_files_sqlite,
+            check_button_broken_files_text_formats,
+            check_button_broken_files_verify_zip_content,
+            check_button_broken_files_strict_image,
+            check_button_broken_files_show_warnings,
             check_button_music_title,
             check_button_music_artist,
             check_button_music_year,
@@ -234,6 +270,7 @@ impl GuiMainNotebook {
 
     pub(crate) fn update_language(&self) {
         self.check_button_duplicate_case_sensitive_name.set_label(Some(&flg!("duplicate_case_sensitive_name")));
+        self.check_button_duplicate_verify_content.set_label(Some(&flg!("duplicate_verify_content")));
         self.check_button_music_title.set_label(Some(&flg!("music_title_checkbox")));
         self.check_button_music_artist.set_label(Some(&flg!("music_artist_checkbox")));
         self.check_button_music_year.set_label(Some(&flg!("music_year_checkbox")));
@@ -265,6 +302,7 @@ impl GuiMainNotebook {
         self.combo_box_duplicate_hash_type.set_tooltip_text(Some(&flg!("duplicate_hash_type_tooltip")));
         self.check_button_duplicate_case_sensitive_name
             .set_tooltip_text(Some(&flg!("duplicate_case_sensitive_name_tooltip")));
+        self.check_button_duplicate_verify_content.set_tooltip_text(Some(&flg!("duplicate_verify_content_tooltip")));
         self.check_button_music_compare_only_in_title_group
             .set_tooltip_text(Some(&flg!("music_compare_only_in_title_group_tooltip")));
 
@@ -287,10 +325,29 @@ impl GuiMainNotebook {
             .set_tooltip_text(Some(&flg!("check_button_general_same_size_tooltip")));
         self.check_button_image_ignore_same_size.set_label(Some(&flg!("check_button_general_same_size")));
         self.check_button_video_ignore_same_size.set_label(Some(&flg!("check_button_general_same_size")));
+        self.check_button_image_find_cropped.set_label(Some(&flg!("check_button_image_find_cropped")));
+        self.check_button_image_find_cropped
+            .set_tooltip_text(Some(&flg!("check_button_image_find_cropped_tooltip")));
 
         self.check_button_broken_files_audio.set_label(Some(&flg!("main_check_box_broken_files_audio")));
         self.check_button_broken_files_archive.set_label(Some(&flg!("main_check_box_broken_files_archive")));
+        self.check_button_broken_files_compressed_archive
+            .set_label(Some(&flg!("main_check_box_broken_files_compressed_archive")));
         self.check_button_broken_files_image.set_label(Some(&flg!("main_check_box_broken_files_image")));
+        self.check_button_broken_files_video.set_label(Some(&flg!("main_check_box_broken_files_video")));
+        self.check_button_broken_files_decode_video
+            .set_label(Some(&flg!("main_check_box_broken_files_decode_video")));
+        self.check_button_broken_files_document.set_label(Some(&flg!("main_check_box_broken_files_document")));
+        self.check_button_broken_files_font.set_label(Some(&flg!("main_check_box_broken_files_font")));
+        self.check_button_broken_files_sqlite.set_label(Some(&flg!("main_check_box_broken_files_sqlite")));
+        self.check_button_broken_files_text_formats
+            .set_label(Some(&flg!("main_check_box_broken_files_text_formats")));
+        self.check_button_broken_files_verify_zip_content
+            .set_label(Some(&flg!("main_check_box_broken_files_verify_zip_content")));
+        self.check_button_broken_files_strict_image
+            .set_label(Some(&flg!("main_check_box_broken_files_strict_image")));
+        self.check_button_broken_files_show_warnings
+            .set_label(Some(&flg!("main_check_box_broken_files_show_warnings")));
         self.check_button_broken_files_pdf.set_label(Some(&flg!("main_check_box_broken_files_pdf")));
 
         self.label_same_music_seconds.set_label(&flg!("same_music_seconds_label"));
@@ -494,6 +551,7 @@ impl GuiMainNotebook {
             for i in &DUPLICATES_CHECK_METHOD_COMBO_BOX {
                 let text = match i.check_method {
                     CheckingMethod::Hash => flg!("duplicate_mode_hash_combo_box"),
+                    CheckingMethod::SampledHash => flg!("duplicate_mode_sampled_hash_combo_box"),
                     CheckingMethod::Size => flg!("duplicate_mode_size_combo_box"),
                     CheckingMethod::Name => flg!("duplicate_mode_name_combo_box"),
                     CheckingMethod::SizeName => flg!("duplicate_mode_size_name_combo_box"),
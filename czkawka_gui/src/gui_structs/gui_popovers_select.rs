@@ -12,6 +12,7 @@ pub struct GuiSelectPopovers {
     pub buttons_popover_select_all_except_newest: gtk4::Button,
     pub buttons_popover_select_one_oldest: gtk4::Button,
     pub buttons_popover_select_one_newest: gtk4::Button,
+    pub buttons_popover_select_all_except_kept_by_rules: gtk4::Button,
     pub buttons_popover_select_custom: gtk4::Button,
     pub buttons_popover_unselect_custom: gtk4::Button,
     pub buttons_popover_select_all_images_except_biggest: gtk4::Button,
@@ -44,6 +45,7 @@ impl GuiSelectPopovers {
         let buttons_popover_select_all_except_newest: gtk4::Button = builder.object("buttons_popover_select_all_except_newest").expect("Cambalache");
         let buttons_popover_select_one_oldest: gtk4::Button = builder.object("buttons_popover_select_one_oldest").expect("Cambalache");
         let buttons_popover_select_one_newest: gtk4::Button = builder.object("buttons_popover_select_one_newest").expect("Cambalache");
+        let buttons_popover_select_all_except_kept_by_rules: gtk4::Button = builder.object("buttons_popover_select_all_except_kept_by_rules").expect("Cambalache");
         let buttons_popover_select_custom: gtk4::Button = builder.object("buttons_popover_select_custom").expect("Cambalache");
         let buttons_popover_unselect_custom: gtk4::Button = builder.object("buttons_popover_unselect_custom").expect("Cambalache");
         let buttons_popover_select_all_images_except_biggest: gtk4::Button = builder.object("buttons_popover_select_all_images_except_biggest").expect("Cambalache");
@@ -73,6 +75,7 @@ impl GuiSelectPopovers {
             buttons_popover_select_all_except_newest,
             buttons_popover_select_one_oldest,
             buttons_popover_select_one_newest,
+            buttons_popover_select_all_except_kept_by_rules,
             buttons_popover_select_custom,
             buttons_popover_unselect_custom,
             buttons_popover_select_all_images_except_biggest,
@@ -95,6 +98,8 @@ impl GuiSelectPopovers {
         self.buttons_popover_select_all_except_newest.set_label(&flg!("popover_select_all_except_newest"));
         self.buttons_popover_select_one_oldest.set_label(&flg!("popover_select_one_oldest"));
         self.buttons_popover_select_one_newest.set_label(&flg!("popover_select_one_newest"));
+        self.buttons_popover_select_all_except_kept_by_rules
+            .set_label(&flg!("popover_select_all_except_kept_by_rules"));
         self.buttons_popover_select_custom.set_label(&flg!("popover_select_custom"));
         self.buttons_popover_unselect_custom.set_label(&flg!("popover_unselect_custom"));
         self.buttons_popover_select_all_images_except_biggest
@@ -23,10 +23,28 @@ pub struct GuiSettings {
     pub label_settings_general_language: gtk4::Label,
     pub combo_box_settings_language: gtk4::ComboBoxText,
     pub check_button_settings_one_filesystem: gtk4::CheckButton,
+    pub check_button_settings_use_ignore_files: gtk4::CheckButton,
+    pub check_button_settings_follow_symlinks: gtk4::CheckButton,
     pub label_settings_number_of_threads: gtk4::Label,
     pub scale_settings_number_of_threads: gtk4::Scale,
     pub label_restart_needed: gtk4::Label,
     pub check_button_settings_use_rust_preview: gtk4::CheckButton,
+    pub label_settings_keeper_rules: gtk4::Label,
+    pub entry_settings_keeper_rules: gtk4::Entry,
+    pub label_settings_min_modified_date: gtk4::Label,
+    pub entry_settings_min_modified_date: gtk4::Entry,
+    pub label_settings_max_modified_date: gtk4::Label,
+    pub entry_settings_max_modified_date: gtk4::Entry,
+    pub label_settings_max_depth: gtk4::Label,
+    pub entry_settings_max_depth: gtk4::Entry,
+    pub label_settings_not_accessed_for_days: gtk4::Label,
+    pub entry_settings_not_accessed_for_days: gtk4::Entry,
+    pub label_settings_owner_uids: gtk4::Label,
+    pub entry_settings_owner_uids: gtk4::Entry,
+    pub label_settings_owner_gids: gtk4::Label,
+    pub entry_settings_owner_gids: gtk4::Entry,
+    pub label_settings_quarantine_folder: gtk4::Label,
+    pub entry_settings_quarantine_folder: gtk4::Entry,
 
     // Duplicates
     pub check_button_settings_hide_hard_links: gtk4::CheckButton,
@@ -71,6 +89,8 @@ impl GuiSettings {
 
         // General
         let check_button_settings_one_filesystem: gtk4::CheckButton = builder.object("check_button_settings_one_filesystem").expect("Cambalache");
+        let check_button_settings_use_ignore_files: gtk4::CheckButton = builder.object("check_button_settings_use_ignore_files").expect("Cambalache");
+        let check_button_settings_follow_symlinks: gtk4::CheckButton = builder.object("check_button_settings_follow_symlinks").expect("Cambalache");
         let check_button_settings_save_at_exit: gtk4::CheckButton = builder.object("check_button_settings_save_at_exit").expect("Cambalache");
         let check_button_settings_load_at_start: gtk4::CheckButton = builder.object("check_button_settings_load_at_start").expect("Cambalache");
         let check_button_settings_confirm_deletion: gtk4::CheckButton = builder.object("check_button_settings_confirm_deletion").expect("Cambalache");
@@ -86,6 +106,22 @@ impl GuiSettings {
         let scale_settings_number_of_threads: gtk4::Scale = builder.object("scale_settings_number_of_threads").expect("Cambalache");
         let label_restart_needed: gtk4::Label = builder.object("label_restart_needed").expect("Cambalache");
         let check_button_settings_use_rust_preview: gtk4::CheckButton = builder.object("check_button_settings_use_rust_preview").expect("Cambalache");
+        let label_settings_keeper_rules: gtk4::Label = builder.object("label_settings_keeper_rules").expect("Cambalache");
+        let entry_settings_keeper_rules: gtk4::Entry = builder.object("entry_settings_keeper_rules").expect("Cambalache");
+        let label_settings_min_modified_date: gtk4::Label = builder.object("label_settings_min_modified_date").expect("Cambalache");
+        let entry_settings_min_modified_date: gtk4::Entry = builder.object("entry_settings_min_modified_date").expect("Cambalache");
+        let label_settings_max_modified_date: gtk4::Label = builder.object("label_settings_max_modified_date").expect("Cambalache");
+        let entry_settings_max_modified_date: gtk4::Entry = builder.object("entry_settings_max_modified_date").expect("Cambalache");
+        let label_settings_max_depth: gtk4::Label = builder.object("label_settings_max_depth").expect("Cambalache");
+        let entry_settings_max_depth: gtk4::Entry = builder.object("entry_settings_max_depth").expect("Cambalache");
+        let label_settings_not_accessed_for_days: gtk4::Label = builder.object("label_settings_not_accessed_for_days").expect("Cambalache");
+        let entry_settings_not_accessed_for_days: gtk4::Entry = builder.object("entry_settings_not_accessed_for_days").expect("Cambalache");
+        let label_settings_owner_uids: gtk4::Label = builder.object("label_settings_owner_uids").expect("Cambalache");
+        let entry_settings_owner_uids: gtk4::Entry = builder.object("entry_settings_owner_uids").expect("Cambalache");
+        let label_settings_owner_gids: gtk4::Label = builder.object("label_settings_owner_gids").expect("Cambalache");
+        let entry_settings_owner_gids: gtk4::Entry = builder.object("entry_settings_owner_gids").expect("Cambalache");
+        let label_settings_quarantine_folder: gtk4::Label = builder.object("label_settings_quarantine_folder").expect("Cambalache");
+        let entry_settings_quarantine_folder: gtk4::Entry = builder.object("entry_settings_quarantine_folder").expect("Cambalache");
 
         // Duplicates
         let check_button_settings_hide_hard_links: gtk4::CheckButton = builder.object("check_button_settings_hide_hard_links").expect("Cambalache");
@@ -133,10 +169,28 @@ impl GuiSettings {
             label_settings_general_language,
             combo_box_settings_language,
             check_button_settings_one_filesystem,
+            check_button_settings_use_ignore_files,
+            check_button_settings_follow_symlinks,
             label_settings_number_of_threads,
             scale_settings_number_of_threads,
             label_restart_needed,
             check_button_settings_use_rust_preview,
+            label_settings_keeper_rules,
+            entry_settings_keeper_rules,
+            label_settings_min_modified_date,
+            entry_settings_min_modified_date,
+            label_settings_max_modified_date,
+            entry_settings_max_modified_date,
+            label_settings_max_depth,
+            entry_settings_max_depth,
+            label_settings_not_accessed_for_days,
+            entry_settings_not_accessed_for_days,
+            label_settings_owner_uids,
+            entry_settings_owner_uids,
+            label_settings_owner_gids,
+            entry_settings_owner_gids,
+            label_settings_quarantine_folder,
+            entry_settings_quarantine_folder,
             check_button_settings_hide_hard_links,
             entry_settings_cache_file_minimal_size,
             entry_settings_prehash_cache_file_minimal_size,
@@ -178,8 +232,18 @@ impl GuiSettings {
         self.check_button_settings_use_trash.set_label(Some(&flg!("settings_use_trash_button")));
         self.label_settings_general_language.set_label(&flg!("settings_language_label"));
         self.check_button_settings_one_filesystem.set_label(Some(&flg!("settings_ignore_other_filesystems")));
+        self.check_button_settings_use_ignore_files.set_label(Some(&flg!("settings_use_ignore_files")));
+        self.check_button_settings_follow_symlinks.set_label(Some(&flg!("settings_follow_symlinks")));
         self.label_settings_number_of_threads.set_label(&flg!("settings_number_of_threads"));
         self.check_button_settings_use_rust_preview.set_label(Some(&flg!("settings_use_rust_preview")));
+        self.label_settings_keeper_rules.set_label(&flg!("settings_keeper_rules"));
+        self.label_settings_min_modified_date.set_label(&flg!("settings_min_modified_date"));
+        self.label_settings_max_modified_date.set_label(&flg!("settings_max_modified_date"));
+        self.label_settings_max_depth.set_label(&flg!("settings_max_depth"));
+        self.label_settings_not_accessed_for_days.set_label(&flg!("settings_not_accessed_for_days"));
+        self.label_settings_owner_uids.set_label(&flg!("settings_owner_uids"));
+        self.label_settings_owner_gids.set_label(&flg!("settings_owner_gids"));
+        self.label_settings_quarantine_folder.set_label(&flg!("settings_quarantine_folder"));
 
         self.check_button_settings_save_at_exit
             .set_tooltip_text(Some(&flg!("settings_save_at_exit_button_tooltip")));
@@ -200,9 +264,21 @@ impl GuiSettings {
         self.label_settings_general_language.set_tooltip_text(Some(&flg!("settings_language_label_tooltip")));
         self.check_button_settings_one_filesystem
             .set_tooltip_text(Some(&flg!("settings_ignore_other_filesystems_tooltip")));
+        self.check_button_settings_use_ignore_files
+            .set_tooltip_text(Some(&flg!("settings_use_ignore_files_tooltip")));
+        self.check_button_settings_follow_symlinks.set_tooltip_text(Some(&flg!("settings_follow_symlinks_tooltip")));
         self.scale_settings_number_of_threads.set_tooltip_text(Some(&flg!("settings_number_of_threads_tooltip")));
         self.check_button_settings_use_rust_preview
             .set_tooltip_text(Some(&flg!("settings_use_rust_preview_tooltip")));
+        self.entry_settings_keeper_rules.set_tooltip_text(Some(&flg!("settings_keeper_rules_tooltip")));
+        self.entry_settings_min_modified_date.set_tooltip_text(Some(&flg!("settings_min_modified_date_tooltip")));
+        self.entry_settings_max_modified_date.set_tooltip_text(Some(&flg!("settings_max_modified_date_tooltip")));
+        self.entry_settings_max_depth.set_tooltip_text(Some(&flg!("settings_max_depth_tooltip")));
+        self.entry_settings_not_accessed_for_days
+            .set_tooltip_text(Some(&flg!("settings_not_accessed_for_days_tooltip")));
+        self.entry_settings_owner_uids.set_tooltip_text(Some(&flg!("settings_owner_uids_tooltip")));
+        self.entry_settings_owner_gids.set_tooltip_text(Some(&flg!("settings_owner_gids_tooltip")));
+        self.entry_settings_quarantine_folder.set_tooltip_text(Some(&flg!("settings_quarantine_folder_tooltip")));
 
         self.check_button_settings_hide_hard_links
             .set_label(Some(&flg!("settings_duplicates_hide_hard_link_button")));
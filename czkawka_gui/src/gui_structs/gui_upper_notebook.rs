@@ -12,6 +12,7 @@ pub struct GuiUpperNotebook {
     pub notebook_upper: gtk4::Notebook,
 
     pub entry_excluded_items: gtk4::Entry,
+    pub entry_included_items: gtk4::Entry,
     pub entry_allowed_extensions: gtk4::Entry,
     pub entry_excluded_extensions: gtk4::Entry,
 
@@ -25,6 +26,7 @@ pub struct GuiUpperNotebook {
     pub buttons_remove_excluded_directory: gtk4::Button,
 
     pub label_excluded_items: gtk4::Label,
+    pub label_included_items: gtk4::Label,
     pub label_allowed_extensions: gtk4::Label,
     pub label_excluded_extensions: gtk4::Label,
 
@@ -44,6 +46,7 @@ impl GuiUpperNotebook {
         let entry_allowed_extensions: gtk4::Entry = builder.object("entry_allowed_extensions").expect("Cambalache");
         let entry_excluded_extensions: gtk4::Entry = builder.object("entry_excluded_extensions").expect("Cambalache");
         let entry_excluded_items: gtk4::Entry = builder.object("entry_excluded_items").expect("Cambalache");
+        let entry_included_items: gtk4::Entry = builder.object("entry_included_items").expect("Cambalache");
 
         let check_button_recursive: gtk4::CheckButton = builder.object("check_button_recursive").expect("Cambalache");
 
@@ -55,6 +58,7 @@ impl GuiUpperNotebook {
         let buttons_remove_excluded_directory: gtk4::Button = builder.object("buttons_remove_excluded_directory").expect("Cambalache");
 
         let label_excluded_items: gtk4::Label = builder.object("label_excluded_items").expect("Cambalache");
+        let label_included_items: gtk4::Label = builder.object("label_included_items").expect("Cambalache");
         let label_allowed_extensions: gtk4::Label = builder.object("label_allowed_extensions").expect("Cambalache");
         let label_excluded_extensions: gtk4::Label = builder.object("label_excluded_extensions").expect("Cambalache");
 
@@ -93,6 +97,7 @@ impl GuiUpperNotebook {
         Self {
             notebook_upper,
             entry_excluded_items,
+            entry_included_items,
             entry_allowed_extensions,
             entry_excluded_extensions,
             check_button_recursive,
@@ -103,6 +108,7 @@ impl GuiUpperNotebook {
             buttons_add_excluded_directory,
             buttons_remove_excluded_directory,
             label_excluded_items,
+            label_included_items,
             label_allowed_extensions,
             label_excluded_extensions,
             entry_general_minimal_size,
@@ -156,8 +162,11 @@ impl GuiUpperNotebook {
         self.entry_excluded_extensions.set_tooltip_text(Some(&flg!("upper_excluded_extensions_tooltip")));
         self.label_excluded_items.set_tooltip_text(Some(&flg!("upper_excluded_items_tooltip")));
         self.entry_excluded_items.set_tooltip_text(Some(&flg!("upper_excluded_items_tooltip")));
+        self.label_included_items.set_tooltip_text(Some(&flg!("upper_included_items_tooltip")));
+        self.entry_included_items.set_tooltip_text(Some(&flg!("upper_included_items_tooltip")));
 
         self.label_excluded_items.set_label(&flg!("upper_excluded_items"));
+        self.label_included_items.set_label(&flg!("upper_included_items"));
         self.label_allowed_extensions.set_label(&flg!("upper_allowed_extensions"));
         self.label_excluded_extensions.set_label(&flg!("upper_excluded_extensions"));
 
@@ -27,7 +27,7 @@ pub struct CheckMethodStruct {
     pub check_method: CheckingMethod,
 }
 
-pub const DUPLICATES_CHECK_METHOD_COMBO_BOX: [CheckMethodStruct; 4] = [
+pub const DUPLICATES_CHECK_METHOD_COMBO_BOX: [CheckMethodStruct; 5] = [
     CheckMethodStruct {
         eng_name: "Hash",
         check_method: CheckingMethod::Hash,
@@ -44,6 +44,11 @@ pub const DUPLICATES_CHECK_METHOD_COMBO_BOX: [CheckMethodStruct; 4] = [
         eng_name: "Size and Name",
         check_method: CheckingMethod::SizeName,
     },
+    // Added at the end, to not change indexes saved in config
+    CheckMethodStruct {
+        eng_name: "Sampled Hash",
+        check_method: CheckingMethod::SampledHash,
+    },
 ];
 
 #[derive(Copy, Clone)]
@@ -26,6 +26,13 @@ pub(crate) fn get_path_buf_from_vector_of_strings(vec_string: &[String]) -> Vec<
     vec_string.iter().map(PathBuf::from).collect()
 }
 
+// Empty entry disables quarantine
+pub(crate) fn get_quarantine_folder(entry: &gtk4::Entry) -> Option<PathBuf> {
+    let text = entry.text();
+    let text = text.trim();
+    (!text.is_empty()).then(|| PathBuf::from(text))
+}
+
 pub(crate) fn print_text_messages_to_text_view(text_messages: &Messages, text_view: &TextView) {
     let mut messages: String = String::new();
     if !text_messages.messages.is_empty() {
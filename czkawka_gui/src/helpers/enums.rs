@@ -28,6 +28,7 @@ pub enum BottomButtonsEnum {
     Save,
     Symlink,
     Hardlink,
+    Ignore,
     Move,
     Compare,
     Sort,
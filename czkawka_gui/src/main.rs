@@ -8,6 +8,7 @@ use connect_things::connect_about_buttons::connect_about_buttons;
 use connect_things::connect_button_compare::connect_button_compare;
 use connect_things::connect_button_delete::connect_button_delete;
 use connect_things::connect_button_hardlink::connect_button_hardlink_symlink;
+use connect_things::connect_button_ignore::connect_button_ignore;
 use connect_things::connect_button_move::connect_button_move;
 use connect_things::connect_button_save::connect_button_save;
 use connect_things::connect_button_search::connect_button_search;
@@ -126,6 +127,7 @@ fn build_ui(application: &Application, cli_args: Option<&CliResult>) {
     connect_button_sort(&gui_data);
     connect_button_stop(&gui_data);
     connect_button_hardlink_symlink(&gui_data);
+    connect_button_ignore(&gui_data);
     connect_button_move(&gui_data);
     connect_button_compare(&gui_data);
 
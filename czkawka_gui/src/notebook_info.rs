@@ -69,6 +69,7 @@ pub static NOTEBOOKS_INFO: [NotebookObject; TOOLS_NUMBER] = [
             BottomButtonsEnum::Sort,
             BottomButtonsEnum::Symlink,
             BottomButtonsEnum::Hardlink,
+            BottomButtonsEnum::Ignore,
             BottomButtonsEnum::Move,
         ],
         tree_view_name: "tree_view_duplicate_finder",
@@ -202,6 +203,7 @@ pub static NOTEBOOKS_INFO: [NotebookObject; TOOLS_NUMBER] = [
             BottomButtonsEnum::Sort,
             BottomButtonsEnum::Symlink,
             BottomButtonsEnum::Hardlink,
+            BottomButtonsEnum::Ignore,
             BottomButtonsEnum::Move,
             BottomButtonsEnum::Compare,
         ],
@@ -240,6 +242,7 @@ pub static NOTEBOOKS_INFO: [NotebookObject; TOOLS_NUMBER] = [
             BottomButtonsEnum::Sort,
             BottomButtonsEnum::Symlink,
             BottomButtonsEnum::Hardlink,
+            BottomButtonsEnum::Ignore,
             BottomButtonsEnum::Move,
         ],
         tree_view_name: "tree_view_similar_videos_finder",
@@ -284,6 +287,7 @@ pub static NOTEBOOKS_INFO: [NotebookObject; TOOLS_NUMBER] = [
             BottomButtonsEnum::Sort,
             BottomButtonsEnum::Symlink,
             BottomButtonsEnum::Hardlink,
+            BottomButtonsEnum::Ignore,
             BottomButtonsEnum::Move,
         ],
         tree_view_name: "tree_view_same_music_finder",
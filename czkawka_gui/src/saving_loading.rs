@@ -44,6 +44,7 @@ const DEFAULT_VIDEO_REMOVE_AUTO_OUTDATED_CACHE: bool = false;
 const DEFAULT_IMAGE_REMOVE_AUTO_OUTDATED_CACHE: bool = true;
 const DEFAULT_DUPLICATE_REMOVE_AUTO_OUTDATED_CACHE: bool = true;
 const DEFAULT_DUPLICATE_CASE_SENSITIVE_NAME_CHECKING: bool = false;
+const DEFAULT_DUPLICATE_VERIFY_CONTENT: bool = false;
 const DEFAULT_GENERAL_IGNORE_OTHER_FILESYSTEMS: bool = false;
 const DEFAULT_USING_RUST_LIBRARIES_TO_SHOW_PREVIEW: bool = true;
 
@@ -53,13 +54,26 @@ const DEFAULT_MUSIC_GROUP_CONTENT_BY_TITLE: bool = false;
 const DEFAULT_BROKEN_FILES_PDF: bool = true;
 const DEFAULT_BROKEN_FILES_AUDIO: bool = true;
 const DEFAULT_BROKEN_FILES_ARCHIVE: bool = true;
+// Fully decompressing big archives is slow, so they are checked only on request
+const DEFAULT_BROKEN_FILES_COMPRESSED_ARCHIVE: bool = false;
 const DEFAULT_BROKEN_FILES_IMAGE: bool = true;
+const DEFAULT_BROKEN_FILES_VIDEO: bool = true;
+const DEFAULT_BROKEN_FILES_DECODE_VIDEO: bool = false;
+const DEFAULT_BROKEN_FILES_DOCUMENT: bool = true;
+// Less common formats, which must be enabled manually
+const DEFAULT_BROKEN_FILES_FONT: bool = false;
+const DEFAULT_BROKEN_FILES_SQLITE: bool = false;
+const DEFAULT_BROKEN_FILES_TEXT_FORMATS: bool = false;
+const DEFAULT_BROKEN_FILES_VERIFY_ZIP_CONTENT: bool = false;
+const DEFAULT_BROKEN_FILES_STRICT_IMAGE: bool = false;
+const DEFAULT_BROKEN_FILES_SHOW_WARNINGS: bool = true;
 
 const DEFAULT_THREAD_NUMBER: u32 = 0;
 
 const DEFAULT_NUMBER_OF_BIGGEST_FILES: &str = "50";
 const DEFAULT_SIMILAR_IMAGES_SIMILARITY: f32 = 0.0;
 const DEFAULT_SIMILAR_IMAGES_IGNORE_SAME_SIZE: bool = false;
+const DEFAULT_SIMILAR_IMAGES_FIND_CROPPED: bool = false;
 const DEFAULT_SIMILAR_VIDEOS_SIMILARITY: f32 = 15.0;
 const DEFAULT_SIMILAR_VIDEOS_IGNORE_SAME_SIZE: bool = false;
 
@@ -195,6 +209,9 @@ pub struct SettingsJson {
     #[serde(default = "default_excluded_items")]
     pub excluded_items: String,
 
+    #[serde(default)]
+    pub included_items: String,
+
     #[serde(default)]
     pub allowed_extensions: String,
 
@@ -261,6 +278,30 @@ pub struct SettingsJson {
     #[serde(default)]
     pub language: String,
 
+    #[serde(default)]
+    pub keeper_rules: String,
+
+    #[serde(default)]
+    pub min_modified_date: String,
+
+    #[serde(default)]
+    pub max_modified_date: String,
+
+    #[serde(default)]
+    pub max_depth: String,
+
+    #[serde(default)]
+    pub not_accessed_for_days: String,
+
+    #[serde(default)]
+    pub owner_uids: String,
+
+    #[serde(default)]
+    pub owner_gids: String,
+
+    #[serde(default)]
+    pub quarantine_folder: String,
+
     #[serde(default)]
     pub combo_box_duplicate_hash_type: u32,
 
@@ -285,6 +326,9 @@ pub struct SettingsJson {
     #[serde(default = "default_similar_images_ignore_same_size")]
     pub similar_images_ignore_same_size: bool,
 
+    #[serde(default = "default_similar_images_find_cropped")]
+    pub similar_images_find_cropped: bool,
+
     #[serde(default = "default_similar_videos_similarity")]
     pub similar_videos_similarity: f64,
 
@@ -297,6 +341,9 @@ pub struct SettingsJson {
     #[serde(default = "default_duplicate_name_case_sensitive")]
     pub duplicate_name_case_sensitive: bool,
 
+    #[serde(default = "default_duplicate_verify_content")]
+    pub duplicate_verify_content: bool,
+
     #[serde(default)]
     pub combo_box_big_files_mode: u32,
 
@@ -309,12 +356,48 @@ pub struct SettingsJson {
     #[serde(default = "default_broken_files_image")]
     pub broken_files_image: bool,
 
+    #[serde(default = "default_broken_files_video")]
+    pub broken_files_video: bool,
+
+    #[serde(default = "default_broken_files_decode_video")]
+    pub broken_files_decode_video: bool,
+
+    #[serde(default = "default_broken_files_document")]
+    pub broken_files_document: bool,
+
+    #[serde(default = "default_broken_files_font")]
+    pub broken_files_font: bool,
+
+    #[serde(default = "default_broken_files_sqlite")]
+    pub broken_files_sqlite: bool,
+
+    #[serde(default = "default_broken_files_text_formats")]
+    pub broken_files_text_formats: bool,
+
+    #[serde(default = "default_broken_files_verify_zip_content")]
+    pub broken_files_verify_zip_content: bool,
+
+    #[serde(default = "default_broken_files_strict_image")]
+    pub broken_files_strict_image: bool,
+
+    #[serde(default = "default_broken_files_show_warnings")]
+    pub broken_files_show_warnings: bool,
+
     #[serde(default = "default_broken_files_archive")]
     pub broken_files_archive: bool,
 
+    #[serde(default = "default_broken_files_compressed_archive")]
+    pub broken_files_compressed_archive: bool,
+
     #[serde(default = "default_ignore_other_filesystems")]
     pub ignore_other_filesystems: bool,
 
+    #[serde(default)]
+    pub use_ignore_files: bool,
+
+    #[serde(default)]
+    pub follow_symlinks: bool,
+
     #[serde(default = "default_thread_number")]
     pub thread_number: u32,
 
@@ -414,6 +497,9 @@ fn default_similar_images_similarity() -> f64 {
 fn default_similar_images_ignore_same_size() -> bool {
     DEFAULT_SIMILAR_IMAGES_IGNORE_SAME_SIZE
 }
+fn default_similar_images_find_cropped() -> bool {
+    DEFAULT_SIMILAR_IMAGES_FIND_CROPPED
+}
 fn default_similar_videos_similarity() -> f64 {
     DEFAULT_SIMILAR_VIDEOS_SIMILARITY as f64
 }
@@ -426,6 +512,9 @@ fn default_music_approximate_comparison() -> bool {
 fn default_duplicate_name_case_sensitive() -> bool {
     DEFAULT_DUPLICATE_CASE_SENSITIVE_NAME_CHECKING
 }
+fn default_duplicate_verify_content() -> bool {
+    DEFAULT_DUPLICATE_VERIFY_CONTENT
+}
 fn default_broken_files_pdf() -> bool {
     DEFAULT_BROKEN_FILES_PDF
 }
@@ -435,9 +524,39 @@ fn default_broken_files_audio() -> bool {
 fn default_broken_files_image() -> bool {
     DEFAULT_BROKEN_FILES_IMAGE
 }
+fn default_broken_files_video() -> bool {
+    DEFAULT_BROKEN_FILES_VIDEO
+}
+fn default_broken_files_decode_video() -> bool {
+    DEFAULT_BROKEN_FILES_DECODE_VIDEO
+}
+fn default_broken_files_document() -> bool {
+    DEFAULT_BROKEN_FILES_DOCUMENT
+}
+fn default_broken_files_font() -> bool {
+    DEFAULT_BROKEN_FILES_FONT
+}
+fn default_broken_files_sqlite() -> bool {
+    DEFAULT_BROKEN_FILES_SQLITE
+}
+fn default_broken_files_text_formats() -> bool {
+    DEFAULT_BROKEN_FILES_TEXT_FORMATS
+}
+fn default_broken_files_verify_zip_content() -> bool {
+    DEFAULT_BROKEN_FILES_VERIFY_ZIP_CONTENT
+}
+fn default_broken_files_strict_image() -> bool {
+    DEFAULT_BROKEN_FILES_STRICT_IMAGE
+}
+fn default_broken_files_show_warnings() -> bool {
+    DEFAULT_BROKEN_FILES_SHOW_WARNINGS
+}
 fn default_broken_files_archive() -> bool {
     DEFAULT_BROKEN_FILES_ARCHIVE
 }
+fn default_broken_files_compressed_archive() -> bool {
+    DEFAULT_BROKEN_FILES_COMPRESSED_ARCHIVE
+}
 fn default_ignore_other_filesystems() -> bool {
     DEFAULT_GENERAL_IGNORE_OTHER_FILESYSTEMS
 }
@@ -491,6 +610,7 @@ fn set_configuration_to_gui_internal(upper_notebook: &GuiUpperNotebook, main_not
     // Resetting excluded items
     {
         upper_notebook.entry_excluded_items.set_text(&default_config.excluded_items);
+        upper_notebook.entry_included_items.set_text(&default_config.included_items);
         upper_notebook.entry_allowed_extensions.set_text(&default_config.allowed_extensions);
         upper_notebook.entry_general_minimal_size.set_text(&default_config.minimal_file_size);
         upper_notebook.entry_general_maximal_size.set_text(&default_config.maximal_file_size);
@@ -524,11 +644,21 @@ fn set_configuration_to_gui_internal(upper_notebook: &GuiUpperNotebook, main_not
             .set_active(default_config.duplicate_delete_outdated_cache_entries);
         settings.check_button_duplicates_use_prehash_cache.set_active(default_config.use_prehash_cache);
         settings.entry_settings_prehash_cache_file_minimal_size.set_text(&default_config.minimal_prehash_cache_size);
+        settings.entry_settings_keeper_rules.set_text(&default_config.keeper_rules);
+        settings.entry_settings_min_modified_date.set_text(&default_config.min_modified_date);
+        settings.entry_settings_max_modified_date.set_text(&default_config.max_modified_date);
+        settings.entry_settings_max_depth.set_text(&default_config.max_depth);
+        settings.entry_settings_not_accessed_for_days.set_text(&default_config.not_accessed_for_days);
+        settings.entry_settings_owner_uids.set_text(&default_config.owner_uids);
+        settings.entry_settings_owner_gids.set_text(&default_config.owner_gids);
+        settings.entry_settings_quarantine_folder.set_text(&default_config.quarantine_folder);
 
         let lang_idx = LANGUAGES_ALL.iter().position(|l| l.short_text == default_config.language).unwrap_or(0);
         settings.combo_box_settings_language.set_active(Some(lang_idx as u32));
 
         settings.check_button_settings_one_filesystem.set_active(default_config.ignore_other_filesystems);
+        settings.check_button_settings_use_ignore_files.set_active(default_config.use_ignore_files);
+        settings.check_button_settings_follow_symlinks.set_active(default_config.follow_symlinks);
         settings.check_button_settings_use_rust_preview.set_active(default_config.use_rust_libraries_to_preview);
 
         // Set combo boxes and check buttons as before
@@ -543,10 +673,26 @@ fn set_configuration_to_gui_internal(upper_notebook: &GuiUpperNotebook, main_not
         main_notebook.combo_box_image_hash_size.set_active(Some(default_config.combo_box_image_hash_size));
         main_notebook.combo_box_big_files_mode.set_active(Some(default_config.combo_box_big_files_mode));
 
+        main_notebook.check_button_duplicate_verify_content.set_active(default_config.duplicate_verify_content);
         main_notebook.check_button_broken_files_audio.set_active(default_config.broken_files_audio);
         main_notebook.check_button_broken_files_pdf.set_active(default_config.broken_files_pdf);
         main_notebook.check_button_broken_files_archive.set_active(default_config.broken_files_archive);
+        main_notebook
+            .check_button_broken_files_compressed_archive
+            .set_active(default_config.broken_files_compressed_archive);
         main_notebook.check_button_broken_files_image.set_active(default_config.broken_files_image);
+        main_notebook.check_button_broken_files_video.set_active(default_config.broken_files_video);
+        main_notebook.check_button_broken_files_decode_video.set_active(default_config.broken_files_decode_video);
+        main_notebook.check_button_broken_files_document.set_active(default_config.broken_files_document);
+        main_notebook.check_button_broken_files_font.set_active(default_config.broken_files_font);
+        main_notebook.check_button_broken_files_sqlite.set_active(default_config.broken_files_sqlite);
+        main_notebook.check_button_broken_files_text_formats.set_active(default_config.broken_files_text_formats);
+        main_notebook
+            .check_button_broken_files_verify_zip_content
+            .set_active(default_config.broken_files_verify_zip_content);
+        main_notebook.check_button_broken_files_strict_image.set_active(default_config.broken_files_strict_image);
+        main_notebook.check_button_broken_files_show_warnings.set_active(default_config.broken_files_show_warnings);
+        main_notebook.check_button_image_find_cropped.set_active(default_config.similar_images_find_cropped);
 
         // Set similarity scale range/value based on chosen image hash size index
         let index = default_config.combo_box_image_hash_size as usize;
@@ -562,12 +708,14 @@ fn set_configuration_to_gui_internal(upper_notebook: &GuiUpperNotebook, main_not
         // Update duplicate-related widget visibility according to chosen method
         {
             let combo_chosen_index = main_notebook.combo_box_duplicate_check_method.active().unwrap_or(0) as usize;
-            if DUPLICATES_CHECK_METHOD_COMBO_BOX[combo_chosen_index].check_method == CheckingMethod::Hash {
+            if [CheckingMethod::Hash, CheckingMethod::SampledHash].contains(&DUPLICATES_CHECK_METHOD_COMBO_BOX[combo_chosen_index].check_method) {
                 main_notebook.combo_box_duplicate_hash_type.set_visible(true);
                 main_notebook.label_duplicate_hash_type.set_visible(true);
+                main_notebook.check_button_duplicate_verify_content.set_visible(true);
             } else {
                 main_notebook.combo_box_duplicate_hash_type.set_visible(false);
                 main_notebook.label_duplicate_hash_type.set_visible(false);
+                main_notebook.check_button_duplicate_verify_content.set_visible(false);
             }
 
             if [CheckingMethod::Name, CheckingMethod::SizeName].contains(&DUPLICATES_CHECK_METHOD_COMBO_BOX[combo_chosen_index].check_method) {
@@ -627,6 +775,7 @@ fn gui_to_settings(upper_notebook: &GuiUpperNotebook, main_notebook: &GuiMainNot
         reference_directories,
         excluded_directories,
         excluded_items: upper_notebook.entry_excluded_items.text().to_string(),
+        included_items: upper_notebook.entry_included_items.text().to_string(),
         allowed_extensions: upper_notebook.entry_allowed_extensions.text().to_string(),
         minimal_file_size: upper_notebook.entry_general_minimal_size.text().to_string(),
         maximal_file_size: upper_notebook.entry_general_maximal_size.text().to_string(),
@@ -649,6 +798,14 @@ fn gui_to_settings(upper_notebook: &GuiUpperNotebook, main_notebook: &GuiMainNot
         use_prehash_cache: settings.check_button_duplicates_use_prehash_cache.is_active(),
         minimal_prehash_cache_size: settings.entry_settings_prehash_cache_file_minimal_size.text().to_string(),
         language: language_text,
+        keeper_rules: settings.entry_settings_keeper_rules.text().to_string(),
+        min_modified_date: settings.entry_settings_min_modified_date.text().to_string(),
+        max_modified_date: settings.entry_settings_max_modified_date.text().to_string(),
+        max_depth: settings.entry_settings_max_depth.text().to_string(),
+        not_accessed_for_days: settings.entry_settings_not_accessed_for_days.text().to_string(),
+        owner_uids: settings.entry_settings_owner_uids.text().to_string(),
+        owner_gids: settings.entry_settings_owner_gids.text().to_string(),
+        quarantine_folder: settings.entry_settings_quarantine_folder.text().to_string(),
         combo_box_duplicate_hash_type: main_notebook.combo_box_duplicate_hash_type.active().unwrap_or(0),
         combo_box_duplicate_check_method: main_notebook.combo_box_duplicate_check_method.active().unwrap_or(0),
         combo_box_image_resize_algorithm: main_notebook.combo_box_image_resize_algorithm.active().unwrap_or(0),
@@ -657,16 +814,30 @@ fn gui_to_settings(upper_notebook: &GuiUpperNotebook, main_notebook: &GuiMainNot
         number_of_biggest_files: main_notebook.entry_big_files_number.text().to_string(),
         similar_images_similarity: main_notebook.scale_similarity_similar_images.value(),
         similar_images_ignore_same_size: main_notebook.check_button_image_ignore_same_size.is_active(),
+        similar_images_find_cropped: main_notebook.check_button_image_find_cropped.is_active(),
         similar_videos_similarity: main_notebook.scale_similarity_similar_videos.value(),
         similar_videos_ignore_same_size: main_notebook.check_button_video_ignore_same_size.is_active(),
         music_approximate_comparison: main_notebook.check_button_music_approximate_comparison.is_active(),
         duplicate_name_case_sensitive: main_notebook.check_button_duplicate_case_sensitive_name.is_active(),
+        duplicate_verify_content: main_notebook.check_button_duplicate_verify_content.is_active(),
         combo_box_big_files_mode: main_notebook.combo_box_big_files_mode.active().unwrap_or(0),
         broken_files_pdf: main_notebook.check_button_broken_files_pdf.is_active(),
         broken_files_audio: main_notebook.check_button_broken_files_audio.is_active(),
         broken_files_image: main_notebook.check_button_broken_files_image.is_active(),
+        broken_files_video: main_notebook.check_button_broken_files_video.is_active(),
+        broken_files_decode_video: main_notebook.check_button_broken_files_decode_video.is_active(),
+        broken_files_document: main_notebook.check_button_broken_files_document.is_active(),
+        broken_files_font: main_notebook.check_button_broken_files_font.is_active(),
+        broken_files_sqlite: main_notebook.check_button_broken_files_sqlite.is_active(),
+        broken_files_text_formats: main_notebook.check_button_broken_files_text_formats.is_active(),
+        broken_files_verify_zip_content: main_notebook.check_button_broken_files_verify_zip_content.is_active(),
+        broken_files_strict_image: main_notebook.check_button_broken_files_strict_image.is_active(),
+        broken_files_show_warnings: main_notebook.check_button_broken_files_show_warnings.is_active(),
         broken_files_archive: main_notebook.check_button_broken_files_archive.is_active(),
+        broken_files_compressed_archive: main_notebook.check_button_broken_files_compressed_archive.is_active(),
         ignore_other_filesystems: settings.check_button_settings_one_filesystem.is_active(),
+        use_ignore_files: settings.check_button_settings_use_ignore_files.is_active(),
+        follow_symlinks: settings.check_button_settings_follow_symlinks.is_active(),
         thread_number: settings.scale_settings_number_of_threads.value() as u32,
         music_compare_by_title: main_notebook.check_button_music_compare_only_in_title_group.is_active(),
         use_rust_libraries_to_preview: settings.check_button_settings_use_rust_preview.is_active(),
@@ -1,5 +1,6 @@
 # In Rust translations
 rust_loaded_preset = Loaded preset { $preset_idx }
+rust_ignored_groups = Marked { $groups } groups as not duplicates, they will be hidden in next scans
 rust_file_already_exists = File \"{ $file }\" already exists, and will not be overridden
 rust_error_removing_file_after_copy = Error while removing file \"{ $file }\" (after copying into different partition), reason: { $reason }
 rust_error_copying_file = Error while copying \"{ $input }\" to \"{ $output }\", reason: { $reason }
@@ -27,12 +28,15 @@ rust_checked_files = Checked { $items_stats } file ({ $size_stats })
 rust_checked_files_bad_extensions = Checked { $items_stats } file
 rust_analyzed_partial_hash = Analyzed partial hash of { $items_stats } files ({ $size_stats })
 rust_analyzed_full_hash = Analyzed full hash of { $items_stats } files ({ $size_stats })
+rust_analyzed_sampled_hash = Analyzed sampled hash of { $items_stats } files ({ $size_stats })
+rust_compared_content_of_files = Compared content of { $items_stats } files ({ $size_stats })
 rust_failed_to_rename_file = Failed to rename file { $old_path } to { $new_path } with error { $error }
 rust_no_included_directories = Cannot start scan when no included directories are set.
 rust_all_dirs_referenced = Cannot start scan when all included directories are set as referenced folders.
 rust_found_empty_folders = Found { $items_found } empty folders
 rust_found_empty_files = Found { $items_found } empty files
 rust_found_similar_images = Found { $items_found } similar image files
+rust_similar_images_cropped_part = { $similarity } - cropped { $percent }% of area
 rust_found_similar_videos = Found { $items_found } similar video files
 rust_no_similarity_method_selected = Cannot find similar music files without any similarity method selected.
 rust_found_similar_music_files = Found { $items_found } similar music files
@@ -42,6 +46,7 @@ rust_no_file_type_selected = Cannot find broken files without any file type sele
 rust_found_broken_files = Found { $items_found } broken files
 rust_found_bad_extensions = Found { $items_found } files with bad extensions
 rust_found_duplicate_files = Found { $items_found } duplicate files
+rust_duplicates_sampled_unverified = groups found by sampled hash are not verified byte by byte
 rust_found_big_files = Found { $items_found } big files
 rust_cannot_load_preset = Cannot change and load preset { $preset_idx } - reason { $reason }, using default settings instead
 rust_saved_preset = Saved preset { $preset_idx }
@@ -65,6 +70,7 @@ rust_delete_confirmation = Are you sure you want the selected items?
 rust_delete_confirmation_number_simple = { $items } items is selected.
 rust_delete_confirmation_number_groups = { $items } items in { $groups } groups are selected.
 rust_delete_confirmation_selected_all_in_group = In { $groups } groups, there are all items selected.
+rust_delete_confirmation_sampled_unverified = Files were compared only by sampled hashes and were not verified byte by byte, so they may still differ.
 
 # Slint translations, but in arrays
 
@@ -101,6 +107,7 @@ delete_button = Delete
 save_button = Save
 sort_button = Sort
 rename_button = Rename
+ignore_button = Ignore
 motto = This program is free to use and will always be.\nSee the The MIT/GPL License for details.
 unicorn = You may not look at unicorn, but unicorn always looks at you.
 repository = Repository
@@ -139,16 +146,19 @@ selection_the_smallest_size = Select the smallest size
 selection_the_smallest_resolution = Select the smallest resolution
 selection_newest = Select newest
 selection_oldest = Select oldest
+selection_all_except_kept_by_rules = Select all except kept by rules
 stage_current = Current Stage:
 stage_all = All Stages:
 subsettings = Subsettings
 subsettings_images_hash_size = Hash Size
 subsettings_images_resize_algorithm = Resize Algorithm
 subsettings_images_ignore_same_size = Ignore images with same size
+subsettings_images_find_cropped = Find cropped images
 subsettings_images_max_difference = Max difference
 subsettings_images_duplicates_hash_type = Hash Type
 subsettings_duplicates_check_method = Check method
 subsettings_duplicates_name_case_sensitive = Case Sensitive(only name modes)
+subsettings_duplicates_verify_content = Verify content byte by byte(only hash mode)
 subsettings_biggest_files_sub_method = Method
 subsettings_biggest_files_sub_number_of_files = Number of files
 subsettings_videos_max_difference = Max difference
@@ -169,11 +179,22 @@ subsettings_broken_files_type = Type of files to check
 subsettings_broken_files_audio = Audio
 subsettings_broken_files_pdf = Pdf
 subsettings_broken_files_archive = Archive
+subsettings_broken_files_compressed_archive = TAR/GZIP/XZ/ZSTD/BZIP2/7Z
 subsettings_broken_files_image = Image
+subsettings_broken_files_video = Video
+subsettings_broken_files_decode_video = Decode video frames
+subsettings_broken_files_document = Document
+subsettings_broken_files_font = Font
+subsettings_broken_files_sqlite = SQLite
+subsettings_broken_files_text_formats = JSON/XML/YAML
+subsettings_broken_files_verify_zip_content = Verify zip content
+subsettings_broken_files_strict_image = Strict image check
+subsettings_broken_files_show_warnings = Show warnings
 settings_global_settings = Global Settings
 settings_dark_theme = Dark theme
 settings_show_only_icons = Show only icons
 settings_excluded_items = Excluded item:
+settings_included_items = Included item:
 settings_allowed_extensions = Allowed extensions:
 settings_excluded_extensions = Excluded extensions:
 settings_file_size = File Size(Kilobytes)
@@ -183,7 +204,17 @@ settings_recursive_search = Recursive search
 settings_use_cache = Use cache
 settings_save_as_json = Also save cache as JSON file
 settings_move_to_trash = Move deleted files to trash
+settings_quarantine_folder = Quarantine folder (empty to disable)
+settings_keeper_rules = Keeper rules (separated by ";")
+settings_min_modified_date = Modified after (YYYY-MM-DD, empty to disable)
+settings_max_modified_date = Modified before (YYYY-MM-DD, empty to disable)
+settings_max_depth = Maximal folder depth (0 - only included folders, empty to disable)
+settings_not_accessed_for_days = Not accessed for days (empty to disable)
+settings_owner_uids = Owner user ids (separated by ",")
+settings_owner_gids = Owner group ids (separated by ",")
 settings_ignore_other_filesystems = Ignore other filesystems (only Linux)
+settings_use_ignore_files = Honour .gitignore and .czkawkaignore files
+settings_follow_symlinks = Follow symlinked folders
 settings_thread_number = Thread number
 settings_restart_required = ---You need to restart app to apply changes in thread number---
 settings_duplicate_image_preview = Image preview
@@ -1,10 +1,13 @@
-use std::path::MAIN_SEPARATOR;
+use std::path::{MAIN_SEPARATOR, Path};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 
 use crossbeam_channel::Sender;
+use czkawka_core::common::deletion_journal::{DeletionJournal, JournalAction};
 use czkawka_core::common::progress_data::ProgressData;
+use czkawka_core::common::quarantine::Quarantine;
+use log::error;
 use slint::{ComponentHandle, Weak};
 
 use crate::model_operations::get_checked_info_from_app;
@@ -24,7 +27,13 @@ pub(crate) fn connect_delete_button(app: &MainWindow, progress_sender: Sender<Pr
         let settings = app.global::<Settings>();
 
         let processor = ModelProcessor::new(active_tab);
-        processor.delete_selected_items(settings.get_move_to_trash(), progress_sender, weak_app, stop_flag);
+        processor.delete_selected_items(
+            settings.get_move_to_trash(),
+            settings.get_quarantine_folder().to_string(),
+            progress_sender,
+            weak_app,
+            stop_flag,
+        );
     });
 
     let a = app.as_weak();
@@ -58,6 +67,10 @@ pub(crate) fn connect_delete_button(app: &MainWindow, progress_sender: Sender<Pr
         } else {
             base.push_str(format!("\n{}", flk!("rust_delete_confirmation_number_simple", items = res.checked_items_number)).as_str());
         }
+        let gui_state = app.global::<GuiState>();
+        if gui_state.get_active_tab() == ActiveTab::DuplicateFiles && gui_state.get_duplicates_sampled_unverified() {
+            base.push_str(format!("\n{}", flk!("rust_delete_confirmation_sampled_unverified")).as_str());
+        }
         translation.set_delete_confirmation_text(base.into());
 
         app.invoke_delete_popup_dialog_configured();
@@ -65,7 +78,14 @@ pub(crate) fn connect_delete_button(app: &MainWindow, progress_sender: Sender<Pr
 }
 
 impl ModelProcessor {
-    fn delete_selected_items(self, remove_to_trash: bool, progress_sender: Sender<ProgressData>, weak_app: Weak<MainWindow>, stop_flag: Arc<AtomicBool>) {
+    fn delete_selected_items(
+        self,
+        remove_to_trash: bool,
+        quarantine_folder: String,
+        progress_sender: Sender<ProgressData>,
+        weak_app: Weak<MainWindow>,
+        stop_flag: Arc<AtomicBool>,
+    ) {
         let is_empty_folder_tab = self.active_tab == ActiveTab::EmptyFolders;
         let model = self.active_tab.get_tool_model(&weak_app.upgrade().expect("Failed to upgrade app :("));
         let simpler_model = model.to_simpler_enumerated_vec();
@@ -73,15 +93,36 @@ impl ModelProcessor {
             let path_idx = self.active_tab.get_str_path_idx();
             let name_idx = self.active_tab.get_str_name_idx();
 
+            let journal = Arc::new(DeletionJournal::new());
+            let journal_action = if remove_to_trash { JournalAction::MoveToTrash } else { JournalAction::Delete };
+
+            // Quarantine, when configured, is used instead of trash or permanent deletion
+            let quarantine = (!quarantine_folder.trim().is_empty()).then(|| Arc::new(Quarantine::new(quarantine_folder.trim())));
+
+            let journal_clone = Arc::clone(&journal);
+            let quarantine_clone = quarantine.clone();
             let dlt_fnc = move |data: &SimplerMainListModel| {
-                remove_single_item(
-                    &format!("{}{MAIN_SEPARATOR}{}", data.val_str[path_idx], data.val_str[name_idx]),
-                    is_empty_folder_tab,
-                    remove_to_trash,
-                )
+                let full_path = format!("{}{MAIN_SEPARATOR}{}", data.val_str[path_idx], data.val_str[name_idx]);
+                let path = Path::new(&full_path);
+                if let Some(quarantine) = &quarantine_clone {
+                    let action = JournalAction::Quarantine {
+                        destination: quarantine.get_destination(path),
+                    };
+                    return journal_clone.record(path, action, || quarantine.move_item(path, is_empty_folder_tab).map(|_| ()));
+                }
+                journal_clone.record(path, journal_action.clone(), || remove_single_item(&full_path, is_empty_folder_tab, remove_to_trash))
             };
 
             self.process_and_update_gui_state(&weak_app, stop_flag, &progress_sender, simpler_model, dlt_fnc, MessageType::Delete);
+
+            if let Err(e) = journal.save() {
+                error!("{e}");
+            }
+            if let Some(quarantine) = quarantine
+                && let Err(e) = quarantine.save_manifest()
+            {
+                error!("{e}");
+            }
         });
     }
 }
@@ -0,0 +1,96 @@
+use std::path::PathBuf;
+
+use czkawka_core::common::ignored_groups::IgnoredGroups;
+use czkawka_core::common::model::ToolType;
+use slint::{ComponentHandle, Model, ModelRc, VecModel};
+
+use crate::connect_row_selection::checker::set_number_of_enabled_items;
+use crate::connect_row_selection::reset_selection;
+use crate::{ActiveTab, Callabler, GuiState, MainListModel, MainWindow, flk};
+
+pub(crate) fn connect_ignore(app: &MainWindow) {
+    let a = app.as_weak();
+    app.global::<Callabler>().on_ignore_checked_groups(move || {
+        let app = a.upgrade().expect("Failed to upgrade app :(");
+        let active_tab = app.global::<GuiState>().get_active_tab();
+        let Some(tool_type) = get_tool_type(active_tab) else {
+            return;
+        };
+
+        let model = active_tab.get_tool_model(&app);
+        let groups = split_checked_groups(&model, active_tab.get_str_path_idx(), active_tab.get_str_name_idx());
+
+        let mut warnings = Vec::new();
+        let mut ignored_groups = IgnoredGroups::load(&mut warnings);
+        let added_groups = groups
+            .iter()
+            .map(|(_items, paths)| paths.as_ref().is_some_and(|paths| ignored_groups.add_group(tool_type, paths.iter().cloned())))
+            .collect::<Vec<_>>();
+        let ignored_groups_number = added_groups.iter().filter(|added| **added).count();
+
+        // View is changed only when groups are really saved, otherwise user would think, that they will be hidden in next scans
+        if ignored_groups_number > 0 {
+            if let Err(e) = ignored_groups.save() {
+                app.global::<GuiState>().set_info_text(e.into());
+                return;
+            }
+
+            let new_model = groups
+                .into_iter()
+                .zip(added_groups)
+                .filter(|(_group, added)| !added)
+                .flat_map(|((items, _paths), _added)| items)
+                .collect::<Vec<_>>();
+            let checked_items = new_model.iter().filter(|e| e.checked && !e.header_row).count();
+
+            active_tab.set_tool_model(&app, ModelRc::new(VecModel::from(new_model)));
+            reset_selection(&app, true);
+            set_number_of_enabled_items(&app, active_tab, checked_items as u64);
+        }
+
+        let mut info_text = flk!("rust_ignored_groups", groups = ignored_groups_number);
+        for warning in warnings {
+            info_text.push('\n');
+            info_text.push_str(&warning);
+        }
+        app.global::<GuiState>().set_info_text(info_text.into());
+    });
+}
+
+fn get_tool_type(active_tab: ActiveTab) -> Option<ToolType> {
+    match active_tab {
+        ActiveTab::DuplicateFiles => Some(ToolType::Duplicate),
+        ActiveTab::SimilarImages => Some(ToolType::SimilarImages),
+        ActiveTab::SimilarVideos => Some(ToolType::SimilarVideos),
+        ActiveTab::SimilarMusic => Some(ToolType::SameMusic),
+        _ => None,
+    }
+}
+
+// Returns all groups in model order, with paths of files only for groups with checked items
+fn split_checked_groups(model: &ModelRc<MainListModel>, path_idx: usize, name_idx: usize) -> Vec<(Vec<MainListModel>, Option<Vec<PathBuf>>)> {
+    let mut groups = Vec::new();
+
+    let mut current_group: Vec<MainListModel> = Vec::new();
+    let mut process_group = |group: Vec<MainListModel>| {
+        let paths = group.iter().any(|e| e.checked).then(|| {
+            group
+                .iter()
+                .filter(|e| !e.header_row || e.filled_header_row)
+                .map(|e| PathBuf::from(e.val_str.row_data(path_idx).unwrap_or_default().as_str()).join(e.val_str.row_data(name_idx).unwrap_or_default().as_str()))
+                .collect()
+        });
+        groups.push((group, paths));
+    };
+    for item in model.iter() {
+        if item.header_row && !current_group.is_empty() {
+            process_group(std::mem::take(&mut current_group));
+        }
+        current_group.push(item);
+    }
+    if !current_group.is_empty() {
+        process_group(current_group);
+    }
+
+    groups
+}
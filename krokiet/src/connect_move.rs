@@ -4,7 +4,9 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::{fs, path, thread};
 
 use crossbeam_channel::Sender;
+use czkawka_core::common::deletion_journal::{DeletionJournal, JournalAction};
 use czkawka_core::common::progress_data::ProgressData;
+use log::error;
 use rfd::FileDialog;
 use slint::{ComponentHandle, Weak};
 
@@ -64,14 +66,29 @@ impl ModelProcessor {
             let path_idx = self.active_tab.get_str_path_idx();
             let name_idx = self.active_tab.get_str_name_idx();
 
-            let mlt_fnc = move |data: &SimplerMainListModel| move_single_item(data, path_idx, name_idx, &output_folder, preserve_structure, copy_mode);
+            let journal = Arc::new(DeletionJournal::new());
+
+            let journal_clone = Arc::clone(&journal);
+            let mlt_fnc = move |data: &SimplerMainListModel| move_single_item(data, path_idx, name_idx, &output_folder, preserve_structure, copy_mode, &journal_clone);
 
             self.process_and_update_gui_state(&weak_app, stop_flag, &progress_sender, simpler_model, mlt_fnc, MessageType::Move);
+
+            if let Err(e) = journal.save() {
+                error!("{e}");
+            }
         });
     }
 }
 
-fn move_single_item(data: &SimplerMainListModel, path_idx: usize, name_idx: usize, output_folder: &str, preserve_structure: bool, copy_mode: bool) -> Result<(), String> {
+fn move_single_item(
+    data: &SimplerMainListModel,
+    path_idx: usize,
+    name_idx: usize,
+    output_folder: &str,
+    preserve_structure: bool,
+    copy_mode: bool,
+    journal: &DeletionJournal,
+) -> Result<(), String> {
     let path = &data.val_str[path_idx];
     let name = &data.val_str[name_idx];
 
@@ -83,25 +100,30 @@ fn move_single_item(data: &SimplerMainListModel, path_idx: usize, name_idx: usiz
     if copy_mode {
         try_to_copy_item(&input_file, &output_file)
     } else {
-        // Try to rename file, may fail due various reasons
-        // It is the easiest way to move file, but only on same partition
-        if fs::rename(&input_file, &output_file).is_ok() {
-            return Ok(());
-        }
+        let action = JournalAction::Move { destination: output_file.clone() };
+        journal.record(&input_file, action, || move_item(&input_file, &output_file))
+    }
+}
+
+fn move_item(input_file: &Path, output_file: &Path) -> Result<(), String> {
+    // Try to rename file, may fail due various reasons
+    // It is the easiest way to move file, but only on same partition
+    if fs::rename(input_file, output_file).is_ok() {
+        return Ok(());
+    }
 
-        // It is possible that this failed, because file is on different partition, so
-        // we need to copy file and then remove old
-        try_to_copy_item(&input_file, &output_file)?;
+    // It is possible that this failed, because file is on different partition, so
+    // we need to copy file and then remove old
+    try_to_copy_item(input_file, output_file)?;
 
-        if let Err(e) = fs::remove_file(&input_file) {
-            return Err(flk!(
-                "rust_error_removing_file_after_copy",
-                file = input_file.to_string_lossy().to_string(),
-                reason = e.to_string()
-            ));
-        }
-        Ok(())
+    if let Err(e) = fs::remove_file(input_file) {
+        return Err(flk!(
+            "rust_error_removing_file_after_copy",
+            file = input_file.to_string_lossy().to_string(),
+            reason = e.to_string()
+        ));
     }
+    Ok(())
 }
 
 // Tries to copy file/folder, and returns error if it fails
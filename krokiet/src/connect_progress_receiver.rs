@@ -93,6 +93,8 @@ fn progress_default(item: &ProgressData) -> ProgressToSend {
         CurrentStage::BadExtensionsChecking => flk!("rust_checked_files_bad_extensions", items_stats = items_stats),
         CurrentStage::DuplicatePreHashing => flk!("rust_analyzed_partial_hash", items_stats = items_stats, size_stats = size_stats),
         CurrentStage::DuplicateFullHashing => flk!("rust_analyzed_full_hash", items_stats = items_stats, size_stats = size_stats),
+        CurrentStage::DuplicateSampledHashing => flk!("rust_analyzed_sampled_hash", items_stats = items_stats, size_stats = size_stats),
+        CurrentStage::DuplicateComparingContent => flk!("rust_compared_content_of_files", items_stats = items_stats, size_stats = size_stats),
 
         CurrentStage::DeletingFiles if item.bytes_to_check != 0 => flk!("rust_deleting_files", items_stats = items_stats, size_stats = size_stats),
         CurrentStage::DeletingFiles => flk!("rust_deleting_no_size_files", items_stats = items_stats),
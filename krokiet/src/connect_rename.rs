@@ -3,7 +3,9 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 
 use crossbeam_channel::Sender;
+use czkawka_core::common::deletion_journal::DeletionJournal;
 use czkawka_core::common::progress_data::ProgressData;
+use log::error;
 use slint::{ComponentHandle, Weak};
 
 use crate::model_operations::model_processor::{MessageType, ModelProcessor};
@@ -34,16 +36,26 @@ impl ModelProcessor {
             let name_idx = self.active_tab.get_str_name_idx();
             let ext_idx = self.active_tab.get_str_proper_extension();
 
-            let rm_fnc = move |data: &SimplerMainListModel| rename_single_item(data, path_idx, name_idx, ext_idx);
+            let journal = Arc::new(DeletionJournal::new());
+
+            let journal_clone = Arc::clone(&journal);
+            let rm_fnc = move |data: &SimplerMainListModel| rename_single_item(data, path_idx, name_idx, ext_idx, &journal_clone);
 
             self.process_and_update_gui_state(&weak_app, stop_flag, &progress_sender, simpler_model, rm_fnc, MessageType::Rename);
+
+            if let Err(e) = journal.save() {
+                error!("{e}");
+            }
         });
     }
 }
 
 #[cfg(not(test))]
-fn rename_single_item(data: &SimplerMainListModel, path_idx: usize, name_idx: usize, ext_idx: usize) -> Result<(), String> {
+fn rename_single_item(data: &SimplerMainListModel, path_idx: usize, name_idx: usize, ext_idx: usize, journal: &DeletionJournal) -> Result<(), String> {
     use std::path::MAIN_SEPARATOR;
+
+    use czkawka_core::common::deletion_journal::JournalAction;
+
     let folder = &data.val_str[path_idx];
     let file_name = &data.val_str[name_idx];
     let new_extension = &data.val_str[ext_idx];
@@ -52,20 +64,23 @@ fn rename_single_item(data: &SimplerMainListModel, path_idx: usize, name_idx: us
     let new_full_path = format!("{folder}{MAIN_SEPARATOR}{file_stem}.{new_extension}");
     let old_full_path = format!("{folder}{MAIN_SEPARATOR}{file_name}");
 
-    if let Err(e) = std::fs::rename(&old_full_path, &new_full_path) {
-        Err(crate::flk!(
-            "rust_failed_to_rename_file",
-            old_path = old_full_path,
-            new_path = new_full_path,
-            error = e.to_string()
-        ))
-    } else {
-        Ok(())
-    }
+    let action = JournalAction::Rename {
+        destination: new_full_path.clone().into(),
+    };
+    journal.record(std::path::Path::new(&old_full_path), action, || {
+        std::fs::rename(&old_full_path, &new_full_path).map_err(|e| {
+            crate::flk!(
+                "rust_failed_to_rename_file",
+                old_path = old_full_path.clone(),
+                new_path = new_full_path.clone(),
+                error = e.to_string()
+            )
+        })
+    })
 }
 
 #[cfg(test)]
-fn rename_single_item(data: &SimplerMainListModel, path_idx: usize, _name_idx: usize, _ext_idx: usize) -> Result<(), String> {
+fn rename_single_item(data: &SimplerMainListModel, path_idx: usize, _name_idx: usize, _ext_idx: usize, _journal: &DeletionJournal) -> Result<(), String> {
     let full_path = &data.val_str[path_idx];
     if full_path.contains("test_error") {
         return Err(format!("Test error for item: {full_path}"));
@@ -95,7 +110,7 @@ mod tests {
             let name_idx = 0;
             let ext_idx = 0;
 
-            let rm_fnc = move |data: &SimplerMainListModel| rename_single_item(data, path_idx, name_idx, ext_idx);
+            let rm_fnc = move |data: &SimplerMainListModel| rename_single_item(data, path_idx, name_idx, ext_idx, &DeletionJournal::new());
 
             let output = Self::process_items(
                 simplified_model,
@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
@@ -6,6 +7,7 @@ use std::thread;
 use chrono::DateTime;
 use crossbeam_channel::Sender;
 use czkawka_core::common::consts::DEFAULT_THREAD_SIZE;
+use czkawka_core::common::metadata_filter::{parse_date, parse_ids};
 use czkawka_core::common::model::{CheckingMethod, FileEntry};
 use czkawka_core::common::progress_data::ProgressData;
 use czkawka_core::common::tool_data::CommonData;
@@ -13,14 +15,14 @@ use czkawka_core::common::traits::{ResultEntry, Search};
 use czkawka_core::common::{split_path, split_path_compare};
 use czkawka_core::tools::bad_extensions::{BadExtensions, BadExtensionsParameters, BadFileEntry};
 use czkawka_core::tools::big_file::{BigFile, BigFileParameters, SearchMode};
-use czkawka_core::tools::broken_files::{BrokenEntry, BrokenFiles, BrokenFilesParameters, CheckedTypes};
+use czkawka_core::tools::broken_files::{BrokenEntry, BrokenFiles, BrokenFilesParameters, CheckedTypes, Severity};
 use czkawka_core::tools::duplicate::{DuplicateEntry, DuplicateFinder, DuplicateFinderParameters};
 use czkawka_core::tools::empty_files::EmptyFiles;
 use czkawka_core::tools::empty_folder::{EmptyFolder, FolderEntry};
 use czkawka_core::tools::invalid_symlinks::{InvalidSymlinks, SymlinksFileEntry};
 use czkawka_core::tools::same_music::{MusicEntry, MusicSimilarity, SameMusic, SameMusicParameters};
 use czkawka_core::tools::similar_images::core::get_string_from_similarity;
-use czkawka_core::tools::similar_images::{ImagesEntry, SimilarImages, SimilarImagesParameters};
+use czkawka_core::tools::similar_images::{CroppedImage, HashCombineMode, ImagesEntry, ImagesGroupingMode, SimilarImages, SimilarImagesParameters};
 use czkawka_core::tools::similar_videos::{SimilarVideos, SimilarVideosParameters, VideosEntry, crop_detect_from_str};
 use czkawka_core::tools::temporary::{Temporary, TemporaryFileEntry};
 use humansize::{BINARY, format_size};
@@ -133,6 +135,10 @@ fn scan_duplicates(
                 custom_settings.duplicate_minimal_hash_cache_size as u64,
                 custom_settings.duplicate_minimal_prehash_cache_size as u64,
                 custom_settings.duplicates_sub_name_case_sensitive,
+                custom_settings.duplicates_sub_verify_content,
+                // Duplicated and similar directories are not shown in GUI, they are available only in CLI
+                false,
+                None,
             );
             let mut tool = DuplicateFinder::new(params);
 
@@ -144,7 +150,7 @@ fn scan_duplicates(
             let mut vector;
             if tool.get_use_reference() {
                 match tool.get_params().check_method {
-                    CheckingMethod::Hash => {
+                    CheckingMethod::Hash | CheckingMethod::SampledHash => {
                         vector = tool
                             .get_files_with_identical_hashes_referenced()
                             .values()
@@ -166,14 +172,14 @@ fn scan_duplicates(
                 }
             } else {
                 match tool.get_params().check_method {
-                    CheckingMethod::Hash => {
+                    CheckingMethod::Hash | CheckingMethod::SampledHash => {
                         vector = tool.get_files_sorted_by_hash().values().flatten().cloned().map(|items| (None, items)).collect::<Vec<_>>();
                     }
                     CheckingMethod::Name | CheckingMethod::Size | CheckingMethod::SizeName => {
                         let values: Vec<_> = match tool.get_params().check_method {
-                            CheckingMethod::Name => tool.get_files_sorted_by_names().values().cloned().collect(),
-                            CheckingMethod::Size => tool.get_files_sorted_by_size().values().cloned().collect(),
-                            CheckingMethod::SizeName => tool.get_files_sorted_by_size_name().values().cloned().collect(),
+                            CheckingMethod::Name => tool.get_files_sorted_by_names().values().flatten().cloned().collect(),
+                            CheckingMethod::Size => tool.get_files_sorted_by_size().values().flatten().cloned().collect(),
+                            CheckingMethod::SizeName => tool.get_files_sorted_by_size_name().values().flatten().cloned().collect(),
                             _ => unreachable!("Invalid check method."),
                         };
                         vector = values.into_iter().map(|items| (None, items)).collect::<Vec<_>>();
@@ -186,15 +192,16 @@ fn scan_duplicates(
                 vec.par_sort_unstable_by(|a, b| split_path_compare(a.path.as_path(), b.path.as_path()));
             }
 
+            let sampled_unverified = tool.contains_only_sampled_groups();
             shared_models.lock().unwrap().shared_duplication_state = Some(tool);
 
             a.upgrade_in_event_loop(move |app| {
-                write_duplicate_results(&app, vector, messages);
+                write_duplicate_results(&app, vector, messages, sampled_unverified);
             })
         })
         .expect("Cannot start thread - not much we can do here");
 }
-fn write_duplicate_results(app: &MainWindow, vector: Vec<(Option<DuplicateEntry>, Vec<DuplicateEntry>)>, messages: String) {
+fn write_duplicate_results(app: &MainWindow, vector: Vec<(Option<DuplicateEntry>, Vec<DuplicateEntry>)>, messages: String, sampled_unverified: bool) {
     let items_found = vector.len();
     let items = Rc::new(VecModel::default());
     for (ref_fe, vec_fe) in vector.into_iter().rev() {
@@ -211,7 +218,12 @@ fn write_duplicate_results(app: &MainWindow, vector: Vec<(Option<DuplicateEntry>
         }
     }
     app.set_duplicate_files_model(items.into());
-    app.invoke_scan_ended(flk!("rust_found_duplicate_files", items_found = items_found).into());
+    let mut scan_ended_text = flk!("rust_found_duplicate_files", items_found = items_found);
+    if sampled_unverified && items_found > 0 {
+        scan_ended_text.push_str(&format!(" - {}", flk!("rust_duplicates_sampled_unverified")));
+    }
+    app.global::<GuiState>().set_duplicates_sampled_unverified(sampled_unverified);
+    app.invoke_scan_ended(scan_ended_text.into());
     app.global::<GuiState>().set_info_text(messages.into());
 }
 fn prepare_data_model_duplicates(fe: &DuplicateEntry) -> (ModelRc<SharedString>, ModelRc<i32>) {
@@ -434,6 +446,11 @@ fn scan_similar_images(
                 resize_algorithm,
                 custom_settings.similar_images_sub_ignore_same_size,
                 custom_settings.similar_images_hide_hard_links,
+                Vec::new(),
+                HashCombineMode::default(),
+                false,
+                custom_settings.similar_images_sub_find_cropped,
+                ImagesGroupingMode::default(),
             );
             let mut tool = SimilarImages::new(params);
 
@@ -460,38 +477,54 @@ fn scan_similar_images(
             }
             vector.sort_by_key(|(_header, vc)| u64::MAX - vc.iter().map(|e| e.size).sum::<u64>()); // Also sorts by size, to show the biggest groups first
 
+            let cropped_images = tool.get_cropped_images().clone();
+
             shared_models.lock().unwrap().shared_similar_images_state = Some(tool);
 
             a.upgrade_in_event_loop(move |app| {
-                write_similar_images_results(&app, vector, messages, hash_size);
+                write_similar_images_results(&app, vector, cropped_images, messages, hash_size);
             })
         })
         .expect("Cannot start thread - not much we can do here");
 }
-fn write_similar_images_results(app: &MainWindow, vector: Vec<(Option<ImagesEntry>, Vec<ImagesEntry>)>, messages: String, hash_size: u8) {
-    let items_found = vector.len();
+fn write_similar_images_results(app: &MainWindow, vector: Vec<(Option<ImagesEntry>, Vec<ImagesEntry>)>, cropped_images: Vec<CroppedImage>, messages: String, hash_size: u8) {
+    let items_found = vector.len() + cropped_images.len();
     let items = Rc::new(VecModel::default());
     for (ref_fe, vec_fe) in vector {
         if let Some(ref_fe) = ref_fe {
-            let (data_model_str, data_model_int) = prepare_data_model_similar_images(&ref_fe, hash_size);
+            let (data_model_str, data_model_int) = prepare_data_model_similar_images(&ref_fe, get_string_from_similarity(&ref_fe.similarity, hash_size));
             insert_data_to_model(&items, data_model_str, data_model_int, Some(true));
         } else {
             insert_data_to_model(&items, ModelRc::new(VecModel::default()), ModelRc::new(VecModel::default()), Some(false));
         }
 
         for fe in vec_fe {
-            let (data_model_str, data_model_int) = prepare_data_model_similar_images(&fe, hash_size);
+            let (data_model_str, data_model_int) = prepare_data_model_similar_images(&fe, get_string_from_similarity(&fe.similarity, hash_size));
             insert_data_to_model(&items, data_model_str, data_model_int, None);
         }
     }
+    // Each cropped image is shown as separate group, with parent image first
+    for cropped_image in cropped_images {
+        let (data_model_str, data_model_int) = prepare_data_model_similar_images(&cropped_image.parent, String::new());
+        insert_data_to_model(&items, ModelRc::new(VecModel::default()), ModelRc::new(VecModel::default()), Some(false));
+        insert_data_to_model(&items, data_model_str, data_model_int, None);
+
+        let similarity = flk!(
+            "rust_similar_images_cropped_part",
+            similarity = get_string_from_similarity(&cropped_image.similarity, hash_size),
+            percent = format!("{:.0}", cropped_image.crop_ratio * 100.0)
+        );
+        let (data_model_str, data_model_int) = prepare_data_model_similar_images(&cropped_image.child, similarity);
+        insert_data_to_model(&items, data_model_str, data_model_int, None);
+    }
     app.set_similar_images_model(items.into());
     app.invoke_scan_ended(flk!("rust_found_similar_images", items_found = items_found).into());
     app.global::<GuiState>().set_info_text(messages.into());
 }
-fn prepare_data_model_similar_images(fe: &ImagesEntry, hash_size: u8) -> (ModelRc<SharedString>, ModelRc<i32>) {
+fn prepare_data_model_similar_images(fe: &ImagesEntry, similarity: String) -> (ModelRc<SharedString>, ModelRc<i32>) {
     let (directory, file) = split_path(fe.get_path());
     let data_model_str = VecModel::from_slice(&[
-        get_string_from_similarity(&fe.similarity, hash_size).into(),
+        similarity.into(),
         format_size(fe.size, BINARY).into(),
         format!("{}x{}", fe.width, fe.height).into(),
         file.into(),
@@ -856,6 +889,24 @@ fn scan_broken_files(
             if custom_settings.broken_files_sub_archive {
                 checked_types |= CheckedTypes::ARCHIVE;
             }
+            if custom_settings.broken_files_sub_compressed_archive {
+                checked_types |= CheckedTypes::TAR | CheckedTypes::GZIP | CheckedTypes::XZ | CheckedTypes::ZSTD | CheckedTypes::BZIP2 | CheckedTypes::SEVEN_ZIP;
+            }
+            if custom_settings.broken_files_sub_video {
+                checked_types |= CheckedTypes::VIDEO;
+            }
+            if custom_settings.broken_files_sub_document {
+                checked_types |= CheckedTypes::DOCUMENT;
+            }
+            if custom_settings.broken_files_sub_font {
+                checked_types |= CheckedTypes::FONT;
+            }
+            if custom_settings.broken_files_sub_sqlite {
+                checked_types |= CheckedTypes::SQLITE;
+            }
+            if custom_settings.broken_files_sub_text_formats {
+                checked_types |= CheckedTypes::JSON | CheckedTypes::XML | CheckedTypes::YAML;
+            }
 
             if checked_types == CheckedTypes::NONE {
                 a.upgrade_in_event_loop(move |app| {
@@ -865,7 +916,17 @@ fn scan_broken_files(
                 return Ok(());
             }
 
-            let params = BrokenFilesParameters::new(checked_types);
+            let params = BrokenFilesParameters::new(
+                checked_types,
+                custom_settings.broken_files_sub_decode_video,
+                custom_settings.broken_files_sub_verify_zip_content,
+                custom_settings.broken_files_sub_strict_image,
+                if custom_settings.broken_files_sub_show_warnings {
+                    Severity::Warning
+                } else {
+                    Severity::Unreadable
+                },
+            );
             let mut tool = BrokenFiles::new(params);
             set_common_settings(&mut tool, &custom_settings, &stop_flag);
 
@@ -996,7 +1057,26 @@ where
     component.set_allowed_extensions(custom_settings.allowed_extensions.clone());
     component.set_excluded_extensions(custom_settings.excluded_extensions.clone());
     component.set_excluded_items(custom_settings.excluded_items.split(',').map(str::to_string).collect());
+    component.set_included_items(custom_settings.included_items.split(',').map(str::to_string).collect());
     component.set_exclude_other_filesystems(custom_settings.ignore_other_file_systems);
+    component.set_use_ignore_files(custom_settings.use_ignore_files);
+    component.set_max_depth(custom_settings.max_depth.trim().parse::<usize>().ok());
+    component.set_follow_symlinks(custom_settings.follow_symlinks);
     component.set_use_cache(custom_settings.use_cache);
     component.set_save_also_as_json(custom_settings.save_also_as_json);
+    // Invalid or empty values disable given filter
+    component.set_minimal_modified_date(parse_date(&custom_settings.min_modified_date).ok());
+    component.set_maximal_modified_date(parse_date(&custom_settings.max_modified_date).ok());
+    component.set_not_accessed_for_days(custom_settings.not_accessed_for_days.trim().parse::<u64>().ok());
+    // Invalid ids are reported, because silently ignoring them would show files of all owners
+    match parse_ids(&custom_settings.owner_uids) {
+        Ok(owner_uids) => component.set_owner_uids(owner_uids),
+        Err(e) => component.get_text_messages_mut().warnings.push(e),
+    }
+    match parse_ids(&custom_settings.owner_gids) {
+        Ok(owner_gids) => component.set_owner_gids(owner_gids),
+        Err(e) => component.get_text_messages_mut().warnings.push(e),
+    }
+    // Set only to not scan quarantine folder, deleting is done by GUI
+    component.set_quarantine_folder(Some(custom_settings.quarantine_folder.trim()).filter(|folder| !folder.is_empty()).map(PathBuf::from));
 }
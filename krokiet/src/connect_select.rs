@@ -1,9 +1,13 @@
+use std::path::{MAIN_SEPARATOR, PathBuf};
+
+use czkawka_core::common::model::FileEntry;
+use czkawka_core::common::tool_data::KeeperRules;
 use slint::{ComponentHandle, Model, ModelRc, VecModel};
 
 use crate::common::connect_i32_into_u64;
 use crate::connect_row_selection::checker::change_number_of_enabled_items;
 use crate::connect_translation::translate_select_mode;
-use crate::{ActiveTab, Callabler, GuiState, MainListModel, MainWindow, SelectMode, SelectModel};
+use crate::{ActiveTab, Callabler, GuiState, MainListModel, MainWindow, SelectMode, SelectModel, Settings};
 
 type SelectionResult = (u64, u64, ModelRc<MainListModel>);
 
@@ -26,6 +30,13 @@ pub(crate) fn connect_select(app: &MainWindow) {
             SelectMode::SelectTheSmallestResolution => select_by_resolution(&current_model, active_tab, false),
             SelectMode::SelectNewest => select_by_size_date(&current_model, active_tab, true, false),
             SelectMode::SelectOldest => select_by_size_date(&current_model, active_tab, false, false),
+            SelectMode::SelectAllExceptKeptByRules => match KeeperRules::parse(&app.global::<Settings>().get_keeper_rules()) {
+                Ok(keeper_rules) => select_all_except_kept_by_rules(&current_model, active_tab, &keeper_rules),
+                Err(e) => {
+                    app.global::<GuiState>().set_info_text(e.into());
+                    return;
+                }
+            },
         };
         active_tab.set_tool_model(&app, new_model);
         change_number_of_enabled_items(&app, active_tab, checked_items as i64 - unchecked_items as i64);
@@ -51,6 +62,7 @@ fn set_select_buttons(app: &MainWindow) {
             SelectMode::SelectNewest,
             SelectMode::SelectTheSmallestSize,
             SelectMode::SelectTheBiggestSize,
+            SelectMode::SelectAllExceptKeptByRules,
         ],
         ActiveTab::SimilarImages => vec![
             SelectMode::SelectOldest,
@@ -59,6 +71,7 @@ fn set_select_buttons(app: &MainWindow) {
             SelectMode::SelectTheBiggestSize,
             SelectMode::SelectTheSmallestResolution,
             SelectMode::SelectTheBiggestResolution,
+            SelectMode::SelectAllExceptKeptByRules,
         ],
         ActiveTab::EmptyFolders
         | ActiveTab::BigFiles
@@ -193,6 +206,53 @@ fn select_by_size_date(model: &ModelRc<MainListModel>, active_tab: ActiveTab, bi
     (checked_items, 0, ModelRc::new(VecModel::from(old_data)))
 }
 
+// Selects all items in every group, except the one chosen by keeper rules and items protected by them
+fn select_all_except_kept_by_rules(model: &ModelRc<MainListModel>, active_tab: ActiveTab, keeper_rules: &KeeperRules) -> SelectionResult {
+    let mut checked_items = 0;
+
+    let is_header_mode = active_tab.get_is_header_mode();
+    assert!(is_header_mode); // non header modes not really have reason to use this function
+
+    let mut old_data = model.iter().collect::<Vec<_>>();
+    let headers_idx = find_header_idx_and_deselect_all(&mut old_data);
+    let path_idx = active_tab.get_str_path_idx();
+    let name_idx = active_tab.get_str_name_idx();
+    let size_idx = active_tab.get_int_size_idx();
+    let date_idx = active_tab.get_int_modification_date_idx();
+
+    for i in 0..(headers_idx.len() - 1) {
+        let first_item_idx = headers_idx[i] + 1;
+        let entries = old_data[first_item_idx..headers_idx[i + 1]]
+            .iter()
+            .map(|item| {
+                let str_data = item.val_str.iter().collect::<Vec<_>>();
+                let int_data = item.val_int.iter().collect::<Vec<_>>();
+                FileEntry {
+                    path: PathBuf::from(format!("{}{MAIN_SEPARATOR}{}", str_data[path_idx], str_data[name_idx])),
+                    size: connect_i32_into_u64(int_data[size_idx], int_data[size_idx + 1]),
+                    modified_date: connect_i32_into_u64(int_data[date_idx], int_data[date_idx + 1]),
+                }
+            })
+            .collect::<Vec<_>>();
+        if entries.is_empty() {
+            continue;
+        }
+
+        let kept_idx = keeper_rules.select_keeper(&entries);
+        for (idx, entry) in entries.iter().enumerate() {
+            if idx == kept_idx || keeper_rules.is_protected(entry) {
+                continue;
+            }
+            if !old_data[first_item_idx + idx].checked {
+                checked_items += 1;
+            }
+            old_data[first_item_idx + idx].checked = true;
+        }
+    }
+
+    (checked_items, 0, ModelRc::new(VecModel::from(old_data)))
+}
+
 fn select_all(model: &ModelRc<MainListModel>) -> SelectionResult {
     let mut checked_items = 0;
     let mut old_data = model.iter().collect::<Vec<_>>();
@@ -192,6 +192,7 @@ fn translate_items(app: &MainWindow) {
     translation.set_save_button_text(flk!("save_button").into());
     translation.set_sort_button_text(flk!("sort_button").into());
     translation.set_rename_button_text(flk!("rename_button").into());
+    translation.set_ignore_button_text(flk!("ignore_button").into());
     translation.set_motto_text(flk!("motto").into());
     translation.set_unicorn_text(flk!("unicorn").into());
     translation.set_repository_text(flk!("repository").into());
@@ -232,10 +233,12 @@ fn translate_items(app: &MainWindow) {
     translation.set_subsettings_images_hash_size_text(flk!("subsettings_images_hash_size").into());
     translation.set_subsettings_images_resize_algorithm_text(flk!("subsettings_images_resize_algorithm").into());
     translation.set_subsettings_images_ignore_same_size_text(flk!("subsettings_images_ignore_same_size").into());
+    translation.set_subsettings_images_find_cropped_text(flk!("subsettings_images_find_cropped").into());
     translation.set_subsettings_images_max_difference_text(flk!("subsettings_images_max_difference").into());
     translation.set_subsettings_images_duplicates_hash_type_text(flk!("subsettings_images_duplicates_hash_type").into());
     translation.set_subsettings_duplicates_check_method_text(flk!("subsettings_duplicates_check_method").into());
     translation.set_subsettings_duplicates_name_case_sensitive_text(flk!("subsettings_duplicates_name_case_sensitive").into());
+    translation.set_subsettings_duplicates_verify_content_text(flk!("subsettings_duplicates_verify_content").into());
     translation.set_subsettings_biggest_files_sub_method_text(flk!("subsettings_biggest_files_sub_method").into());
     translation.set_subsettings_biggest_files_sub_number_of_files_text(flk!("subsettings_biggest_files_sub_number_of_files").into());
     translation.set_subsettings_videos_max_difference_text(flk!("subsettings_videos_max_difference").into());
@@ -256,8 +259,19 @@ fn translate_items(app: &MainWindow) {
     translation.set_subsettings_broken_files_audio_text(flk!("subsettings_broken_files_audio").into());
     translation.set_subsettings_broken_files_pdf_text(flk!("subsettings_broken_files_pdf").into());
     translation.set_subsettings_broken_files_archive_text(flk!("subsettings_broken_files_archive").into());
+    translation.set_subsettings_broken_files_compressed_archive_text(flk!("subsettings_broken_files_compressed_archive").into());
     translation.set_subsettings_broken_files_image_text(flk!("subsettings_broken_files_image").into());
+    translation.set_subsettings_broken_files_video_text(flk!("subsettings_broken_files_video").into());
+    translation.set_subsettings_broken_files_decode_video_text(flk!("subsettings_broken_files_decode_video").into());
+    translation.set_subsettings_broken_files_document_text(flk!("subsettings_broken_files_document").into());
+    translation.set_subsettings_broken_files_font_text(flk!("subsettings_broken_files_font").into());
+    translation.set_subsettings_broken_files_sqlite_text(flk!("subsettings_broken_files_sqlite").into());
+    translation.set_subsettings_broken_files_text_formats_text(flk!("subsettings_broken_files_text_formats").into());
+    translation.set_subsettings_broken_files_verify_zip_content_text(flk!("subsettings_broken_files_verify_zip_content").into());
+    translation.set_subsettings_broken_files_strict_image_text(flk!("subsettings_broken_files_strict_image").into());
+    translation.set_subsettings_broken_files_show_warnings_text(flk!("subsettings_broken_files_show_warnings").into());
     translation.set_settings_excluded_items_text(flk!("settings_excluded_items").into());
+    translation.set_settings_included_items_text(flk!("settings_included_items").into());
     translation.set_settings_allowed_extensions_text(flk!("settings_allowed_extensions").into());
     translation.set_settings_excluded_extensions_text(flk!("settings_excluded_extensions").into());
     translation.set_settings_file_size_text(flk!("settings_file_size").into());
@@ -267,7 +281,17 @@ fn translate_items(app: &MainWindow) {
     translation.set_settings_use_cache_text(flk!("settings_use_cache").into());
     translation.set_settings_save_as_json_text(flk!("settings_save_as_json").into());
     translation.set_settings_move_to_trash_text(flk!("settings_move_to_trash").into());
+    translation.set_settings_quarantine_folder_text(flk!("settings_quarantine_folder").into());
+    translation.set_settings_keeper_rules_text(flk!("settings_keeper_rules").into());
+    translation.set_settings_min_modified_date_text(flk!("settings_min_modified_date").into());
+    translation.set_settings_max_modified_date_text(flk!("settings_max_modified_date").into());
+    translation.set_settings_max_depth_text(flk!("settings_max_depth").into());
+    translation.set_settings_not_accessed_for_days_text(flk!("settings_not_accessed_for_days").into());
+    translation.set_settings_owner_uids_text(flk!("settings_owner_uids").into());
+    translation.set_settings_owner_gids_text(flk!("settings_owner_gids").into());
     translation.set_settings_ignore_other_filesystems_text(flk!("settings_ignore_other_filesystems").into());
+    translation.set_settings_use_ignore_files_text(flk!("settings_use_ignore_files").into());
+    translation.set_settings_follow_symlinks_text(flk!("settings_follow_symlinks").into());
     translation.set_settings_thread_number_text(flk!("settings_thread_number").into());
     translation.set_settings_restart_required_text(flk!("settings_restart_required").into());
     translation.set_settings_duplicate_image_preview_text(flk!("settings_duplicate_image_preview").into());
@@ -415,6 +439,7 @@ pub(crate) fn translate_select_mode(select_mode: SelectMode) -> SharedString {
         SelectMode::SelectTheSmallestResolution => flk!("selection_the_smallest_resolution").into(),
         SelectMode::SelectNewest => flk!("selection_newest").into(),
         SelectMode::SelectOldest => flk!("selection_oldest").into(),
+        SelectMode::SelectAllExceptKeptByRules => flk!("selection_all_except_kept_by_rules").into(),
     }
 }
 
@@ -18,6 +18,7 @@ use slint::VecModel;
 
 use crate::connect_delete::connect_delete_button;
 use crate::connect_directories_changes::connect_add_remove_directories;
+use crate::connect_ignore::connect_ignore;
 use crate::connect_move::connect_move;
 use crate::connect_open::connect_open_items;
 use crate::connect_progress_receiver::connect_progress_gathering;
@@ -39,6 +40,7 @@ use crate::shared_models::SharedModels;
 mod common;
 mod connect_delete;
 mod connect_directories_changes;
+mod connect_ignore;
 mod connect_move;
 mod connect_open;
 mod connect_progress_receiver;
@@ -99,6 +101,7 @@ fn main() {
     connect_move(&app, progress_sender.clone(), stop_flag.clone());
     connect_rename(&app, progress_sender, stop_flag);
     connect_save(&app, Arc::clone(&shared_models));
+    connect_ignore(&app);
     connect_row_selections(&app);
     connect_sort(&app);
     connect_showing_proper_sort_buttons(&app);
@@ -116,6 +116,7 @@ impl StringComboBoxItems {
 
         let duplicates_check_method = Self::convert_to_combobox_items(&[
             ("hash", "Hash", CheckingMethod::Hash),
+            ("sampled_hash", "Sampled Hash", CheckingMethod::SampledHash),
             ("size", "Size", CheckingMethod::Size),
             ("name", "Name", CheckingMethod::Name),
             ("size_and_name", "Size and Name", CheckingMethod::SizeName),
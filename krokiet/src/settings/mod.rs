@@ -351,6 +351,7 @@ pub(crate) fn set_settings_to_gui(app: &MainWindow, custom_settings: &SettingsCu
     settings.set_excluded_directories_model(excluded_directories);
 
     settings.set_excluded_items(custom_settings.excluded_items.clone().into());
+    settings.set_included_items(custom_settings.included_items.clone().into());
     settings.set_allowed_extensions(custom_settings.allowed_extensions.clone().into());
     settings.set_excluded_extensions(custom_settings.excluded_extensions.clone().into());
     settings.set_minimum_file_size(custom_settings.minimum_file_size.to_string().into());
@@ -358,7 +359,17 @@ pub(crate) fn set_settings_to_gui(app: &MainWindow, custom_settings: &SettingsCu
     settings.set_use_cache(custom_settings.use_cache);
     settings.set_save_as_json(custom_settings.save_also_as_json);
     settings.set_move_to_trash(custom_settings.move_deleted_files_to_trash);
+    settings.set_quarantine_folder(custom_settings.quarantine_folder.clone().into());
+    settings.set_keeper_rules(custom_settings.keeper_rules.clone().into());
+    settings.set_min_modified_date(custom_settings.min_modified_date.clone().into());
+    settings.set_max_modified_date(custom_settings.max_modified_date.clone().into());
+    settings.set_max_depth(custom_settings.max_depth.clone().into());
+    settings.set_not_accessed_for_days(custom_settings.not_accessed_for_days.clone().into());
+    settings.set_owner_uids(custom_settings.owner_uids.clone().into());
+    settings.set_owner_gids(custom_settings.owner_gids.clone().into());
     settings.set_ignore_other_filesystems(custom_settings.ignore_other_file_systems);
+    settings.set_use_ignore_files(custom_settings.use_ignore_files);
+    settings.set_follow_symlinks(custom_settings.follow_symlinks);
     settings.set_thread_number(custom_settings.thread_number as f32);
 
     settings.set_recursive_search(custom_settings.recursive_search);
@@ -369,6 +380,7 @@ pub(crate) fn set_settings_to_gui(app: &MainWindow, custom_settings: &SettingsCu
     settings.set_duplicate_minimal_prehash_cache_size(custom_settings.duplicate_minimal_prehash_cache_size.to_string().into());
     settings.set_duplicate_delete_outdated_entries(custom_settings.duplicate_delete_outdated_entries);
     settings.set_duplicates_sub_name_case_sensitive(custom_settings.duplicates_sub_name_case_sensitive);
+    settings.set_duplicates_sub_verify_content(custom_settings.duplicates_sub_verify_content);
     settings.set_similar_images_hide_hard_links(custom_settings.similar_images_hide_hard_links);
     settings.set_similar_images_show_image_preview(custom_settings.similar_images_show_image_preview);
     settings.set_similar_images_delete_outdated_entries(custom_settings.similar_images_delete_outdated_entries);
@@ -380,6 +392,7 @@ pub(crate) fn set_settings_to_gui(app: &MainWindow, custom_settings: &SettingsCu
     set_combobox_custom_settings_items(&settings, custom_settings);
 
     settings.set_similar_images_sub_ignore_same_size(custom_settings.similar_images_sub_ignore_same_size);
+    settings.set_similar_images_sub_find_cropped(custom_settings.similar_images_sub_find_cropped);
     settings.set_similar_images_sub_max_similarity(MAX_HASH_SIZE);
     settings.set_similar_images_sub_current_similarity(custom_settings.similar_images_sub_similarity as f32);
 
@@ -416,7 +429,17 @@ pub(crate) fn set_settings_to_gui(app: &MainWindow, custom_settings: &SettingsCu
     settings.set_broken_files_sub_audio(custom_settings.broken_files_sub_audio);
     settings.set_broken_files_sub_pdf(custom_settings.broken_files_sub_pdf);
     settings.set_broken_files_sub_archive(custom_settings.broken_files_sub_archive);
+    settings.set_broken_files_sub_compressed_archive(custom_settings.broken_files_sub_compressed_archive);
     settings.set_broken_files_sub_image(custom_settings.broken_files_sub_image);
+    settings.set_broken_files_sub_video(custom_settings.broken_files_sub_video);
+    settings.set_broken_files_sub_decode_video(custom_settings.broken_files_sub_decode_video);
+    settings.set_broken_files_sub_document(custom_settings.broken_files_sub_document);
+    settings.set_broken_files_sub_font(custom_settings.broken_files_sub_font);
+    settings.set_broken_files_sub_sqlite(custom_settings.broken_files_sub_sqlite);
+    settings.set_broken_files_sub_text_formats(custom_settings.broken_files_sub_text_formats);
+    settings.set_broken_files_sub_verify_zip_content(custom_settings.broken_files_sub_verify_zip_content);
+    settings.set_broken_files_sub_strict_image(custom_settings.broken_files_sub_strict_image);
+    settings.set_broken_files_sub_show_warnings(custom_settings.broken_files_sub_show_warnings);
 
     let sel_px = 35.0;
     let path_px = 350.0;
@@ -470,6 +493,7 @@ pub(crate) fn collect_settings(app: &MainWindow) -> SettingsCustom {
     let excluded_directories = excluded_directories_model.iter().map(|model| PathBuf::from(model.path.as_str())).collect::<Vec<_>>();
 
     let excluded_items = settings.get_excluded_items().to_string();
+    let included_items = settings.get_included_items().to_string();
     let allowed_extensions = settings.get_allowed_extensions().to_string();
     let excluded_extensions = settings.get_excluded_extensions().to_string();
     let minimum_file_size = settings.get_minimum_file_size().parse::<i32>().unwrap_or(DEFAULT_MINIMUM_SIZE_KB);
@@ -479,7 +503,17 @@ pub(crate) fn collect_settings(app: &MainWindow) -> SettingsCustom {
     let use_cache = settings.get_use_cache();
     let save_also_as_json = settings.get_save_as_json();
     let move_deleted_files_to_trash = settings.get_move_to_trash();
+    let quarantine_folder = settings.get_quarantine_folder().to_string();
+    let keeper_rules = settings.get_keeper_rules().to_string();
+    let min_modified_date = settings.get_min_modified_date().to_string();
+    let max_modified_date = settings.get_max_modified_date().to_string();
+    let max_depth = settings.get_max_depth().to_string();
+    let not_accessed_for_days = settings.get_not_accessed_for_days().to_string();
+    let owner_uids = settings.get_owner_uids().to_string();
+    let owner_gids = settings.get_owner_gids().to_string();
     let ignore_other_file_systems = settings.get_ignore_other_filesystems();
+    let use_ignore_files = settings.get_use_ignore_files();
+    let follow_symlinks = settings.get_follow_symlinks();
     let thread_number = settings.get_thread_number().round() as i32;
 
     let duplicate_image_preview = settings.get_duplicate_image_preview();
@@ -492,6 +526,7 @@ pub(crate) fn collect_settings(app: &MainWindow) -> SettingsCustom {
         .unwrap_or(DEFAULT_MINIMUM_PREHASH_CACHE_SIZE);
     let duplicate_delete_outdated_entries = settings.get_duplicate_delete_outdated_entries();
     let duplicates_sub_name_case_sensitive = settings.get_duplicates_sub_name_case_sensitive();
+    let duplicates_sub_verify_content = settings.get_duplicates_sub_verify_content();
 
     let similar_images_hide_hard_links = settings.get_similar_images_hide_hard_links();
     let similar_images_show_image_preview = settings.get_similar_images_show_image_preview();
@@ -510,6 +545,7 @@ pub(crate) fn collect_settings(app: &MainWindow) -> SettingsCustom {
     let similar_images_sub_resize_algorithm_idx = settings.get_similar_images_sub_resize_algorithm_index();
     let similar_images_sub_resize_algorithm = StringComboBoxItems::get_config_name_from_idx(similar_images_sub_resize_algorithm_idx as usize, &collected_items.resize_algorithm);
     let similar_images_sub_ignore_same_size = settings.get_similar_images_sub_ignore_same_size();
+    let similar_images_sub_find_cropped = settings.get_similar_images_sub_find_cropped();
     let similar_images_sub_similarity = settings.get_similar_images_sub_current_similarity().round() as i32;
 
     let duplicates_sub_check_method_idx = settings.get_duplicates_sub_check_method_index();
@@ -543,7 +579,17 @@ pub(crate) fn collect_settings(app: &MainWindow) -> SettingsCustom {
     let broken_files_sub_audio = settings.get_broken_files_sub_audio();
     let broken_files_sub_pdf = settings.get_broken_files_sub_pdf();
     let broken_files_sub_archive = settings.get_broken_files_sub_archive();
+    let broken_files_sub_compressed_archive = settings.get_broken_files_sub_compressed_archive();
     let broken_files_sub_image = settings.get_broken_files_sub_image();
+    let broken_files_sub_video = settings.get_broken_files_sub_video();
+    let broken_files_sub_decode_video = settings.get_broken_files_sub_decode_video();
+    let broken_files_sub_document = settings.get_broken_files_sub_document();
+    let broken_files_sub_font = settings.get_broken_files_sub_font();
+    let broken_files_sub_sqlite = settings.get_broken_files_sub_sqlite();
+    let broken_files_sub_text_formats = settings.get_broken_files_sub_text_formats();
+    let broken_files_sub_verify_zip_content = settings.get_broken_files_sub_verify_zip_content();
+    let broken_files_sub_strict_image = settings.get_broken_files_sub_strict_image();
+    let broken_files_sub_show_warnings = settings.get_broken_files_sub_show_warnings();
 
     let column_sizes = BTreeMap::from([
         ("duplicates".to_string(), settings.get_duplicates_column_size().iter().collect::<Vec<_>>()),
@@ -564,6 +610,7 @@ pub(crate) fn collect_settings(app: &MainWindow) -> SettingsCustom {
         included_directories_referenced,
         excluded_directories,
         excluded_items,
+        included_items,
         allowed_extensions,
         excluded_extensions,
         minimum_file_size,
@@ -572,7 +619,17 @@ pub(crate) fn collect_settings(app: &MainWindow) -> SettingsCustom {
         use_cache,
         save_also_as_json,
         move_deleted_files_to_trash,
+        quarantine_folder,
+        keeper_rules,
+        min_modified_date,
+        max_modified_date,
+        max_depth,
+        not_accessed_for_days,
+        owner_uids,
+        owner_gids,
         ignore_other_file_systems,
+        use_ignore_files,
+        follow_symlinks,
         thread_number,
         duplicate_image_preview,
         duplicate_hide_hard_links,
@@ -589,10 +646,12 @@ pub(crate) fn collect_settings(app: &MainWindow) -> SettingsCustom {
         similar_images_sub_hash_alg,
         similar_images_sub_resize_algorithm,
         similar_images_sub_ignore_same_size,
+        similar_images_sub_find_cropped,
         similar_images_sub_similarity,
         duplicates_sub_check_method,
         duplicates_sub_available_hash_type,
         duplicates_sub_name_case_sensitive,
+        duplicates_sub_verify_content,
         biggest_files_sub_method,
         biggest_files_sub_number_of_files,
         similar_videos_hide_hard_links,
@@ -612,7 +671,17 @@ pub(crate) fn collect_settings(app: &MainWindow) -> SettingsCustom {
         broken_files_sub_audio,
         broken_files_sub_pdf,
         broken_files_sub_archive,
+        broken_files_sub_compressed_archive,
         broken_files_sub_image,
+        broken_files_sub_video,
+        broken_files_sub_decode_video,
+        broken_files_sub_document,
+        broken_files_sub_font,
+        broken_files_sub_sqlite,
+        broken_files_sub_text_formats,
+        broken_files_sub_verify_zip_content,
+        broken_files_sub_strict_image,
+        broken_files_sub_show_warnings,
         column_sizes,
         similar_videos_vid_hash_duration,
         similar_videos_crop_detect,
@@ -38,6 +38,8 @@ pub struct SettingsCustom {
     #[serde(default = "default_excluded_items")]
     pub excluded_items: String,
     #[serde(default)]
+    pub included_items: String,
+    #[serde(default)]
     pub allowed_extensions: String,
     #[serde(default)]
     pub excluded_extensions: String,
@@ -54,8 +56,28 @@ pub struct SettingsCustom {
     #[serde(default)]
     pub move_deleted_files_to_trash: bool,
     #[serde(default)]
+    pub quarantine_folder: String,
+    #[serde(default)]
+    pub keeper_rules: String,
+    #[serde(default)]
+    pub min_modified_date: String,
+    #[serde(default)]
+    pub max_modified_date: String,
+    #[serde(default)]
+    pub max_depth: String,
+    #[serde(default)]
+    pub not_accessed_for_days: String,
+    #[serde(default)]
+    pub owner_uids: String,
+    #[serde(default)]
+    pub owner_gids: String,
+    #[serde(default)]
     pub ignore_other_file_systems: bool,
     #[serde(default)]
+    pub use_ignore_files: bool,
+    #[serde(default)]
+    pub follow_symlinks: bool,
+    #[serde(default)]
     pub thread_number: i32,
     #[serde(default = "ttrue")]
     pub duplicate_image_preview: bool,
@@ -87,6 +109,8 @@ pub struct SettingsCustom {
     pub similar_images_sub_resize_algorithm: String,
     #[serde(default)]
     pub similar_images_sub_ignore_same_size: bool,
+    #[serde(default)]
+    pub similar_images_sub_find_cropped: bool,
     #[serde(default = "default_image_similarity")]
     pub similar_images_sub_similarity: i32,
     #[serde(default = "default_duplicates_check_method")]
@@ -95,6 +119,8 @@ pub struct SettingsCustom {
     pub duplicates_sub_available_hash_type: String,
     #[serde(default)]
     pub duplicates_sub_name_case_sensitive: bool,
+    #[serde(default)]
+    pub duplicates_sub_verify_content: bool,
     #[serde(default = "default_biggest_method")]
     pub biggest_files_sub_method: String,
     #[serde(default = "default_biggest_files")]
@@ -133,8 +159,29 @@ pub struct SettingsCustom {
     pub broken_files_sub_pdf: bool,
     #[serde(default = "ttrue")]
     pub broken_files_sub_archive: bool,
+    // Fully decompressing big archives is slow, so they are checked only on request
+    #[serde(default)]
+    pub broken_files_sub_compressed_archive: bool,
     #[serde(default = "ttrue")]
     pub broken_files_sub_image: bool,
+    #[serde(default = "ttrue")]
+    pub broken_files_sub_video: bool,
+    #[serde(default)]
+    pub broken_files_sub_decode_video: bool,
+    #[serde(default = "ttrue")]
+    pub broken_files_sub_document: bool,
+    #[serde(default)]
+    pub broken_files_sub_font: bool,
+    #[serde(default)]
+    pub broken_files_sub_sqlite: bool,
+    #[serde(default)]
+    pub broken_files_sub_text_formats: bool,
+    #[serde(default)]
+    pub broken_files_sub_verify_zip_content: bool,
+    #[serde(default)]
+    pub broken_files_sub_strict_image: bool,
+    #[serde(default = "ttrue")]
+    pub broken_files_sub_show_warnings: bool,
     #[serde(default = "default_similar_videos_skip_forward_amount")]
     pub similar_videos_skip_forward_amount: u32,
     #[serde(default = "default_similar_videos_vid_hash_duration")]